@@ -0,0 +1,95 @@
+//! Горячий SELECT с меняющимися значениями: рендер без кэша и с кэшем SQL.
+//! Считает аллокации (через глобальный аллокатор) и время на итерацию.
+//!
+//! cargo bench --bench sql_cache
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use knux::query_builder::PoolQuery;
+use knux::{ExecutorConfig, QueryExecutor, col, val};
+
+struct Counting;
+
+static ALLOCS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(new_size as u64, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const ITERS: u64 = 20_000;
+
+fn build(exec: &QueryExecutor, i: u64) -> PoolQuery<'_, ()> {
+    exec.query::<()>()
+        .select((col("u.id"), col("u.name"), col("o.total")))
+        .from("users u")
+        .join("orders o", col("o.user_id").eq(col("u.id")))
+        .r#where(col("u.age").gt(val(i as i64)))
+        .and_where(col("o.status").eq(val("paid")))
+        .order_by((col("o.total"),))
+        .limit(50)
+}
+
+fn run(label: &str, exec: &QueryExecutor, render: bool) {
+    let iter = |i| {
+        let qb = build(exec, i);
+        if render {
+            black_box(qb.to_sql().unwrap());
+        } else {
+            black_box(qb);
+        }
+    };
+    // прогрев: кэш заполнен, ленивые статики инициализированы
+    iter(0);
+
+    let allocs = ALLOCS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let started = Instant::now();
+    for i in 0..ITERS {
+        iter(i);
+    }
+    let ns = started.elapsed().as_nanos() as u64 / ITERS;
+    let allocs = (ALLOCS.load(Ordering::Relaxed) - allocs) / ITERS;
+    let bytes = (BYTES.load(Ordering::Relaxed) - bytes) / ITERS;
+    println!("{label:<12} {ns:>8} ns/iter {allocs:>6} allocs/iter {bytes:>8} B/iter");
+}
+
+fn main() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let connect = |cfg| rt.block_on(QueryExecutor::connect(cfg)).unwrap();
+    let url = "sqlite::memory:";
+
+    let plain = connect(ExecutorConfig::builder().database_url(url).build());
+    let cached = connect(
+        ExecutorConfig::builder()
+            .database_url(url)
+            .sql_cache(64)
+            .build(),
+    );
+
+    // сборка билдера без рендера — общая часть обоих вариантов
+    run("build only", &plain, false);
+    run("uncached", &plain, true);
+    run("cached", &cached, true);
+    println!("{:?}", cached.sql_cache_stats().unwrap());
+}
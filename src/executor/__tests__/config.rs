@@ -1,3 +1,6 @@
+use std::time::Duration;
+
+use crate::executor::ReplicaStrategy;
 use crate::executor::config::ExecutorConfig;
 
 #[test]
@@ -67,3 +70,66 @@ fn builder_overrides_dsn() {
     assert_eq!(cfg.max_connections, Some(50)); // builder > dsn
     assert_eq!(cfg.min_connections, Some(10)); // builder > dsn
 }
+
+#[test]
+fn query_timeout_from_dsn_and_builder() {
+    let dsn = "sqlite::memory:?query_timeout=2s";
+    let cfg = ExecutorConfig::from_dsn(dsn).unwrap();
+    assert_eq!(cfg.query_timeout, Some(Duration::from_secs(2)));
+
+    let cfg = ExecutorConfig::builder()
+        .query_timeout(Duration::from_millis(500))
+        .build()
+        .merge_override(ExecutorConfig::from_dsn(dsn).unwrap());
+    assert_eq!(cfg.query_timeout, Some(Duration::from_millis(500))); // builder > dsn
+}
+
+#[test]
+fn tx_max_attempts_from_dsn() {
+    let cfg = ExecutorConfig::from_dsn("sqlite::memory:?tx.max_attempts=4").unwrap();
+    assert_eq!(cfg.tx_max_attempts, Some(4));
+    assert!(ExecutorConfig::from_dsn("sqlite::memory:?tx.max_attempts=x").is_err());
+}
+
+#[test]
+fn replicas_from_dsn_and_builder() {
+    let dsn = concat!(
+        "postgres://u@primary/db",
+        "?replica=postgres%3A%2F%2Fu%40r1%2Fdb",
+        "&replica=postgres%3A%2F%2Fu%40r2%2Fdb",
+        "&replica.strategy=least_connections",
+        "&replica.pool.max=4",
+    );
+    let cfg = ExecutorConfig::from_dsn(dsn).unwrap();
+    assert_eq!(
+        cfg.replica_urls,
+        ["postgres://u@r1/db", "postgres://u@r2/db"]
+    );
+    assert_eq!(
+        cfg.replica_strategy,
+        Some(ReplicaStrategy::LeastConnections)
+    );
+    assert_eq!(cfg.replica_max_connections, Some(4));
+    assert!(ExecutorConfig::from_dsn("postgres://u@h/db?replica.strategy=random").is_err());
+
+    // список реплик из билдера заменяет DSN целиком
+    let cfg = ExecutorConfig::builder()
+        .replica("postgres://u@r3/db")
+        .build()
+        .merge_override(ExecutorConfig::from_dsn(dsn).unwrap());
+    assert_eq!(cfg.replica_urls, ["postgres://u@r3/db"]);
+    assert_eq!(cfg.replica_max_connections, Some(4));
+}
+
+#[test]
+fn sql_cache_from_dsn_and_builder() {
+    let cfg = ExecutorConfig::from_dsn("postgres://u@h/db?sql_cache=256").unwrap();
+    assert_eq!(cfg.sql_cache, Some(256));
+    assert!(ExecutorConfig::from_dsn("postgres://u@h/db?sql_cache=many").is_err());
+
+    let cfg = ExecutorConfig::builder()
+        .sql_cache(16)
+        .build()
+        .merge_override(ExecutorConfig::from_dsn("postgres://u@h/db").unwrap());
+    assert_eq!(cfg.sql_cache, Some(16));
+}
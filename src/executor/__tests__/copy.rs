@@ -0,0 +1,165 @@
+#![cfg(feature = "postgres")]
+
+use crate::executor::copy::CopyEncoder;
+use crate::executor::{CopyFormat, QueryExecutor, config::ExecutorConfig};
+use crate::param::Param;
+
+fn encode(format: CopyFormat, rows: Vec<Vec<Param>>) -> Vec<u8> {
+    let mut enc = CopyEncoder::new(format, 3);
+    for row in rows {
+        enc.push_row(row).unwrap();
+    }
+    enc.finish();
+    enc.buf
+}
+
+fn sample() -> Vec<Vec<Param>> {
+    vec![
+        vec![
+            Param::I32(1),
+            Param::Str("tab\there\\".into()),
+            Param::Bool(true),
+        ],
+        vec![
+            Param::I32(2),
+            Param::Str("say \"hi\"\nbye".into()),
+            Param::NullBool,
+        ],
+        vec![Param::I32(3), Param::Str(String::new()), Param::Bool(false)],
+    ]
+}
+
+#[test]
+fn text_format_escapes_and_nulls() {
+    let out = encode(CopyFormat::Text, sample());
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "1\ttab\\there\\\\\tt\n2\tsay \"hi\"\\nbye\t\\N\n3\t\tf\n"
+    );
+}
+
+#[test]
+fn csv_format_quotes_values_and_leaves_null_empty() {
+    let out = encode(CopyFormat::Csv, sample());
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "\"1\",\"tab\there\\\",\"t\"\n\"2\",\"say \"\"hi\"\"\nbye\",\n\"3\",\"\",\"f\"\n"
+    );
+}
+
+#[test]
+fn binary_format_has_header_fields_and_trailer() {
+    let out = encode(
+        CopyFormat::Binary,
+        vec![vec![
+            Param::I32(7),
+            Param::Str("ab".into()),
+            Param::NullBool,
+        ]],
+    );
+
+    let mut expected = b"PGCOPY\n\xff\r\n\0".to_vec();
+    expected.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0]); // флаги + расширение
+    expected.extend_from_slice(&3i16.to_be_bytes());
+    expected.extend_from_slice(&4i32.to_be_bytes());
+    expected.extend_from_slice(&7i32.to_be_bytes());
+    expected.extend_from_slice(&2i32.to_be_bytes());
+    expected.extend_from_slice(b"ab");
+    expected.extend_from_slice(&(-1i32).to_be_bytes());
+    expected.extend_from_slice(&(-1i16).to_be_bytes());
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn row_width_must_match_columns() {
+    let mut enc = CopyEncoder::new(CopyFormat::Text, 3);
+    assert!(enc.push_row(vec![Param::I32(1)]).is_err());
+}
+
+async fn pg_exec() -> QueryExecutor {
+    let url = std::env::var("KNUX_PG_URL")
+        .unwrap_or_else(|_| "postgres://postgres@127.0.0.1:5432/postgres".into());
+    let cfg = ExecutorConfig::builder()
+        .database_url(&url)
+        .max_connections(2)
+        .build();
+    QueryExecutor::connect(cfg).await.unwrap()
+}
+
+#[tokio::test]
+#[ignore = "нужен локальный PostgreSQL (KNUX_PG_URL)"]
+async fn copy_in_loads_rows_in_every_format() {
+    let exec = pg_exec().await;
+    let pool = exec.as_pg_pool().unwrap();
+    sqlx::query("DROP TABLE IF EXISTS knux_copy_in")
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE knux_copy_in (id int4, note text, flag bool)")
+        .execute(pool)
+        .await
+        .unwrap();
+
+    for format in [CopyFormat::Text, CopyFormat::Csv, CopyFormat::Binary] {
+        let n = exec
+            .copy_in("knux_copy_in", ["id", "note", "flag"])
+            .format(format)
+            .rows(sample())
+            .await
+            .unwrap();
+        assert_eq!(n, 3);
+    }
+
+    let notes: Vec<(Option<String>,)> =
+        sqlx::query_as("SELECT note FROM knux_copy_in WHERE id = 2 AND flag IS NULL")
+            .fetch_all(pool)
+            .await
+            .unwrap();
+    assert_eq!(notes.len(), 3);
+    assert!(
+        notes
+            .iter()
+            .all(|(n,)| n.as_deref() == Some("say \"hi\"\nbye"))
+    );
+
+    sqlx::query("DROP TABLE knux_copy_in")
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore = "нужен локальный PostgreSQL (KNUX_PG_URL)"]
+async fn copy_in_inside_transaction_rolls_back() {
+    let exec = pg_exec().await;
+    let pool = exec.as_pg_pool().unwrap();
+    sqlx::query("CREATE TABLE IF NOT EXISTS knux_copy_tx (id int8)")
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("TRUNCATE knux_copy_tx")
+        .execute(pool)
+        .await
+        .unwrap();
+
+    let mut tx = exec.begin().await.unwrap();
+    let rows = futures_util::stream::iter((0..1_000i64).map(|i| vec![Param::I64(i)]));
+    let n = tx
+        .copy_in("knux_copy_tx", ["id"])
+        .stream(rows)
+        .await
+        .unwrap();
+    assert_eq!(n, 1_000);
+    tx.rollback().await.unwrap();
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM knux_copy_tx")
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+
+    sqlx::query("DROP TABLE knux_copy_tx")
+        .execute(pool)
+        .await
+        .unwrap();
+}
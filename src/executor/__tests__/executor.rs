@@ -149,6 +149,157 @@ async fn qb_into_future_many_select_sqlite() {
     assert!(names.contains(&"Alice") && names.contains(&"Cara"));
 }
 
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn window_functions_sqlite() {
+    use crate::expression::{FrameBound, row_number};
+
+    let cfg = ExecutorConfig::builder()
+        .database_url("sqlite::memory:")
+        .max_connections(1)
+        .build();
+    let exec = QueryExecutor::connect(cfg).await.unwrap();
+
+    setup_users(&exec).await;
+
+    let rows: Vec<(String, i64, i64)> = exec
+        .query()
+        .select((
+            col("name"),
+            row_number().over(|w| w.partition_by("is_active").order_by_desc("age")),
+            col("age").sum().over_window("w"),
+        ))
+        .from("users")
+        .window("w", |w| {
+            w.order_by("age")
+                .rows_between(FrameBound::UnboundedPreceding, FrameBound::CurrentRow)
+        })
+        .order_by("age")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        rows,
+        vec![
+            ("Bob".to_string(), 1, 18),
+            ("Cara".to_string(), 2, 43),
+            ("Alice".to_string(), 1, 73),
+        ]
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn case_when_binds_sqlite() {
+    use crate::expression::{case, lit};
+
+    let cfg = ExecutorConfig::builder()
+        .database_url("sqlite::memory:")
+        .max_connections(1)
+        .build();
+    let exec = QueryExecutor::connect(cfg).await.unwrap();
+
+    setup_users(&exec).await;
+
+    // параметры CASE в SELECT идут раньше параметра WHERE
+    let rows: Vec<(String, String)> = exec
+        .query()
+        .select((
+            col("name"),
+            case()
+                .when(col("age").lt(val(20)), lit("young"))
+                .when(col("age").lt(val(28)), lit("mid"))
+                .else_(lit("old"))
+                .end()
+                .r#as("bucket"),
+        ))
+        .from("users")
+        .where_(col("age").gt(val(10)))
+        .order_by("age")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        rows,
+        vec![
+            ("Bob".to_string(), "young".to_string()),
+            ("Cara".to_string(), "mid".to_string()),
+            ("Alice".to_string(), "old".to_string()),
+        ]
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn insert_from_select_sqlite() {
+    use crate::query_builder::QueryBuilder;
+
+    let cfg = ExecutorConfig::builder()
+        .database_url("sqlite::memory:")
+        .max_connections(1)
+        .build();
+    let exec = QueryExecutor::connect(cfg).await.unwrap();
+
+    setup_users(&exec).await;
+    exec.as_sqlite_pool()
+        .unwrap()
+        .execute("CREATE TABLE archive (id INTEGER PRIMARY KEY, name TEXT NOT NULL);")
+        .await
+        .unwrap();
+
+    let copy = || {
+        exec.query::<(i64,)>()
+            .into("archive")
+            .columns((col("id"), col("name")))
+            .insert_from(
+                QueryBuilder::new_empty()
+                    .from("users")
+                    .select((col("id"), col("name")))
+                    .where_(col("age").gte(val(25))),
+            )
+            .ignore()
+    };
+
+    let ids: Vec<(i64,)> = copy().returning((col("id"),)).await.unwrap();
+    assert_eq!(ids.len(), 2);
+
+    // повторная копия упирается в PK и игнорируется
+    assert_eq!(copy().exec().await.unwrap(), 0);
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn chunked_insert_sqlite_is_atomic() {
+    let cfg = ExecutorConfig::builder()
+        .database_url("sqlite::memory:")
+        .max_connections(1)
+        .build();
+    let exec = QueryExecutor::connect(cfg).await.unwrap();
+    exec.as_sqlite_pool()
+        .unwrap()
+        .execute("CREATE TABLE tags (id INTEGER PRIMARY KEY);")
+        .await
+        .unwrap();
+
+    let insert = |ids: Vec<i64>| {
+        exec.query::<()>()
+            .into("tags")
+            .columns((col("id"),))
+            .insert(ids.into_iter().map(val).collect::<Vec<_>>())
+            .chunked(2)
+    };
+
+    assert_eq!(insert((1..=5).collect()).exec().await.unwrap(), 5);
+
+    // дубликат в последней пачке — откатываются и первые
+    assert!(insert(vec![6, 7, 8, 9, 1]).exec().await.is_err());
+    let (n,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM tags")
+        .fetch_one(exec.as_sqlite_pool().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(n, 5);
+}
+
 #[cfg(feature = "sqlite")]
 #[tokio::test]
 async fn qb_one_and_optional_sqlite() {
@@ -193,3 +344,41 @@ async fn qb_one_and_optional_sqlite() {
         .unwrap();
     assert!(maybe.is_none());
 }
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn dialect_is_selected_from_dsn() {
+    // при нескольких фичах диалект берётся из схемы DSN, а не из DEFAULT_DIALECT
+    let cfg = ExecutorConfig::builder()
+        .database_url("sqlite::memory:")
+        .max_connections(1)
+        .build();
+    let exec = QueryExecutor::connect(cfg).await.expect("connect");
+    assert_eq!(exec.dialect(), crate::renderer::Dialect::SQLite);
+
+    let (sql, _) = exec
+        .query::<()>()
+        .from("users")
+        .select((col("id"),))
+        .where_(col("id").eq(val(1)))
+        .to_sql()
+        .unwrap();
+    assert_eq!(sql, r#"SELECT "id" FROM "users" WHERE "id" = ?"#);
+
+    let mut tx = exec.begin().await.unwrap();
+    let (sql, _) = tx
+        .query::<()>()
+        .from("users")
+        .select((col("id"),))
+        .where_(col("id").eq(val(1)))
+        .to_sql()
+        .unwrap();
+    assert_eq!(sql, r#"SELECT "id" FROM "users" WHERE "id" = ?"#);
+
+    let rows = tx
+        .fetch_typed::<(i64, String)>("SELECT ?, ?", vec![Param::I64(7), Param::from("x")])
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![(7, "x".to_string())]);
+    tx.commit().await.unwrap();
+}
@@ -0,0 +1,156 @@
+#![cfg(feature = "sqlite")]
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures_util::TryStreamExt;
+use sqlx::{Executor, FromRow};
+
+use crate::executor::{Error, QueryExecutor, QueryHook, QueryInfo, config::ExecutorConfig};
+use crate::expression::helpers::{col, val};
+use crate::renderer::Dialect;
+
+#[derive(Debug, FromRow)]
+#[allow(dead_code)]
+struct User {
+    id: i64,
+    name: String,
+}
+
+/// Пишет события хука в общий журнал
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<String>>>);
+
+impl Recorder {
+    fn take(&self) -> Vec<String> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+impl QueryHook for Recorder {
+    fn before(&self, q: &QueryInfo<'_>) {
+        assert_eq!(q.dialect, Dialect::SQLite);
+        self.0.lock().unwrap().push(format!("before {}", q.params));
+    }
+
+    fn after(&self, _q: &QueryInfo<'_>, rows: u64, _elapsed: Duration) {
+        self.0.lock().unwrap().push(format!("after {rows}"));
+    }
+
+    fn error(&self, _q: &QueryInfo<'_>, _err: &Error, _elapsed: Duration) {
+        self.0.lock().unwrap().push("error".into());
+    }
+
+    fn slow(&self, _q: &QueryInfo<'_>, _elapsed: Duration) {
+        self.0.lock().unwrap().push("slow".into());
+    }
+}
+
+async fn setup_db(rec: &Recorder) -> QueryExecutor {
+    let cfg = ExecutorConfig::builder()
+        .database_url("sqlite::memory:")
+        .max_connections(1)
+        .build();
+    let exec = QueryExecutor::connect(cfg).await.unwrap();
+
+    exec.as_sqlite_pool()
+        .unwrap()
+        .execute(
+            r#"
+            CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+            INSERT INTO users (name) VALUES ('Alice'), ('Bob'), ('Cara');
+            "#,
+        )
+        .await
+        .unwrap();
+
+    exec.with_hook(rec.clone())
+}
+
+#[tokio::test]
+async fn hooks_see_rows_and_params_of_builder_queries() {
+    let rec = Recorder::default();
+    let exec = setup_db(&rec).await;
+
+    let users: Vec<User> = exec
+        .query()
+        .select("*")
+        .from("users")
+        .r#where(col("id").gt(val(1)))
+        .await
+        .unwrap();
+    assert_eq!(users.len(), 2);
+    assert_eq!(rec.take(), ["before 1", "after 2"]);
+
+    let affected = exec
+        .query::<()>()
+        .into("users")
+        .insert((col("name"), val("Dan")))
+        .exec()
+        .await
+        .unwrap();
+    assert_eq!(affected, 1);
+    assert_eq!(rec.take(), ["before 1", "after 1"]);
+
+    let none: Option<User> = exec
+        .query()
+        .select("*")
+        .from("users")
+        .r#where(col("id").eq(val(100)))
+        .optional()
+        .await
+        .unwrap();
+    assert!(none.is_none());
+    assert_eq!(rec.take(), ["before 1", "after 0"]);
+}
+
+#[tokio::test]
+async fn hooks_count_streamed_rows_and_errors() {
+    let rec = Recorder::default();
+    let exec = setup_db(&rec).await;
+
+    let names: Vec<String> = exec
+        .query::<User>()
+        .select("*")
+        .from("users")
+        .stream()
+        .map_ok(|u| u.name)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(names.len(), 3);
+    assert_eq!(rec.take(), ["before 0", "after 3"]);
+
+    let err = exec
+        .fetch_typed::<User>("SELECT * FROM missing", vec![])
+        .await;
+    assert!(err.is_err());
+    assert_eq!(rec.take(), ["before 0", "error"]);
+}
+
+#[tokio::test]
+async fn hooks_follow_into_transactions() {
+    let rec = Recorder::default();
+    let exec = setup_db(&rec).await;
+
+    let mut tx = exec.begin().await.unwrap();
+    tx.execute("DELETE FROM users WHERE id = 1", vec![])
+        .await
+        .unwrap();
+    let users: Vec<User> = tx.query().select("*").from("users").await.unwrap();
+    assert_eq!(users.len(), 2);
+    tx.rollback().await.unwrap();
+
+    assert_eq!(rec.take(), ["before 0", "after 1", "before 0", "after 2"]);
+}
+
+#[tokio::test]
+async fn slow_threshold_reports_after_callbacks() {
+    let rec = Recorder::default();
+    let exec = setup_db(&rec)
+        .await
+        .with_slow_query_threshold(Duration::ZERO);
+
+    let _: Vec<User> = exec.query().select("*").from("users").await.unwrap();
+    assert_eq!(rec.take(), ["before 0", "after 3", "slow"]);
+}
@@ -0,0 +1,147 @@
+#![cfg(feature = "sqlite")]
+
+use sqlx::{Executor, FromRow};
+
+use crate::executor::{QueryExecutor, config::ExecutorConfig};
+use crate::expression::helpers::{col, val};
+use crate::query_builder::KeysetCursor;
+
+#[derive(Debug, FromRow, PartialEq)]
+struct Post {
+    id: i64,
+    title: String,
+    score: Option<i64>,
+}
+
+async fn setup_db() -> QueryExecutor {
+    let cfg = ExecutorConfig::builder()
+        .database_url("sqlite::memory:")
+        .max_connections(1)
+        .build();
+    let exec = QueryExecutor::connect(cfg).await.unwrap();
+
+    exec.as_sqlite_pool()
+        .unwrap()
+        .execute(
+            r#"
+            CREATE TABLE authors (
+                id INTEGER PRIMARY KEY,
+                active BOOLEAN NOT NULL
+            );
+            CREATE TABLE posts (
+                id INTEGER PRIMARY KEY,
+                author_id INTEGER NOT NULL,
+                title TEXT NOT NULL,
+                score INTEGER
+            );
+            INSERT INTO authors (id, active) VALUES (1, TRUE), (2, FALSE);
+            INSERT INTO posts (id, author_id, title, score) VALUES
+                (1, 1, 'a', 10),
+                (2, 1, 'b', NULL),
+                (3, 1, 'c', 10),
+                (4, 2, 'd', 5),
+                (5, 1, 'e', NULL),
+                (6, 1, 'f', 7),
+                (7, 1, 'g', 10),
+                (8, 2, 'h', 1);
+            "#,
+        )
+        .await
+        .unwrap();
+
+    exec
+}
+
+#[tokio::test]
+async fn paginate_after_walks_all_rows_with_mixed_directions_and_nulls() {
+    let exec = setup_db().await;
+
+    let expected: Vec<Post> = exec
+        .query()
+        .select(("posts.id", "title", "score"))
+        .from("posts")
+        .join("authors", col("authors.id").eq(col("posts.author_id")))
+        .r#where(col("authors.active").eq(val(true)))
+        .order_by("score")
+        .order_by_desc("posts.id")
+        .await
+        .unwrap();
+    assert_eq!(expected.len(), 6);
+
+    let mut seen = Vec::new();
+    let mut cursor: Option<KeysetCursor> = None;
+    let mut pages = 0;
+    loop {
+        let page = exec
+            .query::<Post>()
+            .select(("posts.id", "title", "score"))
+            .from("posts")
+            .join("authors", col("authors.id").eq(col("posts.author_id")))
+            .r#where(col("authors.active").eq(val(true)))
+            .order_by("score")
+            .order_by_desc("posts.id")
+            .limit(2)
+            .paginate_after(cursor.take())
+            .await
+            .unwrap();
+        pages += 1;
+        seen.extend(page.items);
+
+        match page.next_cursor {
+            // курсор отдаём клиенту строкой и принимаем обратно
+            Some(c) => cursor = Some(c.to_string().parse().unwrap()),
+            None => break,
+        }
+    }
+
+    assert_eq!(pages, 3);
+    assert_eq!(seen, expected);
+    // NULL-ы в начале (SQLite), внутри одинакового score — id по убыванию
+    let ids: Vec<_> = seen.iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![5, 2, 6, 7, 3, 1]);
+}
+
+#[tokio::test]
+async fn paginate_after_last_page_has_no_cursor() {
+    let exec = setup_db().await;
+
+    let page = exec
+        .query::<Post>()
+        .from("posts")
+        .order_by("id")
+        .limit(8)
+        .paginate_after(None)
+        .await
+        .unwrap();
+
+    assert_eq!(page.items.len(), 8);
+    assert!(page.next_cursor.is_none());
+}
+
+#[tokio::test]
+async fn paginate_after_inside_tx() {
+    let exec = setup_db().await;
+    let mut tx = exec.begin().await.unwrap();
+
+    let first = tx
+        .query::<Post>()
+        .from("posts")
+        .order_by_desc("id")
+        .limit(3)
+        .paginate_after(None)
+        .await
+        .unwrap();
+    let second = tx
+        .query::<Post>()
+        .from("posts")
+        .order_by_desc("id")
+        .limit(3)
+        .paginate_after(first.next_cursor)
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = second.items.iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![5, 4, 3]);
+
+    tx.rollback().await.unwrap();
+}
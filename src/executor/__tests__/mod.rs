@@ -21,3 +21,39 @@ mod transaction_utils;
 
 #[cfg(test)]
 mod parallel;
+
+#[cfg(test)]
+mod stream;
+
+#[cfg(test)]
+mod keyset;
+
+#[cfg(test)]
+mod paginate;
+
+#[cfg(test)]
+mod copy;
+
+#[cfg(test)]
+mod hooks;
+
+#[cfg(test)]
+mod trace;
+
+#[cfg(test)]
+mod timeout;
+
+#[cfg(test)]
+mod retry;
+
+#[cfg(test)]
+mod tx_options;
+
+#[cfg(test)]
+mod savepoint;
+
+#[cfg(test)]
+mod replica;
+
+#[cfg(test)]
+mod sql_cache;
@@ -0,0 +1,132 @@
+#![cfg(feature = "sqlite")]
+
+use sqlx::{Executor, FromRow};
+
+use crate::executor::{QueryExecutor, config::ExecutorConfig};
+use crate::expression::helpers::{col, val};
+
+#[derive(Debug, FromRow, PartialEq)]
+struct User {
+    id: i64,
+    name: String,
+    age: i32,
+}
+
+#[derive(Debug, FromRow)]
+struct Name {
+    name: String,
+}
+
+async fn setup_db() -> QueryExecutor {
+    let cfg = ExecutorConfig::builder()
+        .database_url("sqlite::memory:")
+        .max_connections(1)
+        .build();
+    let exec = QueryExecutor::connect(cfg).await.unwrap();
+
+    exec.as_sqlite_pool()
+        .unwrap()
+        .execute(
+            r#"
+            CREATE TABLE users (
+                id INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                age INTEGER NOT NULL
+            );
+            INSERT INTO users (name, age) VALUES
+                ('Alice', 30), ('Bob', 18), ('Cara', 25),
+                ('Dan', 41), ('Eve', 22), ('Alice', 35), ('Bob', 50);
+            "#,
+        )
+        .await
+        .unwrap();
+
+    exec
+}
+
+#[tokio::test]
+async fn paginate_returns_items_and_total() {
+    let exec = setup_db().await;
+
+    let page = exec
+        .query::<User>()
+        .from("users")
+        .r#where(col("age").gte(val(20)))
+        .order_by("id")
+        .paginate(2, 2)
+        .await
+        .unwrap();
+
+    assert_eq!(page.total, 6);
+    assert_eq!(page.page, 2);
+    assert_eq!(page.per_page, 2);
+    assert_eq!(page.last_page, 3);
+    let ids: Vec<_> = page.items.iter().map(|u| u.id).collect();
+    assert_eq!(ids, vec![4, 5]);
+}
+
+#[tokio::test]
+async fn paginate_counts_distinct_rows() {
+    let exec = setup_db().await;
+
+    let page = exec
+        .query::<Name>()
+        .from("users")
+        .distinct(("name",))
+        .order_by("name")
+        .paginate(1, 10)
+        .await
+        .unwrap();
+
+    assert_eq!(page.total, 5);
+    assert_eq!(
+        page.items
+            .iter()
+            .map(|n| n.name.as_str())
+            .collect::<Vec<_>>(),
+        ["Alice", "Bob", "Cara", "Dan", "Eve"]
+    );
+    assert_eq!(page.last_page, 1);
+}
+
+#[tokio::test]
+async fn paginate_past_the_end_is_empty() {
+    let exec = setup_db().await;
+
+    let page = exec
+        .query::<User>()
+        .from("users")
+        .order_by("id")
+        .paginate(5, 3)
+        .await
+        .unwrap();
+
+    assert_eq!(page.total, 7);
+    assert_eq!(page.last_page, 3);
+    assert!(page.items.is_empty());
+}
+
+#[tokio::test]
+async fn paginate_inside_tx_uses_tx_connection() {
+    let exec = setup_db().await;
+    let mut tx = exec.begin().await.unwrap();
+
+    tx.execute("INSERT INTO users (name, age) VALUES ('Finn', 60)", vec![])
+        .await
+        .unwrap();
+
+    let page = tx
+        .query::<User>()
+        .from("users")
+        .order_by("id")
+        .paginate(3, 3)
+        .await
+        .unwrap();
+
+    // незакоммиченная строка видна и в COUNT, и в выборке
+    assert_eq!(page.total, 8);
+    assert_eq!(page.items.len(), 2);
+    assert_eq!(page.items[1].name, "Finn");
+
+    tx.rollback().await.unwrap();
+}
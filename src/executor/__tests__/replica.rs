@@ -0,0 +1,159 @@
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use sqlx::{Connection, Executor, SqliteConnection};
+
+    use crate::executor::{DbPool, QueryExecutor, ReplicaStrategy, config::ExecutorConfig};
+    use crate::expression::helpers::{col, table, val};
+
+    /// Файловая база с одной строкой-меткой: по ней видно, откуда пришёл SELECT
+    async fn labeled_db(label: &str) -> String {
+        let ts = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let path: PathBuf = std::env::temp_dir().join(format!(
+            "knux_replica_{label}_{}_{ts}.db",
+            std::process::id()
+        ));
+        let url = format!("sqlite://{}?mode=rwc", path.to_string_lossy());
+        let mut conn = SqliteConnection::connect(&url).await.unwrap();
+        conn.execute("CREATE TABLE t (v TEXT NOT NULL)")
+            .await
+            .unwrap();
+        conn.execute(format!("INSERT INTO t VALUES ('{label}')").as_str())
+            .await
+            .unwrap();
+        conn.close().await.unwrap();
+        url
+    }
+
+    async fn connect(replicas: &[&str], strategy: ReplicaStrategy) -> QueryExecutor {
+        let mut cfg = ExecutorConfig::builder()
+            .database_url(labeled_db("primary").await)
+            .replica_strategy(strategy);
+        for label in replicas {
+            cfg = cfg.replica(labeled_db(label).await);
+        }
+        QueryExecutor::connect(cfg.build()).await.unwrap()
+    }
+
+    fn labels(rows: Vec<(String,)>) -> Vec<String> {
+        rows.into_iter().map(|(v,)| v).collect()
+    }
+
+    #[tokio::test]
+    async fn selects_go_to_replica_and_writes_to_primary() {
+        let exec = connect(&["replica"], ReplicaStrategy::RoundRobin).await;
+        assert_eq!(exec.replica_pools().len(), 1);
+
+        let rows = exec.query::<(String,)>().select("v").from("t").await;
+        assert_eq!(labels(rows.unwrap()), ["replica"]);
+
+        exec.query::<()>()
+            .into("t")
+            .insert((col("v"), val("written")))
+            .exec()
+            .await
+            .unwrap();
+        exec.query::<()>()
+            .update("t")
+            .set((col("v"), val("updated")))
+            .r#where(col("v").eq(val("primary")))
+            .exec()
+            .await
+            .unwrap();
+
+        // запись видна только на основном
+        let rows = exec
+            .query::<(String,)>()
+            .select("v")
+            .from("t")
+            .order_by("v")
+            .on_primary()
+            .await;
+        assert_eq!(labels(rows.unwrap()), ["updated", "written"]);
+        let rows = exec.query::<(String,)>().select("v").from("t").await;
+        assert_eq!(labels(rows.unwrap()), ["replica"]);
+
+        // всё внутри транзакции — на основном
+        let mut tx = exec.begin().await.unwrap();
+        let rows = tx
+            .query::<(String,)>()
+            .select("v")
+            .from("t")
+            .order_by("v")
+            .await;
+        assert_eq!(labels(rows.unwrap()), ["updated", "written"]);
+        tx.query::<()>()
+            .delete(table("t"))
+            .r#where(col("v").eq(val("written")))
+            .exec()
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let one = exec
+            .query::<(String,)>()
+            .select("v")
+            .from("t")
+            .on_primary()
+            .one()
+            .await;
+        assert_eq!(one.unwrap().0, "updated");
+    }
+
+    #[tokio::test]
+    async fn round_robin_alternates_replicas() {
+        let exec = connect(&["r1", "r2"], ReplicaStrategy::RoundRobin).await;
+
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            let rows = exec.query::<(String,)>().select("v").from("t").await;
+            seen.extend(labels(rows.unwrap()));
+        }
+        assert_eq!(seen, ["r1", "r2", "r1", "r2"]);
+
+        // клоны исполнителя делят счётчик
+        let other = exec.clone();
+        let a = other.query::<(String,)>().select("v").from("t").one().await;
+        let b = exec.query::<(String,)>().select("v").from("t").one().await;
+        assert_eq!([a.unwrap().0, b.unwrap().0], ["r1", "r2"]);
+    }
+
+    #[tokio::test]
+    async fn least_connections_skips_busy_replica() {
+        let exec = connect(&["r1", "r2"], ReplicaStrategy::LeastConnections).await;
+
+        #[allow(irrefutable_let_patterns)]
+        let DbPool::Sqlite(busy) = &exec.replica_pools()[0] else {
+            unreachable!()
+        };
+        // соединения возвращаются в пул асинхронно, и на второй реплике
+        // может быть занято до трёх — держим на первой заведомо больше
+        let mut held = Vec::new();
+        for _ in 0..4 {
+            held.push(busy.acquire().await.unwrap());
+        }
+        for _ in 0..3 {
+            let rows = exec.query::<(String,)>().select("v").from("t").await;
+            assert_eq!(labels(rows.unwrap()), ["r2"]);
+        }
+        drop(held);
+
+        // on_replica без реплик — тот же основной пул
+        let cfg = ExecutorConfig::builder()
+            .database_url(labeled_db("solo").await)
+            .build();
+        let solo = QueryExecutor::connect(cfg).await.unwrap();
+        let rows = solo
+            .query::<(String,)>()
+            .select("v")
+            .from("t")
+            .on_replica()
+            .await;
+        assert_eq!(labels(rows.unwrap()), ["solo"]);
+    }
+}
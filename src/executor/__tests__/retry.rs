@@ -0,0 +1,202 @@
+use std::time::Duration;
+
+use crate::executor::RetryPolicy;
+
+#[test]
+fn backoff_delays() {
+    let ms = Duration::from_millis;
+    assert_eq!(RetryPolicy::Immediate.delay(3), Duration::ZERO);
+    assert_eq!(RetryPolicy::Fixed(ms(20)).delay(3), ms(20));
+
+    let exp = RetryPolicy::Exponential {
+        base: ms(10),
+        max: ms(50),
+    };
+    let delays: Vec<_> = (1..=5).map(|n| exp.delay(n)).collect();
+    assert_eq!(delays, [ms(10), ms(20), ms(40), ms(50), ms(50)]);
+    assert_eq!(exp.delay(100), ms(50));
+}
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use sqlx::Executor;
+
+    use crate::executor::{Error, QueryExecutor, RetryPolicy, config::ExecutorConfig};
+
+    /// Файловая база: блокировку записи держит другое соединение.
+    /// `busy_timeout = 0` — занятая база сразу даёт SQLITE_BUSY.
+    async fn make_exec(name: &str) -> QueryExecutor {
+        let ts = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let db_path: PathBuf =
+            std::env::temp_dir().join(format!("knux_{name}_{}_{ts}.db", std::process::id()));
+        let cfg = ExecutorConfig::builder()
+            .database_url(format!("sqlite://{}?mode=rwc", db_path.to_string_lossy()))
+            .max_connections(2)
+            .after_connect_sql("PRAGMA busy_timeout = 0")
+            .tx_retry(RetryPolicy::Fixed(Duration::from_millis(5)))
+            .tx_max_attempts(3)
+            .build();
+        let exec = QueryExecutor::connect(cfg).await.unwrap();
+        exec.as_sqlite_pool()
+            .unwrap()
+            .execute("CREATE TABLE t (id INTEGER PRIMARY KEY, v TEXT NOT NULL)")
+            .await
+            .unwrap();
+        exec
+    }
+
+    async fn count(exec: &QueryExecutor) -> usize {
+        exec.fetch_typed::<(i64,)>("SELECT id FROM t", vec![])
+            .await
+            .unwrap()
+            .len()
+    }
+
+    #[tokio::test]
+    async fn commits_on_ok_and_rolls_back_on_err() {
+        let exec = make_exec("tx_closure").await;
+
+        let n = exec
+            .transaction(async |tx| {
+                tx.execute("INSERT INTO t (v) VALUES ('a')", vec![]).await?;
+                tx.execute("INSERT INTO t (v) VALUES ('b')", vec![]).await
+            })
+            .await
+            .unwrap();
+        assert_eq!(n, 1);
+        assert_eq!(count(&exec).await, 2);
+
+        let res: Result<(), _> = exec
+            .transaction(async |tx| {
+                tx.execute("INSERT INTO t (v) VALUES ('c')", vec![]).await?;
+                Err(Error::NotFound)
+            })
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+        assert_eq!(count(&exec).await, 2);
+    }
+
+    #[tokio::test]
+    async fn panic_rolls_back() {
+        let exec = make_exec("tx_panic").await;
+
+        let task = tokio::spawn({
+            let exec = exec.clone();
+            async move {
+                exec.transaction(async |tx| {
+                    tx.execute("INSERT INTO t (v) VALUES ('a')", vec![]).await?;
+                    panic!("boom");
+                    #[allow(unreachable_code)]
+                    Ok(())
+                })
+                .await
+            }
+        });
+        assert!(task.await.unwrap_err().is_panic());
+        assert_eq!(count(&exec).await, 0);
+    }
+
+    #[tokio::test]
+    async fn retries_busy_database() {
+        let exec = make_exec("tx_retry").await;
+
+        // вторая транзакция держит блокировку записи до конца первой попытки
+        let mut locker = Some(exec.as_sqlite_pool().unwrap().begin().await.unwrap());
+        locker
+            .as_mut()
+            .unwrap()
+            .execute("INSERT INTO t (v) VALUES ('lock')")
+            .await
+            .unwrap();
+
+        let mut attempts = 0;
+        exec.transaction(async |tx| {
+            attempts += 1;
+            let res = tx.execute("INSERT INTO t (v) VALUES ('a')", vec![]).await;
+            if let Some(locker) = locker.take() {
+                let err = res.as_ref().unwrap_err();
+                assert!(err.is_retryable(), "{err:?}");
+                locker.rollback().await.unwrap();
+            }
+            res
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(attempts, 2);
+        assert_eq!(count(&exec).await, 1);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let exec = make_exec("tx_give_up").await;
+
+        let mut locker = exec.as_sqlite_pool().unwrap().begin().await.unwrap();
+        locker
+            .execute("INSERT INTO t (v) VALUES ('lock')")
+            .await
+            .unwrap();
+
+        let mut attempts = 0;
+        let res = exec
+            .transaction(async |tx| {
+                attempts += 1;
+                tx.execute("INSERT INTO t (v) VALUES ('a')", vec![]).await
+            })
+            .await;
+        assert!(res.unwrap_err().is_retryable());
+        assert_eq!(attempts, 3);
+        locker.rollback().await.unwrap();
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod postgres {
+    use crate::executor::{QueryExecutor, RetryPolicy, config::ExecutorConfig};
+
+    #[tokio::test]
+    #[ignore = "нужен локальный PostgreSQL (KNUX_PG_URL)"]
+    async fn retries_serialization_failure_and_deadlock() {
+        let url = std::env::var("KNUX_PG_URL")
+            .unwrap_or_else(|_| "postgres://postgres@127.0.0.1:5432/postgres".into());
+        let cfg = ExecutorConfig::builder()
+            .database_url(&url)
+            .tx_retry(RetryPolicy::Immediate)
+            .tx_max_attempts(3)
+            .build();
+        let exec = QueryExecutor::connect(cfg).await.unwrap();
+
+        let mut codes = ["40001", "40P01"].into_iter();
+        let mut attempts = 0;
+        exec.transaction(async |tx| {
+            attempts += 1;
+            if let Some(code) = codes.next() {
+                let sql = format!(
+                    "DO $$ BEGIN RAISE EXCEPTION 'conflict' USING ERRCODE = '{code}'; END $$"
+                );
+                tx.execute(&sql, vec![]).await?;
+            }
+            Ok(())
+        })
+        .await
+        .unwrap();
+        assert_eq!(attempts, 3);
+
+        // прочие ошибки не повторяются
+        let mut attempts = 0;
+        let res = exec
+            .transaction(async |tx| {
+                attempts += 1;
+                tx.execute("SELECT 1 / 0", vec![]).await
+            })
+            .await;
+        assert!(res.is_err());
+        assert_eq!(attempts, 1);
+    }
+}
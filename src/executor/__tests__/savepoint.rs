@@ -0,0 +1,150 @@
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use sqlx::Executor;
+
+    use crate::executor::{Error, QueryExecutor, config::ExecutorConfig};
+
+    async fn setup_db() -> QueryExecutor {
+        let cfg = ExecutorConfig::builder()
+            .database_url("sqlite::memory:")
+            .max_connections(1)
+            .build();
+        let exec = QueryExecutor::connect(cfg).await.unwrap();
+        exec.as_sqlite_pool()
+            .unwrap()
+            .execute("CREATE TABLE t (id INTEGER PRIMARY KEY)")
+            .await
+            .unwrap();
+        exec
+    }
+
+    async fn ids(exec: &QueryExecutor) -> Vec<i64> {
+        exec.fetch_typed::<(i64,)>("SELECT id FROM t ORDER BY id", vec![])
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(id,)| id)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn rollback_to_keeps_outer_transaction() {
+        let exec = setup_db().await;
+
+        let mut tx = exec.begin().await.unwrap();
+        tx.execute("INSERT INTO t VALUES (1)", vec![])
+            .await
+            .unwrap();
+        tx.savepoint("row_2").await.unwrap();
+        tx.execute("INSERT INTO t VALUES (2)", vec![])
+            .await
+            .unwrap();
+        tx.rollback_to("row_2").await.unwrap();
+
+        // точка остаётся после отката — её можно использовать снова
+        tx.execute("INSERT INTO t VALUES (3)", vec![])
+            .await
+            .unwrap();
+        tx.rollback_to("row_2").await.unwrap();
+        tx.release("row_2").await.unwrap();
+        assert!(tx.rollback_to("row_2").await.is_err());
+
+        tx.commit().await.unwrap();
+        assert_eq!(ids(&exec).await, [1]);
+    }
+
+    #[tokio::test]
+    async fn with_savepoint_skips_failed_rows() {
+        let exec = setup_db().await;
+
+        let mut tx = exec.begin().await.unwrap();
+        let mut skipped = Vec::new();
+        for id in [1, 2, 2, 3, 1] {
+            let res = tx
+                .with_savepoint(async |tx| {
+                    tx.execute("INSERT INTO t VALUES (?)", vec![id.into()])
+                        .await
+                })
+                .await;
+            if res.is_err() {
+                skipped.push(id);
+            }
+        }
+        tx.commit().await.unwrap();
+
+        assert_eq!(skipped, [2, 1]);
+        assert_eq!(ids(&exec).await, [1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn nested_with_savepoint_rolls_back_inner_only() {
+        let exec = setup_db().await;
+
+        let mut tx = exec.begin().await.unwrap();
+        tx.with_savepoint(async |tx| {
+            tx.execute("INSERT INTO t VALUES (1)", vec![]).await?;
+            let inner: Result<(), _> = tx
+                .with_savepoint(async |tx| {
+                    tx.execute("INSERT INTO t VALUES (2)", vec![]).await?;
+                    Err(Error::NotFound)
+                })
+                .await;
+            assert!(matches!(inner, Err(Error::NotFound)));
+            Ok(())
+        })
+        .await
+        .unwrap();
+        tx.commit().await.unwrap();
+
+        assert_eq!(ids(&exec).await, [1]);
+    }
+
+    #[tokio::test]
+    async fn with_savepoint_keeps_closure_error_when_rollback_fails() {
+        let exec = setup_db().await;
+
+        let mut tx = exec.begin().await.unwrap();
+        let res: Result<(), _> = tx
+            .with_savepoint(async |tx| {
+                // точка исчезает вместе с транзакцией — откат к ней упадёт
+                tx.execute("ROLLBACK", vec![]).await?;
+                Err(Error::NotFound)
+            })
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)), "{res:?}");
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod postgres {
+    use crate::executor::{QueryExecutor, config::ExecutorConfig};
+
+    #[tokio::test]
+    #[ignore = "нужен локальный PostgreSQL (KNUX_PG_URL)"]
+    async fn with_savepoint_recovers_aborted_transaction() {
+        let url = std::env::var("KNUX_PG_URL")
+            .unwrap_or_else(|_| "postgres://postgres@127.0.0.1:5432/postgres".into());
+        let cfg = ExecutorConfig::builder().database_url(&url).build();
+        let exec = QueryExecutor::connect(cfg).await.unwrap();
+
+        let mut tx = exec.begin().await.unwrap();
+        tx.execute("CREATE TEMP TABLE knux_sp (id int PRIMARY KEY)", vec![])
+            .await
+            .unwrap();
+        for id in [1, 1, 2] {
+            // без точки сохранения ошибка оборвала бы всю транзакцию
+            let _ = tx
+                .with_savepoint(async |tx| {
+                    tx.execute("INSERT INTO knux_sp VALUES ($1)", vec![id.into()])
+                        .await
+                })
+                .await;
+        }
+        let rows: Vec<(i32,)> = tx
+            .fetch_typed("SELECT id FROM knux_sp ORDER BY id", vec![])
+            .await
+            .unwrap();
+        assert_eq!(rows, [(1,), (2,)]);
+        tx.rollback().await.unwrap();
+    }
+}
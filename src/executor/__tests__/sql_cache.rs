@@ -0,0 +1,197 @@
+use crate::executor::SqlCache;
+use crate::expression::helpers::{col, val};
+use crate::param::Param;
+use crate::query_builder::QueryBuilder;
+use crate::renderer::Dialect;
+use crate::type_helpers::QBClosureHelper;
+
+type QB = QueryBuilder<'static, ()>;
+
+/// WITH, подзапрос во FROM, JOIN, WHERE/HAVING/ORDER BY — всё с параметрами
+fn report(min_total: i32, name: &str, ids: [i64; 3], limit: u64) -> QB {
+    let sub = QB::new_empty()
+        .select(("user_id", "total"))
+        .from(("orders",))
+        .r#where(col("total").gt(val(min_total)))
+        .r#as("o");
+    QB::new_empty()
+        .dialect(Dialect::Postgres)
+        .with(
+            "vip",
+            QB::new_empty()
+                .select(("id",))
+                .from("users")
+                .r#where(col("name").eq(val(name.to_owned()))),
+        )
+        .select((col("u.id"), val(1i32)))
+        .from(("users", sub))
+        .join(
+            "vip",
+            col("vip.id")
+                .eq(col("users.id"))
+                .and(col("u.id").gt(val(0i64))),
+        )
+        .where_in(col("users.id"), (val(ids[0]), val(ids[1]), val(ids[2])))
+        .group_by(("u.id",))
+        .having(col("u.id").gt(val(min_total)))
+        .order_by((col("u.id"),))
+        .limit(limit)
+}
+
+fn cached(qb: QB, cache: &SqlCache) -> (String, Vec<Param>) {
+    let mut qb = qb;
+    qb.sql_cache = Some(cache.clone());
+    qb.to_sql().expect("to_sql")
+}
+
+fn debug(params: &[Param]) -> String {
+    format!("{params:?}")
+}
+
+#[test]
+fn hit_reuses_sql_and_collects_params_in_order() {
+    let cache = SqlCache::new(8);
+
+    let (sql1, p1) = cached(report(10, "ann", [1, 2, 3], 5), &cache);
+    let (sql2, p2) = cached(report(20, "bob", [4, 5, 6], 5), &cache);
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.len), (1, 1, 1));
+
+    // попадание выдаёт те же SQL и параметры, что и рендер без кэша
+    let (plain, plain_params) = report(20, "bob", [4, 5, 6], 5).to_sql().unwrap();
+    assert_eq!(sql1, sql2);
+    assert_eq!(sql2, plain);
+    assert_eq!(debug(&p2), debug(&plain_params));
+    assert_ne!(debug(&p1), debug(&p2));
+}
+
+#[test]
+fn different_shape_misses() {
+    let cache = SqlCache::new(8);
+
+    cached(report(10, "ann", [1, 2, 3], 5), &cache);
+    // другой LIMIT — другая форма
+    let (sql, _) = cached(report(10, "ann", [1, 2, 3], 6), &cache);
+    assert!(sql.ends_with("LIMIT 6"), "{sql}");
+    // другой диалект
+    cached(
+        report(10, "ann", [1, 2, 3], 5).dialect(Dialect::SQLite),
+        &cache,
+    );
+    // другой состав WHERE
+    cached(QB::new_empty().select(("id",)).from("users"), &cache);
+    let (sql, params) = cached(
+        QB::new_empty()
+            .select(("id",))
+            .from("users")
+            .r#where(col("id").eq(val(1i32))),
+        &cache,
+    );
+    assert!(sql.contains("WHERE"), "{sql}");
+    assert_eq!(params.len(), 1);
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.len), (0, 5, 5));
+}
+
+#[test]
+fn capacity_evicts_least_recently_used() {
+    let cache = SqlCache::new(1);
+    let a = || QB::new_empty().select(("id",)).from("a");
+    let b = || QB::new_empty().select(("id",)).from("b");
+
+    cached(a(), &cache);
+    cached(b(), &cache);
+    let (sql, _) = cached(a(), &cache);
+    assert!(sql.contains("a"), "{sql}");
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses), (0, 3));
+    assert_eq!((stats.len, stats.capacity), (1, 1));
+}
+
+#[test]
+fn closure_subquery_is_not_cached() {
+    let cache = SqlCache::new(8);
+    let sub: QBClosureHelper<()> = |q| q.select(("id",)).from("users").r#as("s");
+    let qb = || QB::new_empty().select(("id",)).from((sub,));
+
+    let (sql1, _) = cached(qb(), &cache);
+    let (sql2, _) = cached(qb(), &cache);
+    assert_eq!(sql1, sql2);
+    assert_eq!(cache.stats().len, 0);
+    assert_eq!(cache.stats().hits + cache.stats().misses, 0);
+}
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use sqlx::Executor;
+
+    use crate::executor::{DbPool, QueryExecutor, config::ExecutorConfig};
+    use crate::expression::helpers::{col, val};
+
+    async fn exec() -> QueryExecutor {
+        let cfg = ExecutorConfig::builder()
+            .database_url("sqlite::memory:")
+            .max_connections(1)
+            .sql_cache(16)
+            .build();
+        let exec = QueryExecutor::connect(cfg).await.unwrap();
+        exec.as_sqlite_pool()
+            .unwrap()
+            .execute("CREATE TABLE t (id INTEGER NOT NULL, v TEXT NOT NULL)")
+            .await
+            .unwrap();
+        for (id, v) in [(1, "a"), (2, "b"), (3, "c")] {
+            exec.query::<()>()
+                .into("t")
+                .insert((col("id"), val(id), col("v"), val(v)))
+                .exec()
+                .await
+                .unwrap();
+        }
+        exec
+    }
+
+    #[tokio::test]
+    async fn executor_and_tx_share_cache() {
+        let exec = exec().await;
+
+        for (id, expected) in [(1, "a"), (2, "b"), (3, "c")] {
+            let row = exec
+                .query::<(String,)>()
+                .select("v")
+                .from("t")
+                .r#where(col("id").eq(val(id)))
+                .one()
+                .await
+                .unwrap();
+            assert_eq!(row.0, expected);
+        }
+        let stats = exec.sql_cache_stats().unwrap();
+        assert_eq!((stats.hits, stats.misses, stats.len), (2, 1, 1));
+
+        let mut tx = exec.begin().await.unwrap();
+        let row = tx
+            .query::<(String,)>()
+            .select("v")
+            .from("t")
+            .r#where(col("id").eq(val(2)))
+            .one()
+            .await
+            .unwrap();
+        assert_eq!(row.0, "b");
+        tx.commit().await.unwrap();
+        assert_eq!(exec.sql_cache_stats().unwrap().hits, 3);
+
+        // DML в кэш не попадает
+        assert_eq!(exec.sql_cache_stats().unwrap().len, 1);
+        assert!(exec.clone().with_sql_cache(4).sql_cache_stats().is_some());
+        let pool = DbPool::Sqlite(exec.as_sqlite_pool().unwrap().clone());
+        assert!(
+            QueryExecutor::from_pool(pool, None)
+                .sql_cache_stats()
+                .is_none()
+        );
+    }
+}
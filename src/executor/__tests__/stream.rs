@@ -0,0 +1,163 @@
+#![cfg(feature = "sqlite")]
+
+use futures_util::TryStreamExt;
+use sqlx::{Executor, FromRow};
+
+use crate::executor::{Error, QueryExecutor, config::ExecutorConfig};
+use crate::expression::helpers::{col, table, val};
+
+#[derive(Debug, FromRow, PartialEq)]
+struct User {
+    id: i64,
+    name: String,
+    age: i32,
+    is_active: bool,
+}
+
+async fn setup_db() -> QueryExecutor {
+    let cfg = ExecutorConfig::builder()
+        .database_url("sqlite::memory:")
+        .max_connections(1)
+        .build();
+    let exec = QueryExecutor::connect(cfg).await.unwrap();
+
+    exec.as_sqlite_pool()
+        .unwrap()
+        .execute(
+            r#"
+            CREATE TABLE users (
+                id INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                age INTEGER NOT NULL,
+                is_active BOOLEAN NOT NULL
+            );
+            INSERT INTO users (name, age, is_active) VALUES
+                ('Alice', 30, TRUE),
+                ('Bob',   18, FALSE),
+                ('Cara',  25, TRUE);
+            "#,
+        )
+        .await
+        .unwrap();
+
+    exec
+}
+
+#[tokio::test]
+async fn stream_select_over_pool_yields_rows_in_order() {
+    let exec = setup_db().await;
+
+    let mut rows = exec
+        .query::<User>()
+        .select("*")
+        .from("users")
+        .r#where(col("age").gte(val(20)))
+        .order_by("id")
+        .stream();
+
+    let mut names = Vec::new();
+    while let Some(u) = rows.try_next().await.unwrap() {
+        names.push(u.name);
+    }
+    assert_eq!(names, vec!["Alice", "Cara"]);
+}
+
+#[tokio::test]
+async fn stream_select_inside_tx_sees_uncommitted_rows() {
+    let exec = setup_db().await;
+    let mut tx = exec.begin().await.unwrap();
+
+    tx.execute(
+        "INSERT INTO users (name, age, is_active) VALUES ('Dan', 40, 1)",
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    let users: Vec<User> = tx
+        .query()
+        .select("*")
+        .from("users")
+        .order_by("id")
+        .stream()
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(users.len(), 4);
+    assert_eq!(users[3].name, "Dan");
+
+    tx.rollback().await.unwrap();
+}
+
+#[tokio::test]
+async fn stream_insert_returning_yields_inserted_rows() {
+    let exec = setup_db().await;
+
+    let inserted: Vec<User> = exec
+        .query()
+        .into("users")
+        .columns((col("name"), col("age"), col("is_active")))
+        .insert((
+            val("Eve"),
+            val(22),
+            val(true),
+            val("Finn"),
+            val(33),
+            val(false),
+        ))
+        .returning_all()
+        .stream()
+        .try_collect()
+        .await
+        .unwrap();
+
+    let names: Vec<_> = inserted.iter().map(|u| u.name.as_str()).collect();
+    assert_eq!(names, vec!["Eve", "Finn"]);
+}
+
+#[tokio::test]
+async fn stream_delete_returning_yields_deleted_rows() {
+    let exec = setup_db().await;
+
+    let deleted: Vec<User> = exec
+        .query()
+        .delete(table("users"))
+        .r#where(col("is_active").eq(val(false)))
+        .returning("*")
+        .stream()
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(deleted.len(), 1);
+    assert_eq!(deleted[0].name, "Bob");
+}
+
+#[tokio::test]
+async fn stream_update_without_returning_is_error() {
+    let exec = setup_db().await;
+
+    let err = exec
+        .query::<User>()
+        .update("users")
+        .set((col("age"), val(1)))
+        .stream()
+        .try_next()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::Unsupported(_)));
+}
+
+#[tokio::test]
+async fn stream_without_connection_reports_missing_connection() {
+    let err = crate::query_builder::QueryBuilder::<User>::new_empty()
+        .select("*")
+        .from("users")
+        .stream()
+        .try_next()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::MissingConnection));
+}
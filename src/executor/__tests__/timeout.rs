@@ -0,0 +1,211 @@
+#[cfg(feature = "mysql")]
+#[test]
+fn mysql_hint_goes_after_select_only() {
+    use std::time::Duration;
+
+    use crate::executor::timeout::mysql_hint;
+
+    let t = Duration::from_millis(250);
+    assert_eq!(
+        mysql_hint("SELECT id FROM t", t),
+        "SELECT /*+ MAX_EXECUTION_TIME(250) */ id FROM t"
+    );
+    assert_eq!(
+        mysql_hint("  select\n*", t),
+        "  select /*+ MAX_EXECUTION_TIME(250) */\n*"
+    );
+    // 0 на сервере — «без лимита», поэтому не меньше 1 мс
+    assert_eq!(
+        mysql_hint("SELECT 1", Duration::from_micros(10)),
+        "SELECT /*+ MAX_EXECUTION_TIME(1) */ 1"
+    );
+    assert_eq!(mysql_hint("UPDATE t SET a = 1", t), "UPDATE t SET a = 1");
+    assert_eq!(mysql_hint("SELECTED", t), "SELECTED");
+}
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use std::time::Duration;
+
+    use futures_util::StreamExt;
+    use sqlx::Executor;
+
+    use crate::executor::{Error, QueryExecutor, config::ExecutorConfig};
+
+    const LIMIT: Duration = Duration::from_millis(50);
+
+    /// Одно соединение: после таймаута им же должен выполниться следующий запрос
+    async fn setup_db() -> QueryExecutor {
+        let cfg = ExecutorConfig::builder()
+            .database_url("sqlite::memory:")
+            .max_connections(1)
+            .build();
+        let exec = QueryExecutor::connect(cfg).await.unwrap();
+        exec.as_sqlite_pool()
+            .unwrap()
+            .execute(
+                r#"
+                CREATE TABLE t (id INTEGER);
+                INSERT INTO t VALUES (1), (2), (3);
+                CREATE VIEW endless AS
+                    WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c)
+                    SELECT x FROM c;
+                "#,
+            )
+            .await
+            .unwrap();
+        exec
+    }
+
+    fn assert_timeout<T: std::fmt::Debug>(res: Result<T, Error>) {
+        match res {
+            Err(Error::Timeout(d)) => assert_eq!(d, LIMIT),
+            other => panic!("expected Error::Timeout, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn builder_timeout_cancels_query_and_keeps_connection() {
+        let exec = setup_db().await;
+
+        let res: Result<Vec<(i64,)>, _> = exec
+            .query()
+            .select("x")
+            .from("endless")
+            .timeout(LIMIT)
+            .await;
+        assert_timeout(res);
+
+        let rows: Vec<(i64,)> = exec
+            .query()
+            .select("id")
+            .from("t")
+            .timeout(Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn executor_default_covers_fetch_typed_and_transactions() {
+        let exec = setup_db().await.with_query_timeout(LIMIT);
+
+        assert_timeout(
+            exec.fetch_typed::<(i64,)>("SELECT x FROM endless", vec![])
+                .await,
+        );
+
+        let mut tx = exec.begin().await.unwrap();
+        let res: Result<Vec<(i64,)>, _> = tx.query().select("x").from("endless").await;
+        assert_timeout(res);
+        assert_eq!(
+            tx.execute("DELETE FROM t WHERE id = 1", vec![])
+                .await
+                .unwrap(),
+            1
+        );
+        tx.rollback().await.unwrap();
+
+        let n: Vec<(i64,)> = exec.fetch_typed("SELECT id FROM t", vec![]).await.unwrap();
+        assert_eq!(n.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn stream_ends_with_timeout_error() {
+        let exec = setup_db().await;
+
+        let items: Vec<_> = exec
+            .query::<(i64,)>()
+            .select("x")
+            .from("endless")
+            .timeout(LIMIT)
+            .stream()
+            .collect()
+            .await;
+        let (last, rows) = items.split_last().unwrap();
+        assert!(rows.iter().all(Result::is_ok));
+        assert!(matches!(last, Err(Error::Timeout(d)) if *d == LIMIT));
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod postgres {
+    use std::time::Duration;
+
+    use crate::executor::{Error, QueryExecutor, config::ExecutorConfig};
+
+    async fn pg_exec() -> QueryExecutor {
+        let url = std::env::var("KNUX_PG_URL")
+            .unwrap_or_else(|_| "postgres://postgres@127.0.0.1:5432/postgres".into());
+        let cfg = ExecutorConfig::builder()
+            .database_url(&url)
+            .max_connections(1)
+            .build();
+        QueryExecutor::connect(cfg).await.unwrap()
+    }
+
+    async fn statement_timeout(exec: &QueryExecutor) -> String {
+        let (v,): (String,) = sqlx::query_as("SHOW statement_timeout")
+            .fetch_one(exec.as_pg_pool().unwrap())
+            .await
+            .unwrap();
+        v
+    }
+
+    #[tokio::test]
+    #[ignore = "нужен локальный PostgreSQL (KNUX_PG_URL)"]
+    async fn server_limit_does_not_leak_into_pool() {
+        let exec = pg_exec().await;
+        let before = statement_timeout(&exec).await;
+
+        // тот же пул из одного соединения
+        let res = exec
+            .clone()
+            .with_query_timeout(Duration::from_millis(100))
+            .fetch_typed::<(i32,)>("SELECT 1 FROM pg_sleep(2)", vec![])
+            .await;
+        assert!(matches!(res, Err(Error::Timeout(_))), "{res:?}");
+
+        assert_eq!(statement_timeout(&exec).await, before);
+    }
+
+    #[tokio::test]
+    #[ignore = "нужен локальный PostgreSQL (KNUX_PG_URL)"]
+    async fn server_limit_is_restored_inside_transaction() {
+        let exec = pg_exec().await;
+        let mut tx = exec.begin().await.unwrap();
+        let (before,): (String,) = tx
+            .fetch_typed("SHOW statement_timeout", vec![])
+            .await
+            .unwrap()
+            .remove(0);
+
+        let rows: Vec<(String,)> = tx
+            .query()
+            .select("nspname")
+            .from("pg_namespace")
+            .limit(1)
+            .timeout(Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+
+        // ошибка декодирования не обрывает транзакцию — лимит всё равно возвращается
+        let bad: Result<Vec<(i32,)>, _> = tx
+            .query()
+            .select("nspname")
+            .from("pg_namespace")
+            .limit(1)
+            .timeout(Duration::from_secs(5))
+            .await;
+        assert!(bad.is_err());
+
+        let (after,): (String,) = tx
+            .fetch_typed("SHOW statement_timeout", vec![])
+            .await
+            .unwrap()
+            .remove(0);
+        assert_eq!(after, before);
+        tx.rollback().await.unwrap();
+    }
+}
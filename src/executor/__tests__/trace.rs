@@ -0,0 +1,94 @@
+#![cfg(all(feature = "sqlite", feature = "tracing"))]
+
+use std::sync::{Arc, Mutex};
+
+use sqlx::Executor;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+use crate::executor::{QueryExecutor, config::ExecutorConfig};
+use crate::expression::helpers::{col, val};
+
+/// Собирает поля спанов `knux.query` в строки `name=value`
+#[derive(Clone, Default)]
+struct Fields(Arc<Mutex<Vec<String>>>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("{}={value:?}", field.name()));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("{}={value}", field.name()));
+    }
+}
+
+struct Collect(Fields);
+
+impl Subscriber for Collect {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        if span.metadata().name() == "knux.query" {
+            span.record(&mut self.0.clone());
+        }
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, values: &Record<'_>) {
+        values.record(&mut self.0.clone());
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[tokio::test]
+async fn query_span_records_statement_params_and_rows() {
+    let cfg = ExecutorConfig::builder()
+        .database_url("sqlite::memory:")
+        .max_connections(1)
+        .build();
+    let exec = QueryExecutor::connect(cfg).await.unwrap();
+    exec.as_sqlite_pool()
+        .unwrap()
+        .execute("CREATE TABLE t (id INTEGER); INSERT INTO t VALUES (1), (2), (3);")
+        .await
+        .unwrap();
+
+    let fields = Fields::default();
+    let _guard = tracing::subscriber::set_default(Collect(fields.clone()));
+
+    let rows: Vec<(i64,)> = exec
+        .query()
+        .select("id")
+        .from("t")
+        .r#where(col("id").gt(val(1)))
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 2);
+
+    let got = fields.0.lock().unwrap().clone();
+    assert!(got.contains(&"db.system=sqlite".to_string()), "{got:?}");
+    assert!(
+        got.iter().any(|f| f.starts_with("db.statement=SELECT")),
+        "{got:?}"
+    );
+    assert!(got.contains(&"db.params=1".to_string()), "{got:?}");
+    assert!(got.contains(&"db.rows=2".to_string()), "{got:?}");
+    assert!(got.iter().any(|f| f.starts_with("elapsed_ms=")), "{got:?}");
+}
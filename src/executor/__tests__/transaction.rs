@@ -523,3 +523,54 @@ async fn tx_rollback_is_idempotent_ok() {
     tx.rollback().await.unwrap();
     tx.rollback().await.unwrap(); // повторный no-op
 }
+
+// 6) FOR UPDATE SKIP LOCKED в транзакции: в SQLite клауза опускается, запрос выполняется
+#[tokio::test]
+async fn tx_for_update_skip_locked_runs_on_sqlite() {
+    let db = make_exec("tx_lock", 1).await;
+    setup_schema_and_seed(&db).await;
+
+    let mut tx = db.begin().await.unwrap();
+    let picked: Vec<User> = tx
+        .query()
+        .select("*")
+        .from("users")
+        .r#where(col("is_active").eq(val(true)))
+        .order_by("id")
+        .limit(1)
+        .for_update()
+        .skip_locked()
+        .await
+        .unwrap();
+    assert_eq!(picked.len(), 1);
+    tx.commit().await.unwrap();
+}
+
+#[tokio::test]
+async fn tx_chunked_insert_returning_concatenates_rows() {
+    let db = make_exec("tx_chunked", 1).await;
+    setup_schema_and_seed(&db).await;
+
+    let mut tx = db.begin().await.unwrap();
+    let ids: Vec<(i64,)> = tx
+        .query()
+        .into("users")
+        .columns((col("name"), col("age"), col("is_active")))
+        .insert((
+            val("u1"),
+            val(20),
+            val(true),
+            val("u2"),
+            val(21),
+            val(true),
+            val("u3"),
+            val(22),
+            val(false),
+        ))
+        .returning((col("id"),))
+        .chunked(2)
+        .await
+        .unwrap();
+    assert_eq!(ids.len(), 3);
+    tx.commit().await.unwrap();
+}
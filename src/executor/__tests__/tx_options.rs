@@ -0,0 +1,180 @@
+use crate::executor::{Error, IsolationLevel, TxOptions};
+use crate::renderer::Dialect;
+
+fn sql(opts: TxOptions, dialect: Dialect) -> String {
+    opts.begin_sql(dialect).unwrap().unwrap()
+}
+
+#[test]
+fn default_options_keep_plain_begin() {
+    for dialect in [Dialect::Postgres, Dialect::MySQL, Dialect::SQLite] {
+        assert_eq!(TxOptions::default().begin_sql(dialect).unwrap(), None);
+    }
+}
+
+#[test]
+fn postgres_begin_carries_all_options() {
+    let report = TxOptions::default()
+        .isolation(IsolationLevel::RepeatableRead)
+        .read_only();
+    assert_eq!(
+        sql(report, Dialect::Postgres),
+        "BEGIN ISOLATION LEVEL REPEATABLE READ READ ONLY"
+    );
+
+    let deferred = TxOptions::default()
+        .isolation(IsolationLevel::Serializable)
+        .read_only()
+        .deferrable();
+    assert_eq!(
+        sql(deferred, Dialect::Postgres),
+        "BEGIN ISOLATION LEVEL SERIALIZABLE READ ONLY DEFERRABLE"
+    );
+
+    let bad = TxOptions::default().read_only().deferrable();
+    assert!(matches!(
+        bad.begin_sql(Dialect::Postgres),
+        Err(Error::InvalidTxOptions(_))
+    ));
+}
+
+#[test]
+fn mysql_sets_isolation_before_start() {
+    let ledger = TxOptions::default().isolation(IsolationLevel::Serializable);
+    assert_eq!(
+        sql(ledger, Dialect::MySQL),
+        "SET TRANSACTION ISOLATION LEVEL SERIALIZABLE; START TRANSACTION"
+    );
+    assert_eq!(
+        sql(TxOptions::default().read_only(), Dialect::MySQL),
+        "START TRANSACTION READ ONLY"
+    );
+
+    let err = TxOptions::default()
+        .isolation(IsolationLevel::Serializable)
+        .read_only()
+        .deferrable()
+        .begin_sql(Dialect::MySQL)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "DEFERRABLE transactions are not supported by mysql"
+    );
+}
+
+#[test]
+fn sqlite_maps_isolation_to_lock_mode() {
+    let with = |level| TxOptions::default().isolation(level);
+    assert_eq!(
+        sql(with(IsolationLevel::Serializable), Dialect::SQLite),
+        "BEGIN EXCLUSIVE"
+    );
+    assert_eq!(
+        sql(with(IsolationLevel::RepeatableRead), Dialect::SQLite),
+        "BEGIN IMMEDIATE"
+    );
+    assert_eq!(
+        sql(with(IsolationLevel::ReadCommitted), Dialect::SQLite),
+        "BEGIN DEFERRED"
+    );
+    assert!(matches!(
+        with(IsolationLevel::Serializable)
+            .read_only()
+            .begin_sql(Dialect::SQLite),
+        Err(Error::UnsupportedTxOption {
+            option: "READ ONLY",
+            dialect: Dialect::SQLite
+        })
+    ));
+    assert!(matches!(
+        TxOptions::default().deferrable().begin_sql(Dialect::SQLite),
+        Err(Error::UnsupportedTxOption {
+            option: "DEFERRABLE",
+            dialect: Dialect::SQLite
+        })
+    ));
+}
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use sqlx::Executor;
+
+    use crate::executor::{
+        Error, IsolationLevel, QueryExecutor, TxOptions, config::ExecutorConfig,
+    };
+
+    #[tokio::test]
+    async fn begin_with_runs_sqlite_lock_modes() {
+        let cfg = ExecutorConfig::builder()
+            .database_url("sqlite::memory:")
+            .max_connections(1)
+            .build();
+        let exec = QueryExecutor::connect(cfg).await.unwrap();
+        exec.as_sqlite_pool()
+            .unwrap()
+            .execute("CREATE TABLE t (id INTEGER)")
+            .await
+            .unwrap();
+
+        for level in [IsolationLevel::Serializable, IsolationLevel::RepeatableRead] {
+            let opts = TxOptions::default().isolation(level);
+            let mut tx = exec.begin_with(opts).await.unwrap();
+            tx.execute("INSERT INTO t VALUES (1)", vec![])
+                .await
+                .unwrap();
+            tx.commit().await.unwrap();
+        }
+
+        let n = exec
+            .transaction_with(
+                TxOptions::default().isolation(IsolationLevel::Serializable),
+                async |tx| tx.execute("DELETE FROM t", vec![]).await,
+            )
+            .await
+            .unwrap();
+        assert_eq!(n, 2);
+
+        let err = exec
+            .begin_with(TxOptions::default().deferrable())
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(err, Error::UnsupportedTxOption { .. }));
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod postgres {
+    use crate::executor::{IsolationLevel, QueryExecutor, TxOptions, config::ExecutorConfig};
+
+    #[tokio::test]
+    #[ignore = "нужен локальный PostgreSQL (KNUX_PG_URL)"]
+    async fn begin_with_sets_isolation_and_read_only() {
+        let url = std::env::var("KNUX_PG_URL")
+            .unwrap_or_else(|_| "postgres://postgres@127.0.0.1:5432/postgres".into());
+        let cfg = ExecutorConfig::builder().database_url(&url).build();
+        let exec = QueryExecutor::connect(cfg).await.unwrap();
+
+        let opts = TxOptions::default()
+            .isolation(IsolationLevel::RepeatableRead)
+            .read_only();
+        let mut tx = exec.begin_with(opts).await.unwrap();
+        let rows: Vec<(String, String)> = tx
+            .fetch_typed(
+                "SELECT current_setting('transaction_isolation'), \
+                 current_setting('transaction_read_only')",
+                vec![],
+            )
+            .await
+            .unwrap();
+        assert_eq!(rows, [("repeatable read".into(), "on".into())]);
+
+        // запись в READ ONLY отклоняется сервером
+        assert!(
+            tx.execute("CREATE TEMP TABLE knux_ro (id int)", vec![])
+                .await
+                .is_err()
+        );
+        tx.rollback().await.unwrap();
+    }
+}
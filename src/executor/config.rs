@@ -3,7 +3,7 @@ use url::Url;
 
 use super::utils::{parse_bool, parse_duration, parse_u32};
 use super::{Error, Result};
-use crate::executor::DbPool;
+use crate::executor::{DbPool, ReplicaStrategy, RetryPolicy};
 
 /// Конфиг для инициализации QueryExecutor.
 /// Можно либо передать `database_url` (тогда мы соберём пул),
@@ -26,6 +26,25 @@ pub struct ExecutorConfig {
     pub test_before_acquire: Option<bool>,
     pub is_postgres: bool,
 
+    /// Таймаут запросов по умолчанию (см. `QueryBuilder::timeout`).
+    pub query_timeout: Option<Duration>,
+
+    /// Повторы `QueryExecutor::transaction`: пауза между попытками
+    /// и их число (по умолчанию одна — без повторов).
+    pub tx_retry: Option<RetryPolicy>,
+    pub tx_max_attempts: Option<u32>,
+
+    /// DSN реплик для чтения: SELECT-ы `query()` уходят туда, запись
+    /// и транзакции — на основной пул. Размер пула реплики задаётся отдельно
+    /// (по умолчанию как у основного), остальные настройки общие.
+    pub replica_urls: Vec<String>,
+    pub replica_strategy: Option<ReplicaStrategy>,
+    pub replica_max_connections: Option<u32>,
+    pub replica_min_connections: Option<u32>,
+
+    /// Ёмкость кэша SQL (см. `QueryExecutor::with_sql_cache`); `None` — без кэша.
+    pub sql_cache: Option<usize>,
+
     /// Необязательный SQL, который выполняется на каждом подключении
     /// (полезно для нестандартных установок окружения).
     pub after_connect_sql: Option<String>,
@@ -46,6 +65,14 @@ impl Default for ExecutorConfig {
             test_before_acquire: None,
             after_connect_sql: None,
             is_postgres: false,
+            query_timeout: None,
+            tx_retry: None,
+            tx_max_attempts: None,
+            replica_urls: Vec::new(),
+            replica_strategy: None,
+            replica_max_connections: None,
+            replica_min_connections: None,
+            sql_cache: None,
         }
     }
 }
@@ -96,6 +123,17 @@ impl ExecutorConfig {
                 "pool.idle_timeout" => cfg.idle_timeout = Some(parse_duration(val, key)?),
                 "pool.max_lifetime" => cfg.max_lifetime = Some(parse_duration(val, key)?),
                 "pool.connect_timeout" => cfg.connect_timeout = Some(parse_duration(val, key)?),
+                "query_timeout" => cfg.query_timeout = Some(parse_duration(val, key)?),
+                "tx.max_attempts" => cfg.tx_max_attempts = Some(parse_u32(val, key)?),
+
+                // реплики: replica= можно повторять
+                "replica" if !val.is_empty() => cfg.replica_urls.push(val.to_string()),
+                "replica.strategy" => {
+                    cfg.replica_strategy = Some(ReplicaStrategy::parse(val, key)?);
+                }
+                "replica.pool.max" => cfg.replica_max_connections = Some(parse_u32(val, key)?),
+                "replica.pool.min" => cfg.replica_min_connections = Some(parse_u32(val, key)?),
+                "sql_cache" => cfg.sql_cache = Some(parse_u32(val, key)? as usize),
 
                 // bool
                 "pool.test_before_acquire" => {
@@ -159,6 +197,32 @@ impl ExecutorConfig {
         if self.test_before_acquire.is_none() {
             self.test_before_acquire = other.test_before_acquire;
         }
+        if self.query_timeout.is_none() {
+            self.query_timeout = other.query_timeout;
+        }
+        if self.tx_retry.is_none() {
+            self.tx_retry = other.tx_retry;
+        }
+        if self.tx_max_attempts.is_none() {
+            self.tx_max_attempts = other.tx_max_attempts;
+        }
+
+        // реплики: список целиком — либо из билдера, либо из DSN
+        if self.replica_urls.is_empty() {
+            self.replica_urls = other.replica_urls;
+        }
+        if self.replica_strategy.is_none() {
+            self.replica_strategy = other.replica_strategy;
+        }
+        if self.replica_max_connections.is_none() {
+            self.replica_max_connections = other.replica_max_connections;
+        }
+        if self.replica_min_connections.is_none() {
+            self.replica_min_connections = other.replica_min_connections;
+        }
+        if self.sql_cache.is_none() {
+            self.sql_cache = other.sql_cache;
+        }
 
         // init sql — если в билдере не задан
         if self.after_connect_sql.is_none() {
@@ -215,6 +279,39 @@ impl ExecutorConfigBuilder {
         self.cfg.test_before_acquire = Some(v);
         self
     }
+    pub fn query_timeout(mut self, v: Duration) -> Self {
+        self.cfg.query_timeout = Some(v);
+        self
+    }
+    pub fn tx_retry(mut self, policy: RetryPolicy) -> Self {
+        self.cfg.tx_retry = Some(policy);
+        self
+    }
+    pub fn tx_max_attempts(mut self, v: u32) -> Self {
+        self.cfg.tx_max_attempts = Some(v);
+        self
+    }
+    /// Добавить реплику для чтения (можно вызывать несколько раз)
+    pub fn replica(mut self, url: impl Into<String>) -> Self {
+        self.cfg.replica_urls.push(url.into());
+        self
+    }
+    pub fn replica_strategy(mut self, strategy: ReplicaStrategy) -> Self {
+        self.cfg.replica_strategy = Some(strategy);
+        self
+    }
+    pub fn replica_max_connections(mut self, v: u32) -> Self {
+        self.cfg.replica_max_connections = Some(v);
+        self
+    }
+    pub fn replica_min_connections(mut self, v: u32) -> Self {
+        self.cfg.replica_min_connections = Some(v);
+        self
+    }
+    pub fn sql_cache(mut self, capacity: usize) -> Self {
+        self.cfg.sql_cache = Some(capacity);
+        self
+    }
     pub fn after_connect_sql(mut self, sql: impl Into<String>) -> Self {
         self.cfg.after_connect_sql = Some(sql.into());
         self
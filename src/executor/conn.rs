@@ -0,0 +1,42 @@
+use sqlx::pool::PoolConnection;
+
+use super::{DbPool, QueryExecutor, Result};
+use crate::query_builder::ExecCtx;
+
+/// Выделенное соединение пула: сессионные блокировки и несколько транзакций
+/// подряд на одном соединении (мигратор и т.п.).
+pub(crate) enum DbConn {
+    #[cfg(feature = "postgres")]
+    Postgres(PoolConnection<sqlx::Postgres>),
+    #[cfg(feature = "mysql")]
+    MySql(PoolConnection<sqlx::MySql>),
+    #[cfg(feature = "sqlite")]
+    Sqlite(PoolConnection<sqlx::Sqlite>),
+}
+
+impl QueryExecutor {
+    pub(crate) async fn acquire_conn(&self) -> Result<DbConn> {
+        match &self.pool {
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(pool) => Ok(DbConn::Postgres(pool.acquire().await?)),
+            #[cfg(feature = "mysql")]
+            DbPool::MySql(pool) => Ok(DbConn::MySql(pool.acquire().await?)),
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(pool) => Ok(DbConn::Sqlite(pool.acquire().await?)),
+        }
+    }
+}
+
+impl DbConn {
+    /// Контекст исполнения вне транзакции (autocommit)
+    pub(crate) fn exec_ctx(&mut self) -> ExecCtx<'_> {
+        match self {
+            #[cfg(feature = "postgres")]
+            DbConn::Postgres(conn) => ExecCtx::PgConn(conn),
+            #[cfg(feature = "mysql")]
+            DbConn::MySql(conn) => ExecCtx::MySqlConn(conn),
+            #[cfg(feature = "sqlite")]
+            DbConn::Sqlite(conn) => ExecCtx::SqliteConn(conn),
+        }
+    }
+}
@@ -0,0 +1,406 @@
+use std::borrow::Cow;
+use std::ops::DerefMut;
+
+use futures_core::Stream;
+use futures_util::StreamExt;
+use sqlx::encode::IsNull;
+use sqlx::postgres::{PgArgumentBuffer, PgConnection, PgCopyIn, PgPool, PgPoolCopyExt};
+use sqlx::{Encode, Postgres};
+
+use super::{DbPool, Error, QueryExecutor, Result};
+use crate::executor::transaction::TxExecutor;
+use crate::param::Param;
+use crate::query_builder::ExecCtx;
+use crate::renderer::{Dialect, quote_ident_always};
+
+/// Сколько байт копить перед отправкой `CopyData`
+const SEND_THRESHOLD: usize = 64 * 1024;
+
+/// Формат данных `COPY`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CopyFormat {
+    /// Текстовый формат PG: табуляция между колонками, `\N` — NULL
+    #[default]
+    Text,
+    /// CSV: значения в кавычках, пустое поле без кавычек — NULL
+    Csv,
+    /// Бинарный формат: типы `Param` должны точно совпадать с типами колонок
+    /// (`I32` → `int4`, `I64` → `int8`, `F32` → `float4`, `I8` → `int2`, `Json` → `jsonb`)
+    Binary,
+}
+
+enum CopyTarget<'e> {
+    Pool(PgPool),
+    Conn(&'e mut PgConnection),
+    Unsupported(Dialect),
+}
+
+/// `COPY <table> (<columns>) FROM STDIN` — загрузка строк `Param` без INSERT.
+/// Создаётся через `QueryExecutor::copy_in` или `TxExecutor::copy_in`.
+pub struct CopyIn<'e> {
+    target: CopyTarget<'e>,
+    table: String,
+    columns: Vec<String>,
+    format: CopyFormat,
+}
+
+impl QueryExecutor {
+    /// `COPY ... FROM STDIN` на соединении из пула (только PostgreSQL)
+    pub fn copy_in<I, S>(&self, table: &str, columns: I) -> CopyIn<'_>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let target = match &self.pool {
+            DbPool::Postgres(pool) => CopyTarget::Pool(pool.clone()),
+            #[allow(unreachable_patterns)]
+            other => CopyTarget::Unsupported(other.dialect()),
+        };
+        CopyIn::new(target, table, columns)
+    }
+}
+
+impl<'tx> TxExecutor<'tx> {
+    /// `COPY ... FROM STDIN` на соединении транзакции (только PostgreSQL)
+    pub fn copy_in<I, S>(&mut self, table: &str, columns: I) -> CopyIn<'_>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let target = match self.exec_ctx() {
+            ExecCtx::PgConn(conn) => CopyTarget::Conn(conn),
+            other => CopyTarget::Unsupported(other.dialect().unwrap_or(Dialect::Postgres)),
+        };
+        CopyIn::new(target, table, columns)
+    }
+}
+
+impl<'e> CopyIn<'e> {
+    fn new<I, S>(target: CopyTarget<'e>, table: &str, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            target,
+            table: table.to_string(),
+            columns: columns.into_iter().map(Into::into).collect(),
+            format: CopyFormat::default(),
+        }
+    }
+
+    /// Формат передачи (по умолчанию `Text`)
+    pub fn format(mut self, format: CopyFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Текст команды `COPY`
+    pub fn statement(&self) -> String {
+        let table = self
+            .table
+            .split('.')
+            .map(|p| quote_ident_always(p, Dialect::Postgres))
+            .collect::<Vec<_>>()
+            .join(".");
+        let mut sql = format!("COPY {table}");
+        if !self.columns.is_empty() {
+            let cols: Vec<String> = self
+                .columns
+                .iter()
+                .map(|c| quote_ident_always(c, Dialect::Postgres))
+                .collect();
+            sql.push_str(&format!(" ({})", cols.join(", ")));
+        }
+        sql.push_str(" FROM STDIN");
+        match self.format {
+            CopyFormat::Text => {}
+            CopyFormat::Csv => sql.push_str(" WITH (FORMAT csv)"),
+            CopyFormat::Binary => sql.push_str(" WITH (FORMAT binary)"),
+        }
+        sql
+    }
+
+    /// Загрузить строки из итератора. Возвращает число загруженных строк.
+    pub async fn rows<I, R>(self, rows: I) -> Result<u64>
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = Param>,
+    {
+        self.stream(futures_util::stream::iter(rows)).await
+    }
+
+    /// Загрузить строки из потока — данные уходят по мере чтения
+    pub async fn stream<S, R>(self, rows: S) -> Result<u64>
+    where
+        S: Stream<Item = R>,
+        R: IntoIterator<Item = Param>,
+    {
+        let sql = self.statement();
+        let enc = CopyEncoder::new(self.format, self.columns.len());
+        match self.target {
+            CopyTarget::Pool(pool) => pump(pool.copy_in_raw(&sql).await?, enc, rows).await,
+            CopyTarget::Conn(conn) => pump(conn.copy_in_raw(&sql).await?, enc, rows).await,
+            CopyTarget::Unsupported(d) => Err(Error::Unsupported(
+                format!("COPY FROM STDIN поддерживается только в PostgreSQL, а не {d:?}").into(),
+            )),
+        }
+    }
+}
+
+async fn pump<C, S, R>(mut copy: PgCopyIn<C>, mut enc: CopyEncoder, rows: S) -> Result<u64>
+where
+    C: DerefMut<Target = PgConnection>,
+    S: Stream<Item = R>,
+    R: IntoIterator<Item = Param>,
+{
+    let mut rows = std::pin::pin!(rows);
+    while let Some(row) = rows.next().await {
+        if let Err(e) = enc.push_row(row) {
+            copy.abort(e.to_string()).await?;
+            return Err(e);
+        }
+        if enc.buf.len() >= SEND_THRESHOLD {
+            copy.send(enc.take()).await?;
+        }
+    }
+    enc.finish();
+    if !enc.buf.is_empty() {
+        copy.send(enc.take()).await?;
+    }
+    Ok(copy.finish().await?)
+}
+
+/// Кодирование строк в поток `CopyData`
+pub(crate) struct CopyEncoder {
+    format: CopyFormat,
+    columns: usize,
+    pub(crate) buf: Vec<u8>,
+}
+
+impl CopyEncoder {
+    pub(crate) fn new(format: CopyFormat, columns: usize) -> Self {
+        let mut buf = Vec::with_capacity(SEND_THRESHOLD);
+        if format == CopyFormat::Binary {
+            // сигнатура, флаги, длина расширения заголовка
+            buf.extend_from_slice(b"PGCOPY\n\xff\r\n\0");
+            buf.extend_from_slice(&0i32.to_be_bytes());
+            buf.extend_from_slice(&0i32.to_be_bytes());
+        }
+        Self {
+            format,
+            columns,
+            buf,
+        }
+    }
+
+    pub(crate) fn push_row<R>(&mut self, row: R) -> Result<()>
+    where
+        R: IntoIterator<Item = Param>,
+    {
+        let row: Vec<Param> = row.into_iter().collect();
+        if self.columns != 0 && row.len() != self.columns {
+            return Err(Error::Unsupported(
+                format!(
+                    "copy_in: expected {} values per row, got {}",
+                    self.columns,
+                    row.len()
+                )
+                .into(),
+            ));
+        }
+        match self.format {
+            CopyFormat::Text => {
+                for (i, p) in row.iter().enumerate() {
+                    if i > 0 {
+                        self.buf.push(b'\t');
+                    }
+                    match text_value(p) {
+                        None => self.buf.extend_from_slice(b"\\N"),
+                        Some(v) => escape_text(&mut self.buf, &v),
+                    }
+                }
+                self.buf.push(b'\n');
+            }
+            CopyFormat::Csv => {
+                for (i, p) in row.iter().enumerate() {
+                    if i > 0 {
+                        self.buf.push(b',');
+                    }
+                    if let Some(v) = text_value(p) {
+                        self.buf.push(b'"');
+                        for b in v.bytes() {
+                            if b == b'"' {
+                                self.buf.push(b'"');
+                            }
+                            self.buf.push(b);
+                        }
+                        self.buf.push(b'"');
+                    }
+                }
+                self.buf.push(b'\n');
+            }
+            CopyFormat::Binary => {
+                self.buf
+                    .extend_from_slice(&(row.len() as i16).to_be_bytes());
+                for p in &row {
+                    binary_value(&mut self.buf, p)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Завершить поток (трейлер бинарного формата)
+    pub(crate) fn finish(&mut self) {
+        if self.format == CopyFormat::Binary {
+            self.buf.extend_from_slice(&(-1i16).to_be_bytes());
+        }
+    }
+
+    fn take(&mut self) -> Vec<u8> {
+        std::mem::replace(&mut self.buf, Vec::with_capacity(SEND_THRESHOLD))
+    }
+}
+
+fn escape_text(buf: &mut Vec<u8>, v: &str) {
+    for b in v.bytes() {
+        match b {
+            b'\\' => buf.extend_from_slice(b"\\\\"),
+            b'\t' => buf.extend_from_slice(b"\\t"),
+            b'\n' => buf.extend_from_slice(b"\\n"),
+            b'\r' => buf.extend_from_slice(b"\\r"),
+            _ => buf.push(b),
+        }
+    }
+}
+
+fn float_text(v: f64) -> String {
+    if v.is_nan() {
+        "NaN".into()
+    } else if v.is_infinite() {
+        if v > 0.0 { "Infinity" } else { "-Infinity" }.into()
+    } else {
+        v.to_string()
+    }
+}
+
+/// Значение в текстовом представлении PG; `None` — NULL
+fn text_value(p: &Param) -> Option<Cow<'_, str>> {
+    if p.is_null() {
+        return None;
+    }
+    let v: Cow<'_, str> = match p {
+        Param::I64(v) => v.to_string().into(),
+        Param::I32(v) => v.to_string().into(),
+        Param::I16(v) => v.to_string().into(),
+        Param::I8(v) => v.to_string().into(),
+        Param::F64(v) => float_text(*v).into(),
+        Param::F32(v) => float_text(*v as f64).into(),
+        Param::Bool(b) => if *b { "t" } else { "f" }.into(),
+        Param::Str(s) => s.as_str().into(),
+        Param::Bytes(b) => {
+            use std::fmt::Write;
+            let mut hex = String::with_capacity(2 + b.len() * 2);
+            hex.push_str("\\x");
+            for byte in b {
+                let _ = write!(hex, "{byte:02x}");
+            }
+            hex.into()
+        }
+
+        #[cfg(feature = "time")]
+        Param::Date(v) => v.to_string().into(),
+        #[cfg(feature = "time")]
+        Param::Time(v) => v.to_string().into(),
+        #[cfg(feature = "time")]
+        Param::DateTime(v) => v.to_string().into(),
+        #[cfg(feature = "time")]
+        Param::NaiveDateTime(v) => v.to_string().into(),
+
+        #[cfg(feature = "chrono")]
+        Param::ChronoNaiveDate(v) => v.to_string().into(),
+        #[cfg(feature = "chrono")]
+        Param::ChronoNaiveTime(v) => v.to_string().into(),
+        #[cfg(feature = "chrono")]
+        Param::ChronoNaiveDateTime(v) => v.to_string().into(),
+        #[cfg(feature = "chrono")]
+        Param::ChronoDateTimeUtc(v) => v.to_rfc3339().into(),
+        #[cfg(feature = "chrono")]
+        Param::ChronoDateTimeFixed(v) => v.to_rfc3339().into(),
+
+        #[cfg(feature = "serde_json")]
+        Param::Json(v) => v.to_string().into(),
+        #[cfg(feature = "uuid")]
+        Param::Uuid(v) => v.to_string().into(),
+        #[cfg(feature = "rust_decimal")]
+        Param::Decimal(v) => v.to_string().into(),
+
+        // NULL-варианты обработаны выше
+        _ => return None,
+    };
+    Some(v)
+}
+
+/// Поле бинарного формата: длина (i32, -1 для NULL) + байты `Encode<Postgres>`
+fn binary_value(buf: &mut Vec<u8>, p: &Param) -> Result<()> {
+    fn field<'q, T: Encode<'q, Postgres>>(buf: &mut Vec<u8>, v: &T) -> Result<()> {
+        let mut arg = PgArgumentBuffer::default();
+        let is_null = v
+            .encode_by_ref(&mut arg)
+            .map_err(|e| Error::Sqlx(sqlx::Error::Encode(e)))?;
+        if let IsNull::Yes = is_null {
+            buf.extend_from_slice(&(-1i32).to_be_bytes());
+            return Ok(());
+        }
+        buf.extend_from_slice(&(arg.len() as i32).to_be_bytes());
+        buf.extend_from_slice(&arg);
+        Ok(())
+    }
+
+    if p.is_null() {
+        buf.extend_from_slice(&(-1i32).to_be_bytes());
+        return Ok(());
+    }
+    match p {
+        Param::I64(v) => field(buf, v),
+        Param::I32(v) => field(buf, v),
+        Param::I16(v) => field(buf, v),
+        Param::I8(v) => field(buf, &(*v as i16)),
+        Param::F64(v) => field(buf, v),
+        Param::F32(v) => field(buf, v),
+        Param::Bool(v) => field(buf, v),
+        Param::Str(v) => field(buf, v),
+        Param::Bytes(v) => field(buf, v),
+
+        #[cfg(feature = "time")]
+        Param::Date(v) => field(buf, v),
+        #[cfg(feature = "time")]
+        Param::Time(v) => field(buf, v),
+        #[cfg(feature = "time")]
+        Param::DateTime(v) => field(buf, v),
+        #[cfg(feature = "time")]
+        Param::NaiveDateTime(v) => field(buf, v),
+
+        #[cfg(feature = "chrono")]
+        Param::ChronoNaiveDate(v) => field(buf, v),
+        #[cfg(feature = "chrono")]
+        Param::ChronoNaiveTime(v) => field(buf, v),
+        #[cfg(feature = "chrono")]
+        Param::ChronoNaiveDateTime(v) => field(buf, v),
+        #[cfg(feature = "chrono")]
+        Param::ChronoDateTimeUtc(v) => field(buf, v),
+        #[cfg(feature = "chrono")]
+        Param::ChronoDateTimeFixed(v) => field(buf, v),
+
+        #[cfg(feature = "serde_json")]
+        Param::Json(v) => field(buf, v),
+        #[cfg(feature = "uuid")]
+        Param::Uuid(v) => field(buf, v),
+        #[cfg(feature = "rust_decimal")]
+        Param::Decimal(v) => field(buf, v),
+
+        // NULL-варианты обработаны выше
+        _ => Ok(()),
+    }
+}
@@ -1,4 +1,7 @@
 use std::borrow::Cow;
+use std::time::Duration;
+
+use crate::renderer::Dialect;
 
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -46,6 +49,54 @@ pub enum Error {
     #[error("Unsupported: {0}")]
     Unsupported(Cow<'static, str>),
 
+    #[error("Foreign key check failed after rebuilding table {0}")]
+    ForeignKeyCheck(String),
+
     #[error("Unable to execute query in transaction")]
     NotSendInTx,
+
+    #[error("Query timed out after {0:?}")]
+    Timeout(Duration),
+
+    #[error("{option} transactions are not supported by {dialect}")]
+    UnsupportedTxOption {
+        option: &'static str,
+        dialect: Dialect,
+    },
+
+    #[error("Invalid transaction options: {0}")]
+    InvalidTxOptions(Cow<'static, str>),
+
+    #[error("Replica dialect {replica} does not match primary {primary}")]
+    ReplicaDialect { primary: Dialect, replica: Dialect },
+}
+
+impl Error {
+    /// Попытку можно повторить в новой транзакции: конфликт сериализации
+    /// или дедлок (Postgres `40001`/`40P01`, MySQL 1213), занятая база SQLite
+    pub fn is_retryable(&self) -> bool {
+        let Error::Sqlx(sqlx::Error::Database(db)) = self else {
+            return false;
+        };
+        #[cfg(feature = "postgres")]
+        if db
+            .try_downcast_ref::<sqlx::postgres::PgDatabaseError>()
+            .is_some()
+        {
+            return matches!(db.code().as_deref(), Some("40001" | "40P01"));
+        }
+        #[cfg(feature = "mysql")]
+        if let Some(my) = db.try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>() {
+            return my.number() == 1213;
+        }
+        // SQLITE_BUSY и его расширенные коды (SQLITE_BUSY_SNAPSHOT и т.п.)
+        #[cfg(feature = "sqlite")]
+        if db.try_downcast_ref::<sqlx::sqlite::SqliteError>().is_some() {
+            return db
+                .code()
+                .and_then(|c| c.parse::<i32>().ok())
+                .is_some_and(|c| c & 0xff == 5);
+        }
+        false
+    }
 }
@@ -0,0 +1,224 @@
+//! Пользовательские хуки выполнения запросов: метрики, логирование медленных
+//! запросов. Регистрируются на `QueryExecutor` и наследуются его билдерами и транзакциями.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures_util::StreamExt;
+
+use super::{Error, QueryExecutor, Result, RowStream};
+use crate::renderer::Dialect;
+
+/// Что выполняется: SQL с плейсхолдерами, число параметров и диалект
+#[derive(Clone, Copy, Debug)]
+pub struct QueryInfo<'q> {
+    pub sql: &'q str,
+    pub params: usize,
+    pub dialect: Dialect,
+}
+
+impl<'q> QueryInfo<'q> {
+    #[inline]
+    pub fn new(sql: &'q str, params: usize, dialect: Dialect) -> Self {
+        Self {
+            sql,
+            params,
+            dialect,
+        }
+    }
+}
+
+/// Колбэки вокруг каждого запроса. Все методы по умолчанию пустые.
+///
+/// `rows` — прочитанные строки для SELECT/RETURNING и `rows_affected` для остального.
+pub trait QueryHook: Send + Sync {
+    fn before(&self, _query: &QueryInfo<'_>) {}
+
+    fn after(&self, _query: &QueryInfo<'_>, _rows: u64, _elapsed: Duration) {}
+
+    fn error(&self, _query: &QueryInfo<'_>, _err: &Error, _elapsed: Duration) {}
+
+    /// Запрос дольше порога `with_slow_query_threshold` (после `after`/`error`)
+    fn slow(&self, _query: &QueryInfo<'_>, _elapsed: Duration) {}
+}
+
+impl QueryExecutor {
+    /// Зарегистрировать хук: он получит все запросы этого исполнителя,
+    /// его билдеров и транзакций, открытых после регистрации.
+    pub fn with_hook<H: QueryHook + 'static>(mut self, hook: H) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Порог медленного запроса: превысившие его уходят в `QueryHook::slow`
+    /// (и в `tracing::warn!` с фичей `tracing`).
+    pub fn with_slow_query_threshold(mut self, threshold: Duration) -> Self {
+        self.hooks.set_slow_threshold(threshold);
+        self
+    }
+}
+
+struct HooksInner {
+    hooks: Vec<Arc<dyn QueryHook>>,
+    slow_threshold: Option<Duration>,
+}
+
+/// Набор хуков исполнителя; без хуков и порога — `None`, и обёртки ничего не стоят
+#[derive(Clone, Default)]
+pub(crate) struct QueryHooks(Option<Arc<HooksInner>>);
+
+impl std::fmt::Debug for QueryHooks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner = self.0.as_deref();
+        f.debug_struct("QueryHooks")
+            .field("hooks", &inner.map_or(0, |i| i.hooks.len()))
+            .field("slow_threshold", &inner.and_then(|i| i.slow_threshold))
+            .finish()
+    }
+}
+
+impl QueryHooks {
+    fn edit(&mut self) -> &mut HooksInner {
+        let inner = self.0.get_or_insert_with(|| {
+            Arc::new(HooksInner {
+                hooks: Vec::new(),
+                slow_threshold: None,
+            })
+        });
+        // билдеры держат свои копии Arc — меняем только собственную
+        if Arc::get_mut(inner).is_none() {
+            *inner = Arc::new(HooksInner {
+                hooks: inner.hooks.clone(),
+                slow_threshold: inner.slow_threshold,
+            });
+        }
+        Arc::get_mut(inner).expect("unique hooks")
+    }
+
+    pub(crate) fn push(&mut self, hook: Arc<dyn QueryHook>) {
+        self.edit().hooks.push(hook);
+    }
+
+    pub(crate) fn set_slow_threshold(&mut self, threshold: Duration) {
+        self.edit().slow_threshold = Some(threshold);
+    }
+
+    #[inline]
+    pub(crate) fn is_active(&self) -> bool {
+        self.0.is_some()
+    }
+
+    pub(crate) async fn observe<R, F>(
+        &self,
+        query: QueryInfo<'_>,
+        rows: fn(&R) -> u64,
+        fut: F,
+    ) -> Result<R>
+    where
+        F: Future<Output = Result<R>>,
+    {
+        let Some(inner) = self.0.as_deref() else {
+            return fut.await;
+        };
+        inner.before(&query);
+        let started = Instant::now();
+        let res = fut.await;
+        inner.finish(&query, started.elapsed(), res.as_ref().map(rows));
+        res
+    }
+
+    /// `observe` для `*_send`: future без заимствований, SQL копируется только при активных хуках
+    pub(crate) fn observe_send<R, F>(
+        &self,
+        query: QueryInfo<'_>,
+        rows: fn(&R) -> u64,
+        fut: F,
+    ) -> impl Future<Output = Result<R>> + Send + use<R, F>
+    where
+        F: Future<Output = Result<R>> + Send + 'static,
+        R: Send + 'static,
+    {
+        let hooks = self.clone();
+        let owned = hooks.is_active().then(|| query.sql.to_owned());
+        let (params, dialect) = (query.params, query.dialect);
+        async move {
+            match owned {
+                Some(sql) => {
+                    let query = QueryInfo::new(&sql, params, dialect);
+                    hooks.observe(query, rows, fut).await
+                }
+                None => fut.await,
+            }
+        }
+    }
+
+    /// Построчное чтение: `after` вызывается, когда поток исчерпан.
+    /// SQL передаётся владением — поток живёт дольше билдера.
+    pub(crate) fn observe_stream<'s, T>(
+        &self,
+        sql: String,
+        params: usize,
+        dialect: Dialect,
+        mut stream: RowStream<'s, T>,
+    ) -> RowStream<'s, T>
+    where
+        T: Send + 's,
+    {
+        let Some(inner) = self.0.clone() else {
+            return stream;
+        };
+        Box::pin(async_stream::stream! {
+            let query = QueryInfo::new(&sql, params, dialect);
+            inner.before(&query);
+            let started = Instant::now();
+            let mut rows = 0u64;
+            while let Some(item) = stream.next().await {
+                match &item {
+                    Ok(_) => rows += 1,
+                    Err(e) => inner.finish(&query, started.elapsed(), Err(e)),
+                }
+                let failed = item.is_err();
+                yield item;
+                if failed {
+                    return;
+                }
+            }
+            inner.finish(&query, started.elapsed(), Ok(rows));
+        })
+    }
+}
+
+impl HooksInner {
+    fn before(&self, query: &QueryInfo<'_>) {
+        for h in &self.hooks {
+            h.before(query);
+        }
+    }
+
+    fn finish(
+        &self,
+        query: &QueryInfo<'_>,
+        elapsed: Duration,
+        res: std::result::Result<u64, &Error>,
+    ) {
+        for h in &self.hooks {
+            match res {
+                Ok(rows) => h.after(query, rows, elapsed),
+                Err(e) => h.error(query, e, elapsed),
+            }
+        }
+        if self.slow_threshold.is_some_and(|t| elapsed >= t) {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                db.system = %query.dialect,
+                db.statement = query.sql,
+                elapsed_ms = elapsed.as_secs_f64() * 1000.0,
+                "slow query"
+            );
+            for h in &self.hooks {
+                h.slow(query, elapsed);
+            }
+        }
+    }
+}
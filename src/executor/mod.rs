@@ -1,17 +1,31 @@
 mod __tests__;
 mod config;
+mod conn;
+#[cfg(feature = "postgres")]
+mod copy;
 mod error;
+mod hooks;
+mod replica;
+mod retry;
+mod row;
+mod savepoint;
+mod sql_cache;
+mod timeout;
+pub(crate) mod trace;
 pub mod transaction;
 pub mod transaction_utils;
+mod tx_options;
 pub mod utils;
 
+use std::time::Duration;
+
 use sqlx::Executor;
 #[cfg(feature = "mysql")]
-use sqlx::mysql::{MySqlPool, MySqlPoolOptions, MySqlRow};
+use sqlx::mysql::{MySqlPool, MySqlPoolOptions};
 #[cfg(feature = "postgres")]
-use sqlx::postgres::{PgPool, PgPoolOptions, PgRow};
+use sqlx::postgres::{PgPool, PgPoolOptions};
 #[cfg(feature = "sqlite")]
-use sqlx::sqlite::{SqlitePool, SqlitePoolOptions, SqliteRow};
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
 
 #[cfg(feature = "mysql")]
 use crate::executor::utils::fetch_typed_mysql;
@@ -21,20 +35,30 @@ use crate::executor::utils::fetch_typed_pg;
 use crate::executor::utils::fetch_typed_sqlite;
 
 use crate::{
-    optimizer::{OptimizeConfig, OptimizeConfigBuilder},
+    optimizer::OptimizeConfig,
     param::Param,
-    query_builder::{PoolQuery, QueryBuilder},
+    query_builder::{ExecCtx, PoolQuery, QueryBuilder},
+    renderer::Dialect,
 };
 pub use config::ExecutorConfig;
+pub(crate) use conn::DbConn;
+#[cfg(feature = "postgres")]
+pub use copy::{CopyFormat, CopyIn};
 pub use error::{Error, Result};
+pub(crate) use hooks::QueryHooks;
+pub use hooks::{QueryHook, QueryInfo};
+pub use replica::ReplicaStrategy;
+pub(crate) use replica::{ReadRoute, ReadTarget, Replicas};
+pub use retry::RetryPolicy;
+pub use row::*;
+pub(crate) use sql_cache::SqlCache;
+pub use sql_cache::SqlCacheStats;
+pub(crate) use timeout::stream_timeout;
+pub use tx_options::{IsolationLevel, TxOptions};
 
-// ВЕРХ ФАЙЛА: алиасы под активную БД
-#[cfg(feature = "postgres")]
-pub type DbRow = PgRow;
-#[cfg(feature = "mysql")]
-pub type DbRow = MySqlRow;
-#[cfg(feature = "sqlite")]
-pub type DbRow = SqliteRow;
+/// Поток типизированных строк результата (см. `QueryBuilder::stream`).
+pub type RowStream<'a, T> =
+    std::pin::Pin<Box<dyn futures_core::Stream<Item = Result<T>> + Send + 'a>>;
 
 #[derive(Clone, Debug)]
 pub enum DbPool {
@@ -46,11 +70,43 @@ pub enum DbPool {
     Sqlite(SqlitePool),
 }
 
+impl DbPool {
+    /// Диалект SQL этого пула
+    pub fn dialect(&self) -> Dialect {
+        match self {
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(_) => Dialect::Postgres,
+            #[cfg(feature = "mysql")]
+            DbPool::MySql(_) => Dialect::MySQL,
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(_) => Dialect::SQLite,
+        }
+    }
+
+    /// Соединения, выданные из пула прямо сейчас
+    pub(crate) fn in_use(&self) -> u32 {
+        match self {
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(p) => p.size().saturating_sub(p.num_idle() as u32),
+            #[cfg(feature = "mysql")]
+            DbPool::MySql(p) => p.size().saturating_sub(p.num_idle() as u32),
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(p) => p.size().saturating_sub(p.num_idle() as u32),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct QueryExecutor {
     pub pool: DbPool,
     pub schema: Option<String>,
     pub(crate) optimize_cfg: OptimizeConfig,
+    pub(crate) hooks: QueryHooks,
+    pub(crate) query_timeout: Option<Duration>,
+    pub(crate) tx_retry: RetryPolicy,
+    pub(crate) tx_max_attempts: u32,
+    pub(crate) replicas: Option<Replicas>,
+    pub(crate) sql_cache: Option<SqlCache>,
 }
 
 impl QueryExecutor {
@@ -66,146 +122,46 @@ impl QueryExecutor {
             cfg
         };
 
-        // если передан готовый пул — используем его
-        if let Some(pool) = cfg.pool.clone() {
-            // возможно, выполним after_connect_sql один раз для совместимости?
-            // (обычно это делается на каждый коннект; см. ниже в ветке построения пула)
-            return Ok(Self {
-                pool,
-                schema: cfg.schema.clone(),
-                optimize_cfg: OptimizeConfig::default(),
-            });
-        }
-
-        let url = cfg.database_url.clone().ok_or(Error::MissingConnection)?;
-        let scheme = url::Url::parse(&url)
-            .map_err(Error::InvalidUrl)?
-            .scheme()
-            .to_string();
-
-        // дефолты / опции пула
-        let max_conn = cfg.max_connections.unwrap_or(10);
-        let min_conn = cfg.min_connections.unwrap_or(0);
-        let acquire = cfg.acquire_timeout;
-        let idle = cfg.idle_timeout;
-        let life = cfg.max_lifetime;
-        let test_before = cfg.test_before_acquire.unwrap_or(false);
-        let init_sql_all = cfg.after_connect_sql.clone(); // init SQL для всех СУБД
-        #[cfg(feature = "postgres")]
-        let schema = cfg.schema.clone();
-
-        // выбираем драйвер по схеме URL
-        let pool = match scheme.as_str() {
-            #[cfg(feature = "postgres")]
-            "postgres" | "postgresql" => {
-                let mut opts = PgPoolOptions::new()
-                    .max_connections(max_conn)
-                    .min_connections(min_conn)
-                    .test_before_acquire(test_before);
-                if let Some(d) = acquire {
-                    opts = opts.acquire_timeout(d);
-                }
-                if let Some(d) = idle {
-                    opts = opts.idle_timeout(d);
-                }
-                if let Some(d) = life {
-                    opts = opts.max_lifetime(d);
-                }
-
-                let init_sql_outer = init_sql_all.clone();
-                let schema_outer = schema.clone();
-
-                let pool = opts
-                    .after_connect(move |conn, _| {
-                        let init_sql = init_sql_outer.clone();
-                        let schema = schema_outer.clone();
-                        Box::pin(async move {
-                            if let Some(sql) = init_sql.as_deref() {
-                                conn.execute(sql).await?;
-                            }
-                            if let Some(s) = schema {
-                                let set_path = format!("SET search_path TO {}", s);
-                                let _ = conn.execute(set_path.as_str()).await;
-                            }
-                            Ok(())
-                        })
-                    })
-                    .connect(&url)
-                    .await?;
-                DbPool::Postgres(pool)
+        // готовый пул используем как есть, иначе строим по database_url
+        let pool = match cfg.pool.clone() {
+            Some(pool) => pool,
+            None => {
+                let url = cfg
+                    .database_url
+                    .as_deref()
+                    .ok_or(Error::MissingConnection)?;
+                let max_conn = cfg.max_connections.unwrap_or(10);
+                let min_conn = cfg.min_connections.unwrap_or(0);
+                connect_pool(url, &cfg, max_conn, min_conn).await?
             }
-
-            #[cfg(feature = "mysql")]
-            "mysql" | "mariadb" => {
-                let mut opts = MySqlPoolOptions::new()
-                    .max_connections(max_conn)
-                    .min_connections(min_conn)
-                    .test_before_acquire(test_before);
-                if let Some(d) = acquire {
-                    opts = opts.acquire_timeout(d);
-                }
-                if let Some(d) = idle {
-                    opts = opts.idle_timeout(d);
-                }
-                if let Some(d) = life {
-                    opts = opts.max_lifetime(d);
-                }
-
-                let pool = opts
-                    .after_connect(move |conn, _| {
-                        let init_sql = init_sql_all.clone();
-                        Box::pin(async move {
-                            if let Some(sql) = init_sql {
-                                conn.execute(sql.as_str()).await?;
-                            }
-                            Ok::<_, sqlx::Error>(())
-                        })
-                    })
-                    .connect(&url)
-                    .await?;
-                DbPool::MySql(pool)
-            }
-
-            #[cfg(feature = "sqlite")]
-            "sqlite" => {
-                let mut opts = SqlitePoolOptions::new()
-                    .max_connections(max_conn)
-                    .min_connections(min_conn)
-                    .test_before_acquire(test_before);
-                if let Some(d) = acquire {
-                    opts = opts.acquire_timeout(d);
-                }
-                if let Some(d) = idle {
-                    opts = opts.idle_timeout(d);
-                }
-                if let Some(d) = life {
-                    opts = opts.max_lifetime(d);
-                }
-
-                let pool = opts
-                    .after_connect(move |conn, _| {
-                        let init_sql = init_sql_all.clone();
-                        Box::pin(async move {
-                            if let Some(sql) = init_sql {
-                                conn.execute(sql.as_str()).await?;
-                            }
-                            Ok::<_, sqlx::Error>(())
-                        })
-                    })
-                    .connect(&url)
-                    .await?;
-                DbPool::Sqlite(pool)
-            }
-
-            // если сборка без нужной фичи — вернём осмысленную ошибку
-            _ => return Err(Error::UnsupportedScheme(scheme)),
         };
 
-        Ok(Self {
+        // реплики: размер пула свой, если задан, остальные настройки общие
+        let mut replicas = Vec::with_capacity(cfg.replica_urls.len());
+        for url in &cfg.replica_urls {
+            let max_conn = cfg
+                .replica_max_connections
+                .or(cfg.max_connections)
+                .unwrap_or(10);
+            let min_conn = cfg
+                .replica_min_connections
+                .or(cfg.min_connections)
+                .unwrap_or(0);
+            replicas.push(connect_pool(url, &cfg, max_conn, min_conn).await?);
+        }
+
+        Self {
             pool,
             schema: cfg.schema,
             optimize_cfg: OptimizeConfig::default(),
-        })
+            hooks: QueryHooks::default(),
+            query_timeout: cfg.query_timeout,
+            tx_retry: cfg.tx_retry.unwrap_or_default(),
+            tx_max_attempts: cfg.tx_max_attempts.unwrap_or(1).max(1),
+            replicas: None,
+            sql_cache: cfg.sql_cache.map(SqlCache::new),
+        }
+        .with_replicas(replicas, cfg.replica_strategy.unwrap_or_default())
     }
 
     /// Альтернатива: обернуть уже созданный пул (например, специфичный под БД).
@@ -214,36 +170,54 @@ impl QueryExecutor {
             pool,
             schema,
             optimize_cfg: OptimizeConfig::default(),
+            hooks: QueryHooks::default(),
+            query_timeout: None,
+            tx_retry: RetryPolicy::default(),
+            tx_max_attempts: 1,
+            replicas: None,
+            sql_cache: None,
         }
     }
 
+    /// Диалект, выбранный по схеме DSN (или по типу готового пула)
+    #[inline]
+    pub fn dialect(&self) -> Dialect {
+        self.pool.dialect()
+    }
+
     /// Начать строить запрос (интерфейс дальше останется как у knex-подобного билдера).
     pub fn query<T>(&self) -> PoolQuery<'_, T> {
-        let qb = QueryBuilder::new_pool(self.pool.clone(), self.schema.clone())
-            .with_optimize(self.optimize_cfg.clone());
+        let mut qb = QueryBuilder::new_pool(self.pool.clone(), self.schema.clone())
+            .with_optimize(self.optimize_cfg.clone())
+            .with_hooks(self.hooks.clone());
+        qb.timeout = self.query_timeout;
+        qb.read_route.replicas = self.replicas.clone();
+        qb.sql_cache = self.sql_cache.clone();
         PoolQuery::new(qb)
     }
 
     pub async fn fetch_typed<T>(&self, sql: &str, params: Vec<Param>) -> Result<Vec<T>>
     where
-        for<'r> T: sqlx::FromRow<'r, DbRow> + Send + Unpin,
+        T: FromDbRow,
     {
-        match &self.pool {
-            #[cfg(feature = "postgres")]
-            DbPool::Postgres(pool) => fetch_typed_pg::<T>(pool, sql, params)
-                .await
-                .map_err(Into::into),
+        let query = QueryInfo::new(sql, params.len(), self.dialect());
+        let fut = async {
+            if self.query_timeout.is_some() {
+                let mut ctx = ExecCtx::Pool(self.pool.clone());
+                return ctx.fetch_all_timeout(sql, params, self.query_timeout).await;
+            }
+            match &self.pool {
+                #[cfg(feature = "postgres")]
+                DbPool::Postgres(pool) => fetch_typed_pg::<T>(pool, sql, params).await,
 
-            #[cfg(feature = "mysql")]
-            DbPool::MySql(pool) => fetch_typed_mysql::<T>(pool, sql, params)
-                .await
-                .map_err(Into::into),
+                #[cfg(feature = "mysql")]
+                DbPool::MySql(pool) => fetch_typed_mysql::<T>(pool, sql, params).await,
 
-            #[cfg(feature = "sqlite")]
-            DbPool::Sqlite(pool) => fetch_typed_sqlite::<T>(pool, sql, params)
-                .await
-                .map_err(Into::into),
-        }
+                #[cfg(feature = "sqlite")]
+                DbPool::Sqlite(pool) => fetch_typed_sqlite::<T>(pool, sql, params).await,
+            }
+        };
+        self.hooks.observe(query, trace::rows_vec, fut).await
     }
 
     #[cfg(feature = "sqlite")]
@@ -258,6 +232,7 @@ impl QueryExecutor {
 
     #[cfg(feature = "postgres")]
     pub fn as_pg_pool(&self) -> Option<&PgPool> {
+        #[allow(irrefutable_let_patterns)]
         if let DbPool::Postgres(pool) = &self.pool {
             Some(pool)
         } else {
@@ -267,6 +242,7 @@ impl QueryExecutor {
 
     #[cfg(feature = "mysql")]
     pub fn as_mysql_pool(&self) -> Option<&MySqlPool> {
+        #[allow(irrefutable_let_patterns)]
         if let DbPool::MySql(pool) = &self.pool {
             Some(pool)
         } else {
@@ -274,3 +250,134 @@ impl QueryExecutor {
         }
     }
 }
+
+/// Пул по DSN и общим настройкам конфига (размер передаётся отдельно:
+/// у реплик он свой)
+async fn connect_pool(
+    url: &str,
+    cfg: &ExecutorConfig,
+    max_conn: u32,
+    min_conn: u32,
+) -> Result<DbPool> {
+    let scheme = url::Url::parse(url)
+        .map_err(Error::InvalidUrl)?
+        .scheme()
+        .to_string();
+
+    // опции пула
+    let acquire = cfg.acquire_timeout;
+    let idle = cfg.idle_timeout;
+    let life = cfg.max_lifetime;
+    let test_before = cfg.test_before_acquire.unwrap_or(false);
+    let init_sql_all = cfg.after_connect_sql.clone(); // init SQL для всех СУБД
+    #[cfg(feature = "postgres")]
+    let schema = cfg.schema.clone();
+
+    // выбираем драйвер по схеме URL
+    let pool = match scheme.as_str() {
+        #[cfg(feature = "postgres")]
+        "postgres" | "postgresql" => {
+            let mut opts = PgPoolOptions::new()
+                .max_connections(max_conn)
+                .min_connections(min_conn)
+                .test_before_acquire(test_before);
+            if let Some(d) = acquire {
+                opts = opts.acquire_timeout(d);
+            }
+            if let Some(d) = idle {
+                opts = opts.idle_timeout(d);
+            }
+            if let Some(d) = life {
+                opts = opts.max_lifetime(d);
+            }
+
+            let init_sql_outer = init_sql_all.clone();
+            let schema_outer = schema.clone();
+
+            let pool = opts
+                .after_connect(move |conn, _| {
+                    let init_sql = init_sql_outer.clone();
+                    let schema = schema_outer.clone();
+                    Box::pin(async move {
+                        if let Some(sql) = init_sql.as_deref() {
+                            conn.execute(sql).await?;
+                        }
+                        if let Some(s) = schema {
+                            let set_path = format!("SET search_path TO {}", s);
+                            let _ = conn.execute(set_path.as_str()).await;
+                        }
+                        Ok(())
+                    })
+                })
+                .connect(url)
+                .await?;
+            DbPool::Postgres(pool)
+        }
+
+        #[cfg(feature = "mysql")]
+        "mysql" | "mariadb" => {
+            let mut opts = MySqlPoolOptions::new()
+                .max_connections(max_conn)
+                .min_connections(min_conn)
+                .test_before_acquire(test_before);
+            if let Some(d) = acquire {
+                opts = opts.acquire_timeout(d);
+            }
+            if let Some(d) = idle {
+                opts = opts.idle_timeout(d);
+            }
+            if let Some(d) = life {
+                opts = opts.max_lifetime(d);
+            }
+
+            let pool = opts
+                .after_connect(move |conn, _| {
+                    let init_sql = init_sql_all.clone();
+                    Box::pin(async move {
+                        if let Some(sql) = init_sql {
+                            conn.execute(sql.as_str()).await?;
+                        }
+                        Ok::<_, sqlx::Error>(())
+                    })
+                })
+                .connect(url)
+                .await?;
+            DbPool::MySql(pool)
+        }
+
+        #[cfg(feature = "sqlite")]
+        "sqlite" => {
+            let mut opts = SqlitePoolOptions::new()
+                .max_connections(max_conn)
+                .min_connections(min_conn)
+                .test_before_acquire(test_before);
+            if let Some(d) = acquire {
+                opts = opts.acquire_timeout(d);
+            }
+            if let Some(d) = idle {
+                opts = opts.idle_timeout(d);
+            }
+            if let Some(d) = life {
+                opts = opts.max_lifetime(d);
+            }
+
+            let pool = opts
+                .after_connect(move |conn, _| {
+                    let init_sql = init_sql_all.clone();
+                    Box::pin(async move {
+                        if let Some(sql) = init_sql {
+                            conn.execute(sql.as_str()).await?;
+                        }
+                        Ok::<_, sqlx::Error>(())
+                    })
+                })
+                .connect(url)
+                .await?;
+            DbPool::Sqlite(pool)
+        }
+
+        // если сборка без нужной фичи — вернём осмысленную ошибку
+        _ => return Err(Error::UnsupportedScheme(scheme)),
+    };
+    Ok(pool)
+}
@@ -0,0 +1,114 @@
+//! Чтение с реплик: SELECT из `QueryExecutor::query` уходят на пулы реплик,
+//! запись и транзакции — на основной пул.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::{DbPool, Error, QueryExecutor, Result};
+
+/// Как выбирать реплику для очередного SELECT
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReplicaStrategy {
+    /// По кругу
+    #[default]
+    RoundRobin,
+    /// Реплика с наименьшим числом занятых соединений (при равенстве — по кругу)
+    LeastConnections,
+}
+
+impl ReplicaStrategy {
+    pub(crate) fn parse(v: &str, key: &str) -> Result<Self> {
+        match v {
+            "round_robin" => Ok(ReplicaStrategy::RoundRobin),
+            "least_connections" => Ok(ReplicaStrategy::LeastConnections),
+            _ => Err(Error::Unsupported(
+                format!("{key}={v} (use round_robin/least_connections)").into(),
+            )),
+        }
+    }
+}
+
+/// Пулы реплик исполнителя; клоны делят общий счётчик round-robin
+#[derive(Clone, Debug)]
+pub(crate) struct Replicas {
+    pools: Arc<[DbPool]>,
+    next: Arc<AtomicUsize>,
+    strategy: ReplicaStrategy,
+}
+
+impl Replicas {
+    /// `None`, если реплик нет
+    pub(crate) fn new(pools: Vec<DbPool>, strategy: ReplicaStrategy) -> Option<Self> {
+        if pools.is_empty() {
+            return None;
+        }
+        Some(Self {
+            pools: pools.into(),
+            next: Arc::default(),
+            strategy,
+        })
+    }
+
+    pub(crate) fn pick(&self) -> DbPool {
+        let n = self.pools.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % n;
+        let idx = match self.strategy {
+            ReplicaStrategy::RoundRobin => start,
+            ReplicaStrategy::LeastConnections => (0..n)
+                .map(|i| (start + i) % n)
+                .min_by_key(|&i| self.pools[i].in_use())
+                .unwrap_or(start),
+        };
+        self.pools[idx].clone()
+    }
+
+    pub(crate) fn pools(&self) -> &[DbPool] {
+        &self.pools
+    }
+}
+
+/// Куда направить SELECT пулового билдера
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum ReadTarget {
+    /// На реплику, кроме `FOR UPDATE`/`FOR SHARE` — те идут на основной пул
+    #[default]
+    Auto,
+    Primary,
+    Replica,
+}
+
+/// Маршрут чтения в `QueryBuilder`; без реплик всё остаётся на основном пуле
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ReadRoute {
+    pub(crate) replicas: Option<Replicas>,
+    pub(crate) target: ReadTarget,
+}
+
+impl ReadRoute {
+    /// Пул реплики для SELECT или `None` — остаться на основном
+    pub(crate) fn replica(&self, locks_rows: bool) -> Option<DbPool> {
+        let replicas = self.replicas.as_ref()?;
+        match self.target {
+            ReadTarget::Auto if locks_rows => None,
+            ReadTarget::Auto | ReadTarget::Replica => Some(replicas.pick()),
+            ReadTarget::Primary => None,
+        }
+    }
+}
+
+impl QueryExecutor {
+    /// Читать SELECT-ы `query()` с этих пулов. Пустой список отключает реплики.
+    pub fn with_replicas(mut self, pools: Vec<DbPool>, strategy: ReplicaStrategy) -> Result<Self> {
+        let primary = self.dialect();
+        if let Some(replica) = pools.iter().map(DbPool::dialect).find(|d| *d != primary) {
+            return Err(Error::ReplicaDialect { primary, replica });
+        }
+        self.replicas = Replicas::new(pools, strategy);
+        Ok(self)
+    }
+
+    /// Пулы реплик (пусто, если чтение идёт с основного пула)
+    pub fn replica_pools(&self) -> &[DbPool] {
+        self.replicas.as_ref().map_or(&[], Replicas::pools)
+    }
+}
@@ -0,0 +1,110 @@
+//! Управляемая транзакция `QueryExecutor::transaction`: фиксация и откат
+//! берёт на себя исполнитель, конфликты сериализации и дедлоки повторяются.
+
+use std::time::Duration;
+
+use super::transaction::TxExecutor;
+use super::{QueryExecutor, Result, TxOptions};
+
+/// Пауза между попытками `QueryExecutor::transaction`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RetryPolicy {
+    /// Повторять сразу
+    Immediate,
+    /// Одна и та же пауза
+    Fixed(Duration),
+    /// `base`, `2·base`, `4·base`, … но не дольше `max`
+    Exponential { base: Duration, max: Duration },
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::Exponential {
+            base: Duration::from_millis(10),
+            max: Duration::from_secs(1),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Пауза после неудачной попытки номер `attempt` (с единицы)
+    pub fn delay(&self, attempt: u32) -> Duration {
+        match *self {
+            RetryPolicy::Immediate => Duration::ZERO,
+            RetryPolicy::Fixed(d) => d,
+            RetryPolicy::Exponential { base, max } => {
+                let factor = 1u32
+                    .checked_shl(attempt.saturating_sub(1))
+                    .unwrap_or(u32::MAX);
+                base.saturating_mul(factor).min(max)
+            }
+        }
+    }
+}
+
+impl QueryExecutor {
+    /// Выполнить `f` в транзакции: `Ok` — COMMIT, `Err` — ROLLBACK.
+    /// При панике транзакция откатывается при освобождении соединения.
+    ///
+    /// Если попытка (вместе с COMMIT) упала с `Error::is_retryable`, замыкание
+    /// вызывается заново в новой транзакции — до `tx_max_attempts` раз
+    /// с паузами по `tx_retry` из `ExecutorConfig`. Поэтому внутри не должно быть
+    /// побочных эффектов вне БД, которые нельзя повторить.
+    ///
+    /// ```ignore
+    /// let id = db.transaction(async |tx| {
+    ///     tx.query::<()>().table("accounts").update(...).exec().await?;
+    ///     tx.fetch_typed::<(i64,)>("SELECT ...", vec![]).await
+    /// }).await?;
+    /// ```
+    pub async fn transaction<R, F>(&self, f: F) -> Result<R>
+    where
+        F: AsyncFnMut(&mut TxExecutor<'_>) -> Result<R>,
+    {
+        self.transaction_with(TxOptions::default(), f).await
+    }
+
+    /// `transaction` с уровнем изоляции и режимом доступа (см. `begin_with`)
+    pub async fn transaction_with<R, F>(&self, opts: TxOptions, mut f: F) -> Result<R>
+    where
+        F: AsyncFnMut(&mut TxExecutor<'_>) -> Result<R>,
+    {
+        let mut attempt = 1;
+        loop {
+            match self.transaction_once(opts, &mut f).await {
+                Err(e) if e.is_retryable() && attempt < self.tx_max_attempts => {
+                    tokio::time::sleep(self.tx_retry.delay(attempt)).await;
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    /// Повторы для этого исполнителя (то же, что `ExecutorConfig::tx_retry`
+    /// и `ExecutorConfig::tx_max_attempts`)
+    pub fn with_tx_retry(mut self, policy: RetryPolicy, max_attempts: u32) -> Self {
+        self.tx_retry = policy;
+        self.tx_max_attempts = max_attempts.max(1);
+        self
+    }
+
+    async fn transaction_once<R, F>(&self, opts: TxOptions, f: &mut F) -> Result<R>
+    where
+        F: AsyncFnMut(&mut TxExecutor<'_>) -> Result<R>,
+    {
+        let mut tx = self.begin_with(opts).await?;
+        match f(&mut tx).await {
+            Ok(v) => {
+                tx.commit().await?;
+                Ok(v)
+            }
+            Err(e) => {
+                // ошибка отката не должна скрыть исходную; недооткаченную
+                // транзакцию sqlx откатит при возврате соединения в пул
+                let _ = tx.rollback().await;
+                Err(e)
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Типизация строк результата для всех включённых СУБД.
+//!
+//! При нескольких фичах (`postgres` + `sqlite` и т.п.) конкретный тип строки
+//! известен только во время выполнения, поэтому `T` должен уметь читаться из
+//! строки каждой включённой СУБД. `#[derive(sqlx::FromRow)]` и кортежи
+//! удовлетворяют этому автоматически.
+
+#[cfg(feature = "mysql")]
+use sqlx::mysql::MySqlRow;
+#[cfg(feature = "postgres")]
+use sqlx::postgres::PgRow;
+#[cfg(feature = "sqlite")]
+use sqlx::sqlite::SqliteRow;
+
+/// `FromRow` для строки Postgres (пусто без фичи `postgres`)
+#[doc(hidden)]
+#[cfg(feature = "postgres")]
+pub trait FromPgRow: for<'r> sqlx::FromRow<'r, PgRow> {}
+#[cfg(feature = "postgres")]
+impl<T: for<'r> sqlx::FromRow<'r, PgRow>> FromPgRow for T {}
+#[doc(hidden)]
+#[cfg(not(feature = "postgres"))]
+pub trait FromPgRow {}
+#[cfg(not(feature = "postgres"))]
+impl<T> FromPgRow for T {}
+
+/// `FromRow` для строки MySQL (пусто без фичи `mysql`)
+#[doc(hidden)]
+#[cfg(feature = "mysql")]
+pub trait FromMySqlRow: for<'r> sqlx::FromRow<'r, MySqlRow> {}
+#[cfg(feature = "mysql")]
+impl<T: for<'r> sqlx::FromRow<'r, MySqlRow>> FromMySqlRow for T {}
+#[doc(hidden)]
+#[cfg(not(feature = "mysql"))]
+pub trait FromMySqlRow {}
+#[cfg(not(feature = "mysql"))]
+impl<T> FromMySqlRow for T {}
+
+/// `FromRow` для строки SQLite (пусто без фичи `sqlite`)
+#[doc(hidden)]
+#[cfg(feature = "sqlite")]
+pub trait FromSqliteRow: for<'r> sqlx::FromRow<'r, SqliteRow> {}
+#[cfg(feature = "sqlite")]
+impl<T: for<'r> sqlx::FromRow<'r, SqliteRow>> FromSqliteRow for T {}
+#[doc(hidden)]
+#[cfg(not(feature = "sqlite"))]
+pub trait FromSqliteRow {}
+#[cfg(not(feature = "sqlite"))]
+impl<T> FromSqliteRow for T {}
+
+/// Тип строки результата: `sqlx::FromRow` для строки каждой включённой СУБД.
+/// Реализуется автоматически.
+pub trait FromDbRow: FromPgRow + FromMySqlRow + FromSqliteRow + Send + Unpin {}
+impl<T: FromPgRow + FromMySqlRow + FromSqliteRow + Send + Unpin> FromDbRow for T {}
+
+/// Строка результата, когда включена ровно одна СУБД. В обобщённом коде
+/// используйте [`FromDbRow`].
+#[cfg(all(feature = "postgres", not(any(feature = "mysql", feature = "sqlite"))))]
+pub type DbRow = PgRow;
+#[cfg(all(feature = "mysql", not(any(feature = "postgres", feature = "sqlite"))))]
+pub type DbRow = MySqlRow;
+#[cfg(all(feature = "sqlite", not(any(feature = "postgres", feature = "mysql"))))]
+pub type DbRow = SqliteRow;
@@ -0,0 +1,88 @@
+//! Именованные точки сохранения внутри `TxExecutor`: частичный откат
+//! без потери внешней транзакции (например, пропуск плохих строк при импорте).
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::transaction::TxExecutor;
+use super::{Error, Result};
+use crate::query_builder::ExecCtx;
+use crate::renderer::quote_ident_always;
+
+/// Имена для `with_savepoint` — уникальны и при вложенных вызовах
+static NEXT_SAVEPOINT: AtomicU64 = AtomicU64::new(1);
+
+impl<'tx> TxExecutor<'tx> {
+    /// `SAVEPOINT name`
+    pub async fn savepoint(&mut self, name: &str) -> Result<()> {
+        self.savepoint_cmd("SAVEPOINT", name).await
+    }
+
+    /// `ROLLBACK TO SAVEPOINT name`: отменяет изменения после точки,
+    /// сама точка остаётся и её можно использовать снова
+    pub async fn rollback_to(&mut self, name: &str) -> Result<()> {
+        self.savepoint_cmd("ROLLBACK TO SAVEPOINT", name).await
+    }
+
+    /// `RELEASE SAVEPOINT name`: изменения остаются во внешней транзакции
+    pub async fn release(&mut self, name: &str) -> Result<()> {
+        self.savepoint_cmd("RELEASE SAVEPOINT", name).await
+    }
+
+    /// Выполнить `f` под своей точкой сохранения: при `Err` откатываются
+    /// только изменения `f`, транзакция остаётся рабочей. Возвращается ошибка
+    /// самой `f`, даже если откат к точке не удался (тогда транзакция,
+    /// скорее всего, уже непригодна).
+    ///
+    /// ```ignore
+    /// for row in rows {
+    ///     if let Err(e) = tx.with_savepoint(async |tx| insert(tx, &row).await).await {
+    ///         skipped.push((row, e));
+    ///     }
+    /// }
+    /// ```
+    pub async fn with_savepoint<R, F>(&mut self, f: F) -> Result<R>
+    where
+        F: AsyncFnOnce(&mut TxExecutor<'tx>) -> Result<R>,
+    {
+        let name = format!("knux_sp_{}", NEXT_SAVEPOINT.fetch_add(1, Ordering::Relaxed));
+        self.savepoint(&name).await?;
+        match f(self).await {
+            Ok(v) => {
+                self.release(&name).await?;
+                Ok(v)
+            }
+            Err(e) => {
+                // сбой уборки не должен скрывать настоящую причину
+                if self.rollback_to(&name).await.is_ok() {
+                    let _ = self.release(&name).await;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn savepoint_cmd(&mut self, cmd: &str, name: &str) -> Result<()> {
+        use sqlx::Executor;
+
+        let mut ctx = self.exec_ctx();
+        let dialect = ctx.dialect().ok_or(Error::MissingConnection)?;
+        // без подготовки, простым запросом — как savepoint'ы самого sqlx
+        let sql = format!("{cmd} {}", quote_ident_always(name, dialect));
+        match &mut ctx {
+            ExecCtx::None | ExecCtx::Pool(_) => return Err(Error::MissingConnection),
+            #[cfg(feature = "postgres")]
+            ExecCtx::PgConn(conn) => {
+                conn.execute(sql.as_str()).await?;
+            }
+            #[cfg(feature = "mysql")]
+            ExecCtx::MySqlConn(conn) => {
+                conn.execute(sql.as_str()).await?;
+            }
+            #[cfg(feature = "sqlite")]
+            ExecCtx::SqliteConn(conn) => {
+                conn.execute(sql.as_str()).await?;
+            }
+        }
+        Ok(())
+    }
+}
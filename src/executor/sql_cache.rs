@@ -0,0 +1,106 @@
+//! Кэш отрендеренного SQL: горячий SELECT с теми же «формой» билдера
+//! и отличающимися значениями параметров не строит AST и не рендерит SQL заново.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use hashlink::LruCache;
+
+use super::QueryExecutor;
+
+/// Счётчики кэша SQL (см. `QueryExecutor::with_sql_cache`)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SqlCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Сколько форм запросов сейчас в кэше
+    pub len: usize,
+    pub capacity: usize,
+}
+
+struct Entry {
+    sql: String,
+    /// Число параметров формы — страховка от коллизии отпечатков
+    params: usize,
+}
+
+struct Inner {
+    lru: Mutex<LruCache<u64, Entry>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+/// LRU «отпечаток формы билдера → SQL»; клоны делят один кэш
+#[derive(Clone)]
+pub(crate) struct SqlCache(Arc<Inner>);
+
+impl std::fmt::Debug for SqlCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SqlCache").field(&self.stats()).finish()
+    }
+}
+
+impl SqlCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self(Arc::new(Inner {
+            lru: Mutex::new(LruCache::new(capacity.max(1))),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }))
+    }
+
+    /// SQL формы `key` с `params` параметрами; промах учитывается в счётчиках
+    pub(crate) fn get(&self, key: u64, params: usize) -> Option<String> {
+        let sql = self
+            .lock()
+            .get(&key)
+            .filter(|e| e.params == params)
+            .map(|e| e.sql.clone());
+        let counter = if sql.is_some() {
+            &self.0.hits
+        } else {
+            &self.0.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        sql
+    }
+
+    pub(crate) fn insert(&self, key: u64, params: usize, sql: &str) {
+        let entry = Entry {
+            sql: sql.to_owned(),
+            params,
+        };
+        self.lock().insert(key, entry);
+    }
+
+    pub(crate) fn stats(&self) -> SqlCacheStats {
+        let lru = self.lock();
+        SqlCacheStats {
+            hits: self.0.hits.load(Ordering::Relaxed),
+            misses: self.0.misses.load(Ordering::Relaxed),
+            len: lru.len(),
+            capacity: lru.capacity(),
+        }
+    }
+
+    // паника под замком не портит кэш: запись вставляется целиком
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruCache<u64, Entry>> {
+        self.0.lru.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl QueryExecutor {
+    /// Включить кэш SQL на `capacity` форм запросов (то же, что
+    /// `ExecutorConfig::sql_cache`). Ключ — отпечаток структуры SELECT-билдера
+    /// без значений параметров: при попадании билдер лишь собирает параметры.
+    /// Кэш общий для клонов исполнителя и его транзакций.
+    pub fn with_sql_cache(mut self, capacity: usize) -> Self {
+        self.sql_cache = Some(SqlCache::new(capacity));
+        self
+    }
+
+    /// Попадания и промахи кэша SQL; `None`, если он не включён
+    pub fn sql_cache_stats(&self) -> Option<SqlCacheStats> {
+        self.sql_cache.as_ref().map(SqlCache::stats)
+    }
+}
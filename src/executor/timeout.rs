@@ -0,0 +1,273 @@
+//! Таймаут запроса: на клиенте — `tokio::time::timeout`, на сервере —
+//! `statement_timeout` в Postgres и хинт `MAX_EXECUTION_TIME` для SELECT в MySQL.
+//! Серверный лимит обрывает запрос, даже если клиент уже перестал его ждать.
+//! Не-SELECT внутри транзакции MySQL под таймаутом не выполняется: ограничить
+//! его на сервере нечем, а закрыть соединение транзакции нельзя.
+
+#[cfg(feature = "mysql")]
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use futures_util::future::BoxFuture;
+
+use super::{Error, FromDbRow, QueryExecutor, Result, RowStream};
+use crate::param::Param;
+use crate::query_builder::ExecCtx;
+
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+use super::DbPool;
+
+impl QueryExecutor {
+    /// Таймаут по умолчанию для билдеров, `fetch_typed` и транзакций
+    /// этого исполнителя (то же, что `ExecutorConfig::query_timeout`).
+    pub fn with_query_timeout(mut self, timeout: Duration) -> Self {
+        self.query_timeout = Some(timeout);
+        self
+    }
+}
+
+/// Операция под таймаутом: один и тот же запрос может выполниться
+/// на соединении из пула, в транзакции или с переписанным SQL
+trait Op: Sized {
+    type Out;
+
+    fn run<'c>(self, ctx: &'c mut ExecCtx<'_>, sql: &'c str) -> BoxFuture<'c, Result<Self::Out>>
+    where
+        Self: 'c;
+}
+
+struct FetchAll<T>(Vec<Param>, PhantomData<T>);
+
+impl<T: FromDbRow> Op for FetchAll<T> {
+    type Out = Vec<T>;
+
+    fn run<'c>(self, ctx: &'c mut ExecCtx<'_>, sql: &'c str) -> BoxFuture<'c, Result<Vec<T>>>
+    where
+        Self: 'c,
+    {
+        Box::pin(ctx.fetch_all::<T>(sql, self.0))
+    }
+}
+
+struct Execute(Vec<Param>);
+
+impl Op for Execute {
+    type Out = u64;
+
+    fn run<'c>(self, ctx: &'c mut ExecCtx<'_>, sql: &'c str) -> BoxFuture<'c, Result<u64>>
+    where
+        Self: 'c,
+    {
+        Box::pin(ctx.execute(sql, self.0))
+    }
+}
+
+impl ExecCtx<'_> {
+    /// `fetch_all` с ограничением времени (`None` — без ограничения)
+    pub(crate) async fn fetch_all_timeout<T>(
+        &mut self,
+        sql: &str,
+        params: Vec<Param>,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<T>>
+    where
+        T: FromDbRow,
+    {
+        match timeout {
+            Some(timeout) => {
+                self.run_timeout(sql, timeout, FetchAll(params, PhantomData))
+                    .await
+            }
+            None => self.fetch_all(sql, params).await,
+        }
+    }
+
+    /// `execute` с ограничением времени (`None` — без ограничения)
+    pub(crate) async fn execute_timeout(
+        &mut self,
+        sql: &str,
+        params: Vec<Param>,
+        timeout: Option<Duration>,
+    ) -> Result<u64> {
+        match timeout {
+            Some(timeout) => self.run_timeout(sql, timeout, Execute(params)).await,
+            None => self.execute(sql, params).await,
+        }
+    }
+
+    async fn run_timeout<O: Op>(&mut self, sql: &str, timeout: Duration, op: O) -> Result<O::Out> {
+        match self {
+            ExecCtx::None => Err(Error::MissingConnection),
+
+            // на пуле — своя транзакция: SET LOCAL живёт ровно до её конца
+            #[cfg(feature = "postgres")]
+            ExecCtx::Pool(DbPool::Postgres(pool)) => {
+                let mut conn = pool.acquire().await?;
+                let mut tx = sqlx::Connection::begin(&mut *conn).await?;
+                set_pg_timeout(&mut tx, timeout).await?;
+                let res = within(timeout, op.run(&mut ExecCtx::PgConn(&mut tx), sql)).await;
+                match res {
+                    Ok(v) => {
+                        tx.commit().await?;
+                        Ok(v)
+                    }
+                    Err(e) => {
+                        drop(tx);
+                        close_if_timed_out(&e, &mut conn);
+                        Err(e)
+                    }
+                }
+            }
+            #[cfg(feature = "mysql")]
+            ExecCtx::Pool(DbPool::MySql(pool)) => {
+                let mut conn = pool.acquire().await?;
+                let sql = mysql_hint(sql, timeout);
+                let res = within(timeout, op.run(&mut ExecCtx::MySqlConn(&mut conn), &sql)).await;
+                if let Err(e) = &res {
+                    close_if_timed_out(e, &mut conn);
+                }
+                res
+            }
+            // SQLite выполняет команды соединения по очереди в своём потоке —
+            // брошенный запрос не ломает соединение
+            #[cfg(feature = "sqlite")]
+            ExecCtx::Pool(_) => within(timeout, op.run(self, sql)).await,
+
+            // внутри транзакции прежний лимит читается тем же запросом, что ставит новый,
+            // и возвращается в любом случае, чтобы не задеть следующие запросы
+            #[cfg(feature = "postgres")]
+            ExecCtx::PgConn(conn) => {
+                let (prev, _): (String, String) = sqlx::query_as(
+                    "SELECT current_setting('statement_timeout'), \
+                     set_config('statement_timeout', $1, true)",
+                )
+                .bind(format!("{}ms", millis(timeout)))
+                .fetch_one(&mut **conn)
+                .await?;
+                let res = within(timeout, op.run(&mut ExecCtx::PgConn(conn), sql)).await;
+                let restored = sqlx::query("SELECT set_config('statement_timeout', $1, true)")
+                    .bind(prev)
+                    .execute(&mut **conn)
+                    .await;
+                // в оборванной транзакции восстановить нельзя, но SET LOCAL
+                // и так откатится вместе с ней — важнее исходная ошибка
+                let v = res?;
+                restored?;
+                Ok(v)
+            }
+            // соединение транзакции нельзя закрыть, а брошенный на клиенте
+            // не-SELECT продолжил бы выполняться на сервере
+            #[cfg(feature = "mysql")]
+            ExecCtx::MySqlConn(conn) => match mysql_hint(sql, timeout) {
+                Cow::Borrowed(_) => Err(Error::Unsupported(
+                    "query timeout for non-SELECT statements inside a MySQL transaction".into(),
+                )),
+                sql => within(timeout, op.run(&mut ExecCtx::MySqlConn(conn), &sql)).await,
+            },
+            #[cfg(feature = "sqlite")]
+            ExecCtx::SqliteConn(conn) => {
+                within(timeout, op.run(&mut ExecCtx::SqliteConn(conn), sql)).await
+            }
+        }
+    }
+}
+
+/// Поток с общим дедлайном: по истечении — `Error::Timeout` последним элементом.
+/// Серверный лимит для потоков не ставится.
+pub(crate) fn stream_timeout<'s, T>(
+    mut inner: RowStream<'s, T>,
+    timeout: Option<Duration>,
+) -> RowStream<'s, T>
+where
+    T: Send + 's,
+{
+    let Some(timeout) = timeout else {
+        return inner;
+    };
+    Box::pin(async_stream::stream! {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            match tokio::time::timeout_at(deadline, inner.next()).await {
+                Ok(Some(item)) => yield item,
+                Ok(None) => break,
+                Err(_) => {
+                    yield Err(Error::Timeout(timeout));
+                    break;
+                }
+            }
+        }
+    })
+}
+
+async fn within<R>(timeout: Duration, fut: impl Future<Output = Result<R>>) -> Result<R> {
+    match tokio::time::timeout(timeout, fut).await {
+        Ok(res) => res.map_err(|e| server_timeout(e, timeout)),
+        Err(_) => Err(Error::Timeout(timeout)),
+    }
+}
+
+/// Отмена по серверному лимиту — та же `Error::Timeout`
+fn server_timeout(e: Error, timeout: Duration) -> Error {
+    let Error::Sqlx(sqlx::Error::Database(db)) = &e else {
+        return e;
+    };
+    // 57014 query_canceled — в том числе по statement_timeout
+    if db.code().as_deref() == Some("57014") {
+        return Error::Timeout(timeout);
+    }
+    // 3024 ER_QUERY_TIMEOUT — MAX_EXECUTION_TIME
+    #[cfg(feature = "mysql")]
+    if db
+        .try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>()
+        .is_some_and(|my| my.number() == 3024)
+    {
+        return Error::Timeout(timeout);
+    }
+    e
+}
+
+/// Лимит в миллисекундах; 0 на сервере означает «без лимита»
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+fn millis(timeout: Duration) -> u128 {
+    timeout.as_millis().max(1)
+}
+
+#[cfg(feature = "postgres")]
+async fn set_pg_timeout(conn: &mut sqlx::PgConnection, timeout: Duration) -> Result<()> {
+    sqlx::query("SELECT set_config('statement_timeout', $1, true)")
+        .bind(format!("{}ms", millis(timeout)))
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+/// Соединение с недочитанным запросом не возвращаем в пул
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+fn close_if_timed_out<DB: sqlx::Database>(e: &Error, conn: &mut sqlx::pool::PoolConnection<DB>) {
+    if matches!(e, Error::Timeout(_)) {
+        conn.close_on_drop();
+    }
+}
+
+/// `SELECT /*+ MAX_EXECUTION_TIME(ms) */ ...`; прочие операторы MySQL
+/// ограничиваются только на клиенте
+#[cfg(feature = "mysql")]
+pub(crate) fn mysql_hint(sql: &str, timeout: Duration) -> Cow<'_, str> {
+    let body = sql.trim_start();
+    let is_select = body
+        .get(..6)
+        .is_some_and(|kw| kw.eq_ignore_ascii_case("SELECT"))
+        && body[6..].starts_with(|c: char| c.is_ascii_whitespace());
+    if !is_select {
+        return Cow::Borrowed(sql);
+    }
+    let at = sql.len() - body.len() + 6;
+    Cow::Owned(format!(
+        "{} /*+ MAX_EXECUTION_TIME({}) */{}",
+        &sql[..at],
+        millis(timeout),
+        &sql[at..]
+    ))
+}
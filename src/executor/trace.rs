@@ -0,0 +1,126 @@
+//! Спаны `tracing` вокруг выполнения запросов (фича `tracing`). Без фичи
+//! обёртки ничего не делают и сводятся к исходному future/потоку.
+
+use std::future::Future;
+
+use super::{Result, RowStream};
+use crate::renderer::Dialect;
+
+/// Число строк результата — для спанов и хуков
+#[allow(clippy::ptr_arg)] // передаётся как `fn(&Vec<T>) -> u64`
+pub(crate) fn rows_vec<T>(rows: &Vec<T>) -> u64 {
+    rows.len() as u64
+}
+
+pub(crate) fn rows_opt<T>(row: &Option<T>) -> u64 {
+    row.is_some() as u64
+}
+
+pub(crate) fn rows_one<T>(_: &T) -> u64 {
+    1
+}
+
+pub(crate) fn rows_affected(n: &u64) -> u64 {
+    *n
+}
+
+/// Спан `knux.query`: SQL, число параметров и диалект при старте,
+/// строки (прочитанные или затронутые) и длительность — по завершении.
+/// Создаётся до того, как SQL уйдёт в future/поток.
+pub(crate) struct QuerySpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+impl QuerySpan {
+    #[cfg(feature = "tracing")]
+    pub(crate) fn new(dialect: Dialect, sql: &str, params: usize) -> Self {
+        let span = tracing::debug_span!(
+            "knux.query",
+            db.system = %dialect,
+            db.statement = sql,
+            db.params = params,
+            db.rows = tracing::field::Empty,
+            elapsed_ms = tracing::field::Empty,
+        );
+        Self { span }
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    #[inline(always)]
+    pub(crate) fn new(_dialect: Dialect, _sql: &str, _params: usize) -> Self {
+        Self {}
+    }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) async fn run<R, F>(self, rows: fn(&R) -> u64, fut: F) -> Result<R>
+    where
+        F: Future<Output = Result<R>>,
+    {
+        use tracing::Instrument;
+
+        let started = std::time::Instant::now();
+        let res = fut.instrument(self.span.clone()).await;
+        self.finish(started, res.as_ref().map(rows));
+        res
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    #[inline(always)]
+    pub(crate) async fn run<R, F>(self, _rows: fn(&R) -> u64, fut: F) -> Result<R>
+    where
+        F: Future<Output = Result<R>>,
+    {
+        fut.await
+    }
+
+    /// Для построчного чтения спан закрывается, когда поток исчерпан
+    #[cfg(feature = "tracing")]
+    pub(crate) fn stream<'s, T>(self, mut inner: RowStream<'s, T>) -> RowStream<'s, T>
+    where
+        T: Send + 's,
+    {
+        use futures_util::StreamExt;
+        use tracing::Instrument;
+
+        Box::pin(async_stream::stream! {
+            let started = std::time::Instant::now();
+            let mut rows = 0u64;
+            let mut failed = None;
+            while let Some(item) = inner.next().instrument(self.span.clone()).await {
+                match &item {
+                    Ok(_) => rows += 1,
+                    Err(e) => failed = Some(e.to_string()),
+                }
+                yield item;
+            }
+            match failed {
+                Some(e) => self.finish(started, Err(e)),
+                None => self.finish(started, Ok::<_, String>(rows)),
+            }
+        })
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    #[inline(always)]
+    pub(crate) fn stream<'s, T>(self, inner: RowStream<'s, T>) -> RowStream<'s, T> {
+        inner
+    }
+
+    #[cfg(feature = "tracing")]
+    fn finish<E: std::fmt::Display>(
+        &self,
+        started: std::time::Instant,
+        res: std::result::Result<u64, E>,
+    ) {
+        let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+        self.span.record("elapsed_ms", elapsed_ms);
+        match res {
+            Ok(rows) => {
+                self.span.record("db.rows", rows);
+                tracing::debug!(parent: &self.span, rows, elapsed_ms, "query finished");
+            }
+            Err(e) => tracing::debug!(parent: &self.span, error = %e, elapsed_ms, "query failed"),
+        }
+    }
+}
@@ -1,5 +1,9 @@
+use std::time::Duration;
+
 use super::{Error, Result};
-use crate::executor::{DbPool, QueryExecutor};
+use crate::executor::{
+    DbConn, DbPool, FromDbRow, QueryExecutor, QueryHooks, QueryInfo, SqlCache, TxOptions, trace,
+};
 use sqlx::Acquire;
 
 #[allow(dead_code)]
@@ -19,48 +23,114 @@ pub struct TxExecutor<'a> {
     // Нужен для построения SQL через наш QueryBuilder (диалект/квотинг и т.п.)
     pool: DbPool,
     schema: Option<String>,
+    hooks: QueryHooks,
+    timeout: Option<Duration>,
+    sql_cache: Option<SqlCache>,
 }
 
 impl QueryExecutor {
     pub async fn begin(&self) -> Result<TxExecutor<'_>> {
-        match &self.pool {
+        self.begin_with(TxOptions::default()).await
+    }
+
+    /// Транзакция с уровнем изоляции и режимом доступа (см. `TxOptions`).
+    /// Недопустимые для диалекта параметры — ошибка до обращения к базе.
+    pub async fn begin_with(&self, opts: TxOptions) -> Result<TxExecutor<'_>> {
+        let sql = opts.begin_sql(self.dialect())?;
+        let tx = match &self.pool {
             #[cfg(feature = "postgres")]
-            DbPool::Postgres(pool) => {
-                let tx = pool.begin().await?;
-                Ok(TxExecutor {
-                    tx: Some(DbTx::Postgres(tx)),
-                    pool: self.pool.clone(),
-                    schema: self.schema.clone(),
-                })
-            }
+            DbPool::Postgres(pool) => DbTx::Postgres(begin_pool(pool, sql).await?),
             #[cfg(feature = "mysql")]
-            DbPool::MySql(pool) => {
-                let tx = pool.begin().await?;
-                Ok(TxExecutor {
-                    tx: Some(DbTx::MySql(tx)),
-                    pool: self.pool.clone(),
-                    schema: self.schema.clone(),
-                })
-            }
+            DbPool::MySql(pool) => DbTx::MySql(begin_pool(pool, sql).await?),
             #[cfg(feature = "sqlite")]
-            DbPool::Sqlite(pool) => {
-                let tx = pool.begin().await?;
-                Ok(TxExecutor {
-                    tx: Some(DbTx::Sqlite(tx)),
-                    pool: self.pool.clone(),
-                    schema: self.schema.clone(),
-                })
-            }
-        }
+            DbPool::Sqlite(pool) => DbTx::Sqlite(begin_pool(pool, sql).await?),
+        };
+        Ok(TxExecutor {
+            tx: Some(tx),
+            pool: self.pool.clone(),
+            schema: self.schema.clone(),
+            hooks: self.hooks.clone(),
+            timeout: self.query_timeout,
+            sql_cache: self.sql_cache.clone(),
+        })
+    }
+}
+
+async fn begin_pool<DB: sqlx::Database>(
+    pool: &sqlx::Pool<DB>,
+    sql: Option<String>,
+) -> sqlx::Result<sqlx::Transaction<'static, DB>> {
+    match sql {
+        Some(sql) => pool.begin_with(sql).await,
+        None => pool.begin().await,
+    }
+}
+
+impl DbPool {
+    /// Транзакция прямо на пуле — для внутренних многошаговых операций
+    /// (например, вставка пачками)
+    pub(crate) async fn begin_tx(&self) -> Result<TxExecutor<'static>> {
+        let tx = match self {
+            #[cfg(feature = "postgres")]
+            DbPool::Postgres(pool) => DbTx::Postgres(pool.begin().await?),
+            #[cfg(feature = "mysql")]
+            DbPool::MySql(pool) => DbTx::MySql(pool.begin().await?),
+            #[cfg(feature = "sqlite")]
+            DbPool::Sqlite(pool) => DbTx::Sqlite(pool.begin().await?),
+        };
+        Ok(TxExecutor {
+            tx: Some(tx),
+            pool: self.clone(),
+            schema: None,
+            hooks: QueryHooks::default(),
+            timeout: None,
+            sql_cache: None,
+        })
+    }
+}
+
+impl DbConn {
+    /// Транзакция на этом соединении (после неё соединение остаётся за владельцем)
+    pub(crate) async fn begin(&mut self, exec: &QueryExecutor) -> Result<TxExecutor<'_>> {
+        let tx = match self {
+            #[cfg(feature = "postgres")]
+            DbConn::Postgres(conn) => DbTx::Postgres(conn.begin().await?),
+            #[cfg(feature = "mysql")]
+            DbConn::MySql(conn) => DbTx::MySql(conn.begin().await?),
+            #[cfg(feature = "sqlite")]
+            DbConn::Sqlite(conn) => DbTx::Sqlite(conn.begin().await?),
+        };
+        Ok(TxExecutor {
+            tx: Some(tx),
+            pool: exec.pool.clone(),
+            schema: exec.schema.clone(),
+            hooks: exec.hooks.clone(),
+            timeout: exec.query_timeout,
+            sql_cache: exec.sql_cache.clone(),
+        })
     }
 }
 
 impl<'tx> TxExecutor<'tx> {
     #[inline]
     pub fn query<'s, T>(&'s mut self) -> crate::query_builder::TxQuery<'s, T> {
-        use crate::query_builder::{ExecCtx, QueryBuilder, TxQuery};
+        use crate::query_builder::{QueryBuilder, TxQuery};
+
+        let schema = self.schema.clone();
+        let hooks = self.hooks.clone();
+        let timeout = self.timeout;
+        let sql_cache = self.sql_cache.clone();
+        let mut qb = QueryBuilder::new_tx(schema, self.exec_ctx()).with_hooks(hooks);
+        qb.timeout = timeout;
+        qb.sql_cache = sql_cache;
+        TxQuery::new(qb)
+    }
+
+    /// Контекст исполнения на соединении транзакции
+    pub(crate) fn exec_ctx<'s>(&'s mut self) -> crate::query_builder::ExecCtx<'s> {
+        use crate::query_builder::ExecCtx;
 
-        let exec_ctx: ExecCtx<'s> = match self.tx.as_mut() {
+        match self.tx.as_mut() {
             #[cfg(feature = "postgres")]
             Some(DbTx::Postgres(tx)) => ExecCtx::PgConn(tx.as_mut()),
             #[cfg(feature = "mysql")]
@@ -68,9 +138,12 @@ impl<'tx> TxExecutor<'tx> {
             #[cfg(feature = "sqlite")]
             Some(DbTx::Sqlite(tx)) => ExecCtx::SqliteConn(tx.as_mut()),
             None => ExecCtx::None,
-        };
-        let qb = QueryBuilder::new_tx(self.schema.clone(), exec_ctx);
-        TxQuery::new(qb)
+        }
+    }
+
+    #[inline]
+    pub(crate) fn default_schema(&self) -> Option<String> {
+        self.schema.clone()
     }
 
     pub async fn fetch_typed<T>(
@@ -79,58 +152,85 @@ impl<'tx> TxExecutor<'tx> {
         params: Vec<crate::param::Param>,
     ) -> Result<Vec<T>>
     where
-        T: for<'r> sqlx::FromRow<'r, DbRow> + Send + Unpin,
+        T: FromDbRow,
     {
-        match self.tx.as_mut() {
-            #[cfg(feature = "postgres")]
-            Some(DbTx::Postgres(tx)) => {
-                crate::executor::transaction_utils::fetch_typed_pg_exec::<_, T>(
-                    tx.as_mut(),
-                    sql,
-                    params,
-                )
-                .await
-            }
-            #[cfg(feature = "mysql")]
-            Some(DbTx::MySql(tx)) => {
-                crate::executor::transaction_utils::fetch_typed_mysql_exec::<_, T>(
-                    tx.as_mut(),
-                    sql,
-                    params,
-                )
-                .await
-            }
-            #[cfg(feature = "sqlite")]
-            Some(DbTx::Sqlite(tx)) => {
-                crate::executor::transaction_utils::fetch_typed_sqlite_exec::<_, T>(
-                    tx.as_mut(),
-                    sql,
-                    params,
-                )
-                .await
-            }
-            None => Err(Error::MissingConnection),
+        let query = QueryInfo::new(sql, params.len(), self.pool.dialect());
+        if self.timeout.is_some() {
+            let (hooks, timeout) = (self.hooks.clone(), self.timeout);
+            let mut ctx = self.exec_ctx();
+            let fut = async move { ctx.fetch_all_timeout(sql, params, timeout).await };
+            return hooks.observe(query, trace::rows_vec, fut).await;
         }
+        let tx = self.tx.as_mut();
+        let fut = async move {
+            match tx {
+                #[cfg(feature = "postgres")]
+                Some(DbTx::Postgres(tx)) => {
+                    crate::executor::transaction_utils::fetch_typed_pg_exec::<_, T>(
+                        tx.as_mut(),
+                        sql,
+                        params,
+                    )
+                    .await
+                }
+                #[cfg(feature = "mysql")]
+                Some(DbTx::MySql(tx)) => {
+                    crate::executor::transaction_utils::fetch_typed_mysql_exec::<_, T>(
+                        tx.as_mut(),
+                        sql,
+                        params,
+                    )
+                    .await
+                }
+                #[cfg(feature = "sqlite")]
+                Some(DbTx::Sqlite(tx)) => {
+                    crate::executor::transaction_utils::fetch_typed_sqlite_exec::<_, T>(
+                        tx.as_mut(),
+                        sql,
+                        params,
+                    )
+                    .await
+                }
+                None => Err(Error::MissingConnection),
+            }
+        };
+        self.hooks.observe(query, trace::rows_vec, fut).await
     }
 
     pub async fn execute(&mut self, sql: &str, params: Vec<crate::param::Param>) -> Result<u64> {
-        match self.tx.as_mut() {
-            #[cfg(feature = "postgres")]
-            Some(DbTx::Postgres(tx)) => {
-                crate::executor::transaction_utils::execute_pg_exec(tx.as_mut(), sql, params).await
-            }
-            #[cfg(feature = "mysql")]
-            Some(DbTx::MySql(tx)) => {
-                crate::executor::transaction_utils::execute_mysql_exec(tx.as_mut(), sql, params)
-                    .await
-            }
-            #[cfg(feature = "sqlite")]
-            Some(DbTx::Sqlite(tx)) => {
-                crate::executor::transaction_utils::execute_sqlite_exec(tx.as_mut(), sql, params)
+        let query = QueryInfo::new(sql, params.len(), self.pool.dialect());
+        if self.timeout.is_some() {
+            let (hooks, timeout) = (self.hooks.clone(), self.timeout);
+            let mut ctx = self.exec_ctx();
+            let fut = async move { ctx.execute_timeout(sql, params, timeout).await };
+            return hooks.observe(query, trace::rows_affected, fut).await;
+        }
+        let tx = self.tx.as_mut();
+        let fut = async move {
+            match tx {
+                #[cfg(feature = "postgres")]
+                Some(DbTx::Postgres(tx)) => {
+                    crate::executor::transaction_utils::execute_pg_exec(tx.as_mut(), sql, params)
+                        .await
+                }
+                #[cfg(feature = "mysql")]
+                Some(DbTx::MySql(tx)) => {
+                    crate::executor::transaction_utils::execute_mysql_exec(tx.as_mut(), sql, params)
+                        .await
+                }
+                #[cfg(feature = "sqlite")]
+                Some(DbTx::Sqlite(tx)) => {
+                    crate::executor::transaction_utils::execute_sqlite_exec(
+                        tx.as_mut(),
+                        sql,
+                        params,
+                    )
                     .await
+                }
+                None => Err(Error::MissingConnection),
             }
-            None => Err(Error::MissingConnection),
-        }
+        };
+        self.hooks.observe(query, trace::rows_affected, fut).await
     }
 
     // NEW: вложенная транзакция (SAVEPOINT)
@@ -143,6 +243,9 @@ impl<'tx> TxExecutor<'tx> {
                     tx: Some(DbTx::Postgres(nested)),
                     pool: self.pool.clone(),
                     schema: self.schema.clone(),
+                    hooks: self.hooks.clone(),
+                    timeout: self.timeout,
+                    sql_cache: self.sql_cache.clone(),
                 })
             }
             #[cfg(feature = "mysql")]
@@ -152,6 +255,9 @@ impl<'tx> TxExecutor<'tx> {
                     tx: Some(DbTx::MySql(nested)),
                     pool: self.pool.clone(),
                     schema: self.schema.clone(),
+                    hooks: self.hooks.clone(),
+                    timeout: self.timeout,
+                    sql_cache: self.sql_cache.clone(),
                 })
             }
             #[cfg(feature = "sqlite")]
@@ -161,6 +267,9 @@ impl<'tx> TxExecutor<'tx> {
                     tx: Some(DbTx::Sqlite(nested)),
                     pool: self.pool.clone(),
                     schema: self.schema.clone(),
+                    hooks: self.hooks.clone(),
+                    timeout: self.timeout,
+                    sql_cache: self.sql_cache.clone(),
                 })
             }
             None => Err(Error::MissingConnection),
@@ -196,10 +305,3 @@ impl<'tx> TxExecutor<'tx> {
         Ok(())
     }
 }
-
-#[cfg(feature = "postgres")]
-type DbRow = sqlx::postgres::PgRow;
-#[cfg(all(not(feature = "postgres"), feature = "mysql"))]
-type DbRow = sqlx::mysql::MySqlRow;
-#[cfg(all(not(any(feature = "postgres", feature = "mysql")), feature = "sqlite"))]
-type DbRow = sqlx::sqlite::SqliteRow;
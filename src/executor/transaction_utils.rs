@@ -1,5 +1,7 @@
-use super::Result;
+use super::trace::{QuerySpan, rows_affected, rows_vec};
+use super::{Result, RowStream};
 use crate::param::Param;
+use crate::renderer::Dialect;
 
 // NEW: Postgres
 #[cfg(feature = "postgres")]
@@ -8,6 +10,7 @@ where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
     for<'r> T: sqlx::FromRow<'r, sqlx::postgres::PgRow> + Send + Unpin,
 {
+    let span = QuerySpan::new(Dialect::Postgres, sql, params.len());
     let mut q = sqlx::query_as::<_, T>(sql);
     for p in params {
         q = match p {
@@ -72,7 +75,8 @@ where
             Param::NullChronoNaiveDateTime => q.bind(None::<chrono::NaiveDateTime>),
         };
     }
-    Ok(q.fetch_all(exec).await?)
+    span.run(rows_vec, async { Ok(q.fetch_all(exec).await?) })
+        .await
 }
 
 #[cfg(feature = "postgres")]
@@ -80,6 +84,7 @@ pub async fn execute_pg_exec<'e, E>(exec: E, sql: &str, params: Vec<Param>) -> R
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
+    let span = QuerySpan::new(Dialect::Postgres, sql, params.len());
     let mut q = sqlx::query(sql);
     for p in params {
         q = match p {
@@ -143,7 +148,8 @@ where
             Param::NullChronoNaiveDateTime => q.bind(None::<chrono::NaiveDateTime>),
         };
     }
-    Ok(q.execute(exec).await?.rows_affected())
+    let fut = async { Ok(q.execute(exec).await?.rows_affected()) };
+    span.run(rows_affected, fut).await
 }
 
 // NEW: MySQL
@@ -157,6 +163,7 @@ where
     E: sqlx::Executor<'e, Database = sqlx::MySql>,
     for<'r> T: sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + Send + Unpin,
 {
+    let span = QuerySpan::new(Dialect::MySQL, sql, params.len());
     let mut q = sqlx::query_as::<_, T>(sql);
     for p in params {
         q = match p {
@@ -189,7 +196,8 @@ where
             Param::NullI8 => q.bind(None::<i8>),
         };
     }
-    Ok(q.fetch_all(exec).await?)
+    span.run(rows_vec, async { Ok(q.fetch_all(exec).await?) })
+        .await
 }
 
 #[cfg(feature = "mysql")]
@@ -197,6 +205,7 @@ pub async fn execute_mysql_exec<'e, E>(exec: E, sql: &str, params: Vec<Param>) -
 where
     E: sqlx::Executor<'e, Database = sqlx::MySql>,
 {
+    let span = QuerySpan::new(Dialect::MySQL, sql, params.len());
     let mut q = sqlx::query(sql);
     for p in params {
         q = match p {
@@ -228,7 +237,8 @@ where
             Param::NullI8 => q.bind(None::<i8>),
         };
     }
-    Ok(q.execute(exec).await?.rows_affected())
+    let fut = async { Ok(q.execute(exec).await?.rows_affected()) };
+    span.run(rows_affected, fut).await
 }
 
 #[cfg(feature = "sqlite")]
@@ -241,6 +251,7 @@ where
     E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
     for<'r> T: sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> + Send + Unpin,
 {
+    let span = QuerySpan::new(Dialect::SQLite, sql, params.len());
     let mut q = sqlx::query_as::<_, T>(sql);
     for p in params {
         q = match p {
@@ -272,7 +283,8 @@ where
             Param::NullI8 => q.bind(None::<i8>),
         };
     }
-    Ok(q.fetch_all(exec).await?)
+    span.run(rows_vec, async { Ok(q.fetch_all(exec).await?) })
+        .await
 }
 
 #[cfg(feature = "sqlite")]
@@ -280,6 +292,7 @@ pub async fn execute_sqlite_exec<'e, E>(exec: E, sql: &str, params: Vec<Param>)
 where
     E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
 {
+    let span = QuerySpan::new(Dialect::SQLite, sql, params.len());
     let mut q = sqlx::query(sql);
     for p in params {
         q = match p {
@@ -311,5 +324,65 @@ where
             Param::NullI8 => q.bind(None::<i8>),
         };
     }
-    Ok(q.execute(exec).await?.rows_affected())
+    let fut = async { Ok(q.execute(exec).await?.rows_affected()) };
+    span.run(rows_affected, fut).await
+}
+
+// Потоковое чтение внутри транзакции: SQL и параметры владеет сам поток,
+// соединение заимствуется на всё время чтения.
+#[cfg(feature = "postgres")]
+pub fn stream_typed_pg_exec<'e, T>(
+    conn: &'e mut sqlx::PgConnection,
+    sql: String,
+    params: Vec<Param>,
+) -> RowStream<'e, T>
+where
+    for<'r> T: sqlx::FromRow<'r, sqlx::postgres::PgRow> + Send + Unpin + 'e,
+{
+    use futures_util::TryStreamExt;
+    let span = QuerySpan::new(Dialect::Postgres, &sql, params.len());
+    span.stream(Box::pin(async_stream::try_stream! {
+        let mut rows = super::utils::build_query_as_pg::<T>(&sql, params).fetch(&mut *conn);
+        while let Some(row) = rows.try_next().await? {
+            yield row;
+        }
+    }))
+}
+
+#[cfg(feature = "mysql")]
+pub fn stream_typed_mysql_exec<'e, T>(
+    conn: &'e mut sqlx::MySqlConnection,
+    sql: String,
+    params: Vec<Param>,
+) -> RowStream<'e, T>
+where
+    for<'r> T: sqlx::FromRow<'r, sqlx::mysql::MySqlRow> + Send + Unpin + 'e,
+{
+    use futures_util::TryStreamExt;
+    let span = QuerySpan::new(Dialect::MySQL, &sql, params.len());
+    span.stream(Box::pin(async_stream::try_stream! {
+        let mut rows = super::utils::build_query_as_mysql::<T>(&sql, params).fetch(&mut *conn);
+        while let Some(row) = rows.try_next().await? {
+            yield row;
+        }
+    }))
+}
+
+#[cfg(feature = "sqlite")]
+pub fn stream_typed_sqlite_exec<'e, T>(
+    conn: &'e mut sqlx::SqliteConnection,
+    sql: String,
+    params: Vec<Param>,
+) -> RowStream<'e, T>
+where
+    for<'r> T: sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> + Send + Unpin + 'e,
+{
+    use futures_util::TryStreamExt;
+    let span = QuerySpan::new(Dialect::SQLite, &sql, params.len());
+    span.stream(Box::pin(async_stream::try_stream! {
+        let mut rows = super::utils::build_query_as_sqlite::<T>(&sql, params).fetch(&mut *conn);
+        while let Some(row) = rows.try_next().await? {
+            yield row;
+        }
+    }))
 }
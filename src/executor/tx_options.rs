@@ -0,0 +1,122 @@
+//! Параметры транзакции для `QueryExecutor::begin_with`: уровень изоляции
+//! и режим доступа, переведённые в оператор начала транзакции диалекта.
+
+use super::{Error, Result};
+use crate::renderer::Dialect;
+
+/// Уровень изоляции транзакции
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl IsolationLevel {
+    fn as_sql(self) -> &'static str {
+        match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        }
+    }
+}
+
+/// Параметры `begin_with`. По умолчанию — обычный `BEGIN` без параметров.
+///
+/// SQLite всегда изолирует транзакции как SERIALIZABLE, поэтому уровень там
+/// определяет момент блокировки: `Serializable` — `BEGIN EXCLUSIVE`,
+/// `RepeatableRead` — `BEGIN IMMEDIATE`, остальные — `BEGIN DEFERRED`.
+/// Транзакций только для чтения в SQLite нет — `read_only` там ошибка.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TxOptions {
+    pub isolation: Option<IsolationLevel>,
+    pub read_only: bool,
+    /// Только Postgres и только вместе с `SERIALIZABLE READ ONLY`
+    pub deferrable: bool,
+}
+
+impl TxOptions {
+    pub fn isolation(mut self, level: IsolationLevel) -> Self {
+        self.isolation = Some(level);
+        self
+    }
+
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    pub fn deferrable(mut self) -> Self {
+        self.deferrable = true;
+        self
+    }
+
+    /// Оператор начала транзакции; `None` — параметров нет, подойдёт обычный `BEGIN`
+    pub(crate) fn begin_sql(&self, dialect: Dialect) -> Result<Option<String>> {
+        if *self == TxOptions::default() {
+            return Ok(None);
+        }
+        if self.deferrable && dialect != Dialect::Postgres {
+            return Err(Error::UnsupportedTxOption {
+                option: "DEFERRABLE",
+                dialect,
+            });
+        }
+        if self.read_only && dialect == Dialect::SQLite {
+            return Err(Error::UnsupportedTxOption {
+                option: "READ ONLY",
+                dialect,
+            });
+        }
+
+        let sql = match dialect {
+            Dialect::Postgres => {
+                if self.deferrable
+                    && !(self.read_only && self.isolation == Some(IsolationLevel::Serializable))
+                {
+                    return Err(Error::InvalidTxOptions(
+                        "DEFERRABLE requires SERIALIZABLE READ ONLY".into(),
+                    ));
+                }
+                let mut sql = String::from("BEGIN");
+                if let Some(level) = self.isolation {
+                    sql.push_str(" ISOLATION LEVEL ");
+                    sql.push_str(level.as_sql());
+                }
+                if self.read_only {
+                    sql.push_str(" READ ONLY");
+                }
+                if self.deferrable {
+                    sql.push_str(" DEFERRABLE");
+                }
+                sql
+            }
+            // SET TRANSACTION без SESSION действует ровно на следующую транзакцию
+            Dialect::MySQL => {
+                let mut sql = String::new();
+                if let Some(level) = self.isolation {
+                    sql.push_str("SET TRANSACTION ISOLATION LEVEL ");
+                    sql.push_str(level.as_sql());
+                    sql.push_str("; ");
+                }
+                sql.push_str("START TRANSACTION");
+                if self.read_only {
+                    sql.push_str(" READ ONLY");
+                }
+                sql
+            }
+            Dialect::SQLite => {
+                let mode = match self.isolation {
+                    Some(IsolationLevel::Serializable) => "EXCLUSIVE",
+                    Some(IsolationLevel::RepeatableRead) => "IMMEDIATE",
+                    _ => "DEFERRED",
+                };
+                format!("BEGIN {mode}")
+            }
+        };
+        Ok(Some(sql))
+    }
+}
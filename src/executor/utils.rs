@@ -1,17 +1,21 @@
+use sqlx::Arguments;
+
 #[cfg(feature = "postgres")]
-use sqlx::{Arguments, PgPool, postgres::PgRow};
+use sqlx::{PgPool, postgres::PgRow};
 
 #[cfg(feature = "mysql")]
-use sqlx::{Arguments, MySqlPool, mysql::MySqlRow};
+use sqlx::{MySqlPool, mysql::MySqlRow};
 
 #[cfg(feature = "sqlite")]
-use sqlx::{Arguments, SqlitePool, sqlite::SqliteRow};
+use sqlx::{SqlitePool, sqlite::SqliteRow};
 
 use std::{borrow::Cow, time::Duration};
 
 use crate::param::Param;
+use crate::renderer::Dialect;
 
-use super::{Error, Result};
+use super::trace::{QuerySpan, rows_affected, rows_one, rows_opt, rows_vec};
+use super::{Error, Result, RowStream};
 
 pub fn parse_u32(v: &str, key: &str) -> Result<u32> {
     v.parse::<u32>().map_err(|_| Error::InvalidInt {
@@ -41,8 +45,10 @@ pub async fn fetch_typed_pg<T>(pool: &PgPool, sql: &str, params: Vec<Param>) ->
 where
     for<'r> T: sqlx::FromRow<'r, PgRow> + Send + Unpin,
 {
+    let span = QuerySpan::new(Dialect::Postgres, sql, params.len());
     let q = build_query_as_pg::<T>(sql, params);
-    Ok(q.fetch_all(pool).await?)
+    span.run(rows_vec, async { Ok(q.fetch_all(pool).await?) })
+        .await
 }
 
 #[cfg(feature = "postgres")]
@@ -50,8 +56,10 @@ pub async fn fetch_one_typed_pg<T>(pool: &PgPool, sql: &str, params: Vec<Param>)
 where
     for<'r> T: sqlx::FromRow<'r, PgRow> + Send + Unpin,
 {
+    let span = QuerySpan::new(Dialect::Postgres, sql, params.len());
     let q = build_query_as_pg::<T>(sql, params);
-    Ok(q.fetch_one(pool).await?)
+    span.run(rows_one, async { Ok(q.fetch_one(pool).await?) })
+        .await
 }
 
 #[cfg(feature = "postgres")]
@@ -63,8 +71,10 @@ pub async fn fetch_optional_typed_pg<T>(
 where
     for<'r> T: sqlx::FromRow<'r, PgRow> + Send + Unpin,
 {
+    let span = QuerySpan::new(Dialect::Postgres, sql, params.len());
     let q = build_query_as_pg::<T>(sql, params);
-    Ok(q.fetch_optional(pool).await?)
+    span.run(rows_opt, async { Ok(q.fetch_optional(pool).await?) })
+        .await
 }
 
 #[cfg(feature = "mysql")]
@@ -72,8 +82,10 @@ pub async fn fetch_typed_mysql<T>(pool: &MySqlPool, sql: &str, params: Vec<Param
 where
     for<'r> T: sqlx::FromRow<'r, MySqlRow> + Send + Unpin,
 {
+    let span = QuerySpan::new(Dialect::MySQL, sql, params.len());
     let q = build_query_as_mysql::<T>(sql, params);
-    Ok(q.fetch_all(pool).await?)
+    span.run(rows_vec, async { Ok(q.fetch_all(pool).await?) })
+        .await
 }
 
 #[cfg(feature = "mysql")]
@@ -81,8 +93,10 @@ pub async fn fetch_one_typed_mysql<T>(pool: &MySqlPool, sql: &str, params: Vec<P
 where
     for<'r> T: sqlx::FromRow<'r, MySqlRow> + Send + Unpin,
 {
+    let span = QuerySpan::new(Dialect::MySQL, sql, params.len());
     let q = build_query_as_mysql::<T>(sql, params);
-    Ok(q.fetch_one(pool).await?)
+    span.run(rows_one, async { Ok(q.fetch_one(pool).await?) })
+        .await
 }
 
 #[cfg(feature = "mysql")]
@@ -94,8 +108,10 @@ pub async fn fetch_optional_typed_mysql<T>(
 where
     for<'r> T: sqlx::FromRow<'r, MySqlRow> + Send + Unpin,
 {
+    let span = QuerySpan::new(Dialect::MySQL, sql, params.len());
     let q = build_query_as_mysql::<T>(sql, params);
-    Ok(q.fetch_optional(pool).await?)
+    span.run(rows_opt, async { Ok(q.fetch_optional(pool).await?) })
+        .await
 }
 
 #[cfg(feature = "sqlite")]
@@ -107,8 +123,10 @@ pub async fn fetch_typed_sqlite<T>(
 where
     for<'r> T: sqlx::FromRow<'r, SqliteRow> + Send + Unpin,
 {
+    let span = QuerySpan::new(Dialect::SQLite, sql, params.len());
     let q = build_query_as_sqlite::<T>(sql, params);
-    Ok(q.fetch_all(pool).await?)
+    span.run(rows_vec, async { Ok(q.fetch_all(pool).await?) })
+        .await
 }
 
 #[cfg(feature = "sqlite")]
@@ -120,8 +138,10 @@ pub async fn fetch_one_typed_sqlite<T>(
 where
     for<'r> T: sqlx::FromRow<'r, SqliteRow> + Send + Unpin,
 {
+    let span = QuerySpan::new(Dialect::SQLite, sql, params.len());
     let q = build_query_as_sqlite::<T>(sql, params);
-    Ok(q.fetch_one(pool).await?)
+    span.run(rows_one, async { Ok(q.fetch_one(pool).await?) })
+        .await
 }
 
 #[cfg(feature = "sqlite")]
@@ -133,29 +153,107 @@ pub async fn fetch_optional_typed_sqlite<T>(
 where
     for<'r> T: sqlx::FromRow<'r, SqliteRow> + Send + Unpin,
 {
+    let span = QuerySpan::new(Dialect::SQLite, sql, params.len());
     let q = build_query_as_sqlite::<T>(sql, params);
-    Ok(q.fetch_optional(pool).await?)
+    span.run(rows_opt, async { Ok(q.fetch_optional(pool).await?) })
+        .await
+}
+
+#[cfg(feature = "postgres")]
+pub fn stream_typed_pg<'s, T>(pool: PgPool, sql: String, params: Vec<Param>) -> RowStream<'s, T>
+where
+    for<'r> T: sqlx::FromRow<'r, PgRow> + Send + Unpin + 's,
+{
+    use futures_util::TryStreamExt;
+    let span = QuerySpan::new(Dialect::Postgres, &sql, params.len());
+    span.stream(Box::pin(async_stream::try_stream! {
+        let mut rows = build_query_as_pg::<T>(&sql, params).fetch(&pool);
+        while let Some(row) = rows.try_next().await? {
+            yield row;
+        }
+    }))
+}
+
+#[cfg(feature = "mysql")]
+pub fn stream_typed_mysql<'s, T>(
+    pool: MySqlPool,
+    sql: String,
+    params: Vec<Param>,
+) -> RowStream<'s, T>
+where
+    for<'r> T: sqlx::FromRow<'r, MySqlRow> + Send + Unpin + 's,
+{
+    use futures_util::TryStreamExt;
+    let span = QuerySpan::new(Dialect::MySQL, &sql, params.len());
+    span.stream(Box::pin(async_stream::try_stream! {
+        let mut rows = build_query_as_mysql::<T>(&sql, params).fetch(&pool);
+        while let Some(row) = rows.try_next().await? {
+            yield row;
+        }
+    }))
+}
+
+#[cfg(feature = "sqlite")]
+pub fn stream_typed_sqlite<'s, T>(
+    pool: SqlitePool,
+    sql: String,
+    params: Vec<Param>,
+) -> RowStream<'s, T>
+where
+    for<'r> T: sqlx::FromRow<'r, SqliteRow> + Send + Unpin + 's,
+{
+    use futures_util::TryStreamExt;
+    let span = QuerySpan::new(Dialect::SQLite, &sql, params.len());
+    span.stream(Box::pin(async_stream::try_stream! {
+        let mut rows = build_query_as_sqlite::<T>(&sql, params).fetch(&pool);
+        while let Some(row) = rows.try_next().await? {
+            yield row;
+        }
+    }))
+}
+
+/// Поток из одной ошибки — для случаев, когда запрос не удалось даже подготовить.
+pub(crate) fn error_stream<'s, T: Send + 's>(err: Error) -> RowStream<'s, T> {
+    Box::pin(futures_util::stream::iter([Err(err)]))
 }
 
 #[cfg(feature = "sqlite")]
 pub async fn execute_sqlite(pool: &SqlitePool, sql: &str, params: Vec<Param>) -> Result<u64> {
+    let span = QuerySpan::new(Dialect::SQLite, sql, params.len());
     let args = build_sqlite_args(params);
-    let res = sqlx::query_with(sql, args).execute(pool).await?;
-    Ok(res.rows_affected())
+    let fut = async {
+        Ok(sqlx::query_with(sql, args)
+            .execute(pool)
+            .await?
+            .rows_affected())
+    };
+    span.run(rows_affected, fut).await
 }
 
 #[cfg(feature = "postgres")]
 pub async fn execute_pg(pool: &PgPool, sql: &str, params: Vec<Param>) -> Result<u64> {
+    let span = QuerySpan::new(Dialect::Postgres, sql, params.len());
     let args = build_pg_args(params);
-    let res = sqlx::query_with(sql, args).execute(pool).await?;
-    Ok(res.rows_affected())
+    let fut = async {
+        Ok(sqlx::query_with(sql, args)
+            .execute(pool)
+            .await?
+            .rows_affected())
+    };
+    span.run(rows_affected, fut).await
 }
 
 #[cfg(feature = "mysql")]
 pub async fn execute_mysql(pool: &MySqlPool, sql: &str, params: Vec<Param>) -> Result<u64> {
+    let span = QuerySpan::new(Dialect::MySQL, sql, params.len());
     let args = build_mysql_args(params);
-    let res = sqlx::query_with(sql, args).execute(pool).await?;
-    Ok(res.rows_affected())
+    let fut = async {
+        Ok(sqlx::query_with(sql, args)
+            .execute(pool)
+            .await?
+            .rows_affected())
+    };
+    span.run(rows_affected, fut).await
 }
 
 #[cfg(feature = "sqlite")]
@@ -0,0 +1,135 @@
+use crate::expression::{case, case_on, col, lit, val};
+use crate::param::Param;
+use crate::query_builder::QueryBuilder;
+use crate::renderer::Dialect;
+
+type QB = QueryBuilder<'static, ()>;
+
+fn pg() -> QB {
+    QB::new_empty().dialect(Dialect::Postgres)
+}
+
+fn ints(params: &[Param]) -> Vec<i32> {
+    params
+        .iter()
+        .map(|p| match p {
+            Param::I32(v) => *v,
+            other => panic!("unexpected param: {other:?}"),
+        })
+        .collect()
+}
+
+#[test]
+fn searched_case_in_select_keeps_params_in_order() {
+    let (sql, params) = pg()
+        .from("users")
+        .select(
+            case()
+                .when(col("age").lt(val(18)), val(1))
+                .when(col("age").lt(val(65)), val(2))
+                .else_(val(3))
+                .end()
+                .r#as("bucket"),
+        )
+        .to_sql()
+        .unwrap();
+
+    assert_eq!(
+        sql,
+        r#"SELECT CASE WHEN "age" < $1 THEN $2 WHEN "age" < $3 THEN $4 ELSE $5 END AS "bucket" FROM "users""#
+    );
+    assert_eq!(ints(&params), vec![18, 1, 65, 2, 3]);
+}
+
+#[test]
+fn simple_case_without_else() {
+    let (sql, params) = pg()
+        .from("orders")
+        .select(
+            case_on(col("status"))
+                .when(val(1), lit("new"))
+                .when(val(2), lit("paid"))
+                .end(),
+        )
+        .to_sql()
+        .unwrap();
+
+    assert_eq!(
+        sql,
+        r#"SELECT CASE "status" WHEN $1 THEN 'new' WHEN $2 THEN 'paid' END FROM "orders""#
+    );
+    assert_eq!(ints(&params), vec![1, 2]);
+}
+
+#[test]
+fn else_after_when_and_repeated_else() {
+    // ELSE печатается последним, повторный else_ заменяет прежний
+    let (sql, params) = pg()
+        .from("t")
+        .select(
+            case()
+                .else_(val(9))
+                .when(col("a").eq(val(1)), val(2))
+                .else_(val(3))
+                .end(),
+        )
+        .to_sql()
+        .unwrap();
+
+    assert_eq!(
+        sql,
+        r#"SELECT CASE WHEN "a" = $1 THEN $2 ELSE $3 END FROM "t""#
+    );
+    assert_eq!(ints(&params), vec![1, 2, 3]);
+}
+
+#[test]
+fn case_in_order_by_and_having() {
+    let (sql, params) = pg()
+        .from("tasks")
+        .select(("owner",))
+        .group_by("owner")
+        .having(
+            case()
+                .when(col("owner").eq(val(1)), val(0))
+                .else_(col("owner"))
+                .end()
+                .gt(val(5)),
+        )
+        .order_by(
+            case_on(col("owner"))
+                .when(val(7), val(0))
+                .else_(val(1))
+                .end(),
+        )
+        .to_sql()
+        .unwrap();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "owner" FROM "tasks" GROUP BY "owner" HAVING CASE WHEN "owner" = $1 THEN $2 ELSE "owner" END > $3 ORDER BY CASE "owner" WHEN $4 THEN $5 ELSE $6 END ASC"#
+    );
+    assert_eq!(ints(&params), vec![1, 0, 5, 7, 0, 1]);
+}
+
+#[test]
+fn case_in_update_set() {
+    let (sql, params) = pg()
+        .update("users")
+        .set((
+            col("tier"),
+            case()
+                .when(col("score").gte(val(100)), lit("gold"))
+                .else_(lit("basic"))
+                .end(),
+        ))
+        .where_(col("id").eq(val(42)))
+        .to_sql()
+        .unwrap();
+
+    assert_eq!(
+        sql,
+        r#"UPDATE "users" SET "tier" = CASE WHEN "score" >= $1 THEN 'gold' ELSE 'basic' END WHERE "id" = $2"#
+    );
+    assert_eq!(ints(&params), vec![100, 42]);
+}
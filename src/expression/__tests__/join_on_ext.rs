@@ -275,6 +275,7 @@ fn on_exists_and_on_not_exists_collect_params() {
             alias: None,
             params: p.into(),
             mark_distinct_for_next: false,
+            error: None,
         }
     };
 
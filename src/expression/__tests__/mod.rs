@@ -30,3 +30,9 @@ mod join_on_ext;
 
 #[cfg(test)]
 mod on_builder;
+
+#[cfg(test)]
+mod case;
+
+#[cfg(test)]
+mod raw;
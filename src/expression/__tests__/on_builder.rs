@@ -279,6 +279,7 @@ fn on_exists_and_on_not_exists_collect_params() {
         alias: None,
         params: inner_params.clone().into(),
         mark_distinct_for_next: false,
+        error: None,
     };
 
     let sub = sub_expr.clone();
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use crate::expression::{col, raw_named, raw_with, val};
+use crate::param::Param;
+use crate::query_builder::QueryBuilder;
+use crate::renderer::Dialect;
+
+type QB = QueryBuilder<'static, ()>;
+
+fn qb(d: Dialect) -> QB {
+    QB::new_empty().dialect(d)
+}
+
+fn ints(params: &[Param]) -> Vec<i32> {
+    params
+        .iter()
+        .filter_map(|p| match p {
+            Param::I32(v) => Some(*v),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn raw_with_numbers_binds_together_with_query() {
+    let (sql, params) = qb(Dialect::Postgres)
+        .from("items")
+        .select("*")
+        .r#where(col("id").gt(val(1)))
+        .r#where(raw_with("price > ? AND name = ?", (val(10), val("x"))).unwrap())
+        .limit(5)
+        .to_sql()
+        .unwrap();
+
+    assert_eq!(
+        sql,
+        r#"SELECT * FROM "items" WHERE ("id" > $1) AND (("price" > $2) AND ("name" = $3)) LIMIT 5"#
+    );
+    assert_eq!(ints(&params), vec![1, 10]);
+    assert!(matches!(&params[2], Param::Str(s) if s == "x"));
+}
+
+#[test]
+fn double_question_mark_quotes_identifiers_per_dialect() {
+    let build = |d: Dialect| {
+        qb(d)
+            .from("users")
+            .select(
+                raw_with("?? + ?", ("users.age", val(1)))
+                    .unwrap()
+                    .r#as("next_age"),
+            )
+            .to_sql()
+            .unwrap()
+            .0
+    };
+
+    assert_eq!(
+        build(Dialect::Postgres),
+        r#"SELECT "users"."age" + $1 AS "next_age" FROM "users""#
+    );
+    assert_eq!(
+        build(Dialect::MySQL),
+        "SELECT `users`.`age` + ? AS `next_age` FROM `users`"
+    );
+}
+
+#[test]
+fn named_bindings_repeat_and_skip_casts_and_strings() {
+    let bindings: HashMap<&str, _> = [("min", val(5)), ("col", col("price"))].into();
+    let (sql, params) = qb(Dialect::Postgres)
+        .from("items")
+        .select("*")
+        .r#where(
+            raw_named(
+                ":col: >= :min AND :min::int < :col: * 10 AND note <> ':min'",
+                bindings,
+            )
+            .unwrap(),
+        )
+        .to_sql()
+        .unwrap();
+
+    assert_eq!(
+        sql,
+        r#"SELECT * FROM "items" WHERE (("price" >= $1) AND (CAST($2 AS INT) < ("price" * 10))) AND ("note" <> ':min')"#
+    );
+    assert_eq!(ints(&params), vec![5, 5]);
+}
+
+#[test]
+fn raw_with_and_raw_named_return_errors_instead_of_panicking() {
+    let err = raw_with("a = ? AND b = ?", (val(1),)).unwrap_err();
+    assert!(err.to_string().contains("more placeholders"), "{err}");
+    assert!(raw_with("a = ?", (val(1), val(2))).is_err());
+    assert!(raw_with("a = = ?", (val(1),)).is_err());
+    assert!(raw_named("a = :x", [("y", val(1))]).is_err());
+}
+
+#[test]
+fn strings_in_value_slots_are_bound_params() {
+    let (sql, params) = qb(Dialect::Postgres)
+        .from("users")
+        .select("id")
+        .r#where(raw_named("name = :name OR nick = :name", [("name", "x' OR '1'='1")]).unwrap())
+        .to_sql()
+        .unwrap();
+
+    assert_eq!(
+        sql,
+        r#"SELECT "id" FROM "users" WHERE ("name" = $1) OR ("nick" = $2)"#
+    );
+    assert!(
+        params
+            .iter()
+            .all(|p| matches!(p, Param::Str(s) if s == "x' OR '1'='1"))
+    );
+    assert_eq!(params.len(), 2);
+}
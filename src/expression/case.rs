@@ -0,0 +1,75 @@
+//! `CASE WHEN`: `case().when(cond, then).else_(x).end()` и простая форма
+//! `case_on(col("status")).when(val(1), lit("a")).end()`.
+
+use std::borrow::Cow;
+
+use smallvec::SmallVec;
+use sqlparser::ast::{self, CaseWhen, helpers::attached_token::AttachedToken};
+
+use super::Expression;
+use crate::param::Param;
+
+/// Построитель `CASE`; параметры копятся в порядке печати
+#[derive(Clone, Debug, Default)]
+pub struct CaseBuilder {
+    operand: Option<ast::Expr>,
+    conditions: Vec<CaseWhen>,
+    else_result: Option<ast::Expr>,
+    params: SmallVec<[Param; 8]>,
+    else_params: SmallVec<[Param; 8]>,
+    error: Option<Cow<'static, str>>,
+}
+
+/// `CASE WHEN cond THEN ... END`
+pub fn case() -> CaseBuilder {
+    CaseBuilder::default()
+}
+
+/// `CASE operand WHEN value THEN ... END`
+pub fn case_on(operand: Expression) -> CaseBuilder {
+    CaseBuilder {
+        operand: Some(operand.expr),
+        params: operand.params,
+        error: operand.error,
+        ..Default::default()
+    }
+}
+
+impl CaseBuilder {
+    /// `WHEN cond THEN then` (в `case_on` — `WHEN value THEN then`)
+    pub fn when(mut self, cond: Expression, then: Expression) -> Self {
+        self.params.extend(cond.params);
+        self.params.extend(then.params);
+        self.error = self.error.or(cond.error).or(then.error);
+        self.conditions.push(CaseWhen {
+            condition: cond.expr,
+            result: then.expr,
+        });
+        self
+    }
+
+    /// `ELSE x`; повторный вызов заменяет предыдущий
+    pub fn else_(mut self, x: Expression) -> Self {
+        self.else_params = x.params;
+        self.else_result = Some(x.expr);
+        self.error = self.error.or(x.error);
+        self
+    }
+
+    pub fn end(mut self) -> Expression {
+        self.params.extend(self.else_params);
+        Expression {
+            expr: ast::Expr::Case {
+                case_token: AttachedToken::empty(),
+                end_token: AttachedToken::empty(),
+                operand: self.operand.map(Box::new),
+                conditions: self.conditions,
+                else_result: self.else_result.map(Box::new),
+            },
+            alias: None,
+            params: self.params,
+            mark_distinct_for_next: false,
+            error: self.error,
+        }
+    }
+}
@@ -14,6 +14,7 @@ fn bin(op: ast::BinaryOperator, left: Expression, mut right: Expression) -> Expr
         alias: None,
         params,
         mark_distinct_for_next: false,
+        error: left.error.or(right.error),
     }
 }
 
@@ -43,9 +44,11 @@ impl Expression {
         I: IntoIterator<Item = Expression>,
     {
         let mut params = self.params;
+        let mut error = self.error;
         let mut exprs = vec![];
         for mut e in items.into_iter() {
             params.append(&mut e.params);
+            error = error.or(e.error);
             exprs.push(e.expr);
         }
         Expression {
@@ -57,6 +60,7 @@ impl Expression {
             alias: None,
             params,
             mark_distinct_for_next: false,
+            error,
         }
     }
 
@@ -65,9 +69,11 @@ impl Expression {
         I: IntoIterator<Item = Expression>,
     {
         let mut params = self.params;
+        let mut error = self.error;
         let mut exprs = vec![];
         for mut e in items.into_iter() {
             params.append(&mut e.params);
+            error = error.or(e.error);
             exprs.push(e.expr);
         }
         Expression {
@@ -79,6 +85,7 @@ impl Expression {
             alias: None,
             params,
             mark_distinct_for_next: false,
+            error,
         }
     }
 
@@ -88,6 +95,7 @@ impl Expression {
             alias: None,
             params: smallvec![],
             mark_distinct_for_next: false,
+            error: self.error,
         }
     }
 
@@ -97,6 +105,7 @@ impl Expression {
             alias: None,
             params: smallvec![],
             mark_distinct_for_next: false,
+            error: self.error,
         }
     }
 }
@@ -23,6 +23,7 @@ pub fn col(name: &str) -> Expression {
             alias: None,
             params: smallvec![],
             mark_distinct_for_next: false,
+            error: None,
         }
     } else {
         Expression {
@@ -30,6 +31,7 @@ pub fn col(name: &str) -> Expression {
             alias: None,
             params: smallvec![],
             mark_distinct_for_next: false,
+            error: None,
         }
     };
     ident
@@ -42,6 +44,7 @@ pub fn val<T: Into<Param>>(v: T) -> Expression {
         alias: None,
         params: smallvec![v.into()],
         mark_distinct_for_next: false,
+        error: None,
     }
 }
 
@@ -52,6 +55,7 @@ pub fn lit<S: Into<String>>(s: S) -> Expression {
         alias: None,
         params: smallvec![],
         mark_distinct_for_next: false,
+        error: None,
     }
 }
 
@@ -62,6 +66,7 @@ pub fn raw<A: RawArg>(arg: A) -> Expression {
         alias: None,
         params: smallvec![],
         mark_distinct_for_next: false,
+        error: None,
     }
 }
 
@@ -88,6 +93,7 @@ pub fn schema(name: &str) -> Expression {
         alias: None,
         params: smallvec![],
         mark_distinct_for_next: false,
+        error: None,
     }
 }
 
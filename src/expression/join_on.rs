@@ -153,6 +153,7 @@ impl JoinOnExt for Expression {
                     alias: None,
                     params: sub.params,
                     mark_distinct_for_next: false,
+                    error: sub.error,
                 };
                 self.and(exists)
             }
@@ -173,6 +174,7 @@ impl JoinOnExt for Expression {
                     alias: None,
                     params: sub.params,
                     mark_distinct_for_next: false,
+                    error: sub.error,
                 };
                 self.or(exists)
             }
@@ -193,6 +195,7 @@ impl JoinOnExt for Expression {
                     alias: None,
                     params: sub.params,
                     mark_distinct_for_next: false,
+                    error: sub.error,
                 };
                 self.and(exists)
             }
@@ -205,6 +208,7 @@ impl JoinOnExt for Expression {
                     alias: None,
                     params: sub.params,
                     mark_distinct_for_next: false,
+                    error: sub.error,
                 };
                 self.and(neg)
             }
@@ -224,6 +228,7 @@ impl JoinOnExt for Expression {
                     alias: None,
                     params: sub.params,
                     mark_distinct_for_next: false,
+                    error: sub.error,
                 };
                 self.or(exists)
             }
@@ -236,6 +241,7 @@ impl JoinOnExt for Expression {
                     alias: None,
                     params: sub.params,
                     mark_distinct_for_next: false,
+                    error: sub.error,
                 };
                 self.or(neg)
             }
@@ -447,6 +453,7 @@ fn build_between_expr(
         alias: None,
         params,
         mark_distinct_for_next: false,
+        error: target.error.or(low.error).or(high.error),
     }
 }
 
@@ -461,6 +468,7 @@ fn build_exists_expr(sub: Expression, negated: bool) -> Expression {
             alias: None,
             params: sub.params,
             mark_distinct_for_next: false,
+            error: sub.error,
         },
         ast::Expr::Exists { .. } => {
             if negated {
@@ -473,6 +481,7 @@ fn build_exists_expr(sub: Expression, negated: bool) -> Expression {
                     alias: None,
                     params: sub.params,
                     mark_distinct_for_next: false,
+                    error: sub.error,
                 }
             } else {
                 // уже EXISTS — используем как есть
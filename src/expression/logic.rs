@@ -13,6 +13,7 @@ fn combine(op: ast::BinaryOperator, left: Expression, mut right: Expression) ->
         alias: None,
         params,
         mark_distinct_for_next: false,
+        error: left.error.or(right.error),
     }
 }
 
@@ -32,6 +33,7 @@ impl Expression {
             alias: None,
             params: self.params,
             mark_distinct_for_next: false,
+            error: self.error,
         }
     }
 }
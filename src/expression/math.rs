@@ -14,6 +14,7 @@ fn bin(op: ast::BinaryOperator, left: Expression, mut right: Expression) -> Expr
         alias: None,
         params,
         mark_distinct_for_next: false,
+        error: left.error.or(right.error),
     }
 }
 
@@ -13,6 +13,9 @@ pub struct Expression {
     pub alias: Option<Cow<'static, str>>,
     pub(crate) params: SmallVec<[Param; 8]>,
     pub(crate) mark_distinct_for_next: bool,
+    /// Ошибка построения (например, `over()` не на функции): билдер,
+    /// принявший выражение, переносит её в свои ошибки.
+    pub(crate) error: Option<Cow<'static, str>>,
 }
 
 impl Expression {
@@ -35,24 +38,38 @@ impl Expression {
         (alias_opt, self.expr, self.params)
     }
 
+    /// Выражение без ошибки построения; иначе — `InvalidExpression`
+    pub(crate) fn checked(self) -> crate::query_builder::Result<Self> {
+        match self.error {
+            Some(reason) => Err(crate::query_builder::Error::InvalidExpression { reason }),
+            None => Ok(self),
+        }
+    }
+
     pub fn empty() -> Self {
         Self {
             expr: ast::Expr::Value(ast::Value::Null.into()),
             alias: None,
             params: SmallVec::new(),
             mark_distinct_for_next: false,
+            error: None,
         }
     }
 }
 
 pub mod aggr;
 pub mod alias;
+pub mod case;
 pub mod cmp;
 pub mod helpers;
 pub mod join_on;
 pub mod logic;
 pub mod math;
 pub mod path;
+pub mod window;
 
+pub use crate::query_builder::{raw_named, raw_with};
+pub use case::{CaseBuilder, case, case_on};
 pub use helpers::{col, lit, raw, schema, table, val};
 pub use join_on::{JoinOnBuilder, JoinOnExt};
+pub use window::{FrameBound, WindowBuilder, dense_rank, ntile, rank, row_number};
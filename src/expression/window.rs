@@ -0,0 +1,313 @@
+//! Оконные функции: `row_number().over(|w| w.partition_by("dept").order_by("salary"))`.
+//!
+//! `over` применим к любому вызову функции, в том числе к агрегатам:
+//! `col("amount").sum().over(|w| w.order_by("day").rows_between(..))`.
+
+use std::borrow::Cow;
+
+use smallvec::{SmallVec, smallvec};
+use sqlparser::ast::{
+    Expr, Function, FunctionArg, FunctionArgExpr, FunctionArgumentList, FunctionArguments, Ident,
+    ObjectName, ObjectNamePart, OrderByExpr, OrderByOptions, WindowFrame, WindowFrameBound,
+    WindowFrameUnits, WindowSpec, WindowType,
+};
+
+use super::{Expression, helpers::col};
+use crate::{param::Param, utils::num_expr};
+
+/// Граница рамки окна
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(u64),
+    CurrentRow,
+    Following(u64),
+    UnboundedFollowing,
+}
+
+impl FrameBound {
+    fn into_ast(self) -> WindowFrameBound {
+        match self {
+            FrameBound::UnboundedPreceding => WindowFrameBound::Preceding(None),
+            FrameBound::Preceding(n) => WindowFrameBound::Preceding(Some(Box::new(num_expr(n)))),
+            FrameBound::CurrentRow => WindowFrameBound::CurrentRow,
+            FrameBound::Following(n) => WindowFrameBound::Following(Some(Box::new(num_expr(n)))),
+            FrameBound::UnboundedFollowing => WindowFrameBound::Following(None),
+        }
+    }
+}
+
+/// Аргументы `partition_by`/`order_by` окна: колонка (`&str`/`String`),
+/// `Expression`, их кортеж или `Vec`.
+pub trait WindowArgs {
+    fn into_exprs(self) -> Vec<Expression>;
+}
+
+impl WindowArgs for &str {
+    fn into_exprs(self) -> Vec<Expression> {
+        vec![col(self)]
+    }
+}
+
+impl WindowArgs for String {
+    fn into_exprs(self) -> Vec<Expression> {
+        vec![col(&self)]
+    }
+}
+
+impl WindowArgs for Expression {
+    fn into_exprs(self) -> Vec<Expression> {
+        vec![self]
+    }
+}
+
+impl<A: WindowArgs> WindowArgs for Vec<A> {
+    fn into_exprs(self) -> Vec<Expression> {
+        self.into_iter().flat_map(WindowArgs::into_exprs).collect()
+    }
+}
+
+macro_rules! impl_window_args_for_tuple {
+    ( $($T:ident),+ ) => {
+        impl<$($T: WindowArgs),+> WindowArgs for ( $($T,)+ ) {
+            #[allow(non_snake_case)]
+            fn into_exprs(self) -> Vec<Expression> {
+                let ( $($T,)+ ) = self;
+                let mut v = Vec::new();
+                $( v.extend($T.into_exprs()); )+
+                v
+            }
+        }
+    };
+}
+
+impl_window_args_for_tuple!(A);
+impl_window_args_for_tuple!(A, B);
+impl_window_args_for_tuple!(A, B, C);
+impl_window_args_for_tuple!(A, B, C, D);
+impl_window_args_for_tuple!(A, B, C, D, E);
+impl_window_args_for_tuple!(A, B, C, D, E, F);
+
+/// Описание окна для [`Expression::over`] и `QueryBuilder::window`
+#[derive(Clone, Debug, Default)]
+pub struct WindowBuilder {
+    base: Option<String>,
+    partition_by: Vec<Expr>,
+    order_by: Vec<OrderByExpr>,
+    frame: Option<WindowFrame>,
+    params: SmallVec<[Param; 8]>,
+    error: Option<Cow<'static, str>>,
+}
+
+impl WindowBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Окно на основе именованного: `OVER (w ORDER BY ...)`
+    pub fn base(mut self, name: &str) -> Self {
+        self.base = Some(name.to_string());
+        self
+    }
+
+    pub fn partition_by<A: WindowArgs>(mut self, args: A) -> Self {
+        for e in args.into_exprs() {
+            self.partition_by.push(e.expr);
+            self.params.extend(e.params);
+            self.error = self.error.or(e.error);
+        }
+        self
+    }
+
+    pub fn order_by<A: WindowArgs>(self, args: A) -> Self {
+        self.push_order_by(args, None)
+    }
+
+    pub fn order_by_desc<A: WindowArgs>(self, args: A) -> Self {
+        self.push_order_by(args, Some(false))
+    }
+
+    /// `ROWS BETWEEN start AND end`
+    pub fn rows_between(self, start: FrameBound, end: FrameBound) -> Self {
+        self.frame(WindowFrameUnits::Rows, start, end)
+    }
+
+    /// `RANGE BETWEEN start AND end`
+    pub fn range_between(self, start: FrameBound, end: FrameBound) -> Self {
+        self.frame(WindowFrameUnits::Range, start, end)
+    }
+
+    /// `GROUPS BETWEEN start AND end` (PG/SQLite; в MySQL нет)
+    pub fn groups_between(self, start: FrameBound, end: FrameBound) -> Self {
+        self.frame(WindowFrameUnits::Groups, start, end)
+    }
+
+    fn push_order_by<A: WindowArgs>(mut self, args: A, asc: Option<bool>) -> Self {
+        for e in args.into_exprs() {
+            self.order_by.push(OrderByExpr {
+                expr: e.expr,
+                options: OrderByOptions {
+                    asc,
+                    nulls_first: None,
+                },
+                with_fill: None,
+            });
+            self.params.extend(e.params);
+            self.error = self.error.or(e.error);
+        }
+        self
+    }
+
+    fn frame(mut self, units: WindowFrameUnits, start: FrameBound, end: FrameBound) -> Self {
+        self.frame = Some(WindowFrame {
+            units,
+            start_bound: start.into_ast(),
+            end_bound: Some(end.into_ast()),
+        });
+        self
+    }
+
+    /// Спецификация, параметры и ошибка из выражений `partition_by`/`order_by`
+    pub(crate) fn into_spec(self) -> (WindowSpec, SmallVec<[Param; 8]>, Option<Cow<'static, str>>) {
+        let spec = WindowSpec {
+            window_name: self.base.map(Ident::new),
+            partition_by: self.partition_by,
+            order_by: self.order_by,
+            window_frame: self.frame,
+        };
+        (spec, self.params, self.error)
+    }
+}
+
+fn window_fn(name: &str, args: Vec<Expr>) -> Expr {
+    Expr::Function(Function {
+        name: ObjectName(vec![ObjectNamePart::Identifier(Ident::new(name))]),
+        parameters: FunctionArguments::None,
+        args: FunctionArguments::List(FunctionArgumentList {
+            duplicate_treatment: None,
+            args: args
+                .into_iter()
+                .map(|e| FunctionArg::Unnamed(FunctionArgExpr::Expr(e)))
+                .collect(),
+            clauses: vec![],
+        }),
+        filter: None,
+        over: None,
+        within_group: vec![],
+        uses_odbc_syntax: false,
+        null_treatment: None,
+    })
+}
+
+fn window_expr(name: &str, args: Vec<Expr>) -> Expression {
+    Expression {
+        expr: window_fn(name, args),
+        alias: None,
+        params: smallvec![],
+        mark_distinct_for_next: false,
+        error: None,
+    }
+}
+
+/// ROW_NUMBER()
+pub fn row_number() -> Expression {
+    window_expr("ROW_NUMBER", vec![])
+}
+
+/// RANK()
+pub fn rank() -> Expression {
+    window_expr("RANK", vec![])
+}
+
+/// DENSE_RANK()
+pub fn dense_rank() -> Expression {
+    window_expr("DENSE_RANK", vec![])
+}
+
+/// NTILE(n)
+pub fn ntile(n: u64) -> Expression {
+    window_expr("NTILE", vec![num_expr(n)])
+}
+
+impl Expression {
+    /// `... OVER (...)`. Применяется к вызову функции; на другом выражении
+    /// билдер, принявший его, получит ошибку.
+    pub fn over<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(WindowBuilder) -> WindowBuilder,
+    {
+        let Expr::Function(func) = &mut self.expr else {
+            return self.not_a_function("over()");
+        };
+        let (spec, params, error) = f(WindowBuilder::new()).into_spec();
+        func.over = Some(WindowType::WindowSpec(spec));
+        self.params.extend(params);
+        self.error = self.error.or(error);
+        self
+    }
+
+    /// `... OVER w` — окно из `QueryBuilder::window`; только для вызова функции
+    pub fn over_window(mut self, name: &str) -> Self {
+        let Expr::Function(func) = &mut self.expr else {
+            return self.not_a_function("over_window()");
+        };
+        func.over = Some(WindowType::NamedWindow(Ident::new(name)));
+        self
+    }
+
+    fn not_a_function(mut self, method: &str) -> Self {
+        let msg = format!(
+            "{method}: окно применимо только к вызову функции, а не к `{}`",
+            self.expr
+        );
+        self.error = self.error.or(Some(msg.into()));
+        self
+    }
+
+    /// LAG(expr, offset)
+    pub fn lag(self, offset: u64) -> Self {
+        self.shift("LAG", offset, None)
+    }
+
+    /// LAG(expr, offset, default)
+    pub fn lag_or(self, offset: u64, default: Expression) -> Self {
+        self.shift("LAG", offset, Some(default))
+    }
+
+    /// LEAD(expr, offset)
+    pub fn lead(self, offset: u64) -> Self {
+        self.shift("LEAD", offset, None)
+    }
+
+    /// LEAD(expr, offset, default)
+    pub fn lead_or(self, offset: u64, default: Expression) -> Self {
+        self.shift("LEAD", offset, Some(default))
+    }
+
+    /// FIRST_VALUE(expr)
+    pub fn first_value(mut self) -> Self {
+        self.alias = None;
+        self.expr = window_fn("FIRST_VALUE", vec![self.expr]);
+        self
+    }
+
+    /// LAST_VALUE(expr). Без рамки окно заканчивается текущей строкой —
+    /// обычно нужен `rows_between(UnboundedPreceding, UnboundedFollowing)`.
+    pub fn last_value(mut self) -> Self {
+        self.alias = None;
+        self.expr = window_fn("LAST_VALUE", vec![self.expr]);
+        self
+    }
+
+    fn shift(mut self, name: &str, offset: u64, default: Option<Expression>) -> Self {
+        let mut args = vec![self.expr, num_expr(offset)];
+        if let Some(d) = default {
+            args.push(d.expr);
+            self.params.extend(d.params);
+            self.error = self.error.or(d.error);
+        }
+        self.alias = None;
+        self.expr = window_fn(name, args);
+        self
+    }
+}
@@ -1,11 +1,14 @@
 mod __tests__;
 pub mod executor;
 pub mod expression;
+pub mod migrate;
 pub mod optimizer;
 pub mod param;
 pub mod query_builder;
 pub mod renderer;
 mod runtime_ext;
+pub mod schema;
+pub mod seed;
 mod tests;
 pub mod type_helpers;
 mod utils;
@@ -15,13 +18,8 @@ pub use expression::helpers::*;
 pub use param::Param;
 pub use query_builder::{DeleteBuilder, InsertBuilder, QueryBuilder, UpdateBuilder};
 pub use runtime_ext::SpawnExt;
+pub use schema::SchemaBuilder;
 pub use type_helpers::QBClosureHelper;
 
 #[cfg(not(any(feature = "postgres", feature = "mysql", feature = "sqlite")))]
-compile_error!("Enable exactly one DB feature: `postgres`, `mysql`, or `sqlite`.");
-
-#[cfg(all(feature = "postgres", any(feature = "mysql", feature = "sqlite")))]
-compile_error!("Enable only one DB feature at a time (postgres vs mysql/sqlite).");
-
-#[cfg(all(feature = "mysql", feature = "sqlite"))]
-compile_error!("Enable only one DB feature at a time (mysql vs sqlite).");
+compile_error!("Enable at least one DB feature: `postgres`, `mysql`, or `sqlite`.");
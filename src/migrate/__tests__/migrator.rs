@@ -0,0 +1,320 @@
+#![cfg(feature = "sqlite")]
+
+use crate::executor::{Error as ExecError, ExecutorConfig, QueryExecutor, transaction::TxExecutor};
+use crate::expression::helpers::{col, val};
+use crate::migrate::{Error, Migration, MigrationFuture, Migrator};
+
+struct CreateUsers;
+
+impl Migration for CreateUsers {
+    fn name(&self) -> &str {
+        "0001_create_users"
+    }
+    fn up<'a>(&'a self, tx: &'a mut TxExecutor<'_>) -> MigrationFuture<'a> {
+        Box::pin(async move {
+            tx.schema()
+                .create_table("users", |t| {
+                    t.increments("id");
+                    t.string("name", 255).not_null();
+                })
+                .await
+        })
+    }
+    fn down<'a>(&'a self, tx: &'a mut TxExecutor<'_>) -> MigrationFuture<'a> {
+        Box::pin(async move { tx.schema().drop_table("users").await })
+    }
+}
+
+struct AddEmail;
+
+impl Migration for AddEmail {
+    fn name(&self) -> &str {
+        "0002_add_email"
+    }
+    fn up<'a>(&'a self, tx: &'a mut TxExecutor<'_>) -> MigrationFuture<'a> {
+        Box::pin(async move {
+            tx.schema()
+                .alter_table("users", |t| {
+                    t.string("email", 255).nullable();
+                })
+                .await
+        })
+    }
+    fn down<'a>(&'a self, tx: &'a mut TxExecutor<'_>) -> MigrationFuture<'a> {
+        Box::pin(async move {
+            tx.schema()
+                .alter_table("users", |t| t.drop_column("email"))
+                .await
+        })
+    }
+}
+
+struct CreatePosts;
+
+impl Migration for CreatePosts {
+    fn name(&self) -> &str {
+        "0003_create_posts"
+    }
+    fn up<'a>(&'a self, tx: &'a mut TxExecutor<'_>) -> MigrationFuture<'a> {
+        Box::pin(async move {
+            tx.schema()
+                .create_table("posts", |t| {
+                    t.increments("id");
+                    t.integer("user_id").references("users", "id");
+                })
+                .await?;
+            tx.query::<()>()
+                .into("users")
+                .insert((col("name"), val("admin")))
+                .exec()
+                .await?;
+            Ok(())
+        })
+    }
+    fn down<'a>(&'a self, tx: &'a mut TxExecutor<'_>) -> MigrationFuture<'a> {
+        Box::pin(async move { tx.schema().drop_table("posts").await })
+    }
+}
+
+/// Создаёт таблицу и падает: изменения должны откатиться
+struct Broken;
+
+impl Migration for Broken {
+    fn name(&self) -> &str {
+        "0004_broken"
+    }
+    fn up<'a>(&'a self, tx: &'a mut TxExecutor<'_>) -> MigrationFuture<'a> {
+        Box::pin(async move {
+            tx.schema()
+                .create_table("half_done", |t| {
+                    t.increments("id");
+                })
+                .await?;
+            Err(ExecError::Unsupported("boom".into()))
+        })
+    }
+    fn down<'a>(&'a self, _tx: &'a mut TxExecutor<'_>) -> MigrationFuture<'a> {
+        Box::pin(async move { Ok(()) })
+    }
+}
+
+async fn setup_db() -> QueryExecutor {
+    let cfg = ExecutorConfig::builder()
+        .database_url("sqlite::memory:")
+        .max_connections(1)
+        .after_connect_sql("PRAGMA foreign_keys = ON")
+        .build();
+    QueryExecutor::connect(cfg).await.unwrap()
+}
+
+fn all(exec: &QueryExecutor) -> Migrator<'_> {
+    // порядок регистрации не важен — сортировка по имени
+    Migrator::new(exec)
+        .migration(CreatePosts)
+        .migration(CreateUsers)
+        .migration(AddEmail)
+}
+
+#[tokio::test]
+async fn latest_applies_pending_in_one_batch() {
+    let exec = setup_db().await;
+    let migrator = all(&exec);
+
+    let applied = migrator.latest().await.unwrap();
+    assert_eq!(
+        applied,
+        vec!["0001_create_users", "0002_add_email", "0003_create_posts"]
+    );
+    assert!(exec.schema().has_column("users", "email").await.unwrap());
+    assert!(exec.schema().has_table("posts").await.unwrap());
+
+    let status = migrator.status().await.unwrap();
+    assert!(status.iter().all(|s| s.batch == Some(1)));
+    assert!(status.iter().all(|s| s.applied_at.is_some()));
+
+    assert!(migrator.latest().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn rollback_reverts_last_batch() {
+    let exec = setup_db().await;
+    Migrator::new(&exec)
+        .migration(CreateUsers)
+        .migration(AddEmail)
+        .latest()
+        .await
+        .unwrap();
+
+    let migrator = all(&exec);
+    assert_eq!(migrator.latest().await.unwrap(), vec!["0003_create_posts"]);
+    let batches: Vec<_> = migrator
+        .status()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|s| s.batch)
+        .collect();
+    assert_eq!(batches, vec![Some(1), Some(1), Some(2)]);
+
+    assert_eq!(
+        migrator.rollback().await.unwrap(),
+        vec!["0003_create_posts"]
+    );
+    assert!(!exec.schema().has_table("posts").await.unwrap());
+
+    assert_eq!(
+        migrator.rollback().await.unwrap(),
+        vec!["0002_add_email", "0001_create_users"]
+    );
+    assert!(!exec.schema().has_table("users").await.unwrap());
+    assert!(
+        migrator
+            .status()
+            .await
+            .unwrap()
+            .iter()
+            .all(|s| !s.is_applied())
+    );
+    assert!(migrator.rollback().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn up_to_stops_at_target() {
+    let exec = setup_db().await;
+    let migrator = all(&exec);
+
+    assert_eq!(
+        migrator.up_to("0002_add_email").await.unwrap(),
+        vec!["0001_create_users", "0002_add_email"]
+    );
+    let status = migrator.status().await.unwrap();
+    assert_eq!(status[2].name, "0003_create_posts");
+    assert!(!status[2].is_applied());
+
+    assert!(matches!(
+        migrator.up_to("9999_nope").await,
+        Err(Error::UnknownMigration(name)) if name == "9999_nope"
+    ));
+}
+
+#[tokio::test]
+async fn failed_migration_is_rolled_back() {
+    let exec = setup_db().await;
+    let migrator = all(&exec).migration(Broken);
+
+    let res = migrator.latest().await;
+    assert!(matches!(res, Err(Error::Failed { name, .. }) if name == "0004_broken"));
+    assert!(!exec.schema().has_table("half_done").await.unwrap());
+
+    // предыдущие миграции зафиксированы каждая в своей транзакции
+    let applied: Vec<_> = migrator
+        .status()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|s| s.is_applied())
+        .collect();
+    assert_eq!(applied, vec![true, true, true, false]);
+
+    // замок снят
+    assert!(all(&exec).latest().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn lock_table_prevents_concurrent_run() {
+    let exec = setup_db().await;
+    Migrator::new(&exec)
+        .migration(CreateUsers)
+        .latest()
+        .await
+        .unwrap();
+
+    // другой процесс держит замок
+    exec.query::<()>()
+        .update("knux_migrations_lock")
+        .set((col("is_locked"), val(1)))
+        .exec()
+        .await
+        .unwrap();
+
+    assert!(matches!(all(&exec).latest().await, Err(Error::Locked)));
+    assert!(!exec.schema().has_column("users", "email").await.unwrap());
+
+    exec.query::<()>()
+        .update("knux_migrations_lock")
+        .set((col("is_locked"), val(0)))
+        .exec()
+        .await
+        .unwrap();
+    assert_eq!(all(&exec).latest().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn force_unlock_releases_stale_lock() {
+    let exec = setup_db().await;
+    // до первого запуска таблицы-замка ещё нет
+    all(&exec).force_unlock().await.unwrap();
+
+    // держатель замка упал, не сняв его
+    exec.query::<()>()
+        .update("knux_migrations_lock")
+        .set((col("is_locked"), val(1)))
+        .exec()
+        .await
+        .unwrap();
+    assert!(matches!(all(&exec).latest().await, Err(Error::Locked)));
+
+    all(&exec).force_unlock().await.unwrap();
+    assert_eq!(all(&exec).latest().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn missing_and_duplicate_migrations() {
+    let exec = setup_db().await;
+    all(&exec).latest().await.unwrap();
+
+    let res = Migrator::new(&exec).migration(CreateUsers).rollback().await;
+    assert!(matches!(
+        res,
+        Err(Error::MissingMigrations(names))
+            if names == vec!["0002_add_email", "0003_create_posts"]
+    ));
+
+    let res = Migrator::new(&exec)
+        .migration(CreateUsers)
+        .migration(CreateUsers)
+        .status()
+        .await;
+    assert!(matches!(res, Err(Error::DuplicateMigration(_))));
+}
+
+#[tokio::test]
+async fn custom_table_name() {
+    let exec = setup_db().await;
+    Migrator::new(&exec)
+        .table_name("schema_versions")
+        .migration(CreateUsers)
+        .latest()
+        .await
+        .unwrap();
+
+    assert!(exec.schema().has_table("schema_versions").await.unwrap());
+    assert!(
+        exec.schema()
+            .has_table("schema_versions_lock")
+            .await
+            .unwrap()
+    );
+    assert!(!exec.schema().has_table("knux_migrations").await.unwrap());
+}
+
+#[tokio::test]
+async fn migrator_futures_are_send() {
+    fn assert_send<T: Send>(_: &T) {}
+
+    let exec = setup_db().await;
+    let migrator = all(&exec);
+    assert_send(&migrator.latest());
+    assert_send(&migrator.rollback());
+    assert_send(&migrator.status());
+}
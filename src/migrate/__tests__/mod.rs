@@ -0,0 +1,6 @@
+#[cfg(test)]
+mod migrator;
+#[cfg(test)]
+mod split;
+#[cfg(test)]
+mod sql_files;
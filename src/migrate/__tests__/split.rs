@@ -0,0 +1,69 @@
+use crate::migrate::split_statements;
+
+#[test]
+fn splits_on_semicolons_and_skips_empty() {
+    let sql = "CREATE TABLE a (id int);\n\n;  INSERT INTO a VALUES (1)\n";
+    assert_eq!(
+        split_statements(sql),
+        vec!["CREATE TABLE a (id int)", "INSERT INTO a VALUES (1)"]
+    );
+}
+
+#[test]
+fn ignores_semicolons_in_quotes_and_comments() {
+    let sql = "INSERT INTO t VALUES ('a;b', \"c;d\", `e;f`); -- x;y\n\
+               /* 1; 2 */ SELECT 1;\n-- только комментарий;\n";
+    assert_eq!(
+        split_statements(sql),
+        vec![
+            "INSERT INTO t VALUES ('a;b', \"c;d\", `e;f`)",
+            "-- x;y\n/* 1; 2 */ SELECT 1"
+        ]
+    );
+}
+
+#[test]
+fn escaped_quote_inside_string() {
+    let sql = "INSERT INTO t VALUES ('it''s; fine'); SELECT 2";
+    assert_eq!(
+        split_statements(sql),
+        vec!["INSERT INTO t VALUES ('it''s; fine')", "SELECT 2"]
+    );
+}
+
+#[test]
+fn postgres_dollar_quoted_body() {
+    let sql = "CREATE FUNCTION f() RETURNS int AS $fn$ BEGIN RETURN 1; END; $fn$ LANGUAGE plpgsql;\n\
+               SELECT $1, $$a;b$$;";
+    assert_eq!(
+        split_statements(sql),
+        vec![
+            "CREATE FUNCTION f() RETURNS int AS $fn$ BEGIN RETURN 1; END; $fn$ LANGUAGE plpgsql",
+            "SELECT $1, $$a;b$$"
+        ]
+    );
+}
+
+#[test]
+fn trigger_body_is_one_statement() {
+    let sql = "CREATE TRIGGER tr AFTER INSERT ON t BEGIN\n  \
+               UPDATE t SET n = CASE WHEN n > 0 THEN n ELSE 0 END;\n  \
+               DELETE FROM log;\nEND;\nSELECT 1;";
+    let stmts = split_statements(sql);
+    assert_eq!(stmts.len(), 2, "{stmts:?}");
+    assert!(stmts[0].starts_with("CREATE TRIGGER") && stmts[0].ends_with("END"));
+    assert_eq!(stmts[1], "SELECT 1");
+}
+
+#[test]
+fn mysql_procedure_with_end_if() {
+    let sql = "CREATE PROCEDURE p() BEGIN IF 1 THEN SELECT 1; END IF; END; BEGIN; SELECT 2;";
+    assert_eq!(
+        split_statements(sql),
+        vec![
+            "CREATE PROCEDURE p() BEGIN IF 1 THEN SELECT 1; END IF; END",
+            "BEGIN",
+            "SELECT 2"
+        ]
+    );
+}
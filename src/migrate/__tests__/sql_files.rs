@@ -0,0 +1,211 @@
+#![cfg(feature = "sqlite")]
+
+use std::{fs, path::PathBuf};
+
+use crate::executor::{ExecutorConfig, QueryExecutor};
+use crate::migrate::{Error, Migration, Migrator, SqlMigration};
+
+/// Временный каталог миграций, удаляется в `Drop`
+struct Dir(PathBuf);
+
+impl Dir {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("knux_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    fn file(&self, name: &str, sql: &str) -> &Self {
+        fs::write(self.0.join(name), sql).unwrap();
+        self
+    }
+}
+
+impl Drop for Dir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+async fn setup_db() -> QueryExecutor {
+    let cfg = ExecutorConfig::builder()
+        .database_url("sqlite::memory:")
+        .max_connections(1)
+        .after_connect_sql("PRAGMA foreign_keys = ON")
+        .build();
+    QueryExecutor::connect(cfg).await.unwrap()
+}
+
+fn users_dir(name: &str) -> Dir {
+    let dir = Dir::new(name);
+    dir.file(
+        "0002_seed_users.up.sql",
+        "INSERT INTO users (name) VALUES ('a;b');\nINSERT INTO users (name) VALUES ('c');\n",
+    )
+    .file("0002_seed_users.down.sql", "DELETE FROM users;")
+    .file(
+        "0001_create_users.up.sql",
+        "-- пользователи\nCREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);\n\
+         CREATE INDEX users_name_index ON users (name);",
+    )
+    .file("0001_create_users.down.sql", "DROP TABLE users;")
+    .file("README.md", "не миграция");
+    dir
+}
+
+#[test]
+fn load_dir_pairs_and_orders_files() {
+    let dir = users_dir("load_dir");
+    let list = SqlMigration::load_dir(&dir.0).unwrap();
+    let names: Vec<_> = list.iter().map(|m| m.name()).collect();
+    assert_eq!(names, vec!["0001_create_users", "0002_seed_users"]);
+}
+
+#[test]
+fn load_dir_rejects_bad_files() {
+    let dir = Dir::new("bad_name");
+    dir.file("create_users.up.sql", "SELECT 1");
+    assert!(matches!(
+        SqlMigration::load_dir(&dir.0),
+        Err(Error::InvalidMigrationFile(f)) if f == "create_users.up.sql"
+    ));
+
+    let dir = Dir::new("orphan_down");
+    dir.file("0001_a.down.sql", "SELECT 1");
+    assert!(matches!(
+        SqlMigration::load_dir(&dir.0),
+        Err(Error::InvalidMigrationFile(_))
+    ));
+
+    let dir = Dir::new("plain_sql");
+    dir.file("0001_a.sql", "SELECT 1");
+    assert!(matches!(
+        SqlMigration::load_dir(&dir.0),
+        Err(Error::InvalidMigrationFile(_))
+    ));
+
+    assert!(matches!(
+        SqlMigration::load_dir(std::env::temp_dir().join("knux_no_such_dir")),
+        Err(Error::Io { .. })
+    ));
+}
+
+#[tokio::test]
+async fn applies_and_rolls_back_sql_files() {
+    let exec = setup_db().await;
+    let dir = users_dir("apply");
+    let migrator = Migrator::new(&exec).directory(&dir.0).unwrap();
+
+    assert_eq!(
+        migrator.latest().await.unwrap(),
+        vec!["0001_create_users", "0002_seed_users"]
+    );
+    let names = exec
+        .fetch_typed::<(String,)>("SELECT name FROM users ORDER BY id", vec![])
+        .await
+        .unwrap();
+    assert_eq!(names, vec![("a;b".to_string(),), ("c".to_string(),)]);
+
+    let status = migrator.status().await.unwrap();
+    assert!(
+        status
+            .iter()
+            .all(|s| s.checksum.as_ref().is_some_and(|c| c.len() == 64))
+    );
+
+    assert_eq!(migrator.rollback().await.unwrap().len(), 2);
+    assert!(!exec.schema().has_table("users").await.unwrap());
+}
+
+#[tokio::test]
+async fn edited_applied_file_is_reported() {
+    let exec = setup_db().await;
+    let dir = users_dir("edited");
+    Migrator::new(&exec)
+        .directory(&dir.0)
+        .unwrap()
+        .latest()
+        .await
+        .unwrap();
+
+    dir.file(
+        "0002_seed_users.up.sql",
+        "INSERT INTO users (name) VALUES ('d');",
+    );
+    dir.file("0003_noop.up.sql", "SELECT 1;");
+    let migrator = Migrator::new(&exec).directory(&dir.0).unwrap();
+
+    let res = migrator.latest().await;
+    assert!(matches!(
+        res,
+        Err(Error::ChecksumMismatch { name, .. }) if name == "0002_seed_users"
+    ));
+    assert!(matches!(
+        migrator.rollback().await,
+        Err(Error::ChecksumMismatch { .. })
+    ));
+    // ничего не применено
+    assert!(!migrator.status().await.unwrap()[2].is_applied());
+}
+
+#[tokio::test]
+async fn edited_down_file_is_reported() {
+    let exec = setup_db().await;
+    let dir = users_dir("edited_down");
+    Migrator::new(&exec)
+        .directory(&dir.0)
+        .unwrap()
+        .latest()
+        .await
+        .unwrap();
+
+    dir.file("0001_create_users.down.sql", "DROP TABLE IF EXISTS users;");
+    let migrator = Migrator::new(&exec).directory(&dir.0).unwrap();
+    assert!(matches!(
+        migrator.rollback().await,
+        Err(Error::ChecksumMismatch { name, .. }) if name == "0001_create_users"
+    ));
+    assert!(exec.schema().has_table("users").await.unwrap());
+}
+
+#[tokio::test]
+async fn missing_down_file_fails_rollback() {
+    let exec = setup_db().await;
+    let dir = Dir::new("no_down");
+    dir.file("0001_t.up.sql", "CREATE TABLE t (id INTEGER);");
+    let migrator = Migrator::new(&exec).directory(&dir.0).unwrap();
+
+    migrator.latest().await.unwrap();
+    assert!(matches!(
+        migrator.rollback().await,
+        Err(Error::Failed { name, .. }) if name == "0001_t"
+    ));
+    assert!(exec.schema().has_table("t").await.unwrap());
+}
+
+#[tokio::test]
+async fn upgrades_table_without_checksum_column() {
+    let exec = setup_db().await;
+    exec.schema()
+        .create_table("knux_migrations", |t| {
+            t.increments("id");
+            t.string("name", 255).not_null();
+            t.big_integer("batch").not_null();
+            t.timestamp("migration_time")
+                .not_null()
+                .default_raw("CURRENT_TIMESTAMP");
+        })
+        .await
+        .unwrap();
+
+    let dir = users_dir("upgrade");
+    let migrator = Migrator::new(&exec).directory(&dir.0).unwrap();
+    assert_eq!(migrator.latest().await.unwrap().len(), 2);
+    assert!(
+        exec.schema()
+            .has_column("knux_migrations", "checksum")
+            .await
+            .unwrap()
+    );
+}
@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+
+use crate::executor::Error as ExecError;
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Exec(#[from] ExecError),
+
+    /// Замок занят. Если держатель упал, в MySQL/SQLite замок остаётся —
+    /// снимается через `Migrator::force_unlock()`.
+    #[error("Migration table is already locked by another process")]
+    Locked,
+
+    #[error("Unknown migration: {0}")]
+    UnknownMigration(String),
+
+    #[error("Duplicate migration name: {0}")]
+    DuplicateMigration(String),
+
+    #[error("Applied migrations are missing from the migrator: {}", .0.join(", "))]
+    MissingMigrations(Vec<String>),
+
+    #[error("Migration {name} was changed after it was applied (checksum {applied} != {current})")]
+    ChecksumMismatch {
+        name: String,
+        applied: String,
+        current: String,
+    },
+
+    #[error("Invalid migration file: {0}")]
+    InvalidMigrationFile(String),
+
+    #[error("Failed to read {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Migration {name} failed: {source}")]
+    Failed {
+        name: String,
+        #[source]
+        source: ExecError,
+    },
+}
+
+impl From<crate::query_builder::Error> for Error {
+    fn from(e: crate::query_builder::Error) -> Self {
+        Error::Exec(e.into())
+    }
+}
@@ -0,0 +1,106 @@
+//! Блокировка мигратора: advisory lock в Postgres, таблица-замок в MySQL/SQLite.
+
+use super::{Error, Result};
+use crate::{
+    executor::DbConn,
+    expression::helpers::{col, val},
+    param::Param,
+    query_builder::QueryBuilder,
+    renderer::Dialect,
+    schema::SchemaBuilder,
+};
+
+/// Таблица-замок с единственной строкой `id = 1`
+pub(super) async fn ensure_lock_table(
+    conn: &mut DbConn,
+    dialect: Dialect,
+    schema: Option<String>,
+    lock_table: &str,
+) -> Result<()> {
+    if dialect == Dialect::Postgres {
+        return Ok(());
+    }
+    SchemaBuilder::new(conn.exec_ctx(), schema.clone())
+        .create_table_if_not_exists(lock_table, |t| {
+            t.integer("id").primary();
+            t.integer("is_locked").not_null().default(0);
+        })
+        .await?;
+    QueryBuilder::<()>::new_tx(schema, conn.exec_ctx())
+        .into(lock_table)
+        .insert((col("id"), val(1), col("is_locked"), val(0)))
+        .on_conflict("id")
+        .ignore()
+        .exec()
+        .await?;
+    Ok(())
+}
+
+/// Захват без ожидания: занятый замок → `Error::Locked`
+pub(super) async fn acquire(
+    conn: &mut DbConn,
+    dialect: Dialect,
+    schema: Option<String>,
+    lock_table: &str,
+) -> Result<()> {
+    let locked = match dialect {
+        Dialect::Postgres => {
+            let rows = conn
+                .exec_ctx()
+                .fetch_all::<(bool,)>(
+                    "SELECT pg_try_advisory_lock($1)",
+                    vec![Param::I64(advisory_key(lock_table))],
+                )
+                .await?;
+            rows.first().is_some_and(|(ok,)| *ok)
+        }
+        // UPDATE атомарен: строку переключит только один процесс
+        Dialect::MySQL | Dialect::SQLite => {
+            QueryBuilder::<()>::new_tx(schema, conn.exec_ctx())
+                .update(lock_table)
+                .set((col("is_locked"), val(1)))
+                .r#where((col("id").eq(val(1)), col("is_locked").eq(val(0))))
+                .exec()
+                .await?
+                == 1
+        }
+    };
+    if locked { Ok(()) } else { Err(Error::Locked) }
+}
+
+pub(super) async fn release(
+    conn: &mut DbConn,
+    dialect: Dialect,
+    schema: Option<String>,
+    lock_table: &str,
+) -> Result<()> {
+    match dialect {
+        Dialect::Postgres => {
+            conn.exec_ctx()
+                .fetch_all::<(bool,)>(
+                    "SELECT pg_advisory_unlock($1)",
+                    vec![Param::I64(advisory_key(lock_table))],
+                )
+                .await?;
+        }
+        Dialect::MySQL | Dialect::SQLite => {
+            QueryBuilder::<()>::new_tx(schema, conn.exec_ctx())
+                .update(lock_table)
+                .set((col("is_locked"), val(0)))
+                .r#where(col("id").eq(val(1)))
+                .exec()
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Стабильный ключ advisory lock из имени таблицы (FNV-1a)
+pub(super) fn advisory_key(name: &str) -> i64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in name.bytes() {
+        hash ^= u64::from(b);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash as i64
+}
@@ -0,0 +1,409 @@
+//! Миграции в стиле knex.migrate: `latest` / `rollback` / `status` / `up_to`.
+//!
+//! Каждая миграция выполняется в своей транзакции; применённые записываются в
+//! таблицу миграций (имя, номер батча, время). Параллельный запуск исключается
+//! блокировкой (см. [`lock`]).
+//!
+//! Кроме реализаций [`Migration`] мигратор принимает каталог SQL-файлов
+//! (см. [`SqlMigration`]); их контрольные суммы сохраняются, и изменение уже
+//! применённого файла — ошибка `Error::ChecksumMismatch`.
+
+mod __tests__;
+mod error;
+mod lock;
+mod split;
+mod sql;
+
+use std::{collections::HashSet, future::Future, path::Path, pin::Pin};
+
+use crate::{
+    executor::{DbConn, QueryExecutor, Result as ExecResult, transaction::TxExecutor},
+    expression::helpers::{col, raw, val},
+    param::Param,
+    query_builder::{ExecCtx, QueryBuilder},
+    renderer::Dialect,
+    schema::SchemaBuilder,
+};
+
+pub use error::{Error, Result};
+pub(crate) use split::execute_script;
+pub use split::split_statements;
+pub use sql::SqlMigration;
+
+/// Результат `up`/`down`
+pub type MigrationFuture<'a> = Pin<Box<dyn Future<Output = ExecResult<()>> + Send + 'a>>;
+
+/// Миграция схемы/данных. Порядок применения — по имени, поэтому удобно
+/// начинать имя с номера или даты: `0001_create_users`.
+///
+/// ```ignore
+/// struct CreateUsers;
+///
+/// impl Migration for CreateUsers {
+///     fn name(&self) -> &str {
+///         "0001_create_users"
+///     }
+///     fn up<'a>(&'a self, tx: &'a mut TxExecutor<'_>) -> MigrationFuture<'a> {
+///         Box::pin(async move {
+///             tx.schema()
+///                 .create_table("users", |t| {
+///                     t.increments("id");
+///                 })
+///                 .await
+///         })
+///     }
+///     fn down<'a>(&'a self, tx: &'a mut TxExecutor<'_>) -> MigrationFuture<'a> {
+///         Box::pin(async move { tx.schema().drop_table("users").await })
+///     }
+/// }
+/// ```
+pub trait Migration: Send + Sync {
+    fn name(&self) -> &str;
+    /// Контрольная сумма содержимого; `None` — не проверяется
+    fn checksum(&self) -> Option<String> {
+        None
+    }
+    fn up<'a>(&'a self, tx: &'a mut TxExecutor<'_>) -> MigrationFuture<'a>;
+    fn down<'a>(&'a self, tx: &'a mut TxExecutor<'_>) -> MigrationFuture<'a>;
+}
+
+enum Op<'n> {
+    /// до указанной миграции включительно или все
+    Up(Option<&'n str>),
+    /// последний батч
+    Down,
+}
+
+/// Состояние миграции для `status()`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub name: String,
+    /// `None` — ещё не применена
+    pub batch: Option<i64>,
+    pub applied_at: Option<String>,
+    /// Сохранённая при применении контрольная сумма
+    pub checksum: Option<String>,
+}
+
+impl MigrationStatus {
+    #[inline]
+    pub fn is_applied(&self) -> bool {
+        self.batch.is_some()
+    }
+}
+
+/// Мигратор: `Migrator::new(&db).migration(CreateUsers).latest().await?`
+pub struct Migrator<'e> {
+    exec: &'e QueryExecutor,
+    migrations: Vec<Box<dyn Migration>>,
+    table: String,
+}
+
+impl<'e> Migrator<'e> {
+    pub fn new(exec: &'e QueryExecutor) -> Self {
+        Self {
+            exec,
+            migrations: Vec::new(),
+            table: "knux_migrations".to_string(),
+        }
+    }
+
+    /// Имя таблицы миграций (по умолчанию `knux_migrations`);
+    /// таблица-замок называется `<table>_lock`.
+    pub fn table_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.table = name.into();
+        self
+    }
+
+    pub fn migration<M: Migration + 'static>(mut self, migration: M) -> Self {
+        self.migrations.push(Box::new(migration));
+        self
+    }
+
+    pub fn migration_boxed(mut self, migration: Box<dyn Migration>) -> Self {
+        self.migrations.push(migration);
+        self
+    }
+
+    /// Добавить SQL-миграции из каталога (см. [`SqlMigration::load_dir`])
+    pub fn directory<P: AsRef<Path>>(mut self, dir: P) -> Result<Self> {
+        for m in SqlMigration::load_dir(dir)? {
+            self.migrations.push(Box::new(m));
+        }
+        Ok(self)
+    }
+
+    /// Применить все неприменённые миграции одним новым батчем.
+    /// Возвращает имена применённых.
+    pub async fn latest(&self) -> Result<Vec<String>> {
+        self.with_lock(Op::Up(None)).await
+    }
+
+    /// Применить неприменённые миграции вплоть до `name` включительно
+    pub async fn up_to(&self, name: &str) -> Result<Vec<String>> {
+        self.with_lock(Op::Up(Some(name))).await
+    }
+
+    /// Откатить последний батч (в обратном порядке). Возвращает имена откаченных.
+    pub async fn rollback(&self) -> Result<Vec<String>> {
+        self.with_lock(Op::Down).await
+    }
+
+    /// Все известные миграции по порядку и их состояние
+    pub async fn status(&self) -> Result<Vec<MigrationStatus>> {
+        let sorted = self.sorted()?;
+        let mut conn = self.exec.acquire_conn().await?;
+        self.ensure_table(&mut conn).await?;
+
+        let applied = self.applied(&mut conn).await?;
+        Ok(sorted
+            .into_iter()
+            .map(|m| {
+                let row = applied.iter().find(|a| a.name == m.name());
+                MigrationStatus {
+                    name: m.name().to_string(),
+                    batch: row.and_then(|r| r.batch),
+                    applied_at: row.and_then(|r| r.applied_at.clone()),
+                    checksum: row.and_then(|r| r.checksum.clone()),
+                }
+            })
+            .collect())
+    }
+
+    /// Принудительно снять замок, оставшийся после упавшего процесса
+    /// (аналог `knex migrate:unlock`). В MySQL/SQLite сбрасывает
+    /// `is_locked`; в Postgres ничего не делает — advisory lock снимается
+    /// сервером вместе с сессией.
+    pub async fn force_unlock(&self) -> Result<()> {
+        if self.dialect() == Dialect::Postgres {
+            return Ok(());
+        }
+        let mut conn = self.exec.acquire_conn().await?;
+        let lock_table = self.lock_table();
+        lock::ensure_lock_table(&mut conn, self.dialect(), self.schema(), &lock_table).await?;
+        lock::release(&mut conn, self.dialect(), self.schema(), &lock_table).await
+    }
+
+    // ===== helpers =====
+
+    /// Таблицы, замок и работа на одном выделенном соединении; замок
+    /// снимается и при ошибке.
+    async fn with_lock(&self, op: Op<'_>) -> Result<Vec<String>> {
+        let mut conn = self.exec.acquire_conn().await?;
+        self.ensure_table(&mut conn).await?;
+        let lock_table = self.lock_table();
+        lock::ensure_lock_table(&mut conn, self.dialect(), self.schema(), &lock_table).await?;
+        lock::acquire(&mut conn, self.dialect(), self.schema(), &lock_table).await?;
+
+        let res = match op {
+            Op::Up(target) => self.migrate_up(&mut conn, target).await,
+            Op::Down => self.migrate_down(&mut conn).await,
+        };
+        let released = lock::release(&mut conn, self.dialect(), self.schema(), &lock_table).await;
+        let names = res?;
+        released?;
+        Ok(names)
+    }
+
+    async fn migrate_up(&self, conn: &mut DbConn, target: Option<&str>) -> Result<Vec<String>> {
+        let sorted = self.sorted()?;
+        if let Some(t) = target
+            && !sorted.iter().any(|m| m.name() == t)
+        {
+            return Err(Error::UnknownMigration(t.to_string()));
+        }
+
+        let applied = self.applied(conn).await?;
+        self.verify(&applied, &sorted)?;
+        let done: HashSet<&str> = applied.iter().map(|a| a.name.as_str()).collect();
+        let batch = applied.iter().filter_map(|a| a.batch).max().unwrap_or(0) + 1;
+
+        let mut names = Vec::new();
+        for m in sorted {
+            if !done.contains(m.name()) {
+                self.run_one(conn, m, batch, true).await?;
+                names.push(m.name().to_string());
+            }
+            if target == Some(m.name()) {
+                break;
+            }
+        }
+        Ok(names)
+    }
+
+    async fn migrate_down(&self, conn: &mut DbConn) -> Result<Vec<String>> {
+        let sorted = self.sorted()?;
+        let applied = self.applied(conn).await?;
+        self.verify(&applied, &sorted)?;
+        let Some(batch) = applied.iter().filter_map(|a| a.batch).max() else {
+            return Ok(Vec::new());
+        };
+
+        let mut names = Vec::new();
+        // applied упорядочены по id — откатываем с конца
+        for a in applied.iter().rev().filter(|a| a.batch == Some(batch)) {
+            let Some(m) = sorted.iter().find(|m| m.name() == a.name) else {
+                unreachable!("checked by verify");
+            };
+            self.run_one(conn, *m, batch, false).await?;
+            names.push(a.name.clone());
+        }
+        Ok(names)
+    }
+
+    /// Одна миграция = одна транзакция вместе с записью в таблицу миграций
+    async fn run_one(
+        &self,
+        conn: &mut DbConn,
+        m: &dyn Migration,
+        batch: i64,
+        up: bool,
+    ) -> Result<()> {
+        let failed = |source| Error::Failed {
+            name: m.name().to_string(),
+            source,
+        };
+
+        let mut tx = conn.begin(self.exec).await?;
+        let res = if up {
+            m.up(&mut tx).await
+        } else {
+            m.down(&mut tx).await
+        };
+        res.map_err(failed)?;
+
+        let rows = if up {
+            tx.query::<()>()
+                .into(self.table.as_str())
+                .insert((
+                    col("name"),
+                    val(m.name()),
+                    col("batch"),
+                    val(batch),
+                    col("checksum"),
+                    val(m.checksum().map_or(Param::NullText, Param::from)),
+                ))
+                .exec()
+                .await
+        } else {
+            tx.query::<()>()
+                .delete(self.table.as_str())
+                .r#where(col("name").eq(val(m.name())))
+                .exec()
+                .await
+        };
+        rows.map_err(failed)?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn ensure_table(&self, conn: &mut DbConn) -> Result<()> {
+        SchemaBuilder::new(conn.exec_ctx(), self.schema())
+            .create_table_if_not_exists(&self.table, |t| {
+                t.increments("id");
+                t.string("name", 255).not_null();
+                t.big_integer("batch").not_null();
+                t.timestamp("migration_time")
+                    .not_null()
+                    .default_raw("CURRENT_TIMESTAMP");
+                t.string("checksum", 64).nullable();
+            })
+            .await?;
+
+        // таблица, созданная до появления контрольных сумм
+        if !SchemaBuilder::new(conn.exec_ctx(), self.schema())
+            .has_column(&self.table, "checksum")
+            .await?
+        {
+            SchemaBuilder::new(conn.exec_ctx(), self.schema())
+                .alter_table(&self.table, |t| {
+                    t.string("checksum", 64).nullable();
+                })
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Записи таблицы миграций в порядке применения
+    async fn applied(&self, conn: &mut DbConn) -> Result<Vec<MigrationStatus>> {
+        // время как текст: тип столбца отличается между диалектами
+        let time = match self.dialect() {
+            Dialect::MySQL => "CAST(migration_time AS CHAR)",
+            Dialect::Postgres | Dialect::SQLite => "CAST(migration_time AS TEXT)",
+        };
+        let (sql, params) = QueryBuilder::<()>::new_tx(self.schema(), ExecCtx::None)
+            .dialect(self.dialect())
+            .select((col("name"), col("batch"), raw(time), col("checksum")))
+            .from(self.table.as_str())
+            .order_by("id")
+            .to_sql()?;
+        let rows = conn
+            .exec_ctx()
+            .fetch_all::<(String, i64, String, Option<String>)>(&sql, params)
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(name, batch, time, checksum)| MigrationStatus {
+                name,
+                batch: Some(batch),
+                applied_at: Some(time),
+                checksum,
+            })
+            .collect())
+    }
+
+    /// Миграции по имени; дубли имён — ошибка
+    fn sorted(&self) -> Result<Vec<&dyn Migration>> {
+        let mut sorted: Vec<&dyn Migration> = self.migrations.iter().map(AsRef::as_ref).collect();
+        sorted.sort_by(|a, b| a.name().cmp(b.name()));
+        if let Some(w) = sorted.windows(2).find(|w| w[0].name() == w[1].name()) {
+            return Err(Error::DuplicateMigration(w[0].name().to_string()));
+        }
+        Ok(sorted)
+    }
+
+    /// Применённые миграции, которых нет в миграторе, делают откат невозможным;
+    /// изменённые после применения — расхождение со схемой
+    fn verify(&self, applied: &[MigrationStatus], sorted: &[&dyn Migration]) -> Result<()> {
+        let missing: Vec<String> = applied
+            .iter()
+            .filter(|a| !sorted.iter().any(|m| m.name() == a.name))
+            .map(|a| a.name.clone())
+            .collect();
+        if !missing.is_empty() {
+            return Err(Error::MissingMigrations(missing));
+        }
+
+        for a in applied {
+            let current = sorted
+                .iter()
+                .find(|m| m.name() == a.name)
+                .and_then(|m| m.checksum());
+            if let (Some(applied), Some(current)) = (&a.checksum, current)
+                && *applied != current
+            {
+                return Err(Error::ChecksumMismatch {
+                    name: a.name.clone(),
+                    applied: applied.clone(),
+                    current,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn lock_table(&self) -> String {
+        format!("{}_lock", self.table)
+    }
+
+    #[inline]
+    fn schema(&self) -> Option<String> {
+        self.exec.schema.clone()
+    }
+
+    #[inline]
+    fn dialect(&self) -> Dialect {
+        self.exec.dialect()
+    }
+}
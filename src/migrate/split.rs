@@ -0,0 +1,117 @@
+//! Разбиение SQL-скрипта на отдельные операторы по `;`.
+//!
+//! `;` не считается разделителем внутри строк и идентификаторов в кавычках,
+//! комментариев, dollar-quoted тел Postgres (`$$ ... $$`, `$fn$ ... $fn$`)
+//! и блоков `BEGIN ... END` / `CASE ... END` (триггеры SQLite, процедуры MySQL).
+
+use crate::executor::{Result as ExecResult, transaction::TxExecutor};
+
+/// Операторы скрипта без завершающей `;`; пустые и состоящие
+/// только из комментариев пропускаются.
+pub fn split_statements(sql: &str) -> Vec<&str> {
+    let b = sql.as_bytes();
+    let mut out = Vec::new();
+    let (mut start, mut i) = (0, 0);
+    let mut depth = 0usize;
+    let mut has_code = false;
+    let mut first_word = true;
+    let mut prev_end = false;
+
+    while i < b.len() {
+        match b[i] {
+            q @ (b'\'' | b'"' | b'`') => {
+                i = skip_past(b, i + 1, &[q]);
+                has_code = true;
+                continue;
+            }
+            b'-' if b.get(i + 1) == Some(&b'-') => {
+                i = skip_past(b, i + 2, b"\n");
+                continue;
+            }
+            b'/' if b.get(i + 1) == Some(&b'*') => {
+                i = skip_past(b, i + 2, b"*/");
+                continue;
+            }
+            b'$' => {
+                if let Some(end) = dollar_tag(b, i) {
+                    i = skip_past(b, end + 1, &b[i..=end]);
+                    has_code = true;
+                    continue;
+                }
+                has_code = true;
+            }
+            b';' if depth == 0 => {
+                if has_code {
+                    out.push(sql[start..i].trim());
+                }
+                start = i + 1;
+                has_code = false;
+                first_word = true;
+                prev_end = false;
+            }
+            c if c.is_ascii_alphabetic() || c == b'_' => {
+                let mut end = i + 1;
+                while end < b.len() && (b[end].is_ascii_alphanumeric() || b[end] == b'_') {
+                    end += 1;
+                }
+                let word = &sql[i..end];
+                let is = |kw: &str| word.eq_ignore_ascii_case(kw);
+
+                if prev_end && (is("IF") || is("LOOP") || is("WHILE") || is("REPEAT")) {
+                    // END IF / END LOOP закрывают не BEGIN
+                    depth += 1;
+                } else if prev_end && is("CASE") {
+                    // END CASE: уже учтено на END
+                } else if is("CASE") || (is("BEGIN") && !first_word) {
+                    depth += 1;
+                } else if is("END") {
+                    depth = depth.saturating_sub(1);
+                }
+
+                prev_end = is("END");
+                first_word = false;
+                has_code = true;
+                i = end;
+                continue;
+            }
+            c if !c.is_ascii_whitespace() => {
+                has_code = true;
+                prev_end = false;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    if has_code {
+        out.push(sql[start..].trim());
+    }
+    out
+}
+
+/// Выполнить скрипт по одному оператору через `TxExecutor::execute`
+pub(crate) async fn execute_script(tx: &mut TxExecutor<'_>, sql: &str) -> ExecResult<()> {
+    for stmt in split_statements(sql) {
+        tx.execute(stmt, Vec::new()).await?;
+    }
+    Ok(())
+}
+
+/// Позиция сразу после первого вхождения `pat` начиная с `from` (или конец)
+fn skip_past(b: &[u8], from: usize, pat: &[u8]) -> usize {
+    b.get(from..)
+        .and_then(|rest| rest.windows(pat.len()).position(|w| w == pat))
+        .map_or(b.len(), |p| from + p + pat.len())
+}
+
+/// `$tag$` начиная с `i`: индекс закрывающего `$` тега. `$1` — не тег.
+fn dollar_tag(b: &[u8], i: usize) -> Option<usize> {
+    let mut j = i + 1;
+    if b.get(j).is_some_and(u8::is_ascii_digit) {
+        return None;
+    }
+    while j < b.len() && (b[j].is_ascii_alphanumeric() || b[j] == b'_') {
+        j += 1;
+    }
+    (b.get(j) == Some(&b'$')).then_some(j)
+}
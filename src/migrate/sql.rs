@@ -0,0 +1,134 @@
+//! Миграции из SQL-файлов `NNNN_name.up.sql` / `NNNN_name.down.sql`.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use sha2::{Digest, Sha256};
+
+use super::{Error, Migration, MigrationFuture, Result, execute_script};
+use crate::executor::{Error as ExecError, transaction::TxExecutor};
+
+/// Миграция из SQL-скриптов. Имя — общая часть имён файлов (`0001_create_users`),
+/// контрольная сумма — SHA-256 скриптов `up` и `down`.
+#[derive(Clone, Debug)]
+pub struct SqlMigration {
+    name: String,
+    up: String,
+    down: Option<String>,
+    checksum: String,
+}
+
+impl SqlMigration {
+    pub fn new<N, U>(name: N, up: U, down: Option<String>) -> Self
+    where
+        N: Into<String>,
+        U: Into<String>,
+    {
+        let up = up.into();
+        let checksum = checksum(&up, down.as_deref());
+        Self {
+            name: name.into(),
+            up,
+            down,
+            checksum,
+        }
+    }
+
+    /// Все миграции каталога по имени. Прочие `.sql`-файлы, `.down.sql` без
+    /// пары и имена без числового префикса — `Error::InvalidMigrationFile`.
+    pub fn load_dir<P: AsRef<Path>>(dir: P) -> Result<Vec<Self>> {
+        let dir = dir.as_ref();
+        let io = |path: &Path| {
+            let path = path.to_path_buf();
+            move |source| Error::Io { path, source }
+        };
+
+        let mut files: BTreeMap<String, (Option<String>, Option<String>)> = BTreeMap::new();
+        for entry in fs::read_dir(dir).map_err(io(dir))? {
+            let path = entry.map_err(io(dir))?.path();
+            let Some(file) = path.file_name().and_then(|f| f.to_str()) else {
+                continue;
+            };
+            if !file.ends_with(".sql") || !path.is_file() {
+                continue;
+            }
+
+            let (stem, up) = if let Some(stem) = file.strip_suffix(".up.sql") {
+                (stem, true)
+            } else if let Some(stem) = file.strip_suffix(".down.sql") {
+                (stem, false)
+            } else {
+                return Err(Error::InvalidMigrationFile(file.to_string()));
+            };
+            if !valid_name(stem) {
+                return Err(Error::InvalidMigrationFile(file.to_string()));
+            }
+
+            let sql = fs::read_to_string(&path).map_err(io(&path))?;
+            let slot = files.entry(stem.to_string()).or_default();
+            if up {
+                slot.0 = Some(sql);
+            } else {
+                slot.1 = Some(sql);
+            }
+        }
+
+        files
+            .into_iter()
+            .map(|(name, (up, down))| match up {
+                Some(up) => Ok(Self::new(name, up, down)),
+                None => Err(Error::InvalidMigrationFile(format!(
+                    "{name}.down.sql has no matching {name}.up.sql"
+                ))),
+            })
+            .collect()
+    }
+}
+
+impl Migration for SqlMigration {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn checksum(&self) -> Option<String> {
+        Some(self.checksum.clone())
+    }
+
+    fn up<'a>(&'a self, tx: &'a mut TxExecutor<'_>) -> MigrationFuture<'a> {
+        Box::pin(execute_script(tx, &self.up))
+    }
+
+    fn down<'a>(&'a self, tx: &'a mut TxExecutor<'_>) -> MigrationFuture<'a> {
+        match &self.down {
+            Some(sql) => Box::pin(execute_script(tx, sql)),
+            None => {
+                let err =
+                    ExecError::Unsupported(format!("{}.down.sql not found", self.name).into());
+                Box::pin(async move { Err(err) })
+            }
+        }
+    }
+}
+
+/// `NNNN_name`: цифры, `_`, непустое имя
+fn valid_name(stem: &str) -> bool {
+    let digits = stem.bytes().take_while(u8::is_ascii_digit).count();
+    digits > 0
+        && stem[digits..]
+            .strip_prefix('_')
+            .is_some_and(|rest| !rest.is_empty())
+}
+
+/// `up`, затем `\0` и `down`, если он есть: без `down` сумма равна SHA-256 `up`
+fn checksum(up: &str, down: Option<&str>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(up.as_bytes());
+    if let Some(down) = down {
+        hasher.update([0]);
+        hasher.update(down.as_bytes());
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
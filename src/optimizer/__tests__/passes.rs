@@ -94,13 +94,13 @@ fn simplify_exists_in_where_sets_select_one_and_removes_order() {
                 Some(S::Expr::Exists { subquery, .. }) => {
                     // ORDER BY должен быть удалён
                     if subquery.order_by.is_some() {
-                        return panic!("ORDER BY должен быть удалён");
+                        panic!("ORDER BY должен быть удалён");
                     }
                     // Проекция: ровно один элемент — число 1
                     match &*subquery.body {
                         S::SetExpr::Select(inner_sel) => {
                             if inner_sel.projection.len() != 1 {
-                                return panic!("проекция должна быть из одного элемента");
+                                panic!("проекция должна быть из одного элемента");
                             }
                             match &inner_sel.projection[0] {
                                 S::SelectItem::UnnamedExpr(S::Expr::Value(vws)) => {
@@ -142,7 +142,7 @@ fn simplify_exists_in_projection_ignores_order_even_with_limit() {
                 S::SelectItem::UnnamedExpr(S::Expr::Exists { subquery, .. }) => {
                     // ORDER BY должен быть снят
                     if subquery.order_by.is_some() {
-                        return panic!("ORDER BY должен быть удалён");
+                        panic!("ORDER BY должен быть удалён");
                     }
                     // Проекция подзапроса должна стать SELECT 1
                     match &*subquery.body {
@@ -472,7 +472,7 @@ fn in_to_exists_basic_ast() {
                 Some(S::Expr::Exists { subquery, .. }) => {
                     // Внутренний SELECT: SELECT 1, без ORDER BY
                     if subquery.order_by.is_some() {
-                        return panic!("ORDER BY должен быть удалён");
+                        panic!("ORDER BY должен быть удалён");
                     }
                     match subquery.body.as_ref() {
                         S::SetExpr::Select(isel) => {
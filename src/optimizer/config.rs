@@ -1,4 +1,4 @@
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Hash)]
 pub struct OptimizeConfig {
     // Консервативные
     pub rm_subquery_order_by: bool,
@@ -86,6 +86,44 @@ pub enum Param {
     NullDecimal,
 }
 
+impl Param {
+    /// `true` для любых `Null*`-вариантов.
+    pub fn is_null(&self) -> bool {
+        match self {
+            Param::NullText
+            | Param::NullBytes
+            | Param::NullBool
+            | Param::NullI64
+            | Param::NullI32
+            | Param::NullI16
+            | Param::NullI8
+            | Param::NullF64
+            | Param::NullF32 => true,
+
+            #[cfg(feature = "time")]
+            Param::NullDate | Param::NullTime | Param::NullDateTime | Param::NullNaiveDateTime => {
+                true
+            }
+
+            #[cfg(feature = "chrono")]
+            Param::NullChronoNaiveDate
+            | Param::NullChronoNaiveTime
+            | Param::NullChronoNaiveDateTime
+            | Param::NullChronoDateTimeUtc
+            | Param::NullChronoDateTimeFixed => true,
+
+            #[cfg(feature = "serde_json")]
+            Param::NullJson => true,
+            #[cfg(feature = "uuid")]
+            Param::NullUuid => true,
+            #[cfg(feature = "rust_decimal")]
+            Param::NullDecimal => true,
+
+            _ => false,
+        }
+    }
+}
+
 // ---- From impls ----
 impl From<i8> for Param {
     fn from(v: i8) -> Self {
@@ -0,0 +1,236 @@
+use super::super::*;
+use crate::{
+    expression::helpers::{col, val},
+    renderer::Dialect,
+};
+
+type QB = QueryBuilder<'static, ()>;
+
+fn cursor(values: Vec<Param>) -> Option<KeysetCursor> {
+    Some(KeysetCursor::new(values))
+}
+
+fn builder_error_text(err: Error) -> String {
+    match err {
+        Error::BuilderErrors(list) => list.to_string(),
+        other => panic!("expected BuilderErrors, got {other:?}"),
+    }
+}
+
+#[test]
+fn first_page_has_no_predicate_and_fetches_one_extra_row() {
+    let (sql, params) = QB::new_empty()
+        .dialect(Dialect::SQLite)
+        .from("posts")
+        .order_by("id")
+        .limit(20)
+        .paginate_after(None)
+        .to_sql()
+        .expect("to_sql");
+
+    assert_eq!(
+        sql,
+        r#"SELECT *, "id" AS "__knux_k0" FROM "posts" ORDER BY "id" ASC LIMIT 21"#
+    );
+    assert!(params.is_empty());
+}
+
+#[test]
+fn same_direction_uses_row_value_on_sqlite_and_mysql() {
+    let (sql, params) = QB::new_empty()
+        .dialect(Dialect::SQLite)
+        .from("posts")
+        .select(("id", "title"))
+        .r#where(col("published").eq(val(true)))
+        .order_by(("created_at", "id"))
+        .limit(10)
+        .paginate_after(cursor(vec![Param::Str("2024-01-01".into()), Param::I64(5)]))
+        .to_sql()
+        .expect("to_sql");
+
+    assert_eq!(
+        sql,
+        r#"SELECT "id", "title", "created_at" AS "__knux_k0", "id" AS "__knux_k1" FROM "posts" WHERE ("published" = ?) AND (("created_at", "id") > (?, ?)) ORDER BY "created_at" ASC, "id" ASC LIMIT 11"#
+    );
+    assert!(matches!(
+        params.as_slice(),
+        [Param::Bool(true), Param::Str(s), Param::I64(5)] if s == "2024-01-01"
+    ));
+
+    let (sql, _) = QB::new_empty()
+        .dialect(Dialect::MySQL)
+        .from("posts")
+        .order_by(("created_at", "id"))
+        .limit(10)
+        .paginate_after(cursor(vec![Param::Str("2024-01-01".into()), Param::I64(5)]))
+        .to_sql()
+        .expect("to_sql");
+    assert!(
+        sql.contains("WHERE (`created_at`, `id`) > (?, ?)"),
+        "got:\n{sql}"
+    );
+}
+
+#[test]
+fn postgres_ascending_keys_include_nulls_sorted_last() {
+    let (sql, params) = QB::new_empty()
+        .dialect(Dialect::Postgres)
+        .from("posts")
+        .order_by(("created_at", "id"))
+        .limit(10)
+        .paginate_after(cursor(vec![Param::Str("2024-01-01".into()), Param::I64(5)]))
+        .to_sql()
+        .expect("to_sql");
+
+    assert!(
+        sql.contains(
+            r#"WHERE (("created_at" > $1) OR ("created_at" IS NULL)) OR (("created_at" = $2) AND (("id" > $3) OR ("id" IS NULL)))"#
+        ),
+        "got:\n{sql}"
+    );
+    assert_eq!(params.len(), 3);
+}
+
+#[test]
+fn postgres_descending_keys_use_row_value() {
+    let (sql, _) = QB::new_empty()
+        .dialect(Dialect::Postgres)
+        .from("posts")
+        .order_by_desc(("created_at", "id"))
+        .limit(10)
+        .paginate_after(cursor(vec![Param::Str("2024-01-01".into()), Param::I64(5)]))
+        .to_sql()
+        .expect("to_sql");
+
+    assert!(
+        sql.contains(r#"WHERE ("created_at", "id") < ($1, $2)"#),
+        "got:\n{sql}"
+    );
+    assert!(sql.ends_with(r#"ORDER BY "created_at" DESC, "id" DESC LIMIT 11"#));
+}
+
+#[test]
+fn mixed_directions_are_expanded() {
+    let (sql, params) = QB::new_empty()
+        .dialect(Dialect::SQLite)
+        .from("posts")
+        .order_by("score")
+        .order_by_desc("id")
+        .limit(10)
+        .paginate_after(cursor(vec![Param::I64(7), Param::I64(5)]))
+        .to_sql()
+        .expect("to_sql");
+
+    // SQLite: NULL меньше всех → для DESC они в конце
+    assert!(
+        sql.contains(
+            r#"WHERE ("score" > ?) OR (("score" = ?) AND (("id" < ?) OR ("id" IS NULL)))"#
+        ),
+        "got:\n{sql}"
+    );
+    assert!(matches!(
+        params.as_slice(),
+        [Param::I64(7), Param::I64(7), Param::I64(5)]
+    ));
+}
+
+#[test]
+fn null_cursor_value_compares_with_is_null() {
+    // SQLite: NULL в начале ASC → после NULL идут все непустые значения
+    let (sql, params) = QB::new_empty()
+        .dialect(Dialect::SQLite)
+        .from("posts")
+        .order_by(("score", "id"))
+        .limit(10)
+        .paginate_after(cursor(vec![Param::NullI64, Param::I64(5)]))
+        .to_sql()
+        .expect("to_sql");
+    assert!(
+        sql.contains(r#"WHERE ("score" IS NOT NULL) OR (("score" IS NULL) AND ("id" > ?))"#),
+        "got:\n{sql}"
+    );
+    assert_eq!(params.len(), 1);
+
+    // PG: NULL в конце ASC → только хвост с тем же NULL
+    let (sql, _) = QB::new_empty()
+        .dialect(Dialect::Postgres)
+        .from("posts")
+        .order_by("score")
+        .order_by_desc("id")
+        .limit(10)
+        .paginate_after(cursor(vec![Param::NullI64, Param::I64(5)]))
+        .to_sql()
+        .expect("to_sql");
+    assert!(
+        sql.contains(r#"WHERE ("score" IS NULL) AND ("id" < $1)"#),
+        "got:\n{sql}"
+    );
+}
+
+#[test]
+fn invalid_setups_are_reported_as_builder_errors() {
+    let err = QB::new_empty()
+        .from("posts")
+        .limit(10)
+        .paginate_after(None)
+        .to_sql()
+        .unwrap_err();
+    assert!(builder_error_text(err).contains("хотя бы один ключ ORDER BY"));
+
+    let err = QB::new_empty()
+        .from("posts")
+        .order_by("id")
+        .paginate_after(None)
+        .to_sql()
+        .unwrap_err();
+    assert!(builder_error_text(err).contains("limit(n)"));
+
+    let err = QB::new_empty()
+        .from("posts")
+        .order_by(("created_at", "id"))
+        .limit(10)
+        .paginate_after(cursor(vec![Param::I64(1)]))
+        .to_sql()
+        .unwrap_err();
+    assert!(builder_error_text(err).contains("курсор содержит 1 ключей"));
+}
+
+#[test]
+fn cursor_round_trips_through_string() {
+    let c = KeysetCursor::new(vec![
+        Param::I64(-42),
+        Param::F64(0.1),
+        Param::Str("a:b, c".into()),
+        Param::Bool(true),
+        Param::Bytes(vec![0, 255]),
+        Param::NullText,
+    ]);
+    let s = c.to_string();
+    assert!(s.bytes().all(|b| b.is_ascii_hexdigit()));
+
+    let back: KeysetCursor = s.parse().expect("decode");
+    assert!(matches!(
+        back.values(),
+        [
+            Param::I64(-42),
+            Param::F64(f),
+            Param::Str(text),
+            Param::Bool(true),
+            Param::Bytes(bytes),
+            Param::NullText,
+        ] if *f == 0.1 && text == "a:b, c" && bytes == &[0, 255]
+    ));
+}
+
+#[test]
+fn malformed_cursor_is_rejected() {
+    assert!(matches!(
+        "zz".parse::<KeysetCursor>(),
+        Err(Error::InvalidCursor(_))
+    ));
+    // корректный hex, но не наш формат
+    assert!(matches!(
+        KeysetCursor::decode("6869"),
+        Err(Error::InvalidCursor(_))
+    ));
+}
@@ -48,3 +48,20 @@ fn limit_and_offset() {
         "must reflect OFFSET 5: {norm}"
     );
 }
+
+#[test]
+fn closure_subqueries_inherit_outer_dialect() {
+    use crate::renderer::Dialect;
+    use crate::type_helpers::QBClosureHelper;
+
+    let ids: QBClosureHelper<()> = |q| q.select(("id",)).from("t").limit(5).offset(10);
+    let (sql, _params) = QB::new_empty()
+        .dialect(Dialect::MySQL)
+        .select(("*",))
+        .from("users")
+        .where_in("id", (ids,))
+        .to_sql()
+        .expect("to_sql");
+    // подзапрос строится в MySQL-форме LIMIT, а не в диалекте по умолчанию
+    assert!(sql.contains("LIMIT 10, 5"), "{sql}");
+}
@@ -0,0 +1,95 @@
+use super::super::*;
+use crate::{
+    expression::helpers::{col, val},
+    renderer::Dialect,
+};
+
+type QB = QueryBuilder<'static, ()>;
+
+fn sql_for(dialect: Dialect, qb: QB) -> String {
+    qb.dialect(dialect).to_sql().expect("to_sql").0
+}
+
+fn jobs() -> QB {
+    QB::new_empty()
+        .from("jobs")
+        .select(("id",))
+        .r#where(col("state").eq(val("queued")))
+        .order_by("id")
+        .limit(10)
+}
+
+#[test]
+fn for_update_skip_locked_per_dialect() {
+    let qb = || jobs().for_update().skip_locked();
+
+    assert_eq!(
+        sql_for(Dialect::Postgres, qb()),
+        r#"SELECT "id" FROM "jobs" WHERE "state" = $1 ORDER BY "id" ASC LIMIT 10 FOR UPDATE SKIP LOCKED"#
+    );
+    assert_eq!(
+        sql_for(Dialect::MySQL, qb()),
+        "SELECT `id` FROM `jobs` WHERE `state` = ? ORDER BY `id` ASC LIMIT 10 FOR UPDATE SKIP LOCKED"
+    );
+    // в SQLite блокировок строк нет — в Lenient клауза опускается
+    assert_eq!(
+        sql_for(Dialect::SQLite, qb()),
+        r#"SELECT "id" FROM "jobs" WHERE "state" = ? ORDER BY "id" ASC LIMIT 10"#
+    );
+}
+
+#[test]
+fn share_no_key_update_nowait_and_of() {
+    let sql = sql_for(
+        Dialect::Postgres,
+        jobs().for_share().of(["jobs", "q.tasks"]).no_wait(),
+    );
+    assert!(
+        sql.ends_with(r#" FOR SHARE OF "jobs", "q"."tasks" NOWAIT"#),
+        "{sql}"
+    );
+
+    // последний for_* задаёт силу блокировки
+    let qb = || jobs().for_share().for_no_key_update();
+    assert!(sql_for(Dialect::Postgres, qb()).ends_with(" FOR NO KEY UPDATE"));
+    // MySQL не знает NO KEY UPDATE — берётся более сильная FOR UPDATE
+    assert!(sql_for(Dialect::MySQL, qb()).ends_with(" FOR UPDATE"));
+}
+
+#[test]
+fn lock_modifiers_require_lock_mode() {
+    for qb in [jobs().skip_locked(), jobs().no_wait(), jobs().of(["jobs"])] {
+        let err = qb.dialect(Dialect::Postgres).to_sql().unwrap_err();
+        assert!(matches!(err, Error::BuilderErrors(_)), "{err:?}");
+    }
+}
+
+#[test]
+fn paginate_count_query_has_no_lock() {
+    let p = jobs()
+        .for_update()
+        .dialect(Dialect::Postgres)
+        .paginate(1, 10);
+    let (count_sql, _) = p.count_to_sql().expect("count_to_sql");
+    assert!(!count_sql.contains("FOR UPDATE"), "{count_sql}");
+    let (sql, _) = p.to_sql().expect("to_sql");
+    assert!(sql.ends_with(" FOR UPDATE"), "{sql}");
+}
+
+#[test]
+fn lock_with_set_operation_is_rejected_in_lenient_mode() {
+    let qb = || jobs().union((jobs(),)).for_update();
+    for d in [Dialect::Postgres, Dialect::MySQL] {
+        let err = qb().dialect(d).to_sql().unwrap_err();
+        assert!(
+            matches!(
+                err,
+                Error::SQLRenderError(crate::renderer::Error::UnsupportedFeature { .. })
+            ),
+            "{err:?}"
+        );
+    }
+    // в SQLite блокировка опускается, как и без UNION
+    let sql = sql_for(Dialect::SQLite, qb());
+    assert!(!sql.contains("FOR UPDATE"), "{sql}");
+}
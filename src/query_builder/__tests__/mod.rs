@@ -45,3 +45,15 @@ mod union;
 
 #[cfg(test)]
 mod intersect_except;
+
+#[cfg(test)]
+mod keyset;
+
+#[cfg(test)]
+mod paginate;
+
+#[cfg(test)]
+mod window;
+
+#[cfg(test)]
+mod lock;
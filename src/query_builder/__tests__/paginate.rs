@@ -0,0 +1,139 @@
+use super::super::*;
+use crate::{
+    expression::helpers::{col, val},
+    renderer::Dialect,
+};
+
+type QB = QueryBuilder<'static, ()>;
+type Sql = (String, Vec<Param>);
+
+fn page_and_count_sql(qb: QB, page: u64, per_page: u64) -> (Sql, Sql) {
+    let p = qb.paginate(page, per_page);
+    let count = p.count_to_sql().expect("count_to_sql");
+    (p.to_sql().expect("to_sql"), count)
+}
+
+#[test]
+fn paginate_sets_limit_offset_and_strips_count_query() {
+    let ((sql, params), (count_sql, count_params)) = page_and_count_sql(
+        QB::new_empty()
+            .dialect(Dialect::SQLite)
+            .from("users")
+            .select(("id", "name"))
+            .r#where(col("age").gt(val(18)))
+            .order_by("id"),
+        3,
+        10,
+    );
+
+    assert_eq!(
+        sql,
+        r#"SELECT "id", "name" FROM "users" WHERE "age" > ? ORDER BY "id" ASC LIMIT 10 OFFSET 20"#
+    );
+    assert_eq!(
+        count_sql,
+        r#"SELECT COUNT(*) AS "total" FROM "users" WHERE "age" > ?"#
+    );
+    assert_eq!(params.len(), 1);
+    assert_eq!(count_params.len(), 1);
+}
+
+#[test]
+fn count_keeps_joins() {
+    let (_, (count_sql, _)) = page_and_count_sql(
+        QB::new_empty()
+            .dialect(Dialect::Postgres)
+            .from("users")
+            .join("orders", col("orders.user_id").eq(col("users.id")))
+            .select(("users.id", "orders.total")),
+        1,
+        5,
+    );
+
+    assert!(
+        count_sql.starts_with(r#"SELECT COUNT(*) AS "total" FROM "users" INNER JOIN "orders""#)
+            || count_sql.starts_with(r#"SELECT COUNT(*) AS "total" FROM "users" JOIN "orders""#),
+        "got:\n{count_sql}"
+    );
+}
+
+#[test]
+fn distinct_is_counted_over_subquery() {
+    let (_, (count_sql, _)) = page_and_count_sql(
+        QB::new_empty()
+            .dialect(Dialect::SQLite)
+            .from("users")
+            .distinct(("name",))
+            .order_by("name"),
+        1,
+        10,
+    );
+
+    assert_eq!(
+        count_sql,
+        r#"SELECT COUNT(*) AS "total" FROM (SELECT DISTINCT "name" FROM "users") AS "knux_count""#
+    );
+}
+
+#[test]
+fn group_by_having_is_counted_over_subquery() {
+    let (_, (count_sql, count_params)) = page_and_count_sql(
+        QB::new_empty()
+            .dialect(Dialect::MySQL)
+            .from("orders")
+            .select(("user_id",))
+            .group_by("user_id")
+            .having(col("user_id").gt(val(1))),
+        2,
+        10,
+    );
+
+    assert_eq!(
+        count_sql,
+        "SELECT COUNT(*) AS `total` FROM (SELECT `user_id` FROM `orders` GROUP BY `user_id` HAVING `user_id` > ?) AS `knux_count`"
+    );
+    assert_eq!(count_params.len(), 1);
+}
+
+#[test]
+fn set_ops_are_counted_over_subquery_and_ctes_are_hoisted() {
+    let (_, (count_sql, count_params)) = page_and_count_sql(
+        QB::new_empty()
+            .dialect(Dialect::SQLite)
+            .with(
+                "active",
+                QB::new_empty()
+                    .from("users")
+                    .r#where(col("active").eq(val(true))),
+            )
+            .from("active")
+            .select("id")
+            .union(QB::new_empty().from("admins").select("id"))
+            .order_by("id"),
+        1,
+        10,
+    );
+
+    assert_eq!(
+        count_sql,
+        r#"WITH "active" AS (SELECT * FROM "users" WHERE "active" = ?) SELECT COUNT(*) AS "total" FROM ((SELECT "id" FROM "active") UNION (SELECT "id" FROM "admins")) AS "knux_count""#
+    );
+    assert_eq!(count_params.len(), 1);
+}
+
+#[test]
+fn zero_page_or_per_page_is_builder_error() {
+    let err = QB::new_empty()
+        .from("users")
+        .paginate(0, 10)
+        .to_sql()
+        .unwrap_err();
+    assert!(matches!(err, Error::BuilderErrors(_)));
+
+    let err = QB::new_empty()
+        .from("users")
+        .paginate(1, 0)
+        .to_sql()
+        .unwrap_err();
+    assert!(matches!(err, Error::BuilderErrors(_)));
+}
@@ -230,7 +230,7 @@ fn insert_into_with_default_schema() {
     );
 }
 
-#[cfg(not(feature = "mysql"))]
+#[cfg(not(all(feature = "mysql", not(feature = "postgres"))))]
 #[test]
 fn insert_returning_is_emitted() {
     let (sql, _params) = QB::new_empty()
@@ -280,14 +280,14 @@ fn update_with_from_sources() {
     assert!(sqln.contains(&format!("UPDATE {}", qi("t"))), "got: {sql}");
     assert!(sqln.contains(" SET "), "got: {sql}");
 
-    #[cfg(feature = "mysql")]
+    #[cfg(all(feature = "mysql", not(feature = "postgres")))]
     {
         assert!(
             !sqln.contains(" FROM "),
             "MySQL не должен печатать FROM в UPDATE: {sql}"
         );
     }
-    #[cfg(not(feature = "mysql"))]
+    #[cfg(not(all(feature = "mysql", not(feature = "postgres"))))]
     {
         assert!(
             sqln.contains(&format!("FROM {}, {}", qi("a"), qi("b"))),
@@ -315,14 +315,14 @@ fn delete_basic_where_and_returning() {
     assert!(sqln.contains(&format!("FROM {}", qi("t"))), "got: {sql}");
     assert!(sqln.contains(" WHERE "), "got: {sql}");
 
-    #[cfg(feature = "mysql")]
+    #[cfg(all(feature = "mysql", not(feature = "postgres")))]
     {
         assert!(
             !sql.contains("RETURNING"),
             "MySQL не должен печатать RETURNING: {sql}"
         );
     }
-    #[cfg(not(feature = "mysql"))]
+    #[cfg(not(all(feature = "mysql", not(feature = "postgres"))))]
     {
         assert!(sql.contains("RETURNING"), "got: {sql}");
     }
@@ -346,3 +346,29 @@ fn delete_using_multiple_tables() {
     );
     assert!(params.is_empty());
 }
+
+#[test]
+fn to_sql_debug_inlines_params_for_every_builder() {
+    use crate::renderer::Dialect;
+
+    let q = QB::new_empty()
+        .dialect(Dialect::Postgres)
+        .from("users")
+        .select("*")
+        .r#where(col("name").eq(val("O'Brien")))
+        .r#where(col("age").gt(val(30)))
+        .to_sql_debug()
+        .unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT * FROM "users" WHERE ("name" = 'O''Brien') AND ("age" > 30)"#
+    );
+
+    let ins = QB::new_empty()
+        .dialect(Dialect::SQLite)
+        .into("blobs")
+        .insert((col("data"), val(vec![1u8, 255u8])))
+        .to_sql_debug()
+        .unwrap();
+    assert_eq!(ins, r#"INSERT INTO "blobs" ("data") VALUES (X'01ff')"#);
+}
@@ -0,0 +1,136 @@
+use super::super::*;
+use crate::{
+    expression::{
+        FrameBound::{CurrentRow, Following, Preceding, UnboundedFollowing, UnboundedPreceding},
+        dense_rank,
+        helpers::{col, val},
+        ntile, rank, row_number,
+    },
+    renderer::Dialect,
+};
+
+type QB = QueryBuilder<'static, ()>;
+
+fn sql_for(dialect: Dialect, qb: QB) -> String {
+    qb.dialect(dialect).to_sql().expect("to_sql").0
+}
+
+#[test]
+fn ranking_functions_with_partition_and_order() {
+    let qb = || {
+        QB::new_empty().from("emp").select((
+            col("id"),
+            row_number()
+                .over(|w| w.partition_by("dept").order_by_desc("salary"))
+                .r#as("rn"),
+            rank().over(|w| w.order_by("salary")),
+            dense_rank().over(|w| w.order_by(("dept", col("salary")))),
+            ntile(4).over(|w| w.order_by("salary")),
+        ))
+    };
+
+    assert_eq!(
+        sql_for(Dialect::Postgres, qb()),
+        r#"SELECT "id", ROW_NUMBER() OVER (PARTITION BY "dept" ORDER BY "salary" DESC) AS "rn", RANK() OVER (ORDER BY "salary" ASC), DENSE_RANK() OVER (ORDER BY "dept" ASC, "salary" ASC), NTILE(4) OVER (ORDER BY "salary" ASC) FROM "emp""#
+    );
+    assert_eq!(
+        sql_for(Dialect::MySQL, qb()),
+        "SELECT `id`, ROW_NUMBER() OVER (PARTITION BY `dept` ORDER BY `salary` DESC) AS `rn`, RANK() OVER (ORDER BY `salary` ASC), DENSE_RANK() OVER (ORDER BY `dept` ASC, `salary` ASC), NTILE(4) OVER (ORDER BY `salary` ASC) FROM `emp`"
+    );
+    assert_eq!(
+        sql_for(Dialect::SQLite, qb()),
+        sql_for(Dialect::Postgres, qb())
+    );
+}
+
+#[test]
+fn lag_lead_with_default_collects_params() {
+    let (sql, params) = QB::new_empty()
+        .dialect(Dialect::Postgres)
+        .from("prices")
+        .select((
+            col("price").lag(1).over(|w| w.order_by("day")),
+            col("price")
+                .lead_or(2, val(0))
+                .over(|w| w.partition_by("ticker").order_by("day")),
+        ))
+        .to_sql()
+        .unwrap();
+
+    assert_eq!(
+        sql,
+        r#"SELECT LAG("price", 1) OVER (ORDER BY "day" ASC), LEAD("price", 2, $1) OVER (PARTITION BY "ticker" ORDER BY "day" ASC) FROM "prices""#
+    );
+    assert!(matches!(params.as_slice(), [Param::I32(0)]), "{params:?}");
+}
+
+#[test]
+fn aggregate_over_window_with_frames() {
+    let qb = QB::new_empty().from("sales").select((
+        col("amount")
+            .sum()
+            .over(|w| w.order_by("day").rows_between(Preceding(6), CurrentRow))
+            .r#as("week"),
+        col("amount")
+            .avg()
+            .over(|w| w.range_between(UnboundedPreceding, Following(1))),
+        col("amount").last_value().over(|w| {
+            w.order_by("day")
+                .groups_between(CurrentRow, UnboundedFollowing)
+        }),
+    ));
+
+    assert_eq!(
+        sql_for(Dialect::SQLite, qb),
+        r#"SELECT SUM("amount") OVER (ORDER BY "day" ASC ROWS BETWEEN 6 PRECEDING AND CURRENT ROW) AS "week", AVG("amount") OVER (RANGE BETWEEN UNBOUNDED PRECEDING AND 1 FOLLOWING), LAST_VALUE("amount") OVER (ORDER BY "day" ASC GROUPS BETWEEN CURRENT ROW AND UNBOUNDED FOLLOWING) FROM "sales""#
+    );
+}
+
+#[test]
+fn named_windows() {
+    let qb = || {
+        QB::new_empty()
+            .from("emp")
+            .select((
+                col("salary").sum().over_window("w"),
+                rank().over(|w| w.base("w").order_by("salary")),
+            ))
+            .window("w", |w| w.partition_by("dept"))
+            .order_by("id")
+    };
+
+    assert_eq!(
+        sql_for(Dialect::Postgres, qb()),
+        r#"SELECT SUM("salary") OVER "w", RANK() OVER ("w" ORDER BY "salary" ASC) FROM "emp" WINDOW "w" AS (PARTITION BY "dept") ORDER BY "id" ASC"#
+    );
+    assert_eq!(
+        sql_for(Dialect::MySQL, qb()),
+        "SELECT SUM(`salary`) OVER `w`, RANK() OVER (`w` ORDER BY `salary` ASC) FROM `emp` WINDOW `w` AS (PARTITION BY `dept`) ORDER BY `id` ASC"
+    );
+}
+
+#[test]
+fn over_on_non_function_is_builder_error() {
+    let cases = [
+        QB::new_empty()
+            .from("t")
+            .select(col("a").over(|w| w.order_by("b"))),
+        QB::new_empty()
+            .from("t")
+            .select(val(1).over_window("w").r#as("x"))
+            .window("w", |w| w.order_by("b")),
+        // ошибка переживает сравнение и попадает в WHERE
+        QB::new_empty()
+            .from("t")
+            .select("a")
+            .r#where(col("a").over_window("w").gt(val(1))),
+    ];
+    for qb in cases {
+        let err = qb.dialect(Dialect::Postgres).to_sql().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("окно применимо только к вызову функции"),
+            "{err}"
+        );
+    }
+}
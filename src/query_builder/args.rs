@@ -4,7 +4,8 @@ use std::sync::{Arc, Mutex};
 
 use crate::expression::{self, Expression};
 use crate::param::Param;
-use crate::query_builder::QueryBuilder;
+use crate::query_builder::{DEFAULT_DIALECT, QueryBuilder};
+use crate::renderer::Dialect;
 use smallvec::SmallVec;
 use sqlparser::ast;
 
@@ -95,7 +96,7 @@ impl<'a> QBArg<'a> {
     /// Для `Subquery/Closure` вернёт ошибку с пояснением.
     pub fn try_into_expr(self) -> Result<(ast::Expr, SmallVec<[Param; 8]>)> {
         match self {
-            QBArg::Expr(e) => Ok((e.expr, e.params)),
+            QBArg::Expr(e) => e.checked().map(|e| (e.expr, e.params)),
             QBArg::Subquery(_) => Err(Error::InvalidExpression {
                 reason: "argument is a subquery; use resolve_into_expr_with(...) instead".into(),
             }),
@@ -111,17 +112,29 @@ impl<'a> QBArg<'a> {
     /// и возвращает `(ast::Query, params)`. Её предоставит сам `QueryBuilder`
     /// там, где уже есть контекст (`SELECT`, `WHERE IN (subquery)`, и т.п.).
     pub fn resolve_into_expr_with<F>(self, build_subquery: F) -> Result<(ast::Expr, Vec<Param>)>
+    where
+        F: FnOnce(QueryBuilder) -> Result<(ast::Query, Vec<Param>)>,
+    {
+        self.resolve_into_expr_in(DEFAULT_DIALECT, build_subquery)
+    }
+
+    /// То же, но замыкание получает билдер с диалектом внешнего запроса
+    pub(crate) fn resolve_into_expr_in<F>(
+        self,
+        dialect: Dialect,
+        build_subquery: F,
+    ) -> Result<(ast::Expr, Vec<Param>)>
     where
         F: FnOnce(QueryBuilder) -> Result<(ast::Query, Vec<Param>)>,
     {
         match self {
-            QBArg::Expr(e) => Ok((e.expr, e.params.into_vec())),
+            QBArg::Expr(e) => e.checked().map(|e| (e.expr, e.params.into_vec())),
             QBArg::Subquery(qb) => {
                 let (q, params) = build_subquery(qb)?;
                 Ok((ast::Expr::Subquery(Box::new(q)), params))
             }
             QBArg::Closure(c) => {
-                let built = c.try_call(QueryBuilder::new_empty())?;
+                let built = c.try_call(QueryBuilder::new_empty().dialect(dialect))?;
                 let (q, params) = build_subquery(built)?;
                 Ok((ast::Expr::Subquery(Box::new(q)), params))
             }
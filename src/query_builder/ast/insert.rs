@@ -24,40 +24,51 @@ impl<'a, T> InsertBuilder<'a, T> {
                 });
             }
         };
-        if self.rows.is_empty() {
+        if self.rows.is_empty() && self.select_source.is_none() {
             return Err(Error::InvalidExpression {
                 reason: "insert: no VALUES rows".into(),
             });
         }
+        if !self.rows.is_empty() && self.select_source.is_some() {
+            return Err(Error::InvalidExpression {
+                reason: "insert: VALUES rows and insert_from() are mutually exclusive".into(),
+            });
+        }
         if !self.builder_errors.is_empty() {
             return Err(Error::InvalidExpression {
                 reason: format!("insert: build errors: {:?}", self.builder_errors).into(),
             });
         }
 
-        // 2) VALUES → Query(SetExpr::Values)
-        let rows_exprs: Vec<Vec<S::Expr>> = self
-            .rows
-            .iter()
-            .map(|r| r.values.iter().cloned().collect())
-            .collect();
+        // 2) VALUES → Query(SetExpr::Values) или готовый SELECT из insert_from()
+        let mut params: Vec<Param> = Vec::new();
+        let query = if let Some(src) = self.select_source {
+            params.extend(src.params);
+            src.query
+        } else {
+            let rows_exprs: Vec<Vec<S::Expr>> = self
+                .rows
+                .iter()
+                .map(|r| r.values.iter().cloned().collect())
+                .collect();
 
-        let values = S::Values {
-            rows: rows_exprs,
-            explicit_row: false,
-        };
+            let values = S::Values {
+                rows: rows_exprs,
+                explicit_row: false,
+            };
 
-        let query = S::Query {
-            with: None,
-            body: Box::new(S::SetExpr::Values(values)),
-            fetch: None,
-            for_clause: None,
-            format_clause: None,
-            limit_clause: None,
-            locks: vec![],
-            order_by: None,
-            pipe_operators: vec![],
-            settings: None,
+            S::Query {
+                with: None,
+                body: Box::new(S::SetExpr::Values(values)),
+                fetch: None,
+                for_clause: None,
+                format_clause: None,
+                limit_clause: None,
+                locks: vec![],
+                order_by: None,
+                pipe_operators: vec![],
+                settings: None,
+            }
         };
 
         // 3) RETURNING
@@ -221,8 +232,7 @@ impl<'a, T> InsertBuilder<'a, T> {
             table_alias: None,
         };
 
-        // 6) Параметры — порядок важен: сначала VALUES, потом RHS из merge()
-        let mut params: Vec<Param> = Vec::new();
+        // 6) Параметры — порядок важен: сначала VALUES/SELECT, потом RHS из merge()
         for r in self.rows {
             if !r.params.is_empty() {
                 params.extend(r.params);
@@ -247,11 +257,16 @@ impl<'a, T> InsertBuilder<'a, T> {
                 reason: "insert: table is not set".into(),
             });
         };
-        if self.rows.is_empty() {
+        if self.rows.is_empty() && self.select_source.is_none() {
             return Err(Error::InvalidExpression {
                 reason: "insert: no VALUES rows".into(),
             });
         }
+        if !self.rows.is_empty() && self.select_source.is_some() {
+            return Err(Error::InvalidExpression {
+                reason: "insert: VALUES rows and insert_from() are mutually exclusive".into(),
+            });
+        }
         if !self.builder_errors.is_empty() {
             return Err(Error::InvalidExpression {
                 reason: format!("insert: build errors: {:?}", self.builder_errors).into(),
@@ -260,33 +275,39 @@ impl<'a, T> InsertBuilder<'a, T> {
 
         // 2) Вынуть строки целиком, чтобы переместить values/params без клонирования
         let rows = mem::take(&mut self.rows);
-
-        // VALUES → Query(SetExpr::Values)
         let mut params: Vec<Param> = Vec::new();
-        let mut rows_exprs: Vec<Vec<S::Expr>> = Vec::with_capacity(rows.len());
-        for mut r in rows {
-            rows_exprs.push(mem::take(&mut r.values).into_vec()); // move
-            if !r.params.is_empty() {
-                params.extend(mem::take(&mut r.params)); // move
+
+        let query = if let Some(src) = self.select_source.take() {
+            // INSERT ... SELECT: параметры подзапроса идут первыми
+            params.extend(src.params);
+            src.query
+        } else {
+            // VALUES → Query(SetExpr::Values)
+            let mut rows_exprs: Vec<Vec<S::Expr>> = Vec::with_capacity(rows.len());
+            for mut r in rows {
+                rows_exprs.push(mem::take(&mut r.values).into_vec()); // move
+                if !r.params.is_empty() {
+                    params.extend(mem::take(&mut r.params)); // move
+                }
             }
-        }
 
-        let values = S::Values {
-            rows: rows_exprs,
-            explicit_row: false,
-        };
+            let values = S::Values {
+                rows: rows_exprs,
+                explicit_row: false,
+            };
 
-        let query = S::Query {
-            with: None,
-            body: Box::new(S::SetExpr::Values(values)),
-            fetch: None,
-            for_clause: None,
-            format_clause: None,
-            limit_clause: None,
-            locks: vec![],
-            order_by: None,
-            pipe_operators: vec![],
-            settings: None,
+            S::Query {
+                with: None,
+                body: Box::new(S::SetExpr::Values(values)),
+                fetch: None,
+                for_clause: None,
+                format_clause: None,
+                limit_clause: None,
+                locks: vec![],
+                order_by: None,
+                pipe_operators: vec![],
+                settings: None,
+            }
         };
 
         // 3) RETURNING (заберём список, если был)
@@ -1,6 +1,7 @@
 mod delete;
 mod insert;
 mod query;
+mod shape;
 mod update;
 
 use crate::query_builder::{
@@ -21,10 +21,10 @@ impl<'a, T> QueryBuilder<'a, T> {
         let limit_clause = self.build_limit_clause();
         let with = self.take_with_ast();
 
-        // соберём params и FROM сразу
-        let params_sv = mem::take(&mut self.params);
+        // params копим в порядке появления плейсхолдеров в SQL: WITH идёт первым
+        let mut params: Vec<Param> = mem::take(&mut self.params).into_vec();
         let from_items = mem::take(&mut self.from_items);
-        let (from, mut params) = self.form_from_items(params_sv, from_items)?;
+        let (from, from_params) = self.form_from_items(SmallVec::new(), from_items)?;
 
         // --- projection + select_params в одном проходе ---
         let (projection, select_params): (Vec<SelectItem>, Vec<Param>) =
@@ -97,6 +97,17 @@ impl<'a, T> QueryBuilder<'a, T> {
                 )
             };
 
+        // --- WINDOW name AS (...) ---
+        let mut window_params: Vec<Param> = Vec::new();
+        let named_window = self
+            .windows
+            .drain(..)
+            .map(|n| {
+                window_params.extend(n.params);
+                n.def
+            })
+            .collect();
+
         let select = Select {
             distinct: distinct_opt,
             top: None,
@@ -110,7 +121,7 @@ impl<'a, T> QueryBuilder<'a, T> {
             distribute_by: vec![],
             sort_by: vec![],
             having,
-            named_window: vec![],
+            named_window,
             qualify: None,
             connect_by: None,
             exclude: None,
@@ -125,10 +136,11 @@ impl<'a, T> QueryBuilder<'a, T> {
         let mut body = SetExpr::Select(Box::new(select));
 
         // set-ops: без промежуточного Vec
+        let mut set_params: Vec<Param> = Vec::new();
         if !self.set_ops.is_empty() {
             for node in self.set_ops.drain(..) {
                 if !node.params.is_empty() {
-                    params.extend(node.params.into_iter());
+                    set_params.extend(node.params);
                 }
                 body = SetExpr::SetOperation {
                     op: node.op,
@@ -158,22 +170,24 @@ impl<'a, T> QueryBuilder<'a, T> {
             )));
         }
 
-        // WHERE/HAVING params в конец
+        // дальше — в порядке рендера: SELECT, FROM/JOIN, WHERE, GROUP BY, HAVING, WINDOW, set-ops, ORDER BY
+        params.extend(select_params);
+        params.extend(from_params);
         if let Some(node) = self.where_clause.take() {
             if !node.params.is_empty() {
                 params.extend(node.params.into_iter());
             }
         }
+        params.extend(group_params);
         if let Some(node) = self.having_clause.take() {
             if !node.params.is_empty() {
                 params.extend(node.params.into_iter());
             }
         }
 
-        // ORDER BY уже собран (order_params), SELECT-параметры тоже
+        params.extend(window_params);
+        params.extend(set_params);
         params.extend(order_params);
-        params.extend(select_params);
-        params.extend(group_params);
 
         Ok((query, params))
     }
@@ -192,10 +206,10 @@ impl<'a, T> QueryBuilder<'a, T> {
         let limit_clause = self.build_limit_clause();
         let with = self.take_with_ast();
 
-        // соберём params и FROM сразу
-        let params_sv = mem::take(&mut self.params);
+        // params копим в порядке появления плейсхолдеров в SQL: WITH идёт первым
+        let mut params: Vec<Param> = mem::take(&mut self.params).into_vec();
         let from_items = mem::take(&mut self.from_items);
-        let (from, mut params) = self.form_from_items(params_sv, from_items)?;
+        let (from, from_params) = self.form_from_items(SmallVec::new(), from_items)?;
 
         // --- projection + select_params в одном проходе ---
         let (projection, select_params): (Vec<SelectItem>, Vec<Param>) =
@@ -268,6 +282,17 @@ impl<'a, T> QueryBuilder<'a, T> {
                 )
             };
 
+        // --- WINDOW name AS (...) ---
+        let mut window_params: Vec<Param> = Vec::new();
+        let named_window = self
+            .windows
+            .drain(..)
+            .map(|n| {
+                window_params.extend(n.params);
+                n.def
+            })
+            .collect();
+
         let select = Select {
             distinct: distinct_opt,
             top: None,
@@ -281,7 +306,7 @@ impl<'a, T> QueryBuilder<'a, T> {
             distribute_by: vec![],
             sort_by: vec![],
             having,
-            named_window: vec![],
+            named_window,
             qualify: None,
             connect_by: None,
             exclude: None,
@@ -296,10 +321,11 @@ impl<'a, T> QueryBuilder<'a, T> {
         let mut body = SetExpr::Select(Box::new(select));
 
         // SET-операции
+        let mut set_params: Vec<Param> = Vec::new();
         if !self.set_ops.is_empty() {
             for node in self.set_ops.drain(..) {
                 if !node.params.is_empty() {
-                    params.extend(node.params.into_iter());
+                    set_params.extend(node.params);
                 }
                 body = SetExpr::SetOperation {
                     op: node.op,
@@ -329,21 +355,24 @@ impl<'a, T> QueryBuilder<'a, T> {
             ))));
         }
 
-        // WHERE/HAVING params в конец
+        // дальше — в порядке рендера: SELECT, FROM/JOIN, WHERE, GROUP BY, HAVING, WINDOW, set-ops, ORDER BY
+        params.extend(select_params);
+        params.extend(from_params);
         if let Some(node) = self.where_clause.take() {
             if !node.params.is_empty() {
                 params.extend(node.params.into_iter());
             }
         }
+        params.extend(group_params);
         if let Some(node) = self.having_clause.take() {
             if !node.params.is_empty() {
                 params.extend(node.params.into_iter());
             }
         }
 
+        params.extend(window_params);
+        params.extend(set_params);
         params.extend(order_params);
-        params.extend(select_params);
-        params.extend(group_params);
 
         Ok((query, params))
     }
@@ -467,7 +496,7 @@ impl<'a, T> QueryBuilder<'a, T> {
                     });
                 }
                 FromItem::SubqueryClosure(closure) => {
-                    let built = closure.call(QueryBuilder::new_empty());
+                    let built = closure.call(QueryBuilder::new_empty().dialect(self.dialect));
                     let alias = built.alias.clone();
                     let (q, p) = built.build_query_ast()?;
                     if !p.is_empty() {
@@ -0,0 +1,141 @@
+//! Отпечаток «формы» SELECT-билдера для кэша SQL и сбор одних параметров
+//! без построения AST. Порядок обхода повторяет `form_query_ast`.
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::mem;
+
+use crate::param::Param;
+
+use super::super::QueryBuilder;
+use super::FromItem;
+
+impl<'a, T> QueryBuilder<'a, T> {
+    /// Отпечаток всего, что влияет на SQL (без значений параметров), и число
+    /// параметров. `None` — кэшировать нельзя: ошибки билдера или подзапрос-замыкание.
+    pub(crate) fn shape(&self) -> Option<(u64, usize)> {
+        let mut h = DefaultHasher::new();
+        let params = self.hash_shape(&mut h)?;
+        Some((h.finish(), params))
+    }
+
+    fn hash_shape(&self, h: &mut DefaultHasher) -> Option<usize> {
+        if !self.builder_errors.is_empty() {
+            return None;
+        }
+        self.dialect.hash(h);
+        self.optimize_cfg.hash(h);
+        self.lock.hash(h);
+        self.alias.hash(h);
+        (self.limit_num, self.offset_num).hash(h);
+        (self.select_distinct, self.with_recursive).hash(h);
+
+        let mut params = self.params.len();
+        self.with_items.len().hash(h);
+        for n in &self.with_items {
+            n.cte.hash(h);
+            params += n.params.len();
+        }
+        self.distinct_on_items.len().hash(h);
+        for n in &self.distinct_on_items {
+            n.expr.hash(h);
+            params += n.params.len();
+        }
+        self.select_items.len().hash(h);
+        for n in &self.select_items {
+            n.item.hash(h);
+            params += n.params.len();
+        }
+
+        self.from_items.len().hash(h);
+        for (i, item) in self.from_items.iter().enumerate() {
+            let joins = self.from_joins.get(i).map_or(&[][..], |j| &j[..]);
+            joins.len().hash(h);
+            for n in joins {
+                n.join.hash(h);
+                params += n.params.len();
+            }
+            match item {
+                FromItem::TableName(name) => {
+                    0u8.hash(h);
+                    name.hash(h);
+                }
+                FromItem::Subquery(qb) => {
+                    1u8.hash(h);
+                    params += qb.hash_shape(h)?;
+                }
+                FromItem::SubqueryClosure(_) => return None,
+            }
+        }
+
+        self.where_clause.as_ref().map(|n| &n.expr).hash(h);
+        params += self.where_clause.as_ref().map_or(0, |n| n.params.len());
+        self.group_by_items.len().hash(h);
+        for n in &self.group_by_items {
+            n.expr.hash(h);
+            params += n.params.len();
+        }
+        self.having_clause.as_ref().map(|n| &n.expr).hash(h);
+        params += self.having_clause.as_ref().map_or(0, |n| n.params.len());
+        self.windows.len().hash(h);
+        for n in &self.windows {
+            n.def.hash(h);
+            params += n.params.len();
+        }
+        self.set_ops.len().hash(h);
+        for n in &self.set_ops {
+            (&n.op, &n.quantifier, &n.right).hash(h);
+            params += n.params.len();
+        }
+        self.order_by_items.len().hash(h);
+        for n in &self.order_by_items {
+            n.expr.hash(h);
+            params += n.params.len();
+        }
+        Some(params)
+    }
+
+    /// Параметры в порядке плейсхолдеров — как их собрал бы `form_query_ast`
+    pub(crate) fn take_params(&mut self) -> Vec<Param> {
+        let mut params = mem::take(&mut self.params).into_vec();
+        // WITH дописывается к уже накопленным, см. `take_with_ast`
+        for n in self.with_items.drain(..) {
+            params.extend(n.params);
+        }
+        for n in self.distinct_on_items.drain(..) {
+            params.extend(n.params);
+        }
+        for n in self.select_items.drain(..) {
+            params.extend(n.params);
+        }
+        let from_items = mem::take(&mut self.from_items);
+        for (i, item) in from_items.into_iter().enumerate() {
+            if let Some(joins) = self.from_joins.get_mut(i) {
+                for n in joins.drain(..) {
+                    params.extend(n.params);
+                }
+            }
+            if let FromItem::Subquery(mut qb) = item {
+                params.extend(qb.take_params());
+            }
+        }
+        if let Some(n) = self.where_clause.take() {
+            params.extend(n.params);
+        }
+        for n in self.group_by_items.drain(..) {
+            params.extend(n.params);
+        }
+        if let Some(n) = self.having_clause.take() {
+            params.extend(n.params);
+        }
+        for n in self.windows.drain(..) {
+            params.extend(n.params);
+        }
+        for n in self.set_ops.drain(..) {
+            params.extend(n.params);
+        }
+        for n in self.order_by_items.drain(..) {
+            params.extend(n.params);
+        }
+        params
+    }
+}
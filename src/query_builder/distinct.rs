@@ -37,6 +37,13 @@ impl<'a, T> QueryBuilder<'a, T> {
         for it in list {
             match it {
                 QBArg::Expr(e) => {
+                    let e = match e.checked() {
+                        Ok(e) => e,
+                        Err(err) => {
+                            self.push_builder_error(format!("distinct(): {err}"));
+                            continue;
+                        }
+                    };
                     // кладём в SELECT как UnnamedExpr + локальные параметры узла
                     self.push_select_expr(e.expr, e.params);
                 }
@@ -46,7 +53,7 @@ impl<'a, T> QueryBuilder<'a, T> {
                     }
                 }
                 QBArg::Closure(c) => {
-                    let built = c.call(QueryBuilder::new_empty());
+                    let built = c.call(QueryBuilder::new_empty().dialect(self.dialect));
                     if let Ok((q, p)) = built.build_query_ast() {
                         self.push_select_subquery(q, p);
                     }
@@ -70,6 +77,13 @@ impl<'a, T> QueryBuilder<'a, T> {
         for it in list {
             match it {
                 QBArg::Expr(e) => {
+                    let e = match e.checked() {
+                        Ok(e) => e,
+                        Err(err) => {
+                            self.push_builder_error(format!("distinct_on(): {err}"));
+                            continue;
+                        }
+                    };
                     self.distinct_on_items.push(DistinctOnNode {
                         expr: e.expr,
                         params: e.params,
@@ -85,7 +99,7 @@ impl<'a, T> QueryBuilder<'a, T> {
                     }
                 }
                 QBArg::Closure(c) => {
-                    let built = c.call(QueryBuilder::new_empty());
+                    let built = c.call(QueryBuilder::new_empty().dialect(self.dialect));
                     if let Ok((q, p)) = built.build_query_ast() {
                         let expr = SqlExpr::Subquery(Box::new(q));
                         let mut sv = SmallVec::new();
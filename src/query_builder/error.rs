@@ -17,6 +17,10 @@ pub enum Error {
     #[error("Subquery requires a builder function (missing context)")]
     MissingSubqueryBuilder,
 
+    /// Курсор keyset-пагинации повреждён или не от этого запроса
+    #[error("Invalid keyset cursor: {0}")]
+    InvalidCursor(Cow<'static, str>),
+
     #[error(transparent)]
     SQLRenderError(#[from] renderer::Error),
 
@@ -1,9 +1,12 @@
+use std::time::Duration;
+
 use crate::{
     executor::{
-        DbPool, Error as ExecError, Result as ExecResult, transaction_utils as tx_exec,
-        utils as pool_exec,
+        DbPool, Error as ExecError, FromDbRow, Result as ExecResult, RowStream,
+        transaction_utils as tx_exec, utils as pool_exec,
     },
     param::Param,
+    renderer::Dialect,
 };
 
 #[allow(dead_code)]
@@ -23,6 +26,20 @@ pub enum ExecCtx<'e> {
 }
 
 impl<'e> ExecCtx<'e> {
+    /// Диалект соединения; `None` — контекст без соединения
+    pub fn dialect(&self) -> Option<Dialect> {
+        match self {
+            ExecCtx::None => None,
+            ExecCtx::Pool(pool) => Some(pool.dialect()),
+            #[cfg(feature = "postgres")]
+            ExecCtx::PgConn(_) => Some(Dialect::Postgres),
+            #[cfg(feature = "mysql")]
+            ExecCtx::MySqlConn(_) => Some(Dialect::MySQL),
+            #[cfg(feature = "sqlite")]
+            ExecCtx::SqliteConn(_) => Some(Dialect::SQLite),
+        }
+    }
+
     pub async fn execute(&mut self, sql: &str, params: Vec<Param>) -> ExecResult<u64> {
         match self {
             ExecCtx::None => Err(ExecError::MissingConnection),
@@ -49,19 +66,95 @@ impl<'e> ExecCtx<'e> {
         }
     }
 
+    /// SELECT с типизацией строк; контекст не потребляется, поэтому на одном
+    /// соединении транзакции можно выполнить несколько запросов подряд.
+    pub async fn fetch_all<T>(&mut self, sql: &str, params: Vec<Param>) -> ExecResult<Vec<T>>
+    where
+        T: FromDbRow,
+    {
+        match self {
+            ExecCtx::None => Err(ExecError::MissingConnection),
+
+            ExecCtx::Pool(pool) => match pool {
+                #[cfg(feature = "postgres")]
+                DbPool::Postgres(p) => pool_exec::fetch_typed_pg::<T>(&*p, sql, params).await,
+                #[cfg(feature = "mysql")]
+                DbPool::MySql(p) => pool_exec::fetch_typed_mysql::<T>(&*p, sql, params).await,
+                #[cfg(feature = "sqlite")]
+                DbPool::Sqlite(p) => pool_exec::fetch_typed_sqlite::<T>(&*p, sql, params).await,
+            },
+
+            #[cfg(feature = "postgres")]
+            ExecCtx::PgConn(conn) => {
+                tx_exec::fetch_typed_pg_exec::<_, T>(&mut **conn, sql, params).await
+            }
+            #[cfg(feature = "mysql")]
+            ExecCtx::MySqlConn(conn) => {
+                tx_exec::fetch_typed_mysql_exec::<_, T>(&mut **conn, sql, params).await
+            }
+            #[cfg(feature = "sqlite")]
+            ExecCtx::SqliteConn(conn) => {
+                tx_exec::fetch_typed_sqlite_exec::<_, T>(&mut **conn, sql, params).await
+            }
+        }
+    }
+
+    /// Построчное чтение результата: через пул или внутри транзакции
+    /// (соединение заимствуется на всё время жизни потока).
+    pub fn fetch_stream<T>(self, sql: String, params: Vec<Param>) -> RowStream<'e, T>
+    where
+        T: FromDbRow + 'e,
+    {
+        match self {
+            ExecCtx::None => pool_exec::error_stream(ExecError::MissingConnection),
+
+            ExecCtx::Pool(pool) => match pool {
+                #[cfg(feature = "postgres")]
+                DbPool::Postgres(p) => pool_exec::stream_typed_pg::<T>(p, sql, params),
+                #[cfg(feature = "mysql")]
+                DbPool::MySql(p) => pool_exec::stream_typed_mysql::<T>(p, sql, params),
+                #[cfg(feature = "sqlite")]
+                DbPool::Sqlite(p) => pool_exec::stream_typed_sqlite::<T>(p, sql, params),
+            },
+
+            #[cfg(feature = "postgres")]
+            ExecCtx::PgConn(conn) => tx_exec::stream_typed_pg_exec::<T>(conn, sql, params),
+            #[cfg(feature = "mysql")]
+            ExecCtx::MySqlConn(conn) => tx_exec::stream_typed_mysql_exec::<T>(conn, sql, params),
+            #[cfg(feature = "sqlite")]
+            ExecCtx::SqliteConn(conn) => tx_exec::stream_typed_sqlite_exec::<T>(conn, sql, params),
+        }
+    }
+
     pub fn select_send<T>(
         self,
         sql: String,
         params: Vec<crate::param::Param>,
     ) -> ExecResult<impl core::future::Future<Output = ExecResult<Vec<T>>> + Send + 'static>
     where
-        T: for<'r> sqlx::FromRow<'r, super::DbRow> + Send + Unpin + 'static,
+        T: FromDbRow + 'static,
+    {
+        self.select_send_within::<T>(sql, params, None)
+    }
+
+    pub(crate) fn select_send_within<T>(
+        self,
+        sql: String,
+        params: Vec<crate::param::Param>,
+        timeout: Option<Duration>,
+    ) -> ExecResult<impl core::future::Future<Output = ExecResult<Vec<T>>> + Send + 'static>
+    where
+        T: FromDbRow + 'static,
     {
         let pool = match self {
             ExecCtx::Pool(p) => p, // перемещаем пул
             _ => return Err(ExecError::MissingConnection),
         };
         Ok(async move {
+            if timeout.is_some() {
+                let mut ctx = ExecCtx::Pool(pool);
+                return ctx.fetch_all_timeout(&sql, params, timeout).await;
+            }
             match pool {
                 #[cfg(feature = "postgres")]
                 DbPool::Postgres(p) => pool_exec::fetch_typed_pg::<T>(&p, &sql, params).await,
@@ -79,13 +172,34 @@ impl<'e> ExecCtx<'e> {
         params: Vec<crate::param::Param>,
     ) -> ExecResult<impl core::future::Future<Output = ExecResult<T>> + Send + 'static>
     where
-        T: for<'r> sqlx::FromRow<'r, super::DbRow> + Send + Unpin + 'static,
+        T: FromDbRow + 'static,
+    {
+        self.select_one_send_within::<T>(sql, params, None)
+    }
+
+    pub(crate) fn select_one_send_within<T>(
+        self,
+        sql: String,
+        params: Vec<crate::param::Param>,
+        timeout: Option<Duration>,
+    ) -> ExecResult<impl core::future::Future<Output = ExecResult<T>> + Send + 'static>
+    where
+        T: FromDbRow + 'static,
     {
         let pool = match self {
             ExecCtx::Pool(p) => p,
             _ => return Err(ExecError::MissingConnection),
         };
         Ok(async move {
+            if timeout.is_some() {
+                let mut ctx = ExecCtx::Pool(pool);
+                return ctx
+                    .fetch_all_timeout::<T>(&sql, params, timeout)
+                    .await?
+                    .into_iter()
+                    .next()
+                    .ok_or(ExecError::NotFound);
+            }
             match pool {
                 #[cfg(feature = "postgres")]
                 DbPool::Postgres(p) => pool_exec::fetch_one_typed_pg::<T>(&p, &sql, params).await,
@@ -103,13 +217,32 @@ impl<'e> ExecCtx<'e> {
         params: Vec<crate::param::Param>,
     ) -> ExecResult<impl core::future::Future<Output = ExecResult<Option<T>>> + Send + 'static>
     where
-        T: for<'r> sqlx::FromRow<'r, super::DbRow> + Send + Unpin + 'static,
+        T: FromDbRow + 'static,
+    {
+        self.select_optional_send_within::<T>(sql, params, None)
+    }
+
+    pub(crate) fn select_optional_send_within<T>(
+        self,
+        sql: String,
+        params: Vec<crate::param::Param>,
+        timeout: Option<Duration>,
+    ) -> ExecResult<impl core::future::Future<Output = ExecResult<Option<T>>> + Send + 'static>
+    where
+        T: FromDbRow + 'static,
     {
         let pool = match self {
             ExecCtx::Pool(p) => p,
             _ => return Err(ExecError::MissingConnection),
         };
         Ok(async move {
+            if timeout.is_some() {
+                let mut ctx = ExecCtx::Pool(pool);
+                return ctx
+                    .fetch_all_timeout::<T>(&sql, params, timeout)
+                    .await
+                    .map(|rows| rows.into_iter().next());
+            }
             match pool {
                 #[cfg(feature = "postgres")]
                 DbPool::Postgres(p) => {
@@ -131,12 +264,25 @@ impl<'e> ExecCtx<'e> {
         self,
         sql: String,
         params: Vec<crate::param::Param>,
+    ) -> ExecResult<impl core::future::Future<Output = ExecResult<u64>> + Send + 'static> {
+        self.execute_send_within(sql, params, None)
+    }
+
+    pub(crate) fn execute_send_within(
+        self,
+        sql: String,
+        params: Vec<crate::param::Param>,
+        timeout: Option<Duration>,
     ) -> ExecResult<impl core::future::Future<Output = ExecResult<u64>> + Send + 'static> {
         let pool = match self {
             ExecCtx::Pool(p) => p,
             _ => return Err(ExecError::MissingConnection),
         };
         Ok(async move {
+            if timeout.is_some() {
+                let mut ctx = ExecCtx::Pool(pool);
+                return ctx.execute_timeout(&sql, params, timeout).await;
+            }
             match pool {
                 #[cfg(feature = "postgres")]
                 DbPool::Postgres(p) => pool_exec::execute_pg(&p, &sql, params).await,
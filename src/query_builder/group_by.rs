@@ -37,6 +37,13 @@ impl<'a, T> QueryBuilder<'a, T> {
         for it in items {
             match it {
                 QBArg::Expr(e) => {
+                    let e = match e.checked() {
+                        Ok(e) => e,
+                        Err(err) => {
+                            self.push_builder_error(format!("group_by(): {err}"));
+                            continue;
+                        }
+                    };
                     let expr: SqlExpr = e.expr;
                     let mut params: SmallVec<[Param; 8]> = e.params;
 
@@ -0,0 +1,118 @@
+use crate::expression::Expression;
+use crate::expression::helpers::{col, val};
+use crate::param::Param;
+use crate::query_builder::QueryBuilder;
+use crate::renderer::Dialect;
+
+type QB = QueryBuilder<'static, ()>;
+
+fn ids(n: i32) -> Vec<Expression> {
+    (1..=n).map(val).collect()
+}
+
+#[test]
+fn chunked_splits_rows_into_statements() {
+    let chunks = QB::new_empty()
+        .dialect(Dialect::Postgres)
+        .into("tags")
+        .columns((col("id"),))
+        .insert(ids(5))
+        .chunked(2)
+        .to_sql_chunks()
+        .expect("to_sql_chunks");
+
+    let sqls: Vec<&str> = chunks.iter().map(|(s, _)| s.as_str()).collect();
+    assert_eq!(
+        sqls,
+        vec![
+            r#"INSERT INTO "tags" ("id") VALUES ($1), ($2)"#,
+            r#"INSERT INTO "tags" ("id") VALUES ($1), ($2)"#,
+            r#"INSERT INTO "tags" ("id") VALUES ($1)"#,
+        ]
+    );
+    let params: Vec<i32> = chunks
+        .iter()
+        .flat_map(|(_, p)| p)
+        .map(|p| match p {
+            Param::I32(v) => *v,
+            other => panic!("unexpected param: {other:?}"),
+        })
+        .collect();
+    assert_eq!(params, vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn chunked_repeats_returning_and_merge_in_every_chunk() {
+    let chunks = QB::new_empty()
+        .dialect(Dialect::Postgres)
+        .into("counters")
+        .columns((col("id"), col("hits")))
+        .insert((val(1_i32), val(0_i32), val(2_i32), val(0_i32)))
+        .on_conflict((col("id"),))
+        .merge((col("hits"), val(10_i32)))
+        .returning((col("id"),))
+        .chunked(1)
+        .to_sql_chunks()
+        .expect("to_sql_chunks");
+
+    assert_eq!(chunks.len(), 2);
+    for (sql, params) in &chunks {
+        assert!(sql.contains("ON CONFLICT"), "{sql}");
+        assert!(sql.ends_with(r#"RETURNING "id""#), "{sql}");
+        assert_eq!(params.len(), 3);
+    }
+}
+
+#[test]
+fn default_chunk_size_follows_dialect_bind_limit() {
+    // SQLite: 32766 плейсхолдеров / 2 колонки = 16383 строк в пачке
+    let values: Vec<_> = (0..20_000).flat_map(|i| [val(i), val(i)]).collect();
+    let chunks = QB::new_empty()
+        .dialect(Dialect::SQLite)
+        .into("pairs")
+        .columns((col("a"), col("b")))
+        .insert(values)
+        .to_sql_chunks()
+        .expect("to_sql_chunks");
+
+    let sizes: Vec<usize> = chunks.iter().map(|(_, p)| p.len()).collect();
+    assert_eq!(sizes, vec![32_766, 7_234]);
+}
+
+#[test]
+fn small_insert_stays_single_statement() {
+    let chunks = QB::new_empty()
+        .into("tags")
+        .columns((col("id"),))
+        .insert(ids(3))
+        .to_sql_chunks()
+        .expect("to_sql_chunks");
+    assert_eq!(chunks.len(), 1);
+}
+
+#[test]
+fn chunked_zero_is_error() {
+    let res = QB::new_empty()
+        .into("tags")
+        .columns((col("id"),))
+        .insert(ids(3))
+        .chunked(0)
+        .to_sql_chunks();
+    assert!(res.is_err());
+}
+
+#[test]
+fn default_chunk_size_counts_params_of_row_expressions() {
+    // у каждой строки одна колонка, но два параметра: 32766 / 2 = 16383 строк
+    let values: Vec<_> = (0..20_000).map(|i| val(i).add(val(1))).collect();
+    let chunks = QB::new_empty()
+        .dialect(Dialect::SQLite)
+        .into("sums")
+        .columns((col("a"),))
+        .insert(values)
+        .to_sql_chunks()
+        .expect("to_sql_chunks");
+
+    let sizes: Vec<usize> = chunks.iter().map(|(_, p)| p.len()).collect();
+    assert_eq!(sizes, vec![32_766, 7_234]);
+}
@@ -0,0 +1,161 @@
+use crate::expression::helpers::{col, val};
+use crate::param::Param;
+use crate::query_builder::QueryBuilder;
+use crate::renderer::Dialect;
+use crate::type_helpers::QBClosureHelper;
+
+type QB = QueryBuilder<'static, ()>;
+
+fn ints(params: &[Param]) -> Vec<i32> {
+    params
+        .iter()
+        .map(|p| match p {
+            Param::I32(v) => *v,
+            other => panic!("unexpected param: {other:?}"),
+        })
+        .collect()
+}
+
+fn live_rows() -> QB {
+    QB::new_empty()
+        .from("live")
+        .select((col("id"), col("name")))
+        .r#where(col("age").gt(val(30_i32)))
+}
+
+#[test]
+fn insert_from_subquery_per_dialect() {
+    let ins = |d: Dialect| {
+        QB::new_empty()
+            .dialect(d)
+            .into("archive")
+            .columns((col("id"), col("name")))
+            .insert_from(live_rows())
+            .to_sql()
+            .expect("to_sql")
+    };
+
+    let (sql, params) = ins(Dialect::Postgres);
+    assert_eq!(
+        sql,
+        r#"INSERT INTO "archive" ("id", "name") SELECT "id", "name" FROM "live" WHERE "age" > $1"#
+    );
+    assert_eq!(ints(&params), vec![30]);
+
+    let (sql, _) = ins(Dialect::MySQL);
+    assert_eq!(
+        sql,
+        "INSERT INTO `archive` (`id`, `name`) SELECT `id`, `name` FROM `live` WHERE `age` > ?"
+    );
+}
+
+#[test]
+fn insert_from_closure_without_columns() {
+    let (sql, params) = QB::new_empty()
+        .dialect(Dialect::SQLite)
+        .into("archive")
+        .insert_from::<QBClosureHelper<()>>(|qb| qb.from("live").select("*"))
+        .to_sql()
+        .expect("to_sql");
+
+    assert_eq!(sql, r#"INSERT INTO "archive" SELECT * FROM "live""#);
+    assert!(params.is_empty());
+}
+
+#[test]
+fn insert_from_with_upsert_keeps_param_order() {
+    // параметры подзапроса идут раньше параметров DO UPDATE SET
+    let (sql, params) = QB::new_empty()
+        .dialect(Dialect::Postgres)
+        .into("archive")
+        .columns((col("id"), col("name")))
+        .insert_from(live_rows())
+        .on_conflict((col("id"),))
+        .merge((col("name"), val(7_i32)))
+        .returning((col("id"),))
+        .to_sql()
+        .expect("to_sql");
+
+    assert_eq!(
+        sql,
+        r#"INSERT INTO "archive" ("id", "name") SELECT "id", "name" FROM "live" WHERE "age" > $1 ON CONFLICT ("id") DO UPDATE SET "name" = $2 RETURNING "id""#
+    );
+    assert_eq!(ints(&params), vec![30, 7]);
+}
+
+#[test]
+fn insert_from_ignore_per_dialect() {
+    let ins = |d: Dialect| {
+        QB::new_empty()
+            .dialect(d)
+            .into("archive")
+            .insert_from::<QBClosureHelper<()>>(|qb| qb.from("live").select("*"))
+            .ignore()
+            .to_sql()
+            .expect("to_sql")
+            .0
+    };
+
+    assert_eq!(
+        ins(Dialect::Postgres),
+        r#"INSERT INTO "archive" SELECT * FROM "live" ON CONFLICT DO NOTHING"#
+    );
+    assert_eq!(
+        ins(Dialect::MySQL),
+        "INSERT IGNORE INTO `archive` SELECT * FROM `live`"
+    );
+    assert_eq!(
+        ins(Dialect::SQLite),
+        r#"INSERT OR IGNORE INTO "archive" SELECT * FROM "live""#
+    );
+}
+
+#[test]
+fn sqlite_upsert_from_select_gets_where_true() {
+    // без WHERE SQLite прочитал бы ON CONFLICT как ON у JOIN
+    let (sql, _) = QB::new_empty()
+        .dialect(Dialect::SQLite)
+        .into("archive")
+        .columns((col("id"), col("name")))
+        .insert_from::<QBClosureHelper<()>>(|qb| qb.from("live").select(("id", "name")))
+        .on_conflict((col("id"),))
+        .merge_all()
+        .to_sql()
+        .expect("to_sql");
+
+    assert_eq!(
+        sql,
+        r#"INSERT INTO "archive" ("id", "name") SELECT "id", "name" FROM "live" WHERE true ON CONFLICT ("id") DO UPDATE SET "id" = EXCLUDED."id", "name" = EXCLUDED."name""#
+    );
+}
+
+#[test]
+fn mysql_upsert_from_select_uses_values_fn() {
+    let (sql, _) = QB::new_empty()
+        .dialect(Dialect::MySQL)
+        .into("archive")
+        .columns((col("id"), col("name")))
+        .insert_from(live_rows())
+        .merge_all()
+        .to_sql()
+        .expect("to_sql");
+
+    assert_eq!(
+        sql,
+        "INSERT INTO `archive` (`id`, `name`) SELECT `id`, `name` FROM `live` WHERE `age` > ? ON DUPLICATE KEY UPDATE `id` = VALUES(`id`), `name` = VALUES(`name`)"
+    );
+}
+
+#[test]
+fn insert_from_conflicts_with_values_rows() {
+    let err = QB::new_empty()
+        .into("archive")
+        .columns((col("id"),))
+        .insert((val(1_i32),))
+        .insert_from(live_rows())
+        .to_sql();
+    assert!(err.is_err());
+
+    let ins = QB::new_empty().into("archive").insert_from((val(1_i32),));
+    assert_eq!(ins.builder_errors.len(), 1);
+}
@@ -3,3 +3,9 @@ mod insert;
 
 #[cfg(test)]
 mod insert_ext;
+
+#[cfg(test)]
+mod insert_select;
+
+#[cfg(test)]
+mod chunk;
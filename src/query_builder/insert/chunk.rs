@@ -0,0 +1,149 @@
+use std::mem;
+use std::time::Duration;
+
+use smallvec::SmallVec;
+
+use super::core_fn::InsertBuilder;
+use crate::executor::{FromDbRow, QueryHooks, QueryInfo, Result as ExecResult, trace};
+use crate::param::Param;
+use crate::query_builder::{ExecCtx, Result};
+use crate::renderer::Dialect;
+
+/// Предел плейсхолдеров в одном запросе
+#[inline]
+fn bind_limit(dialect: Dialect) -> usize {
+    match dialect {
+        Dialect::Postgres => 65_535, // u16 в протоколе
+        Dialect::MySQL => 65_535,    // prepared statement
+        Dialect::SQLite => 32_766,   // SQLITE_MAX_VARIABLE_NUMBER с 3.32
+    }
+}
+
+impl<'a, T> InsertBuilder<'a, T> {
+    /// Вставлять VALUES пачками по `rows` строк: отдельный INSERT на пачку,
+    /// все — в одной транзакции. Без вызова размер считается по лимиту
+    /// плейсхолдеров диалекта и самой «тяжёлой» по параметрам строке.
+    pub fn chunked(mut self, rows: usize) -> Self {
+        if rows == 0 {
+            self.push_builder_error("chunked(): chunk size must be greater than 0");
+        } else {
+            self.chunk_rows = Some(rows);
+        }
+        self
+    }
+
+    /// SQL по пачкам — то, что выполнят `exec()`/`.await`
+    pub fn to_sql_chunks(mut self) -> Result<Vec<(String, Vec<Param>)>> {
+        self.render_chunks()
+    }
+
+    /// Строк в пачке. Параметры `merge(...)` повторяются в каждом запросе,
+    /// поэтому вычитаются из лимита; строка может связывать больше
+    /// параметров, чем у неё колонок, поэтому берётся максимум по строкам.
+    fn rows_per_chunk(&self) -> usize {
+        if let Some(n) = self.chunk_rows {
+            return n;
+        }
+        let per_row = self.rows.iter().map(|r| r.params.len()).max().unwrap_or(0);
+        (bind_limit(self.dialect).saturating_sub(self.params.len()) / per_row.max(1)).max(1)
+    }
+
+    pub(crate) fn render_chunks(&mut self) -> Result<Vec<(String, Vec<Param>)>> {
+        let per_chunk = self.rows_per_chunk();
+        if self.rows.len() <= per_chunk {
+            return Ok(vec![self.render_sql()?]);
+        }
+
+        // рендер забирает колонки/RETURNING/параметры merge — восстанавливаем на каждую пачку
+        let columns = self.columns.clone();
+        let returning = self.returning.clone();
+        let params = self.params.clone();
+
+        let mut rows = mem::take(&mut self.rows).into_vec();
+        let mut out = Vec::with_capacity(rows.len().div_ceil(per_chunk));
+        while !rows.is_empty() {
+            let rest = rows.split_off(per_chunk.min(rows.len()));
+            self.rows = SmallVec::from_vec(mem::replace(&mut rows, rest));
+            self.columns = columns.clone();
+            self.returning = returning.clone();
+            self.params = params.clone();
+            out.push(self.render_sql()?);
+        }
+        Ok(out)
+    }
+}
+
+/// Выполнить пачки по очереди. Внутри транзакции — на её соединении;
+/// на пуле несколько пачек оборачиваются в свою транзакцию.
+/// Таймаут действует на каждую пачку отдельно.
+pub(crate) async fn execute_chunks(
+    ctx: &mut ExecCtx<'_>,
+    hooks: &QueryHooks,
+    dialect: Dialect,
+    timeout: Option<Duration>,
+    chunks: Vec<(String, Vec<Param>)>,
+) -> ExecResult<u64> {
+    if let (ExecCtx::Pool(pool), true) = (&*ctx, chunks.len() > 1) {
+        let mut tx = pool.begin_tx().await?;
+        let total = execute_all(&mut tx.exec_ctx(), hooks, dialect, timeout, chunks).await?;
+        tx.commit().await?;
+        return Ok(total);
+    }
+    execute_all(ctx, hooks, dialect, timeout, chunks).await
+}
+
+/// То же для `RETURNING`: строки всех пачек склеиваются по порядку
+pub(crate) async fn fetch_chunks<R>(
+    ctx: &mut ExecCtx<'_>,
+    hooks: &QueryHooks,
+    dialect: Dialect,
+    timeout: Option<Duration>,
+    chunks: Vec<(String, Vec<Param>)>,
+) -> ExecResult<Vec<R>>
+where
+    R: FromDbRow,
+{
+    if let (ExecCtx::Pool(pool), true) = (&*ctx, chunks.len() > 1) {
+        let mut tx = pool.begin_tx().await?;
+        let rows = fetch_all(&mut tx.exec_ctx(), hooks, dialect, timeout, chunks).await?;
+        tx.commit().await?;
+        return Ok(rows);
+    }
+    fetch_all(ctx, hooks, dialect, timeout, chunks).await
+}
+
+/// Хуки видят каждую пачку отдельным запросом
+async fn execute_all(
+    ctx: &mut ExecCtx<'_>,
+    hooks: &QueryHooks,
+    dialect: Dialect,
+    timeout: Option<Duration>,
+    chunks: Vec<(String, Vec<Param>)>,
+) -> ExecResult<u64> {
+    let mut total = 0;
+    for (sql, params) in chunks {
+        let query = QueryInfo::new(&sql, params.len(), dialect);
+        let fut = ctx.execute_timeout(&sql, params, timeout);
+        total += hooks.observe(query, trace::rows_affected, fut).await?;
+    }
+    Ok(total)
+}
+
+async fn fetch_all<R>(
+    ctx: &mut ExecCtx<'_>,
+    hooks: &QueryHooks,
+    dialect: Dialect,
+    timeout: Option<Duration>,
+    chunks: Vec<(String, Vec<Param>)>,
+) -> ExecResult<Vec<R>>
+where
+    R: FromDbRow,
+{
+    let mut out = Vec::new();
+    for (sql, params) in chunks {
+        let query = QueryInfo::new(&sql, params.len(), dialect);
+        let fut = ctx.fetch_all_timeout::<R>(&sql, params, timeout);
+        out.extend(hooks.observe(query, trace::rows_vec, fut).await?);
+    }
+    Ok(out)
+}
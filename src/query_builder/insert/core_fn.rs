@@ -1,7 +1,10 @@
 use std::marker::PhantomData;
 use std::pin::Pin;
+use std::time::Duration;
 
-use crate::executor::{DbPool, DbRow, Error as ExecError, Result as ExecResult};
+use crate::executor::{
+    DbPool, Error as ExecError, FromDbRow, QueryHooks, QueryInfo, Result as ExecResult, trace,
+};
 use crate::optimizer::OptimizeConfig;
 use crate::param::Param;
 use crate::query_builder::ExecCtx;
@@ -15,21 +18,18 @@ use crate::utils::expr_to_object_name;
 use smallvec::SmallVec;
 use sqlparser::ast::{Expr as SqlExpr, Ident, ObjectName, SelectItem};
 
-#[cfg(feature = "mysql")]
-use crate::executor::utils::fetch_typed_mysql;
 #[cfg(feature = "postgres")]
 use crate::executor::utils::fetch_typed_pg;
 #[cfg(feature = "sqlite")]
 use crate::executor::utils::fetch_typed_sqlite;
 
-#[cfg(feature = "mysql")]
-use crate::executor::transaction_utils::fetch_typed_mysql_exec;
 #[cfg(feature = "postgres")]
 use crate::executor::transaction_utils::fetch_typed_pg_exec;
 #[cfg(feature = "sqlite")]
 use crate::executor::transaction_utils::fetch_typed_sqlite_exec;
 
-use super::utils::{ConflictSpec, InsertRowNode};
+use super::chunk::{execute_chunks, fetch_chunks};
+use super::utils::{ConflictSpec, InsertRowNode, InsertSelectNode};
 
 /// Билдер INSERT INTO ... VALUES ...
 #[derive(Debug, Clone)]
@@ -37,10 +37,12 @@ pub struct InsertBuilder<'a, T = ()> {
     pub(crate) table: Option<ObjectName>,
     pub(crate) columns: SmallVec<[Ident; 8]>,
     pub(crate) rows: SmallVec<[InsertRowNode; 1]>,
+    pub(crate) select_source: Option<InsertSelectNode>,
     pub(crate) params: SmallVec<[Param; 8]>,
     pub(crate) returning: SmallVec<[SelectItem; 4]>,
     pub(crate) on_conflict: Option<ConflictSpec>,
     pub(crate) insert_ignore: bool, // для MySQL/SQLite/PG-DO-NOTHING
+    pub(crate) chunk_rows: Option<usize>, // None — размер пачки по лимиту диалекта
 
     // ошибки сбора (аналогично QueryBuilder)
     pub(crate) builder_errors: SmallVec<[std::borrow::Cow<'static, str>; 2]>,
@@ -50,6 +52,8 @@ pub struct InsertBuilder<'a, T = ()> {
     pub(crate) dialect: Dialect,
     pub(crate) exec_ctx: ExecCtx<'a>,
     pub(crate) optimize_cfg: OptimizeConfig,
+    pub(crate) hooks: QueryHooks,
+    pub(crate) timeout: Option<Duration>,
     _t: PhantomData<T>,
 }
 
@@ -60,6 +64,7 @@ impl<'a, T> InsertBuilder<'a, T> {
             table: None,
             columns: SmallVec::new(),
             rows: SmallVec::new(),
+            select_source: None,
             params: qb.params, // переносим накопленные параметры (если были)
             builder_errors: SmallVec::new(),
             default_schema: qb.default_schema,
@@ -67,8 +72,11 @@ impl<'a, T> InsertBuilder<'a, T> {
             returning: SmallVec::new(),
             on_conflict: None,
             insert_ignore: false,
+            chunk_rows: None,
             exec_ctx: qb.exec_ctx,
             optimize_cfg: qb.optimize_cfg,
+            hooks: qb.hooks,
+            timeout: qb.timeout,
             _t: PhantomData,
         }
     }
@@ -161,7 +169,7 @@ impl<'a, T> InsertBuilder<'a, T> {
         let mut take = 0usize;
 
         for it in items {
-            match it.resolve_into_expr_with(|qb| qb.build_query_ast()) {
+            match it.resolve_into_expr_in(self.dialect, |qb| qb.build_query_ast()) {
                 Ok((expr, p)) => {
                     cur_vals.push(expr);
                     cur_params.extend(p);
@@ -185,25 +193,84 @@ impl<'a, T> InsertBuilder<'a, T> {
         self
     }
 
+    /// Данные из подзапроса: `INSERT INTO t (cols) SELECT ...`.
+    /// Принимает `QueryBuilder` или `|qb| qb.from(...).select(...)`; с `insert()` не сочетается.
+    pub fn insert_from<L>(mut self, source: L) -> Self
+    where
+        L: ArgList<'a>,
+    {
+        let mut args = source.into_vec();
+        if args.len() != 1 {
+            self.push_builder_error(format!(
+                "insert_from(): expected 1 argument (subquery), got {}",
+                args.len()
+            ));
+            return self;
+        }
+
+        let built = match args.remove(0) {
+            QBArg::Subquery(qb) => qb.build_query_ast(),
+            QBArg::Closure(c) => c
+                .call(QueryBuilder::new_empty().dialect(self.dialect))
+                .build_query_ast(),
+            QBArg::Expr(_) => {
+                self.push_builder_error(
+                    "insert_from(): expression is not allowed here; pass a subquery or closure",
+                );
+                return self;
+            }
+        };
+        match built {
+            Ok((query, params)) => {
+                self.select_source = Some(InsertSelectNode { query, params });
+            }
+            Err(e) => self.push_builder_error(format!("insert_from(): {e}")),
+        }
+        self
+    }
+
     /// Выполнить INSERT **без** `RETURNING`. Возвращает `rows_affected`.
+    /// Много строк разбиваются на пачки (см. `chunked`), результаты суммируются.
     pub async fn exec(mut self) -> ExecResult<u64> {
-        let (sql, params) = self.render_sql().map_err(ExecError::from)?;
-        self.exec_ctx.execute(&sql, params).await
+        let chunks = self.render_chunks().map_err(ExecError::from)?;
+        execute_chunks(
+            &mut self.exec_ctx,
+            &self.hooks,
+            self.dialect,
+            self.timeout,
+            chunks,
+        )
+        .await
     }
 
     pub fn exec_send(
         mut self,
     ) -> ExecResult<impl core::future::Future<Output = ExecResult<u64>> + Send + 'static> {
-        let (sql, params) = self.render_sql().map_err(ExecError::from)?;
-        let ctx = self.exec_ctx.clone();
-        ctx.execute_send(sql, params)
+        let mut chunks = self.render_chunks().map_err(ExecError::from)?;
+        if chunks.len() == 1 {
+            let (sql, params) = chunks.pop().expect("one chunk");
+            let query = QueryInfo::new(&sql, params.len(), self.dialect);
+            let fut =
+                self.exec_ctx
+                    .clone()
+                    .execute_send_within(sql.clone(), params, self.timeout)?;
+            let fut = self.hooks.observe_send(query, trace::rows_affected, fut);
+            return Ok(futures_util::future::Either::Left(fut));
+        }
+        let ExecCtx::Pool(pool) = self.exec_ctx.clone() else {
+            return Err(ExecError::MissingConnection);
+        };
+        let (hooks, dialect, timeout) = (self.hooks.clone(), self.dialect, self.timeout);
+        Ok(futures_util::future::Either::Right(async move {
+            execute_chunks(&mut ExecCtx::Pool(pool), &hooks, dialect, timeout, chunks).await
+        }))
     }
 
     // pub fn into_send<R>(
     //     mut self,
     // ) -> ExecResult<impl core::future::Future<Output = ExecResult<Vec<R>>> + Send + 'static>
     // where
-    //     R: for<'r> sqlx::FromRow<'r, DbRow> + Send + Unpin + 'static,
+    //     R: FromDbRow + 'static,
     // {
     //     if self.returning.is_empty() {
     //         return Err(ExecError::Unsupported(
@@ -263,7 +330,7 @@ impl<'a, T> InsertBuilder<'a, T> {
             }
 
             // значение — любое выражение/подзапрос
-            match val_arg.resolve_into_expr_with(|qb| qb.build_query_ast()) {
+            match val_arg.resolve_into_expr_in(self.dialect, |qb| qb.build_query_ast()) {
                 Ok((expr, p)) => {
                     values.push(expr);
                     params.extend(p);
@@ -318,7 +385,7 @@ impl<'a, T> QueryBuilder<'a, T> {
 
 impl<'a, T> std::future::IntoFuture for InsertBuilder<'a, T>
 where
-    T: for<'r> sqlx::FromRow<'r, DbRow> + Send + Unpin + 'a,
+    T: FromDbRow + 'a,
 {
     type Output = ExecResult<Vec<T>>;
     type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'a>>;
@@ -331,19 +398,57 @@ where
                     "INSERT без RETURNING: используйте .exec(). Для чтения результатов добавьте .returning(...).".into()
                 ));
             }
-            let (sql, params) = self.render_sql().map_err(ExecError::from)?;
-
-            match self.exec_ctx {
-                ExecCtx::None => Err(ExecError::MissingConnection),
-
-                // ---- исполнение через пул ----
-                ExecCtx::Pool(pool) => match pool {
+            let mut chunks = self.render_chunks().map_err(ExecError::from)?;
+            if chunks.len() > 1 {
+                if self.dialect == Dialect::MySQL {
+                    return Err(ExecError::Unsupported(
+                        "MySQL не поддерживает INSERT ... RETURNING; выполните .exec() и, при необходимости, отдельный SELECT."
+                            .into(),
+                    ));
+                }
+                return fetch_chunks::<T>(
+                    &mut self.exec_ctx,
+                    &self.hooks,
+                    self.dialect,
+                    self.timeout,
+                    chunks,
+                )
+                .await;
+            }
+            let (sql, params) = chunks.pop().expect("one chunk");
+
+            let query = QueryInfo::new(&sql, params.len(), self.dialect);
+            let dialect = self.dialect;
+            let (mut ctx, hooks, timeout) = (self.exec_ctx, self.hooks, self.timeout);
+            let fut = async {
+                // MySQL RETURNING отклоняется ниже, не доходя до сервера
+                if timeout.is_some() && dialect != Dialect::MySQL {
+                    return ctx.fetch_all_timeout::<T>(&sql, params, timeout).await;
+                }
+                match ctx {
+                    ExecCtx::None => Err(ExecError::MissingConnection),
+
+                    // ---- исполнение через пул ----
+                    ExecCtx::Pool(pool) => match pool {
+                        #[cfg(feature = "postgres")]
+                        DbPool::Postgres(p) => fetch_typed_pg::<T>(&p, &sql, params).await,
+                        #[cfg(feature = "mysql")]
+                        DbPool::MySql(_) => {
+                            // В MySQL UPDATE … RETURNING нет — отдаём понятную ошибку
+                            Err(ExecError::Unsupported(
+                                "MySQL не поддерживает INSERT ... RETURNING; выполните .exec() и, при необходимости, отдельный SELECT."
+                                    .into(),
+                            ))
+                        }
+                        #[cfg(feature = "sqlite")]
+                        DbPool::Sqlite(p) => fetch_typed_sqlite::<T>(&p, &sql, params).await,
+                    },
+
+                    // ---- исполнение ВНУТРИ транзакции ----
                     #[cfg(feature = "postgres")]
-                    DbPool::Postgres(p) => fetch_typed_pg::<T>(&p, &sql, params)
-                        .await
-                        .map_err(Into::into),
+                    ExecCtx::PgConn(conn) => fetch_typed_pg_exec::<_, T>(conn, &sql, params).await,
                     #[cfg(feature = "mysql")]
-                    DbPool::MySql(p) => {
+                    ExecCtx::MySqlConn(_) => {
                         // В MySQL UPDATE … RETURNING нет — отдаём понятную ошибку
                         Err(ExecError::Unsupported(
                             "MySQL не поддерживает INSERT ... RETURNING; выполните .exec() и, при необходимости, отдельный SELECT."
@@ -351,27 +456,12 @@ where
                         ))
                     }
                     #[cfg(feature = "sqlite")]
-                    DbPool::Sqlite(p) => fetch_typed_sqlite::<T>(&p, &sql, params)
-                        .await
-                        .map_err(Into::into),
-                },
-
-                // ---- исполнение ВНУТРИ транзакции ----
-                #[cfg(feature = "postgres")]
-                ExecCtx::PgConn(conn) => fetch_typed_pg_exec::<_, T>(conn, &sql, params).await,
-                #[cfg(feature = "mysql")]
-                ExecCtx::MySqlConn(conn) => {
-                    // В MySQL UPDATE … RETURNING нет — отдаём понятную ошибку
-                    Err(ExecError::Unsupported(
-                        "MySQL не поддерживает INSERT ... RETURNING; выполните .exec() и, при необходимости, отдельный SELECT."
-                            .into(),
-                    ))
-                }
-                #[cfg(feature = "sqlite")]
-                ExecCtx::SqliteConn(conn) => {
-                    fetch_typed_sqlite_exec::<_, T>(conn, &sql, params).await
+                    ExecCtx::SqliteConn(conn) => {
+                        fetch_typed_sqlite_exec::<_, T>(conn, &sql, params).await
+                    }
                 }
-            }
+            };
+            hooks.observe(query, trace::rows_vec, fut).await
         })
     }
 }
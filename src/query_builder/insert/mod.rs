@@ -1,4 +1,5 @@
 mod __tests__;
+mod chunk;
 mod core_fn;
 mod merge;
 mod on_conflict;
@@ -1,6 +1,6 @@
 use crate::param::Param;
 use smallvec::SmallVec;
-use sqlparser::ast::{Expr as SqlExpr, Ident};
+use sqlparser::ast::{Expr as SqlExpr, Ident, Query};
 
 /// Одна строка для VALUES(...)
 #[derive(Debug, Clone)]
@@ -16,6 +16,13 @@ impl InsertRowNode {
     }
 }
 
+/// Источник `INSERT ... SELECT`
+#[derive(Debug, Clone)]
+pub(crate) struct InsertSelectNode {
+    pub query: Query,
+    pub params: Vec<Param>,
+}
+
 #[derive(Debug, Clone)]
 pub enum MergeValue {
     Expr(SqlExpr),       // обычное выражение справа
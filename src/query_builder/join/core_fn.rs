@@ -239,7 +239,7 @@ impl<'a, K> QueryBuilder<'a, K> {
 
             QBArg::Closure(c) => {
                 // Выполняем замыкание на пустом билдере, берём его alias
-                let built = c.call(QueryBuilder::new_empty());
+                let built = c.call(QueryBuilder::new_empty().dialect(self.dialect));
                 let alias = built.alias.clone();
                 let (q, params) = built.build_query_ast()?;
                 Ok((
@@ -265,6 +265,7 @@ impl<'a, K> QueryBuilder<'a, K> {
         match arg {
             JoinOnArg::None => Ok((None, SmallVec::new())),
             JoinOnArg::Expr(e) => {
+                let e = e.checked()?;
                 let params = clone_params(&e);
                 Ok((Some(JoinConstraint::On(e.expr)), params))
             }
@@ -311,6 +312,7 @@ impl<'a, K> QueryBuilder<'a, K> {
                 let chain = f(JoinOnBuilder::default());
                 match chain.build() {
                     Some(expr) => {
+                        let expr = expr.checked()?;
                         let params = clone_params(&expr);
                         let ast = expr.expr;
                         Ok((Some(JoinConstraint::On(ast)), params))
@@ -0,0 +1,628 @@
+//! Keyset-пагинация (`WHERE (ключи) > (курсор) ORDER BY ключи LIMIT n`).
+//!
+//! Ключи берутся из `order_by_items`, значения последней строки страницы
+//! дочитываются служебными колонками `__knux_k{i}` и упаковываются в
+//! непрозрачный [`KeysetCursor`].
+
+use std::{fmt, future::Future, pin::Pin, str::FromStr};
+
+use smallvec::SmallVec;
+use sqlparser::ast::{BinaryOperator as BO, Expr as SqlExpr, Ident, SelectItem, Value};
+use sqlx::{Column, Row, ValueRef};
+
+use crate::{
+    executor::{FromDbRow, Result as ExecResult},
+    param::Param,
+    query_builder::{Error, QueryBuilder, Result, order_by::OrderByNode, select::SelectItemNode},
+    renderer::Dialect,
+};
+
+/// Префикс служебных колонок с ключами курсора.
+const KEY_PREFIX: &str = "__knux_k";
+/// Версия формата курсора (первые байты до hex-кодирования).
+const CURSOR_VERSION: &str = "k1";
+
+/// Непрозрачный курсор: значения ключей ORDER BY последней строки страницы.
+///
+/// Передаётся клиенту строкой (`to_string()` / `parse()`); формат внутренний.
+#[derive(Debug, Clone)]
+pub struct KeysetCursor(Vec<Param>);
+
+/// Страница keyset-пагинации.
+#[derive(Debug, Clone)]
+pub struct KeysetPage<T> {
+    pub items: Vec<T>,
+    /// `None` — это последняя страница.
+    pub next_cursor: Option<KeysetCursor>,
+}
+
+/// Отложенный запрос страницы (см. [`QueryBuilder::paginate_after`]).
+pub struct KeysetQuery<'a, T> {
+    inner: QueryBuilder<'a, KeysetRow<T>>,
+    per_page: usize,
+}
+
+/// Строка результата + значения ключей из служебных колонок.
+pub struct KeysetRow<T> {
+    item: T,
+    keys: Vec<Param>,
+}
+
+impl<'a, T> QueryBuilder<'a, T> {
+    /// Keyset (cursor) пагинация по текущему ORDER BY.
+    ///
+    /// Размер страницы — `limit(n)`; `cursor = None` — первая страница.
+    /// Для каждого ключа учитывается направление (`order_by` / `order_by_desc`)
+    /// и положение NULL, принятое в диалекте по умолчанию (PG: NULL «больше»
+    /// любого значения, MySQL/SQLite — «меньше»). Когда это безопасно,
+    /// предикат пишется row-value сравнением `(a, b) > (?, ?)`, иначе —
+    /// раскрытым `a > ? OR (a = ? AND b > ?)`. Предикат добавляется к WHERE
+    /// через AND, поэтому JOIN-ы и уже заданные условия сохраняются.
+    ///
+    /// Ключи должны быть выражениями, допустимыми в WHERE (не алиасы SELECT и
+    /// не агрегаты), а их набор — однозначно упорядочивать строки (добавьте
+    /// первичный ключ последним).
+    pub fn paginate_after(mut self, cursor: Option<KeysetCursor>) -> KeysetQuery<'a, T> {
+        if !self.set_ops.is_empty() {
+            self.push_builder_error(
+                "paginate_after(): UNION/INTERSECT/EXCEPT не поддерживаются, оберните запрос в подзапрос",
+            );
+        }
+        if self.order_by_items.is_empty() {
+            self.push_builder_error("paginate_after(): требуется хотя бы один ключ ORDER BY");
+        }
+        if self.order_by_items.iter().any(|n| !n.params.is_empty()) {
+            self.push_builder_error(
+                "paginate_after(): ключи ORDER BY не должны содержать параметров",
+            );
+        }
+        if self.offset_num.is_some() {
+            self.push_builder_error("paginate_after(): OFFSET несовместим с keyset-пагинацией");
+        }
+        let per_page = match self.limit_num {
+            Some(n) if n > 0 => n,
+            _ => {
+                self.push_builder_error("paginate_after(): задайте размер страницы через limit(n)");
+                0
+            }
+        };
+
+        if let Some(KeysetCursor(values)) = cursor {
+            if values.len() != self.order_by_items.len() {
+                self.push_builder_error(format!(
+                    "paginate_after(): курсор содержит {} ключей, а ORDER BY — {}",
+                    values.len(),
+                    self.order_by_items.len()
+                ));
+            } else if !self.order_by_items.is_empty() {
+                let (pred, params) = keyset_predicate(&self.order_by_items, values, self.dialect);
+                self.attach_where_with_and(pred, params);
+            }
+        }
+
+        // лишняя строка — признак следующей страницы
+        self.limit_num = Some(per_page + 1);
+
+        if self.select_items.is_empty() {
+            self.select_items.push(SelectItemNode::new(
+                SelectItem::Wildcard(Default::default()),
+                SmallVec::new(),
+            ));
+        }
+        for i in 0..self.order_by_items.len() {
+            let expr = self.order_by_items[i].expr.expr.clone();
+            self.select_items.push(SelectItemNode::new(
+                SelectItem::ExprWithAlias {
+                    expr,
+                    alias: Ident::new(format!("{KEY_PREFIX}{i}")),
+                },
+                SmallVec::new(),
+            ));
+        }
+
+        KeysetQuery {
+            inner: self.retype(),
+            per_page: per_page as usize,
+        }
+    }
+}
+
+impl<'a, T> KeysetQuery<'a, T> {
+    /// SQL страницы (с keyset-предикатом, служебными колонками и `LIMIT n + 1`).
+    #[inline]
+    pub fn to_sql(self) -> Result<(String, Vec<Param>)> {
+        self.inner.to_sql()
+    }
+}
+
+impl<'a, T> std::future::IntoFuture for KeysetQuery<'a, T>
+where
+    T: FromDbRow + 'a,
+{
+    type Output = ExecResult<KeysetPage<T>>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            let per_page = self.per_page;
+            let mut rows = self.inner.await?;
+
+            let next_cursor = if rows.len() > per_page {
+                rows.truncate(per_page);
+                rows.last().map(|r| KeysetCursor(r.keys.clone()))
+            } else {
+                None
+            };
+
+            Ok(KeysetPage {
+                items: rows.into_iter().map(|r| r.item).collect(),
+                next_cursor,
+            })
+        })
+    }
+}
+
+macro_rules! try_as {
+    ($row:ident, $idx:ident; $($ty:ty => $wrap:expr),* $(,)?) => {
+        $(
+            if let Ok(v) = $row.try_get::<$ty, _>($idx) {
+                return Ok($wrap(v));
+            }
+        )*
+    };
+}
+
+/// `FromRow` для `KeysetRow` и чтение служебных колонок — для строки каждой
+/// включённой СУБД; `$fallback` — последняя попытка перед ошибкой.
+macro_rules! keyset_row {
+    ($row:ty, $decode:ident, |$r:ident, $i:ident| $fallback:block) => {
+        impl<'r, T> sqlx::FromRow<'r, $row> for KeysetRow<T>
+        where
+            T: sqlx::FromRow<'r, $row>,
+        {
+            fn from_row(row: &'r $row) -> core::result::Result<Self, sqlx::Error> {
+                let item = T::from_row(row)?;
+                let mut keys = Vec::new();
+                for (idx, c) in row.columns().iter().enumerate() {
+                    if c.name().starts_with(KEY_PREFIX) {
+                        keys.push($decode(row, idx)?);
+                    }
+                }
+                Ok(Self { item, keys })
+            }
+        }
+
+        /// Значение служебной колонки → `Param` (перебор типов, совместимых с колонкой).
+        fn $decode($r: &$row, $i: usize) -> core::result::Result<Param, sqlx::Error> {
+            if $r.try_get_raw($i)?.is_null() {
+                return Ok(Param::NullText);
+            }
+
+            try_as!($r, $i;
+                i64 => Param::I64,
+                i32 => Param::I32,
+                i16 => Param::I16,
+                f64 => Param::F64,
+                f32 => Param::F32,
+                bool => Param::Bool,
+                String => Param::Str,
+                Vec<u8> => Param::Bytes,
+            );
+            #[cfg(feature = "uuid")]
+            try_as!($r, $i; uuid::Uuid => Param::Uuid);
+            #[cfg(feature = "rust_decimal")]
+            try_as!($r, $i; rust_decimal::Decimal => Param::Decimal);
+            #[cfg(feature = "time")]
+            try_as!($r, $i;
+                time::OffsetDateTime => Param::DateTime,
+                time::PrimitiveDateTime => Param::NaiveDateTime,
+                time::Date => Param::Date,
+                time::Time => Param::Time,
+            );
+            #[cfg(feature = "chrono")]
+            try_as!($r, $i;
+                chrono::DateTime<chrono::Utc> => Param::ChronoDateTimeUtc,
+                chrono::NaiveDateTime => Param::ChronoNaiveDateTime,
+                chrono::NaiveDate => Param::ChronoNaiveDate,
+                chrono::NaiveTime => Param::ChronoNaiveTime,
+            );
+
+            $fallback
+
+            Err(sqlx::Error::ColumnDecode {
+                index: $r.columns()[$i].name().to_string(),
+                source: "keyset: unsupported key column type".into(),
+            })
+        }
+    };
+}
+
+#[cfg(feature = "postgres")]
+keyset_row!(sqlx::postgres::PgRow, decode_key_pg, |row, idx| {});
+#[cfg(feature = "mysql")]
+keyset_row!(sqlx::mysql::MySqlRow, decode_key_mysql, |row, idx| {});
+// SQLite хранит даты и прочее «по декларации» как текст
+#[cfg(feature = "sqlite")]
+keyset_row!(sqlx::sqlite::SqliteRow, decode_key_sqlite, |row, idx| {
+    if let Ok(v) = row.try_get_unchecked::<String, _>(idx) {
+        return Ok(Param::Str(v));
+    }
+});
+
+/// NULL в ORDER BY по умолчанию: PG — «больше всех», MySQL/SQLite — «меньше всех».
+#[inline]
+fn nulls_sort_first(dialect: Dialect, desc: bool) -> bool {
+    match dialect {
+        Dialect::Postgres => desc,
+        Dialect::MySQL | Dialect::SQLite => !desc,
+    }
+}
+
+#[inline]
+fn placeholder() -> SqlExpr {
+    SqlExpr::Value(Value::Placeholder("?".into()).into())
+}
+
+#[inline]
+fn binary(left: SqlExpr, op: BO, right: SqlExpr) -> SqlExpr {
+    SqlExpr::BinaryOp {
+        left: Box::new(left),
+        op,
+        right: Box::new(right),
+    }
+}
+
+/// Строит предикат «строго после курсора» для ключей ORDER BY.
+fn keyset_predicate(
+    keys: &[OrderByNode],
+    values: Vec<Param>,
+    dialect: Dialect,
+) -> (SqlExpr, SmallVec<[Param; 8]>) {
+    let desc: SmallVec<[bool; 4]> = keys
+        .iter()
+        .map(|k| k.expr.options.asc == Some(false))
+        .collect();
+
+    // row-value: одно направление, без NULL в курсоре и NULL сортируются
+    // в начало — тогда строки с NULL корректно отсекаются сравнением
+    let row_value = keys.len() > 1
+        && desc.iter().all(|d| *d == desc[0])
+        && values.iter().all(|v| !v.is_null())
+        && nulls_sort_first(dialect, desc[0]);
+
+    if row_value {
+        let op = if desc[0] { BO::Lt } else { BO::Gt };
+        let left = SqlExpr::Tuple(keys.iter().map(|k| k.expr.expr.clone()).collect());
+        let right = SqlExpr::Tuple(keys.iter().map(|_| placeholder()).collect());
+        return (binary(left, op, right), values.into_iter().collect());
+    }
+
+    let mut params: SmallVec<[Param; 8]> = SmallVec::new();
+    let mut disjuncts: Vec<SqlExpr> = Vec::with_capacity(keys.len());
+
+    for i in 0..keys.len() {
+        let col = &keys[i].expr.expr;
+        let value = &values[i];
+        let nulls_first = nulls_sort_first(dialect, desc[i]);
+
+        // «строго после» по i-му ключу
+        let after = if value.is_null() {
+            // после NULL идут только значения, если NULL в начале
+            if !nulls_first {
+                continue;
+            }
+            (SqlExpr::IsNotNull(Box::new(col.clone())), None)
+        } else {
+            let op = if desc[i] { BO::Lt } else { BO::Gt };
+            let cmp = binary(col.clone(), op, placeholder());
+            let e = if nulls_first {
+                cmp
+            } else {
+                binary(cmp, BO::Or, SqlExpr::IsNull(Box::new(col.clone())))
+            };
+            (e, Some(value.clone()))
+        };
+
+        // равенство по всем предыдущим ключам
+        let mut conj: Option<SqlExpr> = None;
+        for j in 0..i {
+            let prev = &keys[j].expr.expr;
+            let eq = if values[j].is_null() {
+                SqlExpr::IsNull(Box::new(prev.clone()))
+            } else {
+                params.push(values[j].clone());
+                binary(prev.clone(), BO::Eq, placeholder())
+            };
+            conj = Some(match conj {
+                Some(c) => binary(c, BO::And, eq),
+                None => eq,
+            });
+        }
+
+        let (after_expr, after_param) = after;
+        params.extend(after_param);
+        disjuncts.push(match conj {
+            Some(c) => binary(c, BO::And, after_expr),
+            None => after_expr,
+        });
+    }
+
+    let pred = disjuncts
+        .into_iter()
+        .reduce(|acc, d| binary(acc, BO::Or, d))
+        // курсор указывает на последнюю возможную строку
+        .unwrap_or_else(|| SqlExpr::Value(Value::Boolean(false).into()));
+
+    (pred, params)
+}
+
+// ---------- кодирование курсора ----------
+
+impl KeysetCursor {
+    /// Курсор из готовых значений ключей (в порядке ORDER BY).
+    #[inline]
+    pub fn new(values: Vec<Param>) -> Self {
+        Self(values)
+    }
+
+    /// Значения ключей в порядке ORDER BY.
+    #[inline]
+    pub fn values(&self) -> &[Param] {
+        &self.0
+    }
+
+    /// Непрозрачная строка (hex), безопасная для URL.
+    pub fn encode(&self) -> String {
+        let mut raw = String::from(CURSOR_VERSION);
+        for v in &self.0 {
+            let (tag, payload) = encode_param(v);
+            raw.push(tag);
+            raw.push_str(itoa::Buffer::new().format(payload.len()));
+            raw.push(':');
+            raw.push_str(&payload);
+        }
+
+        let mut out = String::with_capacity(raw.len() * 2);
+        for b in raw.bytes() {
+            out.push(char::from_digit((b >> 4) as u32, 16).unwrap());
+            out.push(char::from_digit((b & 0x0f) as u32, 16).unwrap());
+        }
+        out
+    }
+
+    /// Разбор строки, полученной из [`encode`](Self::encode).
+    pub fn decode(s: &str) -> Result<Self> {
+        let bytes = hex_decode(s.as_bytes()).ok_or_else(|| invalid("not a hex string"))?;
+        let raw = String::from_utf8(bytes).map_err(|_| invalid("not utf-8"))?;
+        let mut rest = raw
+            .strip_prefix(CURSOR_VERSION)
+            .ok_or_else(|| invalid("unknown cursor version"))?;
+
+        let mut values = Vec::new();
+        while let Some(tag) = rest.chars().next() {
+            rest = &rest[tag.len_utf8()..];
+            let (len, tail) = rest
+                .split_once(':')
+                .ok_or_else(|| invalid("truncated cursor"))?;
+            let len: usize = len.parse().map_err(|_| invalid("bad length"))?;
+            if tail.len() < len || !tail.is_char_boundary(len) {
+                return Err(invalid("truncated cursor"));
+            }
+            let (payload, tail) = tail.split_at(len);
+            values.push(decode_param(tag, payload)?);
+            rest = tail;
+        }
+        Ok(Self(values))
+    }
+}
+
+impl fmt::Display for KeysetCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+impl FromStr for KeysetCursor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::decode(s)
+    }
+}
+
+#[inline]
+fn invalid(reason: &'static str) -> Error {
+    Error::InvalidCursor(reason.into())
+}
+
+fn hex_decode(s: &[u8]) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    s.chunks(2)
+        .map(|p| {
+            let hi = (p[0] as char).to_digit(16)?;
+            let lo = (p[1] as char).to_digit(16)?;
+            Some((hi * 16 + lo) as u8)
+        })
+        .collect()
+}
+
+fn encode_param(p: &Param) -> (char, String) {
+    match p {
+        v if v.is_null() => ('n', String::new()),
+        Param::I64(v) => ('i', v.to_string()),
+        Param::I32(v) => ('j', v.to_string()),
+        Param::I16(v) => ('h', v.to_string()),
+        Param::I8(v) => ('g', v.to_string()),
+        // биты — точный round-trip
+        Param::F64(v) => ('f', v.to_bits().to_string()),
+        Param::F32(v) => ('e', v.to_bits().to_string()),
+        Param::Bool(v) => ('b', if *v { "1" } else { "0" }.into()),
+        Param::Str(v) => ('s', v.clone()),
+        Param::Bytes(v) => ('x', v.iter().map(|b| format!("{b:02x}")).collect()),
+
+        #[cfg(feature = "time")]
+        Param::DateTime(v) => (
+            'T',
+            format!(
+                "{},{}",
+                v.unix_timestamp_nanos(),
+                v.offset().whole_seconds()
+            ),
+        ),
+        #[cfg(feature = "time")]
+        Param::NaiveDateTime(v) => ('P', v.assume_utc().unix_timestamp_nanos().to_string()),
+        #[cfg(feature = "time")]
+        Param::Date(v) => ('D', v.to_julian_day().to_string()),
+        #[cfg(feature = "time")]
+        Param::Time(v) => {
+            let (h, m, s, ns) = v.as_hms_nano();
+            ('t', format!("{h},{m},{s},{ns}"))
+        }
+
+        #[cfg(feature = "chrono")]
+        Param::ChronoDateTimeUtc(v) => (
+            'C',
+            format!("{},{}", v.timestamp(), v.timestamp_subsec_nanos()),
+        ),
+        #[cfg(feature = "chrono")]
+        Param::ChronoDateTimeFixed(v) => (
+            'F',
+            format!(
+                "{},{},{}",
+                v.timestamp(),
+                v.timestamp_subsec_nanos(),
+                v.offset().local_minus_utc()
+            ),
+        ),
+        #[cfg(feature = "chrono")]
+        Param::ChronoNaiveDateTime(v) => {
+            let utc = v.and_utc();
+            (
+                'N',
+                format!("{},{}", utc.timestamp(), utc.timestamp_subsec_nanos()),
+            )
+        }
+        #[cfg(feature = "chrono")]
+        Param::ChronoNaiveDate(v) => {
+            use chrono::Datelike;
+            ('A', v.num_days_from_ce().to_string())
+        }
+        #[cfg(feature = "chrono")]
+        Param::ChronoNaiveTime(v) => {
+            use chrono::Timelike;
+            (
+                'M',
+                format!("{},{}", v.num_seconds_from_midnight(), v.nanosecond()),
+            )
+        }
+
+        #[cfg(feature = "serde_json")]
+        Param::Json(v) => ('J', v.to_string()),
+        #[cfg(feature = "uuid")]
+        Param::Uuid(v) => ('u', v.to_string()),
+        #[cfg(feature = "rust_decimal")]
+        Param::Decimal(v) => ('d', v.to_string()),
+
+        // все Null*-варианты обработаны выше
+        _ => ('n', String::new()),
+    }
+}
+
+fn decode_param(tag: char, s: &str) -> Result<Param> {
+    fn num<N: FromStr>(s: &str) -> Result<N> {
+        s.parse().map_err(|_| invalid("bad number"))
+    }
+    #[allow(dead_code)]
+    fn parts<const N: usize>(s: &str) -> Result<[&str; N]> {
+        let v: Vec<&str> = s.split(',').collect();
+        v.try_into().map_err(|_| invalid("bad composite value"))
+    }
+
+    Ok(match tag {
+        'n' => Param::NullText,
+        'i' => Param::I64(num(s)?),
+        'j' => Param::I32(num(s)?),
+        'h' => Param::I16(num(s)?),
+        'g' => Param::I8(num(s)?),
+        'f' => Param::F64(f64::from_bits(num(s)?)),
+        'e' => Param::F32(f32::from_bits(num(s)?)),
+        'b' => Param::Bool(s == "1"),
+        's' => Param::Str(s.to_string()),
+        'x' => Param::Bytes(hex_decode(s.as_bytes()).ok_or_else(|| invalid("bad bytes"))?),
+
+        #[cfg(feature = "time")]
+        'T' => {
+            let [ns, off] = parts::<2>(s)?;
+            let off = time::UtcOffset::from_whole_seconds(num(off)?)
+                .map_err(|_| invalid("bad offset"))?;
+            let dt = time::OffsetDateTime::from_unix_timestamp_nanos(num(ns)?)
+                .map_err(|_| invalid("bad timestamp"))?;
+            Param::DateTime(dt.to_offset(off))
+        }
+        #[cfg(feature = "time")]
+        'P' => {
+            let dt = time::OffsetDateTime::from_unix_timestamp_nanos(num(s)?)
+                .map_err(|_| invalid("bad timestamp"))?;
+            Param::NaiveDateTime(time::PrimitiveDateTime::new(dt.date(), dt.time()))
+        }
+        #[cfg(feature = "time")]
+        'D' => Param::Date(time::Date::from_julian_day(num(s)?).map_err(|_| invalid("bad date"))?),
+        #[cfg(feature = "time")]
+        't' => {
+            let [h, m, sec, ns] = parts::<4>(s)?;
+            Param::Time(
+                time::Time::from_hms_nano(num(h)?, num(m)?, num(sec)?, num(ns)?)
+                    .map_err(|_| invalid("bad time"))?,
+            )
+        }
+
+        #[cfg(feature = "chrono")]
+        'C' => {
+            let [secs, ns] = parts::<2>(s)?;
+            Param::ChronoDateTimeUtc(
+                chrono::DateTime::from_timestamp(num(secs)?, num(ns)?)
+                    .ok_or_else(|| invalid("bad timestamp"))?,
+            )
+        }
+        #[cfg(feature = "chrono")]
+        'F' => {
+            let [secs, ns, off] = parts::<3>(s)?;
+            let off =
+                chrono::FixedOffset::east_opt(num(off)?).ok_or_else(|| invalid("bad offset"))?;
+            let dt = chrono::DateTime::from_timestamp(num(secs)?, num(ns)?)
+                .ok_or_else(|| invalid("bad timestamp"))?;
+            Param::ChronoDateTimeFixed(dt.with_timezone(&off))
+        }
+        #[cfg(feature = "chrono")]
+        'N' => {
+            let [secs, ns] = parts::<2>(s)?;
+            let dt = chrono::DateTime::from_timestamp(num(secs)?, num(ns)?)
+                .ok_or_else(|| invalid("bad timestamp"))?;
+            Param::ChronoNaiveDateTime(dt.naive_utc())
+        }
+        #[cfg(feature = "chrono")]
+        'A' => Param::ChronoNaiveDate(
+            chrono::NaiveDate::from_num_days_from_ce_opt(num(s)?)
+                .ok_or_else(|| invalid("bad date"))?,
+        ),
+        #[cfg(feature = "chrono")]
+        'M' => {
+            let [secs, ns] = parts::<2>(s)?;
+            Param::ChronoNaiveTime(
+                chrono::NaiveTime::from_num_seconds_from_midnight_opt(num(secs)?, num(ns)?)
+                    .ok_or_else(|| invalid("bad time"))?,
+            )
+        }
+
+        #[cfg(feature = "serde_json")]
+        'J' => Param::Json(serde_json::from_str(s).map_err(|_| invalid("bad json"))?),
+        #[cfg(feature = "uuid")]
+        'u' => Param::Uuid(uuid::Uuid::parse_str(s).map_err(|_| invalid("bad uuid"))?),
+        #[cfg(feature = "rust_decimal")]
+        'd' => Param::Decimal(num(s)?),
+
+        _ => return Err(invalid("unknown value tag")),
+    })
+}
@@ -0,0 +1,67 @@
+use crate::query_builder::QueryBuilder;
+use crate::renderer::ast::{LockStrength, LockWait, RowLock};
+
+impl<'a, T> QueryBuilder<'a, T> {
+    /// `FOR UPDATE`
+    pub fn for_update(self) -> Self {
+        self.set_lock_strength(LockStrength::Update)
+    }
+
+    /// `FOR SHARE`
+    pub fn for_share(self) -> Self {
+        self.set_lock_strength(LockStrength::Share)
+    }
+
+    /// `FOR NO KEY UPDATE` (PG). В MySQL при `FeaturePolicy::Strict` — `UnsupportedFeature`,
+    /// при `Lenient` понижается до `FOR UPDATE`
+    pub fn for_no_key_update(self) -> Self {
+        self.set_lock_strength(LockStrength::NoKeyUpdate)
+    }
+
+    /// `... SKIP LOCKED` — занятые строки пропускаются
+    pub fn skip_locked(self) -> Self {
+        self.set_lock_wait(LockWait::SkipLocked, "skip_locked()")
+    }
+
+    /// `... NOWAIT` — ошибка вместо ожидания занятых строк
+    pub fn no_wait(self) -> Self {
+        self.set_lock_wait(LockWait::NoWait, "no_wait()")
+    }
+
+    /// `... OF t1, t2` — блокировать строки только этих таблиц (имена или алиасы)
+    pub fn of<I, S>(mut self, tables: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        match self.lock.as_mut() {
+            Some(lock) => lock.of.extend(tables.into_iter().map(Into::into)),
+            None => self.push_builder_error("of(): сначала вызовите for_update()/for_share()"),
+        }
+        self
+    }
+
+    fn set_lock_strength(mut self, strength: LockStrength) -> Self {
+        match self.lock.as_mut() {
+            Some(lock) => lock.strength = strength,
+            None => {
+                self.lock = Some(RowLock {
+                    strength,
+                    of: vec![],
+                    wait: LockWait::Wait,
+                })
+            }
+        }
+        self
+    }
+
+    fn set_lock_wait(mut self, wait: LockWait, ctx: &'static str) -> Self {
+        match self.lock.as_mut() {
+            Some(lock) => lock.wait = wait,
+            None => {
+                self.push_builder_error(format!("{ctx}: сначала вызовите for_update()/for_share()"))
+            }
+        }
+        self
+    }
+}
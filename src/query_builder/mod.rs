@@ -1,10 +1,10 @@
-use std::{borrow::Cow, marker::PhantomData};
+use std::{borrow::Cow, marker::PhantomData, time::Duration};
 
 use crate::{
-    executor::{DbPool, DbRow},
+    executor::{DbPool, FromDbRow, QueryHooks, ReadRoute, SqlCache},
     optimizer::OptimizeConfig,
     param::Param,
-    renderer::Dialect,
+    renderer::{Dialect, ast::RowLock},
 };
 use smallvec::{SmallVec, smallvec};
 
@@ -22,15 +22,23 @@ mod group_by;
 mod having;
 pub mod insert;
 mod join;
+mod keyset;
 mod limit;
+mod lock;
 mod order_by;
+mod paginate;
+mod raw;
+mod replica;
 mod schema;
 mod select;
 mod sql;
+mod stream;
+mod timeout;
 pub mod typestate;
 mod union;
 mod update;
 mod where_clause;
+mod window;
 mod with;
 
 use ast::FromItem;
@@ -42,22 +50,29 @@ use group_by::GroupByNode;
 use having::HavingNode;
 pub use insert::InsertBuilder;
 use join::JoinNode;
+pub use keyset::{KeysetCursor, KeysetPage, KeysetQuery};
 use order_by::OrderByNode;
+pub use paginate::{Page, Paginate};
+pub use raw::{raw_named, raw_with};
 use select::SelectItemNode;
 pub use typestate::{PoolQuery, TxQuery};
 use union::SetOpNode;
 pub use update::UpdateBuilder;
 use where_clause::WhereNode;
+use window::WindowNode;
 use with::WithItemNode;
 
 pub use join::on;
 
+/// Диалект билдеров без соединения (`new_empty`, `ExecCtx::None`): единственная
+/// включённая СУБД, при нескольких — первая из postgres, mysql, sqlite.
+/// С соединением диалект берётся из пула/транзакции.
 #[cfg(feature = "postgres")]
-const DEFAULT_DIALECT: Dialect = Dialect::Postgres;
-#[cfg(feature = "mysql")]
-const DEFAULT_DIALECT: Dialect = Dialect::MySQL;
-#[cfg(feature = "sqlite")]
-const DEFAULT_DIALECT: Dialect = Dialect::SQLite;
+pub(crate) const DEFAULT_DIALECT: Dialect = Dialect::Postgres;
+#[cfg(all(feature = "mysql", not(feature = "postgres")))]
+pub(crate) const DEFAULT_DIALECT: Dialect = Dialect::MySQL;
+#[cfg(all(feature = "sqlite", not(any(feature = "postgres", feature = "mysql"))))]
+pub(crate) const DEFAULT_DIALECT: Dialect = Dialect::SQLite;
 
 pub struct QueryOne<'a, T>(pub(super) QueryBuilder<'a, T>);
 pub struct QueryOptional<'a, T>(pub(super) QueryBuilder<'a, T>);
@@ -79,6 +94,8 @@ pub struct QueryBuilder<'a, T = ()> {
     pub(self) limit_num: Option<u64>,
     pub(self) offset_num: Option<u64>,
     pub(self) having_clause: Option<HavingNode>,
+    pub(self) windows: SmallVec<[WindowNode; 1]>,
+    pub(self) lock: Option<RowLock>,
     pub(self) select_distinct: bool,
     pub(self) distinct_on_items: SmallVec<[DistinctOnNode; 2]>,
     pub(self) with_items: SmallVec<[WithItemNode; 1]>,
@@ -86,12 +103,17 @@ pub struct QueryBuilder<'a, T = ()> {
     pub(self) set_ops: SmallVec<[SetOpNode; 1]>,
     pub(crate) exec_ctx: ExecCtx<'a>,
     pub(crate) optimize_cfg: OptimizeConfig,
+    pub(crate) hooks: QueryHooks,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) read_route: ReadRoute,
+    pub(crate) sql_cache: Option<SqlCache>,
     _t: PhantomData<T>,
 }
 
 impl<'a, T> QueryBuilder<'a, T> {
     #[inline]
     pub fn new_pool(pool: DbPool, schema: Option<String>) -> Self {
+        let dialect = pool.dialect();
         Self {
             select_items: smallvec![],
             from_items: smallvec![],
@@ -104,8 +126,10 @@ impl<'a, T> QueryBuilder<'a, T> {
             group_by_items: smallvec![],
             order_by_items: smallvec![],
             having_clause: None,
+            windows: smallvec![],
+            lock: None,
             alias: None,
-            dialect: DEFAULT_DIALECT,
+            dialect,
             limit_num: None,
             offset_num: None,
             select_distinct: false,
@@ -115,12 +139,17 @@ impl<'a, T> QueryBuilder<'a, T> {
             set_ops: smallvec![],
             exec_ctx: ExecCtx::Pool(pool),
             optimize_cfg: OptimizeConfig::default(),
+            hooks: QueryHooks::default(),
+            timeout: None,
+            read_route: ReadRoute::default(),
+            sql_cache: None,
             _t: PhantomData,
         }
     }
 
     #[inline]
     pub fn new_tx(schema: Option<String>, exec_ctx: ExecCtx<'a>) -> Self {
+        let dialect = exec_ctx.dialect().unwrap_or(DEFAULT_DIALECT);
         Self {
             select_items: smallvec![],
             from_items: smallvec![],
@@ -133,8 +162,10 @@ impl<'a, T> QueryBuilder<'a, T> {
             group_by_items: smallvec![],
             order_by_items: smallvec![],
             having_clause: None,
+            windows: smallvec![],
+            lock: None,
             alias: None,
-            dialect: DEFAULT_DIALECT,
+            dialect,
             limit_num: None,
             offset_num: None,
             select_distinct: false,
@@ -144,6 +175,10 @@ impl<'a, T> QueryBuilder<'a, T> {
             set_ops: smallvec![],
             exec_ctx,
             optimize_cfg: OptimizeConfig::default(),
+            hooks: QueryHooks::default(),
+            timeout: None,
+            read_route: ReadRoute::default(),
+            sql_cache: None,
             _t: PhantomData,
         }
     }
@@ -162,6 +197,8 @@ impl<'a, T> QueryBuilder<'a, T> {
             group_by_items: smallvec![],
             order_by_items: smallvec![],
             having_clause: None,
+            windows: smallvec![],
+            lock: None,
             alias: None,
             dialect: DEFAULT_DIALECT,
             limit_num: None,
@@ -173,6 +210,10 @@ impl<'a, T> QueryBuilder<'a, T> {
             set_ops: smallvec![],
             exec_ctx: ExecCtx::None,
             optimize_cfg: OptimizeConfig::default(),
+            hooks: QueryHooks::default(),
+            timeout: None,
+            read_route: ReadRoute::default(),
+            sql_cache: None,
             _t: PhantomData,
         }
     }
@@ -261,6 +302,48 @@ impl<'a, T> QueryBuilder<'a, T> {
     fn is_mysql(&self) -> bool {
         self.dialect == crate::renderer::Dialect::MySQL
     }
+
+    /// Хуки исполнителя, создавшего билдер
+    #[inline]
+    pub(crate) fn with_hooks(mut self, hooks: QueryHooks) -> Self {
+        self.hooks = hooks;
+        self
+    }
+
+    /// Тот же билдер с другим типом строки результата.
+    fn retype<U>(self) -> QueryBuilder<'a, U> {
+        QueryBuilder {
+            select_items: self.select_items,
+            from_items: self.from_items,
+            where_clause: self.where_clause,
+            params: self.params,
+            default_schema: self.default_schema,
+            pending_schema: self.pending_schema,
+            alias: self.alias,
+            dialect: self.dialect,
+            builder_errors: self.builder_errors,
+            from_joins: self.from_joins,
+            group_by_items: self.group_by_items,
+            order_by_items: self.order_by_items,
+            limit_num: self.limit_num,
+            offset_num: self.offset_num,
+            having_clause: self.having_clause,
+            windows: self.windows,
+            lock: self.lock,
+            select_distinct: self.select_distinct,
+            distinct_on_items: self.distinct_on_items,
+            with_items: self.with_items,
+            with_recursive: self.with_recursive,
+            set_ops: self.set_ops,
+            exec_ctx: self.exec_ctx,
+            optimize_cfg: self.optimize_cfg,
+            hooks: self.hooks,
+            timeout: self.timeout,
+            read_route: self.read_route,
+            sql_cache: self.sql_cache,
+            _t: PhantomData,
+        }
+    }
 }
 
 impl<'a, T> Default for QueryBuilder<'a, T> {
@@ -269,7 +352,7 @@ impl<'a, T> Default for QueryBuilder<'a, T> {
     }
 }
 
-use crate::executor::{Error as ExecError, Result as ExecResult};
+use crate::executor::{Error as ExecError, QueryInfo, Result as ExecResult, trace};
 
 #[cfg(feature = "mysql")]
 use crate::executor::utils::{
@@ -300,46 +383,49 @@ use std::{future::Future, pin::Pin};
 // === Vec<T> ===
 impl<'a, T> std::future::IntoFuture for QueryBuilder<'a, T>
 where
-    T: for<'r> sqlx::FromRow<'r, DbRow> + Send + Unpin + 'a,
+    T: FromDbRow + 'a,
 {
     type Output = crate::executor::Result<Vec<T>>;
     type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + 'a>>;
 
     fn into_future(mut self) -> Self::IntoFuture {
         Box::pin(async move {
+            self.route_read();
             let (sql, params) = self.render_sql().map_err(ExecError::from)?;
 
-            match self.exec_ctx {
-                ExecCtx::None => Err(ExecError::MissingConnection),
-
-                // ---- исполнение через пул ----
-                ExecCtx::Pool(pool) => match pool {
+            let query = QueryInfo::new(&sql, params.len(), self.dialect);
+            let (mut ctx, hooks, timeout) = (self.exec_ctx, self.hooks, self.timeout);
+            let fut = async {
+                if timeout.is_some() {
+                    return ctx.fetch_all_timeout::<T>(&sql, params, timeout).await;
+                }
+                match ctx {
+                    ExecCtx::None => Err(ExecError::MissingConnection),
+
+                    // ---- исполнение через пул ----
+                    ExecCtx::Pool(pool) => match pool {
+                        #[cfg(feature = "postgres")]
+                        DbPool::Postgres(p) => fetch_typed_pg::<T>(&p, &sql, params).await,
+                        #[cfg(feature = "mysql")]
+                        DbPool::MySql(p) => fetch_typed_mysql::<T>(&p, &sql, params).await,
+                        #[cfg(feature = "sqlite")]
+                        DbPool::Sqlite(p) => fetch_typed_sqlite::<T>(&p, &sql, params).await,
+                    },
+
+                    // ---- исполнение ВНУТРИ транзакции ----
                     #[cfg(feature = "postgres")]
-                    DbPool::Postgres(p) => fetch_typed_pg::<T>(&p, &sql, params)
-                        .await
-                        .map_err(Into::into),
+                    ExecCtx::PgConn(conn) => fetch_typed_pg_exec::<_, T>(conn, &sql, params).await,
                     #[cfg(feature = "mysql")]
-                    DbPool::MySql(p) => fetch_typed_mysql::<T>(&p, &sql, params)
-                        .await
-                        .map_err(Into::into),
+                    ExecCtx::MySqlConn(conn) => {
+                        fetch_typed_mysql_exec::<_, T>(conn, &sql, params).await
+                    }
                     #[cfg(feature = "sqlite")]
-                    DbPool::Sqlite(p) => fetch_typed_sqlite::<T>(&p, &sql, params)
-                        .await
-                        .map_err(Into::into),
-                },
-
-                // ---- исполнение ВНУТРИ транзакции ----
-                #[cfg(feature = "postgres")]
-                ExecCtx::PgConn(conn) => fetch_typed_pg_exec::<_, T>(conn, &sql, params).await,
-                #[cfg(feature = "mysql")]
-                ExecCtx::MySqlConn(conn) => {
-                    fetch_typed_mysql_exec::<_, T>(conn, &sql, params).await
-                }
-                #[cfg(feature = "sqlite")]
-                ExecCtx::SqliteConn(conn) => {
-                    fetch_typed_sqlite_exec::<_, T>(conn, &sql, params).await
+                    ExecCtx::SqliteConn(conn) => {
+                        fetch_typed_sqlite_exec::<_, T>(conn, &sql, params).await
+                    }
                 }
-            }
+            };
+            hooks.observe(query, trace::rows_vec, fut).await
         })
     }
 }
@@ -347,42 +433,52 @@ where
 // === one() ===
 impl<'a, T> std::future::IntoFuture for QueryOne<'a, T>
 where
-    T: for<'r> sqlx::FromRow<'r, DbRow> + Send + Unpin + 'a,
+    T: FromDbRow + 'a,
 {
     type Output = crate::executor::Result<T>;
     type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + 'a>>;
 
     fn into_future(mut self) -> Self::IntoFuture {
         Box::pin(async move {
+            self.0.route_read();
             let (sql, params) = self.0.render_sql()?;
-            match self.0.exec_ctx {
-                ExecCtx::None => Err(ExecError::MissingConnection),
+            let query = QueryInfo::new(&sql, params.len(), self.0.dialect);
+            let (mut ctx, hooks, timeout) = (self.0.exec_ctx, self.0.hooks, self.0.timeout);
+            let fut = async {
+                if timeout.is_some() {
+                    let rows = ctx.fetch_all_timeout::<T>(&sql, params, timeout).await?;
+                    return rows.into_iter().next().ok_or(ExecError::NotFound);
+                }
+                match ctx {
+                    ExecCtx::None => Err(ExecError::MissingConnection),
+
+                    ExecCtx::Pool(pool) => match pool {
+                        #[cfg(feature = "postgres")]
+                        DbPool::Postgres(p) => fetch_one_typed_pg(&p, &sql, params).await,
+                        #[cfg(feature = "mysql")]
+                        DbPool::MySql(p) => fetch_one_typed_mysql(&p, &sql, params).await,
+                        #[cfg(feature = "sqlite")]
+                        DbPool::Sqlite(p) => fetch_one_typed_sqlite(&p, &sql, params).await,
+                    },
 
-                ExecCtx::Pool(pool) => match pool {
                     #[cfg(feature = "postgres")]
-                    DbPool::Postgres(p) => fetch_one_typed_pg(&p, &sql, params).await,
+                    ExecCtx::PgConn(conn) => {
+                        let rows = fetch_typed_pg_exec::<_, T>(conn, &sql, params).await?;
+                        rows.into_iter().next().ok_or(ExecError::NotFound)
+                    }
                     #[cfg(feature = "mysql")]
-                    DbPool::MySql(p) => fetch_one_typed_mysql(&p, &sql, params).await,
+                    ExecCtx::MySqlConn(conn) => {
+                        let rows = fetch_typed_mysql_exec::<_, T>(conn, &sql, params).await?;
+                        rows.into_iter().next().ok_or(ExecError::NotFound)
+                    }
                     #[cfg(feature = "sqlite")]
-                    DbPool::Sqlite(p) => fetch_one_typed_sqlite(&p, &sql, params).await,
-                },
-
-                #[cfg(feature = "postgres")]
-                ExecCtx::PgConn(conn) => {
-                    let rows = fetch_typed_pg_exec::<_, T>(conn, &sql, params).await?;
-                    rows.into_iter().next().ok_or(ExecError::NotFound)
-                }
-                #[cfg(feature = "mysql")]
-                ExecCtx::MySqlConn(conn) => {
-                    let rows = fetch_typed_mysql_exec::<_, T>(conn, &sql, params).await?;
-                    rows.into_iter().next().ok_or(ExecError::NotFound)
+                    ExecCtx::SqliteConn(conn) => {
+                        let rows = fetch_typed_sqlite_exec::<_, T>(conn, &sql, params).await?;
+                        rows.into_iter().next().ok_or(ExecError::NotFound)
+                    }
                 }
-                #[cfg(feature = "sqlite")]
-                ExecCtx::SqliteConn(conn) => {
-                    let rows = fetch_typed_sqlite_exec::<_, T>(conn, &sql, params).await?;
-                    rows.into_iter().next().ok_or(ExecError::NotFound)
-                }
-            }
+            };
+            hooks.observe(query, trace::rows_one, fut).await
         })
     }
 }
@@ -390,82 +486,100 @@ where
 // === optional() ===
 impl<'a, T> std::future::IntoFuture for QueryOptional<'a, T>
 where
-    T: for<'r> sqlx::FromRow<'r, DbRow> + Send + Unpin + 'a,
+    T: FromDbRow + 'a,
 {
     type Output = crate::executor::Result<Option<T>>;
     type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + 'a>>;
 
     fn into_future(mut self) -> Self::IntoFuture {
         Box::pin(async move {
+            self.0.route_read();
             let (sql, params) = self.0.render_sql()?;
-            match self.0.exec_ctx {
-                ExecCtx::None => Err(ExecError::MissingConnection),
+            let query = QueryInfo::new(&sql, params.len(), self.0.dialect);
+            let (mut ctx, hooks, timeout) = (self.0.exec_ctx, self.0.hooks, self.0.timeout);
+            let fut = async {
+                if timeout.is_some() {
+                    let rows = ctx.fetch_all_timeout::<T>(&sql, params, timeout).await?;
+                    return Ok(rows.into_iter().next());
+                }
+                match ctx {
+                    ExecCtx::None => Err(ExecError::MissingConnection),
+
+                    ExecCtx::Pool(pool) => match pool {
+                        #[cfg(feature = "postgres")]
+                        DbPool::Postgres(p) => fetch_optional_typed_pg(&p, &sql, params).await,
+                        #[cfg(feature = "mysql")]
+                        DbPool::MySql(p) => fetch_optional_typed_mysql(&p, &sql, params).await,
+                        #[cfg(feature = "sqlite")]
+                        DbPool::Sqlite(p) => fetch_optional_typed_sqlite(&p, &sql, params).await,
+                    },
 
-                ExecCtx::Pool(pool) => match pool {
                     #[cfg(feature = "postgres")]
-                    DbPool::Postgres(p) => fetch_optional_typed_pg(&p, &sql, params).await,
+                    ExecCtx::PgConn(conn) => {
+                        let rows = fetch_typed_pg_exec::<_, T>(conn, &sql, params).await?;
+                        Ok(rows.into_iter().next())
+                    }
                     #[cfg(feature = "mysql")]
-                    DbPool::MySql(p) => fetch_optional_typed_mysql(&p, &sql, params).await,
+                    ExecCtx::MySqlConn(conn) => {
+                        let rows = fetch_typed_mysql_exec::<_, T>(conn, &sql, params).await?;
+                        Ok(rows.into_iter().next())
+                    }
                     #[cfg(feature = "sqlite")]
-                    DbPool::Sqlite(p) => fetch_optional_typed_sqlite(&p, &sql, params).await,
-                },
-
-                #[cfg(feature = "postgres")]
-                ExecCtx::PgConn(conn) => {
-                    let rows = fetch_typed_pg_exec::<_, T>(conn, &sql, params).await?;
-                    Ok(rows.into_iter().next())
+                    ExecCtx::SqliteConn(conn) => {
+                        let rows = fetch_typed_sqlite_exec::<_, T>(conn, &sql, params).await?;
+                        Ok(rows.into_iter().next())
+                    }
                 }
-                #[cfg(feature = "mysql")]
-                ExecCtx::MySqlConn(conn) => {
-                    let rows = fetch_typed_mysql_exec::<_, T>(conn, &sql, params).await?;
-                    Ok(rows.into_iter().next())
-                }
-                #[cfg(feature = "sqlite")]
-                ExecCtx::SqliteConn(conn) => {
-                    let rows = fetch_typed_sqlite_exec::<_, T>(conn, &sql, params).await?;
-                    Ok(rows.into_iter().next())
-                }
-            }
+            };
+            hooks.observe(query, trace::rows_opt, fut).await
         })
     }
 }
 
 impl<'a, T> QueryBuilder<'a, T>
 where
-    T: for<'r> sqlx::FromRow<'r, DbRow> + Send + Unpin + 'static,
+    T: FromDbRow + 'static,
 {
     /// Future, пригодный для tokio::spawn (Send + 'static). Только для ExecCtx::Pool.
     pub fn into_send(
         mut self,
     ) -> ExecResult<impl core::future::Future<Output = ExecResult<Vec<T>>> + Send + 'static>
     where
-        T: for<'r> sqlx::FromRow<'r, DbRow> + Send + Unpin + 'static,
+        T: FromDbRow + 'static,
     {
+        self.route_read();
         let (sql, params) = self.render_sql()?;
-        // перемещаем контекст (если нельзя — возьмите clone()):
-        let ctx = self.exec_ctx.clone(); // ExecCtx: Clone у вас уже есть
-        ctx.select_send::<T>(sql, params)
+        let query = QueryInfo::new(&sql, params.len(), self.dialect);
+        let ctx = self.exec_ctx.clone();
+        let fut = ctx.select_send_within::<T>(sql.clone(), params, self.timeout)?;
+        Ok(self.hooks.observe_send(query, trace::rows_vec, fut))
     }
 
     pub fn one_send(
         mut self,
     ) -> ExecResult<impl core::future::Future<Output = ExecResult<T>> + Send + 'static>
     where
-        T: for<'r> sqlx::FromRow<'r, DbRow> + Send + Unpin + 'static,
+        T: FromDbRow + 'static,
     {
+        self.route_read();
         let (sql, params) = self.render_sql()?;
+        let query = QueryInfo::new(&sql, params.len(), self.dialect);
         let ctx = self.exec_ctx.clone();
-        ctx.select_one_send::<T>(sql, params)
+        let fut = ctx.select_one_send_within::<T>(sql.clone(), params, self.timeout)?;
+        Ok(self.hooks.observe_send(query, trace::rows_one, fut))
     }
 
     pub fn optional_send(
         mut self,
     ) -> ExecResult<impl core::future::Future<Output = ExecResult<Option<T>>> + Send + 'static>
     where
-        T: for<'r> sqlx::FromRow<'r, DbRow> + Send + Unpin + 'static,
+        T: FromDbRow + 'static,
     {
+        self.route_read();
         let (sql, params) = self.render_sql()?;
+        let query = QueryInfo::new(&sql, params.len(), self.dialect);
         let ctx = self.exec_ctx.clone();
-        ctx.select_optional_send::<T>(sql, params)
+        let fut = ctx.select_optional_send_within::<T>(sql.clone(), params, self.timeout)?;
+        Ok(self.hooks.observe_send(query, trace::rows_opt, fut))
     }
 }
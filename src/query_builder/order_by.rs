@@ -26,24 +26,50 @@ impl<'a, T> QueryBuilder<'a, T> {
     /// - `Expression` → как есть
     /// - ⛔ `QueryBuilder`/замыкания — не поддерживаются в ORDER BY (фиксируем ошибку)
     pub fn order_by<A>(mut self, args: A) -> Self
+    where
+        A: ArgList<'a>,
+    {
+        self.push_order_by_items(args, None, "order_by()");
+        self
+    }
+
+    /// ORDER BY <expr1> DESC, <expr2> DESC, ...
+    ///
+    /// Принимает те же аргументы, что и [`order_by`](Self::order_by).
+    pub fn order_by_desc<A>(mut self, args: A) -> Self
+    where
+        A: ArgList<'a>,
+    {
+        self.push_order_by_items(args, Some(false), "order_by_desc()");
+        self
+    }
+
+    fn push_order_by_items<A>(&mut self, args: A, asc: Option<bool>, ctx: &'static str)
     where
         A: ArgList<'a>,
     {
         let items = args.into_vec();
         if items.is_empty() {
-            return self;
+            return;
         }
 
         for it in items {
             match it {
                 QBArg::Expr(e) => {
+                    let e = match e.checked() {
+                        Ok(e) => e,
+                        Err(err) => {
+                            self.push_builder_error(format!("order_by(): {err}"));
+                            continue;
+                        }
+                    };
                     let expr: SqlExpr = e.expr;
                     let mut params: SmallVec<[Param; 8]> = e.params;
 
                     let ob = OrderByExpr {
                         expr,
                         options: OrderByOptions {
-                            asc: None,
+                            asc,
                             nulls_first: None,
                         },
                         with_fill: None,
@@ -57,13 +83,11 @@ impl<'a, T> QueryBuilder<'a, T> {
                     }));
                 }
                 QBArg::Subquery(_) | QBArg::Closure(_) => {
-                    self.push_builder_error(
-                        "order_by(): подзапросы/замыкания в ORDER BY не поддерживаются",
-                    );
+                    self.push_builder_error(format!(
+                        "{ctx}: подзапросы/замыкания в ORDER BY не поддерживаются"
+                    ));
                 }
             }
         }
-
-        self
     }
 }
@@ -0,0 +1,185 @@
+//! Offset-пагинация с подсчётом общего числа строк (`paginate(page, per_page)`).
+
+use std::{future::Future, pin::Pin};
+
+use smallvec::SmallVec;
+use sqlparser::ast::{Ident, SelectItem};
+
+use crate::{
+    executor::{FromDbRow, QueryInfo, Result as ExecResult, trace},
+    expression::helpers::col,
+    param::Param,
+    query_builder::{FromItem, QueryBuilder, Result, select::SelectItemNode},
+};
+
+/// Алиас подзапроса, в который оборачивается запрос для COUNT.
+const COUNT_SUBQUERY_ALIAS: &str = "knux_count";
+
+/// Страница offset-пагинации.
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Общее число строк без LIMIT/OFFSET.
+    pub total: u64,
+    /// Номер страницы, с 1.
+    pub page: u64,
+    pub per_page: u64,
+    /// Номер последней страницы (минимум 1).
+    pub last_page: u64,
+}
+
+/// Отложенный запрос страницы + COUNT (см. [`QueryBuilder::paginate`]).
+pub struct Paginate<'a, T> {
+    query: QueryBuilder<'a, T>,
+    count: QueryBuilder<'a, (i64,)>,
+    page: u64,
+    per_page: u64,
+}
+
+impl<'a, T> QueryBuilder<'a, T> {
+    /// Страница `page` (с 1) по `per_page` строк и общее количество строк.
+    ///
+    /// COUNT выводится из этого же запроса без ORDER BY/LIMIT/OFFSET. Если в
+    /// запросе есть `DISTINCT`, `GROUP BY`, `HAVING` или UNION/INTERSECT/EXCEPT,
+    /// он оборачивается в подзапрос:
+    /// `SELECT COUNT(*) AS total FROM (...) AS knux_count`.
+    /// Внутри транзакции оба запроса идут через её соединение.
+    pub fn paginate(mut self, page: u64, per_page: u64) -> Paginate<'a, T> {
+        if page == 0 {
+            self.push_builder_error("paginate(): номер страницы начинается с 1");
+        }
+        if per_page == 0 {
+            self.push_builder_error("paginate(): per_page должен быть больше 0");
+        }
+
+        let mut base: QueryBuilder<'a, ()> = self.retype();
+
+        // замыкания в FROM одноразовые — раскрываем до клонирования
+        let dialect = base.dialect;
+        for item in base.from_items.iter_mut() {
+            if let FromItem::SubqueryClosure(c) = item {
+                let built = c.call(QueryBuilder::new_empty().dialect(dialect));
+                *item = FromItem::Subquery(Box::new(built));
+            }
+        }
+
+        let count = base.clone().into_count_query();
+
+        let mut query: QueryBuilder<'a, T> = base.retype();
+        query.limit_num = Some(per_page);
+        query.offset_num = Some(page.saturating_sub(1).saturating_mul(per_page));
+
+        Paginate {
+            query,
+            count,
+            page,
+            per_page,
+        }
+    }
+}
+
+impl<'a> QueryBuilder<'a, ()> {
+    /// `SELECT COUNT(*) AS total ...` по тем же FROM/JOIN/WHERE.
+    fn into_count_query(mut self) -> QueryBuilder<'a, (i64,)> {
+        self.order_by_items.clear();
+        self.limit_num = None;
+        self.offset_num = None;
+        self.lock = None;
+
+        let total = SelectItemNode::new(
+            SelectItem::ExprWithAlias {
+                expr: col("*").count_all().expr,
+                alias: Ident::new("total"),
+            },
+            SmallVec::new(),
+        );
+
+        // проекция влияет на число строк — считаем поверх подзапроса
+        let wrap = self.select_distinct
+            || !self.distinct_on_items.is_empty()
+            || !self.group_by_items.is_empty()
+            || self.having_clause.is_some()
+            || !self.set_ops.is_empty();
+
+        if !wrap {
+            self.select_items.clear();
+            self.select_items.push(total);
+            return self.retype();
+        }
+
+        // WITH запроса поднимаем наверх: внутри подзапроса он не рендерится
+        let mut outer = QueryBuilder::<'a, ()>::new_empty().dialect(self.dialect);
+        outer.optimize_cfg = self.optimize_cfg.clone();
+        outer.hooks = self.hooks.clone();
+        outer.timeout = self.timeout;
+        outer.with_items = std::mem::take(&mut self.with_items);
+        outer.with_recursive = self.with_recursive;
+
+        let mut outer = outer.from(self.r#as(COUNT_SUBQUERY_ALIAS));
+        outer.select_items.push(total);
+        outer.retype()
+    }
+}
+
+impl<'a, T> Paginate<'a, T> {
+    /// SQL страницы (с LIMIT/OFFSET).
+    #[inline]
+    pub fn to_sql(self) -> Result<(String, Vec<Param>)> {
+        self.query.to_sql()
+    }
+
+    /// SQL подсчёта общего числа строк.
+    #[inline]
+    pub fn count_to_sql(&self) -> Result<(String, Vec<Param>)> {
+        self.count.clone().to_sql()
+    }
+}
+
+impl<'a, T> std::future::IntoFuture for Paginate<'a, T>
+where
+    T: FromDbRow + 'a,
+{
+    type Output = ExecResult<Page<T>>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        let Paginate {
+            mut query,
+            mut count,
+            page,
+            per_page,
+        } = self;
+
+        Box::pin(async move {
+            query.route_read();
+            let (count_sql, count_params) = count.render_sql()?;
+            let (sql, params) = query.render_sql()?;
+
+            // один контекст на оба запроса: в транзакции это её соединение
+            let hooks = query.hooks.clone();
+            let info = QueryInfo::new(&count_sql, count_params.len(), count.dialect);
+            let fut =
+                query
+                    .exec_ctx
+                    .fetch_all_timeout::<(i64,)>(&count_sql, count_params, query.timeout);
+            let total = hooks
+                .observe(info, trace::rows_vec, fut)
+                .await?
+                .first()
+                .map_or(0, |(n,)| (*n).max(0) as u64);
+            let info = QueryInfo::new(&sql, params.len(), query.dialect);
+            let fut = query
+                .exec_ctx
+                .fetch_all_timeout::<T>(&sql, params, query.timeout);
+            let items = hooks.observe(info, trace::rows_vec, fut).await?;
+
+            Ok(Page {
+                items,
+                total,
+                page,
+                per_page,
+                last_page: total.div_ceil(per_page).max(1),
+            })
+        })
+    }
+}
@@ -0,0 +1,227 @@
+//! Сырой SQL с биндингами в духе `knex.raw`: `?` — значение, `??` — идентификатор,
+//! `:name` / `:name:` — то же по имени. Значения подставляются как плейсхолдеры,
+//! параметры идут в порядке текста, так что `$n` в PG нумеруются вместе с запросом.
+//! Строка в слоте значения — тоже значение (как в knex), а не имя колонки.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use smallvec::SmallVec;
+use sqlparser::ast::{self, Expr as SqlExpr};
+
+use super::args::{ArgList, IntoQBArg, QBArg};
+use super::where_clause::utils::parse_where_expr;
+use super::{Error, Result};
+use crate::expression::Expression;
+use crate::param::Param;
+
+/// Фрагмент SQL с позиционными биндингами:
+/// `raw_with("price > ? AND ?? = ?", (val(10), "users.name", val("x")))?`.
+/// Ошибка — если число `?`/`??` не совпадает с числом биндингов или SQL не парсится.
+pub fn raw_with<'a, L>(sql: &str, bindings: L) -> Result<Expression>
+where
+    L: ArgList<'a>,
+{
+    bind_positional(sql, bindings.into_vec()).map(into_expression)
+}
+
+/// Фрагмент SQL с именованными биндингами из map/списка пар:
+/// `raw_named("price > :min AND :col: = :name", [("min", val(10)), ...])?`.
+/// Ошибка — если в SQL есть имя без биндинга или SQL не парсится.
+pub fn raw_named<'a, I, K, V>(sql: &str, bindings: I) -> Result<Expression>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<String>,
+    V: IntoQBArg<'a>,
+{
+    let named = bindings
+        .into_iter()
+        .map(|(k, v)| (k.into(), v.into_qb_arg()))
+        .collect();
+    bind_named(sql, named).map(into_expression)
+}
+
+fn into_expression((expr, params): (SqlExpr, SmallVec<[Param; 8]>)) -> Expression {
+    Expression {
+        expr,
+        alias: None,
+        params,
+        mark_distinct_for_next: false,
+        error: None,
+    }
+}
+
+pub(crate) fn bind_positional(
+    sql: &str,
+    bindings: Vec<QBArg<'_>>,
+) -> Result<(SqlExpr, SmallVec<[Param; 8]>)> {
+    let total = bindings.len();
+    let mut it = bindings.into_iter();
+    let mut used = 0;
+    let mut next = |_: Slot| {
+        used += 1;
+        let arg = it.next().ok_or_else(|| Error::InvalidExpression {
+            reason: format!("more placeholders than bindings ({total})").into(),
+        })?;
+        resolve(arg)
+    };
+    let res = substitute(sql, false, &mut next)?;
+    if used != total {
+        return Err(Error::InvalidExpression {
+            reason: format!("{total} bindings, but {used} placeholders").into(),
+        });
+    }
+    Ok(res)
+}
+
+pub(crate) fn bind_named(
+    sql: &str,
+    bindings: Vec<(String, QBArg<'_>)>,
+) -> Result<(SqlExpr, SmallVec<[Param; 8]>)> {
+    let mut named: HashMap<String, (SqlExpr, Vec<Param>)> = HashMap::new();
+    for (name, arg) in bindings {
+        named.insert(name, resolve(arg)?);
+    }
+    let mut next = |slot: Slot| match slot {
+        Slot::Named(name) => named
+            .get(name)
+            .cloned()
+            .ok_or_else(|| Error::InvalidExpression {
+                reason: format!("no binding for :{name}").into(),
+            }),
+        Slot::Positional => Err(Error::InvalidExpression {
+            reason: "positional `?` in named raw SQL".into(),
+        }),
+    };
+    substitute(sql, true, &mut next)
+}
+
+fn resolve(arg: QBArg<'_>) -> Result<(SqlExpr, Vec<Param>)> {
+    arg.resolve_into_expr_with(|qb| qb.build_query_ast())
+}
+
+enum Slot<'s> {
+    Positional,
+    Named(&'s str),
+}
+
+/// Разбор текста: плейсхолдеры заменяются биндингами, строки в кавычках
+/// и приведения `::type` остаются как есть.
+fn substitute<'s, F>(
+    sql: &'s str,
+    named: bool,
+    next: &mut F,
+) -> Result<(SqlExpr, SmallVec<[Param; 8]>)>
+where
+    F: FnMut(Slot<'s>) -> Result<(SqlExpr, Vec<Param>)>,
+{
+    let bytes = sql.as_bytes();
+    let mut out = String::with_capacity(sql.len());
+    let mut params: SmallVec<[Param; 8]> = SmallVec::new();
+    let mut i = 0;
+    let mut copied = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            q @ (b'\'' | b'"' | b'`') => {
+                // кавычки-удвоения ('') — это закрытие и новое открытие, разбор тот же
+                i += 1;
+                while i < bytes.len() && bytes[i] != q {
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'?' => {
+                out.push_str(&sql[copied..i]);
+                let ident = bytes.get(i + 1) == Some(&b'?');
+                let (expr, p) = next(Slot::Positional)?;
+                if ident {
+                    push_ident(&mut out, &expr)?;
+                    i += 2;
+                } else {
+                    push_value(&mut out, &mut params, expr, p);
+                    i += 1;
+                }
+                copied = i;
+            }
+            b':' if named => {
+                if bytes.get(i + 1) == Some(&b':') {
+                    i += 2;
+                    continue;
+                }
+                let start = i + 1;
+                let mut end = start;
+                while end < bytes.len()
+                    && (bytes[end] == b'_' || bytes[end].is_ascii_alphanumeric())
+                {
+                    end += 1;
+                }
+                if end == start || bytes[start].is_ascii_digit() {
+                    i += 1;
+                    continue;
+                }
+                out.push_str(&sql[copied..i]);
+                let (expr, p) = next(Slot::Named(&sql[start..end]))?;
+                // `:name:` (но не `:name::type`) — идентификатор
+                let ident = bytes.get(end) == Some(&b':') && bytes.get(end + 1) != Some(&b':');
+                if ident {
+                    push_ident(&mut out, &expr)?;
+                    end += 1;
+                } else {
+                    push_value(&mut out, &mut params, expr, p);
+                }
+                i = end;
+                copied = i;
+            }
+            _ => i += 1,
+        }
+    }
+    out.push_str(&sql[copied..]);
+
+    Ok((parse_where_expr(&out)?, params))
+}
+
+/// Строка в слоте значения (`&str` превращается в `col()`) уходит в параметры
+/// целиком и в текст SQL не попадает.
+fn push_value(out: &mut String, params: &mut SmallVec<[Param; 8]>, expr: SqlExpr, p: Vec<Param>) {
+    let text = match expr {
+        SqlExpr::Identifier(id) => id.value,
+        SqlExpr::CompoundIdentifier(ids) => ids
+            .into_iter()
+            .map(|id| id.value)
+            .collect::<Vec<_>>()
+            .join("."),
+        SqlExpr::Value(_) | SqlExpr::Function(_) | SqlExpr::Nested(_) | SqlExpr::Subquery(_) => {
+            let _ = write!(out, "{expr}");
+            params.extend(p);
+            return;
+        }
+        _ => {
+            let _ = write!(out, "({expr})");
+            params.extend(p);
+            return;
+        }
+    };
+    out.push('?');
+    params.push(Param::Str(text));
+}
+
+fn push_ident(out: &mut String, expr: &SqlExpr) -> Result<()> {
+    let parts: &[ast::Ident] = match expr {
+        SqlExpr::Identifier(id) => std::slice::from_ref(id),
+        SqlExpr::CompoundIdentifier(ids) => ids,
+        _ => {
+            return Err(Error::InvalidExpression {
+                reason: format!("identifier binding expects a column/table name, got `{expr}`")
+                    .into(),
+            });
+        }
+    };
+    for (n, part) in parts.iter().enumerate() {
+        if n > 0 {
+            out.push('.');
+        }
+        let _ = write!(out, "\"{}\"", part.value.replace('"', "\"\""));
+    }
+    Ok(())
+}
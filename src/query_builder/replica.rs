@@ -0,0 +1,31 @@
+use crate::{
+    executor::ReadTarget,
+    query_builder::{ExecCtx, QueryBuilder},
+};
+
+impl<'a, T> QueryBuilder<'a, T> {
+    /// Читать с основного пула, даже если настроены реплики
+    /// (например, сразу после записи, когда реплика могла отстать).
+    pub fn on_primary(mut self) -> Self {
+        self.read_route.target = ReadTarget::Primary;
+        self
+    }
+
+    /// Читать с реплики, даже с `FOR UPDATE`/`FOR SHARE`. Без реплик
+    /// и внутри транзакции ничего не меняет.
+    pub fn on_replica(mut self) -> Self {
+        self.read_route.target = ReadTarget::Replica;
+        self
+    }
+
+    /// Перед исполнением SELECT: подменить основной пул пулом реплики.
+    /// INSERT/UPDATE/DELETE из этого билдера маршрут не используют.
+    pub(crate) fn route_read(&mut self) {
+        if !matches!(self.exec_ctx, ExecCtx::Pool(_)) {
+            return;
+        }
+        if let Some(pool) = self.read_route.replica(self.lock.is_some()) {
+            self.exec_ctx = ExecCtx::Pool(pool);
+        }
+    }
+}
@@ -34,6 +34,13 @@ impl<'a, T> QueryBuilder<'a, T> {
             match arg {
                 // ===== Expression =====
                 QBArg::Expr(e) => {
+                    let e = match e.checked() {
+                        Ok(e) => e,
+                        Err(err) => {
+                            self.push_builder_error(format!("select(): {err}"));
+                            continue;
+                        }
+                    };
                     let (alias_opt, expr, params) = e.into_projection_parts();
 
                     let item = if let Some(alias) = alias_opt {
@@ -61,7 +68,7 @@ impl<'a, T> QueryBuilder<'a, T> {
 
                 // ===== Closure → Subquery =====
                 QBArg::Closure(c) => {
-                    let built = c.call(QueryBuilder::new_empty());
+                    let built = c.call(QueryBuilder::new_empty().dialect(self.dialect));
                     match built.build_query_ast() {
                         Ok((q, p)) => {
                             let item = SelectItem::UnnamedExpr(SqlExpr::Subquery(Box::new(q)));
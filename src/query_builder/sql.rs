@@ -9,27 +9,69 @@ use crate::{
 
 impl<'a, T> QueryBuilder<'a, T> {
     #[inline]
-    pub fn to_sql(self) -> Result<(String, Vec<Param>)> {
+    pub fn to_sql(mut self) -> Result<(String, Vec<Param>)> {
+        // билдер исполнителя с кэшем SQL — через кэш
+        if self.sql_cache.is_some() {
+            return self.render_sql();
+        }
         let dialect = self.dialect.clone();
         let opt_cfg = self.optimize_cfg.clone();
+        let lock = self.lock.take();
 
         let (mut query_ast, params) = self.build_query_ast()?;
         optimizer::apply_query(&mut query_ast, &opt_cfg);
 
-        let rq = renderer::map_to_render_query(&query_ast);
+        let mut rq = renderer::map_to_render_query(&query_ast);
+        rq.lock = lock;
         let sql = form_sql(dialect, rq)?;
 
         Ok((sql, params))
     }
 
+    /// SQL с подставленными значениями параметров — **только для логов и отладки**.
+    /// Не выполняйте результат: безопасность даёт только `to_sql()` с bind'ами.
+    pub fn to_sql_debug(self) -> Result<String> {
+        let dialect = self.dialect;
+        let (sql, params) = self.to_sql()?;
+        Ok(renderer::inline_params(&sql, &params, dialect))
+    }
+
+    /// То же, что `to_sql()`, но SQL разложен по строкам (`renderer::format_sql`);
+    /// плейсхолдеры и параметры не меняются.
+    pub fn to_sql_pretty(self) -> Result<(String, Vec<Param>)> {
+        let dialect = self.dialect;
+        let (sql, params) = self.to_sql()?;
+        Ok((renderer::format_sql(&sql, dialect), params))
+    }
+
+    /// SQL и параметры для исполнения; с кэшем SQL при попадании
+    /// AST не строится — собираются только параметры
     pub(crate) fn render_sql(&mut self) -> Result<(String, Vec<Param>)> {
+        let Some(cache) = self.sql_cache.clone() else {
+            return self.render_sql_uncached();
+        };
+        let Some((key, n_params)) = self.shape() else {
+            return self.render_sql_uncached();
+        };
+        if let Some(sql) = cache.get(key, n_params) {
+            return Ok((sql, self.take_params()));
+        }
+        let (sql, params) = self.render_sql_uncached()?;
+        cache.insert(key, n_params, &sql);
+        Ok((sql, params))
+    }
+
+    fn render_sql_uncached(&mut self) -> Result<(String, Vec<Param>)> {
         let dialect = self.dialect.clone();
         let opt_cfg = self.optimize_cfg.clone();
+        // блокировка строк в sqlparser-AST не выражается полностью — ставим после маппинга
+        let lock = self.lock.clone();
 
         let (mut query_ast, params) = self.form_query_ast()?;
         optimizer::apply_query(&mut query_ast, &opt_cfg);
 
-        let rq = renderer::map_to_render_query(&query_ast);
+        let mut rq = renderer::map_to_render_query(&query_ast);
+        rq.lock = lock;
         let sql = form_sql(dialect, rq)?;
 
         Ok((sql, params))
@@ -51,6 +93,22 @@ impl<'a, T> InsertBuilder<'a, T> {
         Ok((sql, params))
     }
 
+    /// SQL с подставленными значениями параметров — **только для логов и отладки**.
+    /// Не выполняйте результат: безопасность даёт только `to_sql()` с bind'ами.
+    pub fn to_sql_debug(self) -> Result<String> {
+        let dialect = self.dialect;
+        let (sql, params) = self.to_sql()?;
+        Ok(renderer::inline_params(&sql, &params, dialect))
+    }
+
+    /// То же, что `to_sql()`, но SQL разложен по строкам (`renderer::format_sql`);
+    /// плейсхолдеры и параметры не меняются.
+    pub fn to_sql_pretty(self) -> Result<(String, Vec<Param>)> {
+        let dialect = self.dialect;
+        let (sql, params) = self.to_sql()?;
+        Ok((renderer::format_sql(&sql, dialect), params))
+    }
+
     #[inline]
     pub(crate) fn render_sql(&mut self) -> Result<(String, Vec<Param>)> {
         let dialect = self.dialect.clone();
@@ -81,6 +139,22 @@ impl<'a, T> UpdateBuilder<'a, T> {
         Ok((sql, params))
     }
 
+    /// SQL с подставленными значениями параметров — **только для логов и отладки**.
+    /// Не выполняйте результат: безопасность даёт только `to_sql()` с bind'ами.
+    pub fn to_sql_debug(self) -> Result<String> {
+        let dialect = self.dialect;
+        let (sql, params) = self.to_sql()?;
+        Ok(renderer::inline_params(&sql, &params, dialect))
+    }
+
+    /// То же, что `to_sql()`, но SQL разложен по строкам (`renderer::format_sql`);
+    /// плейсхолдеры и параметры не меняются.
+    pub fn to_sql_pretty(self) -> Result<(String, Vec<Param>)> {
+        let dialect = self.dialect;
+        let (sql, params) = self.to_sql()?;
+        Ok((renderer::format_sql(&sql, dialect), params))
+    }
+
     #[inline]
     pub(crate) fn render_sql(&mut self) -> Result<(String, Vec<Param>)> {
         let dialect = self.dialect.clone();
@@ -111,6 +185,22 @@ impl<'a, T> DeleteBuilder<'a, T> {
         Ok((sql, params))
     }
 
+    /// SQL с подставленными значениями параметров — **только для логов и отладки**.
+    /// Не выполняйте результат: безопасность даёт только `to_sql()` с bind'ами.
+    pub fn to_sql_debug(self) -> Result<String> {
+        let dialect = self.dialect;
+        let (sql, params) = self.to_sql()?;
+        Ok(renderer::inline_params(&sql, &params, dialect))
+    }
+
+    /// То же, что `to_sql()`, но SQL разложен по строкам (`renderer::format_sql`);
+    /// плейсхолдеры и параметры не меняются.
+    pub fn to_sql_pretty(self) -> Result<(String, Vec<Param>)> {
+        let dialect = self.dialect;
+        let (sql, params) = self.to_sql()?;
+        Ok((renderer::format_sql(&sql, dialect), params))
+    }
+
     #[inline]
     pub(crate) fn render_sql(&mut self) -> Result<(String, Vec<Param>)> {
         let dialect = self.dialect.clone();
@@ -132,13 +222,8 @@ fn form_sql(dialect: Dialect, rq: R::Query) -> Result<String> {
         Dialect::MySQL => renderer::cfg_mysql_knex(),
         Dialect::SQLite => renderer::cfg_sqlite_knex(),
     };
-    let sql = if matches!(cfg.policy, FeaturePolicy::Strict) {
-        renderer::try_render_sql_query(&rq, &cfg)?
-    } else {
-        renderer::render_sql_query(&rq, &cfg)
-    };
-
-    Ok(sql)
+    // валидация отсекает и то, что не исполнится при любой политике
+    Ok(renderer::try_render_sql_query(&rq, &cfg)?)
 }
 
 fn form_dml_sql(dialect: Dialect, rstmt: R::Stmt) -> Result<String> {
@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+use crate::{
+    executor::{
+        Error as ExecError, FromDbRow, QueryHooks, Result as ExecResult, RowStream, stream_timeout,
+        utils::error_stream,
+    },
+    param::Param,
+    query_builder::{
+        ExecCtx, InsertBuilder, QueryBuilder, Result, delete::DeleteBuilder, update::UpdateBuilder,
+    },
+    renderer::Dialect,
+};
+
+impl<'a, T> QueryBuilder<'a, T>
+where
+    T: FromDbRow + 'a,
+{
+    /// Выполнить SELECT и читать строки по одной (`sqlx::fetch`), не собирая весь `Vec<T>`.
+    ///
+    /// Ошибки сборки/рендера приходят первым (и единственным) элементом потока.
+    pub fn stream(mut self) -> RowStream<'a, T> {
+        self.route_read();
+        let rendered = self.render_sql();
+        fetch_rows(
+            self.exec_ctx,
+            &self.hooks,
+            self.dialect,
+            self.timeout,
+            rendered,
+        )
+    }
+}
+
+impl<'a, T> InsertBuilder<'a, T>
+where
+    T: FromDbRow + 'a,
+{
+    /// Потоковое чтение строк `INSERT ... RETURNING`.
+    pub fn stream(mut self) -> RowStream<'a, T> {
+        if let Err(e) = check_returning(!self.returning.is_empty(), self.dialect, "INSERT") {
+            return error_stream(e);
+        }
+        let rendered = self.render_sql();
+        fetch_rows(
+            self.exec_ctx,
+            &self.hooks,
+            self.dialect,
+            self.timeout,
+            rendered,
+        )
+    }
+}
+
+impl<'a, T> UpdateBuilder<'a, T>
+where
+    T: FromDbRow + 'a,
+{
+    /// Потоковое чтение строк `UPDATE ... RETURNING`.
+    pub fn stream(mut self) -> RowStream<'a, T> {
+        if let Err(e) = check_returning(!self.returning.is_empty(), self.dialect, "UPDATE") {
+            return error_stream(e);
+        }
+        let rendered = self.render_sql();
+        fetch_rows(
+            self.exec_ctx,
+            &self.hooks,
+            self.dialect,
+            self.timeout,
+            rendered,
+        )
+    }
+}
+
+impl<'a, T> DeleteBuilder<'a, T>
+where
+    T: FromDbRow + 'a,
+{
+    /// Потоковое чтение строк `DELETE ... RETURNING`.
+    pub fn stream(mut self) -> RowStream<'a, T> {
+        if let Err(e) = check_returning(!self.returning.is_empty(), self.dialect, "DELETE") {
+            return error_stream(e);
+        }
+        let rendered = self.render_sql();
+        fetch_rows(
+            self.exec_ctx,
+            &self.hooks,
+            self.dialect,
+            self.timeout,
+            rendered,
+        )
+    }
+}
+
+/// DML читается потоком только с `RETURNING`, и не в MySQL
+fn check_returning(has_returning: bool, dialect: Dialect, stmt: &str) -> ExecResult<()> {
+    if !has_returning {
+        return Err(ExecError::Unsupported(
+            format!(
+                "{stmt} без RETURNING: используйте .exec(). Для получения строк добавьте .returning(...)."
+            )
+            .into(),
+        ));
+    }
+    if dialect == Dialect::MySQL {
+        return Err(ExecError::Unsupported(
+            format!(
+                "MySQL не поддерживает {stmt} ... RETURNING; выполните .exec() и, при необходимости, отдельный SELECT."
+            )
+            .into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Общая часть `stream()`: поток строк с таймаутом и хуками.
+/// SQL копируется, только если хуки подключены.
+fn fetch_rows<'a, T>(
+    exec_ctx: ExecCtx<'a>,
+    hooks: &QueryHooks,
+    dialect: Dialect,
+    timeout: Option<Duration>,
+    rendered: Result<(String, Vec<Param>)>,
+) -> RowStream<'a, T>
+where
+    T: FromDbRow + 'a,
+{
+    let (sql, params) = match rendered {
+        Ok(r) => r,
+        Err(e) => return error_stream(e.into()),
+    };
+    let n_params = params.len();
+    let observed = hooks.is_active().then(|| sql.clone());
+    let stream = stream_timeout(exec_ctx.fetch_stream(sql, params), timeout);
+    match observed {
+        Some(sql) => hooks.observe_stream(sql, n_params, dialect, stream),
+        None => stream,
+    }
+}
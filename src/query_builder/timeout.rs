@@ -0,0 +1,15 @@
+use std::time::Duration;
+
+use crate::query_builder::QueryBuilder;
+
+impl<'a, T> QueryBuilder<'a, T> {
+    /// Ограничить время выполнения: по истечении — `executor::Error::Timeout`.
+    /// Кроме ожидания на клиенте, лимит ставится и на сервере: `statement_timeout`
+    /// в Postgres, `MAX_EXECUTION_TIME` для SELECT в MySQL. Наследуется
+    /// INSERT/UPDATE/DELETE, собранными из этого билдера; внутри транзакции
+    /// MySQL таймаут для них не поддерживается — `executor::Error::Unsupported`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
@@ -1,6 +1,6 @@
 use std::pin::Pin;
 
-use crate::executor::{DbRow, Result as ExecResult};
+use crate::executor::{FromDbRow, Result as ExecResult, RowStream};
 
 mod pool_query;
 mod send_exec;
@@ -12,7 +12,7 @@ pub use tx_query::TxQuery;
 
 impl<'a, T> std::future::IntoFuture for PoolQuery<'a, T>
 where
-    T: for<'r> sqlx::FromRow<'r, DbRow> + Send + Unpin + 'a,
+    T: FromDbRow + 'a,
 {
     type Output = ExecResult<Vec<T>>;
     type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + 'a>>;
@@ -25,7 +25,7 @@ where
 
 impl<'a, T> std::future::IntoFuture for TxQuery<'a, T>
 where
-    T: for<'r> sqlx::FromRow<'r, DbRow> + Send + Unpin + 'a,
+    T: FromDbRow + 'a,
 {
     type Output = ExecResult<Vec<T>>;
     type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + 'a>>;
@@ -35,3 +35,25 @@ where
         self.0.into_future()
     }
 }
+
+impl<'a, T> PoolQuery<'a, T>
+where
+    T: FromDbRow + 'a,
+{
+    /// Построчное чтение результата через пул (см. `QueryBuilder::stream`).
+    #[inline]
+    pub fn stream(self) -> RowStream<'a, T> {
+        self.0.stream()
+    }
+}
+
+impl<'a, T> TxQuery<'a, T>
+where
+    T: FromDbRow + 'a,
+{
+    /// Построчное чтение результата на соединении транзакции.
+    #[inline]
+    pub fn stream(self) -> RowStream<'a, T> {
+        self.0.stream()
+    }
+}
@@ -1,12 +1,15 @@
-use std::pin::Pin;
+use std::{pin::Pin, time::Duration};
 
 use tokio::task::JoinHandle;
 
 use crate::{
-    executor::{DbPool, DbRow, Error as ExecError, Result as ExecResult, utils},
+    executor::{
+        DbPool, Error as ExecError, FromDbRow, QueryHooks, QueryInfo, Result as ExecResult, trace,
+        utils,
+    },
     param::Param,
     query_builder::{
-        InsertBuilder, delete::DeleteBuilder, typestate::send_query::SendQuery,
+        ExecCtx, InsertBuilder, delete::DeleteBuilder, typestate::send_query::SendQuery,
         update::UpdateBuilder,
     },
     renderer::Dialect,
@@ -17,6 +20,8 @@ pub struct SendExec {
     pool: DbPool,
     sql: String,
     params: Vec<Param>,
+    hooks: QueryHooks,
+    timeout: Option<Duration>,
 }
 
 impl std::future::IntoFuture for SendExec {
@@ -24,16 +29,30 @@ impl std::future::IntoFuture for SendExec {
     type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'static>>;
 
     fn into_future(self) -> Self::IntoFuture {
-        let SendExec { pool, sql, params } = self;
+        let SendExec {
+            pool,
+            sql,
+            params,
+            hooks,
+            timeout,
+        } = self;
         Box::pin(async move {
-            match pool {
-                #[cfg(feature = "postgres")]
-                DbPool::Postgres(p) => utils::execute_pg(&p, &sql, params).await,
-                #[cfg(feature = "mysql")]
-                DbPool::MySql(p) => utils::execute_mysql(&p, &sql, params).await,
-                #[cfg(feature = "sqlite")]
-                DbPool::Sqlite(p) => utils::execute_sqlite(&p, &sql, params).await,
-            }
+            let query = QueryInfo::new(&sql, params.len(), pool.dialect());
+            let fut = async {
+                if timeout.is_some() {
+                    let mut ctx = ExecCtx::Pool(pool);
+                    return ctx.execute_timeout(&sql, params, timeout).await;
+                }
+                match pool {
+                    #[cfg(feature = "postgres")]
+                    DbPool::Postgres(p) => utils::execute_pg(&p, &sql, params).await,
+                    #[cfg(feature = "mysql")]
+                    DbPool::MySql(p) => utils::execute_mysql(&p, &sql, params).await,
+                    #[cfg(feature = "sqlite")]
+                    DbPool::Sqlite(p) => utils::execute_sqlite(&p, &sql, params).await,
+                }
+            };
+            hooks.observe(query, trace::rows_affected, fut).await
         })
     }
 }
@@ -41,7 +60,7 @@ impl std::future::IntoFuture for SendExec {
 // Вспомогательный удобный spawn для обоих типов:
 impl<T> SendQuery<T>
 where
-    T: for<'r> sqlx::FromRow<'r, DbRow> + Send + Unpin + 'static,
+    T: FromDbRow + 'static,
 {
     #[inline]
     pub fn spawn(self) -> JoinHandle<ExecResult<Vec<T>>> {
@@ -57,7 +76,6 @@ impl SendExec {
 
 impl<'a, T> InsertBuilder<'a, T> {
     pub fn into_send(mut self) -> ExecResult<SendExec> {
-        use crate::query_builder::ExecCtx;
         if self.returning.is_empty() {
             return Err(ExecError::Unsupported(
                 "INSERT без RETURNING: используйте .exec(). Для чтения результатов добавьте .returning(...).".into()
@@ -76,13 +94,18 @@ impl<'a, T> InsertBuilder<'a, T> {
             ExecCtx::Pool(p) => p,
             _ => return Err(ExecError::MissingConnection),
         };
-        Ok(SendExec { pool, sql, params })
+        Ok(SendExec {
+            pool,
+            sql,
+            params,
+            hooks: self.hooks,
+            timeout: self.timeout,
+        })
     }
 }
 
 impl<'a, T> UpdateBuilder<'a, T> {
     pub fn into_send(mut self) -> ExecResult<SendExec> {
-        use crate::query_builder::ExecCtx;
         if self.returning.is_empty() {
             return Err(ExecError::Unsupported(
                 "UPDATE without RETURNING: use `.exec()` instead.".into(),
@@ -101,13 +124,18 @@ impl<'a, T> UpdateBuilder<'a, T> {
             ExecCtx::Pool(p) => p,
             _ => return Err(ExecError::MissingConnection),
         };
-        Ok(SendExec { pool, sql, params })
+        Ok(SendExec {
+            pool,
+            sql,
+            params,
+            hooks: self.hooks,
+            timeout: self.timeout,
+        })
     }
 }
 
 impl<'a, T> DeleteBuilder<'a, T> {
     pub fn into_send(mut self) -> ExecResult<SendExec> {
-        use crate::query_builder::ExecCtx;
         if self.returning.is_empty() {
             return Err(ExecError::Unsupported(
                 "DELETE without RETURNING: use `.exec()` instead.".into(),
@@ -119,6 +147,12 @@ impl<'a, T> DeleteBuilder<'a, T> {
             ExecCtx::Pool(p) => p,
             _ => return Err(ExecError::MissingConnection),
         };
-        Ok(SendExec { pool, sql, params })
+        Ok(SendExec {
+            pool,
+            sql,
+            params,
+            hooks: self.hooks,
+            timeout: self.timeout,
+        })
     }
 }
@@ -1,45 +1,63 @@
-use std::{marker::PhantomData, pin::Pin};
+use std::{marker::PhantomData, pin::Pin, time::Duration};
 
 use crate::{
-    executor::{DbPool, DbRow, Error as ExecError, Result as ExecResult, utils},
+    executor::{
+        DbPool, Error as ExecError, FromDbRow, QueryHooks, QueryInfo, Result as ExecResult, trace,
+        utils,
+    },
     param::Param,
+    query_builder::ExecCtx,
 };
 
 pub struct SendQuery<T> {
     pool: DbPool,
     sql: String,
     params: Vec<Param>,
+    hooks: QueryHooks,
+    timeout: Option<Duration>,
     _t: PhantomData<T>,
 }
 
 impl<T> std::future::IntoFuture for SendQuery<T>
 where
-    T: for<'r> sqlx::FromRow<'r, DbRow> + Send + Unpin + 'static,
+    T: FromDbRow + 'static,
 {
     type Output = ExecResult<Vec<T>>;
     type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'static>>;
 
     fn into_future(self) -> Self::IntoFuture {
         let SendQuery {
-            pool, sql, params, ..
+            pool,
+            sql,
+            params,
+            hooks,
+            timeout,
+            ..
         } = self;
         Box::pin(async move {
-            match pool {
-                #[cfg(feature = "postgres")]
-                DbPool::Postgres(p) => utils::fetch_typed_pg::<T>(&p, &sql, params).await,
-                #[cfg(feature = "mysql")]
-                DbPool::MySql(p) => utils::fetch_typed_mysql::<T>(&p, &sql, params).await,
-                #[cfg(feature = "sqlite")]
-                DbPool::Sqlite(p) => utils::fetch_typed_sqlite::<T>(&p, &sql, params).await,
-            }
-            .map_err(Into::into)
+            let query = QueryInfo::new(&sql, params.len(), pool.dialect());
+            let fut = async {
+                if timeout.is_some() {
+                    let mut ctx = ExecCtx::Pool(pool);
+                    return ctx.fetch_all_timeout(&sql, params, timeout).await;
+                }
+                match pool {
+                    #[cfg(feature = "postgres")]
+                    DbPool::Postgres(p) => utils::fetch_typed_pg::<T>(&p, &sql, params).await,
+                    #[cfg(feature = "mysql")]
+                    DbPool::MySql(p) => utils::fetch_typed_mysql::<T>(&p, &sql, params).await,
+                    #[cfg(feature = "sqlite")]
+                    DbPool::Sqlite(p) => utils::fetch_typed_sqlite::<T>(&p, &sql, params).await,
+                }
+            };
+            hooks.observe(query, trace::rows_vec, fut).await
         })
     }
 }
 
 impl<'a, T> super::PoolQuery<'a, T> {
     pub fn into_send(mut self) -> ExecResult<SendQuery<T>> {
-        use crate::query_builder::ExecCtx;
+        self.0.route_read();
         let (sql, params) = self.0.render_sql()?;
         let pool = match self.0.exec_ctx {
             ExecCtx::Pool(p) => p,
@@ -49,6 +67,8 @@ impl<'a, T> super::PoolQuery<'a, T> {
             pool,
             sql,
             params,
+            hooks: self.0.hooks,
+            timeout: self.0.timeout,
             _t: PhantomData,
         })
     }
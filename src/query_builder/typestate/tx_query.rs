@@ -1,8 +1,11 @@
+use std::time::Duration;
+
 use crate::{
-    expression::JoinOnBuilder,
+    expression::{JoinOnBuilder, WindowBuilder},
     param::Param,
     query_builder::{
-        InsertBuilder, QueryBuilder, QueryOne, QueryOptional, Result as QBResult,
+        InsertBuilder, KeysetCursor, KeysetQuery, Paginate, QueryBuilder, QueryOne, QueryOptional,
+        Result as QBResult,
         args::{ArgList, IntoQBArg},
         delete::DeleteBuilder,
         join::JoinOnArg,
@@ -145,6 +148,16 @@ impl<'a, T> TxQuery<'a, T> {
         Self(self.0.group_by(items))
     }
 
+    // WINDOW
+
+    #[inline]
+    pub fn window<F>(self, name: &str, f: F) -> Self
+    where
+        F: FnOnce(WindowBuilder) -> WindowBuilder,
+    {
+        Self(self.0.window(name, f))
+    }
+
     // LIMIT
 
     #[inline]
@@ -162,6 +175,42 @@ impl<'a, T> TxQuery<'a, T> {
         Self(self.0.limit_offset(limit, offset))
     }
 
+    // LOCK
+
+    #[inline]
+    pub fn for_update(self) -> Self {
+        Self(self.0.for_update())
+    }
+
+    #[inline]
+    pub fn for_share(self) -> Self {
+        Self(self.0.for_share())
+    }
+
+    #[inline]
+    pub fn for_no_key_update(self) -> Self {
+        Self(self.0.for_no_key_update())
+    }
+
+    #[inline]
+    pub fn skip_locked(self) -> Self {
+        Self(self.0.skip_locked())
+    }
+
+    #[inline]
+    pub fn no_wait(self) -> Self {
+        Self(self.0.no_wait())
+    }
+
+    #[inline]
+    pub fn of<I, S>(self, tables: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self(self.0.of(tables))
+    }
+
     // ORDER BY
 
     #[inline]
@@ -169,6 +218,11 @@ impl<'a, T> TxQuery<'a, T> {
         Self(self.0.order_by(items))
     }
 
+    #[inline]
+    pub fn order_by_desc<A: ArgList<'a>>(self, items: A) -> Self {
+        Self(self.0.order_by_desc(items))
+    }
+
     // SCHEMA
 
     #[inline]
@@ -201,6 +255,18 @@ impl<'a, T> TxQuery<'a, T> {
         self.0.to_sql()
     }
 
+    /// См. `QueryBuilder::to_sql_debug` — только для логов
+    #[inline]
+    pub fn to_sql_debug(self) -> QBResult<String> {
+        self.0.to_sql_debug()
+    }
+
+    /// См. `QueryBuilder::to_sql_pretty`
+    #[inline]
+    pub fn to_sql_pretty(self) -> QBResult<(String, Vec<Param>)> {
+        self.0.to_sql_pretty()
+    }
+
     // UNION
 
     #[inline]
@@ -872,6 +938,29 @@ impl<'a, T> TxQuery<'a, T> {
         Self(self.0.or_where_raw(raw))
     }
 
+    #[inline]
+    pub fn where_raw_with<L>(self, raw: &str, bindings: L) -> Self
+    where
+        L: ArgList<'a>,
+    {
+        Self(self.0.where_raw_with(raw, bindings))
+    }
+
+    #[inline]
+    pub fn or_where_raw_with<L>(self, raw: &str, bindings: L) -> Self
+    where
+        L: ArgList<'a>,
+    {
+        Self(self.0.or_where_raw_with(raw, bindings))
+    }
+
+    // TIMEOUT
+
+    #[inline]
+    pub fn timeout(self, timeout: Duration) -> Self {
+        Self(self.0.timeout(timeout))
+    }
+
     // FETCH
 
     #[inline]
@@ -883,4 +972,14 @@ impl<'a, T> TxQuery<'a, T> {
     pub fn optional(self) -> QueryOptional<'a, T> {
         self.0.optional()
     }
+
+    #[inline]
+    pub fn paginate(self, page: u64, per_page: u64) -> Paginate<'a, T> {
+        self.0.paginate(page, per_page)
+    }
+
+    #[inline]
+    pub fn paginate_after(self, cursor: Option<KeysetCursor>) -> KeysetQuery<'a, T> {
+        self.0.paginate_after(cursor)
+    }
 }
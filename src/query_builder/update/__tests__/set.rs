@@ -1,4 +1,5 @@
 use crate::expression::{col, lit, val};
+use crate::query_builder::DEFAULT_DIALECT;
 use crate::query_builder::args::ArgList;
 use crate::query_builder::update::set::parse_assignments_pairs;
 use smallvec::SmallVec;
@@ -9,7 +10,7 @@ fn set_parse_single_pair_collects_param() {
     let flat: Vec<crate::query_builder::args::QBArg> = (col("a"), val(1)).into_vec();
     let mut carry: SmallVec<[crate::param::Param; 8]> = SmallVec::new();
 
-    let res = parse_assignments_pairs(&mut carry, flat).expect("ok");
+    let res = parse_assignments_pairs(&mut carry, flat, DEFAULT_DIALECT).expect("ok");
     assert_eq!(res.len(), 1);
     assert_eq!(res[0].col, "a");
     // Правое выражение могло дать bind-параметр (если val -> bind)
@@ -26,7 +27,7 @@ fn set_parse_multiple_pairs_preserves_order_and_params() {
     let flat = (col("a"), val(1), col("b"), val(2)).into_vec();
     let mut carry: SmallVec<[crate::param::Param; 8]> = SmallVec::new();
 
-    let res = parse_assignments_pairs(&mut carry, flat).expect("ok");
+    let res = parse_assignments_pairs(&mut carry, flat, DEFAULT_DIALECT).expect("ok");
     assert_eq!(res.len(), 2);
     assert_eq!(res[0].col, "a");
     assert_eq!(res[1].col, "b");
@@ -39,7 +40,7 @@ fn set_left_compound_identifier_takes_last_segment() {
     let flat = (col("t.a"), val(1)).into_vec();
     let mut carry: SmallVec<[crate::param::Param; 8]> = SmallVec::new();
 
-    let res = parse_assignments_pairs(&mut carry, flat).expect("ok");
+    let res = parse_assignments_pairs(&mut carry, flat, DEFAULT_DIALECT).expect("ok");
     assert_eq!(res.len(), 1);
     assert_eq!(res[0].col, "a");
 }
@@ -47,7 +48,7 @@ fn set_left_compound_identifier_takes_last_segment() {
 #[test]
 fn set_empty_list_error() {
     let mut carry: SmallVec<[crate::param::Param; 8]> = SmallVec::new();
-    let err = parse_assignments_pairs(&mut carry, ().into_vec()).unwrap_err();
+    let err = parse_assignments_pairs(&mut carry, ().into_vec(), DEFAULT_DIALECT).unwrap_err();
     assert!(
         err.contains("empty assignment list"),
         "unexpected err: {err}"
@@ -60,7 +61,7 @@ fn set_odd_number_of_items_error() {
     let flat = (col("a"), val(1), col("b")).into_vec();
     let mut carry: SmallVec<[crate::param::Param; 8]> = SmallVec::new();
 
-    let err = parse_assignments_pairs(&mut carry, flat).unwrap_err();
+    let err = parse_assignments_pairs(&mut carry, flat, DEFAULT_DIALECT).unwrap_err();
     assert!(
         err.contains("expected pairs (col, value)"),
         "unexpected err: {err}"
@@ -73,7 +74,7 @@ fn set_left_not_identifier_error() {
     let flat = (lit("not_ident"), val(1)).into_vec();
     let mut carry: SmallVec<[crate::param::Param; 8]> = SmallVec::new();
 
-    let err = parse_assignments_pairs(&mut carry, flat).unwrap_err();
+    let err = parse_assignments_pairs(&mut carry, flat, DEFAULT_DIALECT).unwrap_err();
     assert!(
         err.contains("left item must be a column identifier")
             || err.contains("invalid compound identifier"),
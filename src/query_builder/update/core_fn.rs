@@ -1,7 +1,10 @@
 use std::marker::PhantomData;
 use std::pin::Pin;
+use std::time::Duration;
 
-use crate::executor::{DbPool, DbRow, Error as ExecError, Result as ExecResult};
+use crate::executor::{
+    DbPool, Error as ExecError, FromDbRow, QueryHooks, QueryInfo, Result as ExecResult, trace,
+};
 use crate::optimizer::OptimizeConfig;
 use crate::query_builder::args::{ArgList, QBArg};
 use crate::query_builder::ast::FromItem;
@@ -13,15 +16,11 @@ use sqlparser::ast::{Expr as SqlExpr, ObjectName, SelectItem, SqliteOnConflict};
 
 use super::set::parse_assignments_pairs;
 
-#[cfg(feature = "mysql")]
-use crate::executor::utils::fetch_typed_mysql;
 #[cfg(feature = "postgres")]
 use crate::executor::utils::fetch_typed_pg;
 #[cfg(feature = "sqlite")]
 use crate::executor::utils::fetch_typed_sqlite;
 
-#[cfg(feature = "mysql")]
-use crate::executor::transaction_utils::fetch_typed_mysql_exec;
 #[cfg(feature = "postgres")]
 use crate::executor::transaction_utils::fetch_typed_pg_exec;
 #[cfg(feature = "sqlite")]
@@ -46,6 +45,8 @@ pub struct UpdateBuilder<'a, T> {
     pub(crate) dialect: Dialect,
     pub(crate) exec_ctx: ExecCtx<'a>,
     pub(crate) optimize_cfg: OptimizeConfig,
+    pub(crate) hooks: QueryHooks,
+    pub(crate) timeout: Option<Duration>,
     _t: PhantomData<T>,
 }
 
@@ -66,6 +67,8 @@ impl<'a, T> UpdateBuilder<'a, T> {
             sqlite_or: None,
             exec_ctx: qb.exec_ctx,
             optimize_cfg: qb.optimize_cfg,
+            hooks: qb.hooks,
+            timeout: qb.timeout,
             _t: PhantomData,
         }
     }
@@ -76,7 +79,7 @@ impl<'a, T> UpdateBuilder<'a, T> {
         L: ArgList<'a>,
     {
         let flat = assignments.into_vec();
-        match parse_assignments_pairs(&mut self.params, flat) {
+        match parse_assignments_pairs(&mut self.params, flat, self.dialect) {
             Ok(items) => self.set.extend(items),
             Err(msg) => self.push_builder_error(msg),
         }
@@ -175,7 +178,9 @@ impl<'a, T> UpdateBuilder<'a, T> {
     /// Выполнить UPDATE без RETURNING и вернуть rows_affected.
     pub async fn exec(mut self) -> ExecResult<u64> {
         let (sql, params) = self.render_sql().map_err(ExecError::from)?;
-        self.exec_ctx.execute(&sql, params).await
+        let query = QueryInfo::new(&sql, params.len(), self.dialect);
+        let fut = self.exec_ctx.execute_timeout(&sql, params, self.timeout);
+        self.hooks.observe(query, trace::rows_affected, fut).await
     }
 
     /// SQLite: UPDATE OR REPLACE ...
@@ -227,7 +232,7 @@ impl<'a, T> UpdateBuilder<'a, T> {
         let mut params: SmallVec<[Param; 8]> = SmallVec::new();
 
         for it in items {
-            match it.resolve_into_expr_with(|qb| qb.build_query_ast()) {
+            match it.resolve_into_expr_in(self.dialect, |qb| qb.build_query_ast()) {
                 Ok((e, p)) => {
                     exprs.push(e);
                     if !p.is_empty() {
@@ -296,7 +301,7 @@ impl<'a, T> QueryBuilder<'a, T> {
 
 impl<'a, T> std::future::IntoFuture for UpdateBuilder<'a, T>
 where
-    T: for<'r> sqlx::FromRow<'r, DbRow> + Send + Unpin + 'a,
+    T: FromDbRow + 'a,
 {
     type Output = ExecResult<Vec<T>>;
     type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'a>>;
@@ -305,17 +310,38 @@ where
         Box::pin(async move {
             let (sql, params) = self.render_sql().map_err(ExecError::from)?;
 
-            match self.exec_ctx {
-                ExecCtx::None => Err(ExecError::MissingConnection),
-
-                // ---- исполнение через пул ----
-                ExecCtx::Pool(pool) => match pool {
+            let query = QueryInfo::new(&sql, params.len(), self.dialect);
+            let dialect = self.dialect;
+            let (mut ctx, hooks, timeout) = (self.exec_ctx, self.hooks, self.timeout);
+            let fut = async {
+                // MySQL RETURNING отклоняется ниже, не доходя до сервера
+                if timeout.is_some() && dialect != Dialect::MySQL {
+                    return ctx.fetch_all_timeout::<T>(&sql, params, timeout).await;
+                }
+                match ctx {
+                    ExecCtx::None => Err(ExecError::MissingConnection),
+
+                    // ---- исполнение через пул ----
+                    ExecCtx::Pool(pool) => match pool {
+                        #[cfg(feature = "postgres")]
+                        DbPool::Postgres(p) => fetch_typed_pg::<T>(&p, &sql, params).await,
+                        #[cfg(feature = "mysql")]
+                        DbPool::MySql(_) => {
+                            // В MySQL UPDATE … RETURNING нет — отдаём понятную ошибку
+                            Err(ExecError::Unsupported(
+                                "MySQL does not support UPDATE ... RETURNING; use .exec() then SELECT"
+                                    .into(),
+                            ))
+                        }
+                        #[cfg(feature = "sqlite")]
+                        DbPool::Sqlite(p) => fetch_typed_sqlite::<T>(&p, &sql, params).await,
+                    },
+
+                    // ---- исполнение ВНУТРИ транзакции ----
                     #[cfg(feature = "postgres")]
-                    DbPool::Postgres(p) => fetch_typed_pg::<T>(&p, &sql, params)
-                        .await
-                        .map_err(Into::into),
+                    ExecCtx::PgConn(conn) => fetch_typed_pg_exec::<_, T>(conn, &sql, params).await,
                     #[cfg(feature = "mysql")]
-                    DbPool::MySql(p) => {
+                    ExecCtx::MySqlConn(_) => {
                         // В MySQL UPDATE … RETURNING нет — отдаём понятную ошибку
                         Err(ExecError::Unsupported(
                             "MySQL does not support UPDATE ... RETURNING; use .exec() then SELECT"
@@ -323,27 +349,12 @@ where
                         ))
                     }
                     #[cfg(feature = "sqlite")]
-                    DbPool::Sqlite(p) => fetch_typed_sqlite::<T>(&p, &sql, params)
-                        .await
-                        .map_err(Into::into),
-                },
-
-                // ---- исполнение ВНУТРИ транзакции ----
-                #[cfg(feature = "postgres")]
-                ExecCtx::PgConn(conn) => fetch_typed_pg_exec::<_, T>(conn, &sql, params).await,
-                #[cfg(feature = "mysql")]
-                ExecCtx::MySqlConn(conn) => {
-                    // В MySQL UPDATE … RETURNING нет — отдаём понятную ошибку
-                    Err(ExecError::Unsupported(
-                        "MySQL does not support UPDATE ... RETURNING; use .exec() then SELECT"
-                            .into(),
-                    ))
-                }
-                #[cfg(feature = "sqlite")]
-                ExecCtx::SqliteConn(conn) => {
-                    fetch_typed_sqlite_exec::<_, T>(conn, &sql, params).await
+                    ExecCtx::SqliteConn(conn) => {
+                        fetch_typed_sqlite_exec::<_, T>(conn, &sql, params).await
+                    }
                 }
-            }
+            };
+            hooks.observe(query, trace::rows_vec, fut).await
         })
     }
 }
@@ -359,7 +370,12 @@ impl<'a, T> UpdateBuilder<'a, T> {
         }
 
         let (sql, params) = self.render_sql().map_err(ExecError::from)?;
-        self.exec_ctx.execute_send(sql, params)
+        let query = QueryInfo::new(&sql, params.len(), self.dialect);
+        let fut = self
+            .exec_ctx
+            .clone()
+            .execute_send_within(sql.clone(), params, self.timeout)?;
+        Ok(self.hooks.observe_send(query, trace::rows_affected, fut))
     }
 
     // // опционально, если есть RETURNING-путь:
@@ -367,7 +383,7 @@ impl<'a, T> UpdateBuilder<'a, T> {
     //     mut self,
     // ) -> ExecResult<impl core::future::Future<Output = ExecResult<Vec<R>>> + Send + 'static>
     // where
-    //     R: for<'r> sqlx::FromRow<'r, DbRow> + Send + Unpin + 'static,
+    //     R: FromDbRow + 'static,
     // {
     //     if self.returning.is_empty() {
     //         return Err(ExecError::Unsupported(
@@ -1,5 +1,6 @@
 use crate::param::Param;
 use crate::query_builder::args::QBArg;
+use crate::renderer::Dialect;
 use smallvec::SmallVec;
 use sqlparser::ast::Expr as SqlExpr;
 
@@ -14,6 +15,7 @@ pub(crate) struct Assignment {
 pub(crate) fn parse_assignments_pairs(
     carry_params: &mut SmallVec<[Param; 8]>,
     flat: Vec<QBArg>,
+    dialect: Dialect,
 ) -> Result<SmallVec<[Assignment; 8]>, std::borrow::Cow<'static, str>> {
     if flat.is_empty() {
         return Err("set(): empty assignment list".into());
@@ -40,7 +42,7 @@ pub(crate) fn parse_assignments_pairs(
         };
 
         // Правая часть — произвольное выражение (в т.ч. с параметрами/подзапросом)
-        match kv.resolve_into_expr_with(|qb| qb.build_query_ast()) {
+        match kv.resolve_into_expr_in(dialect, |qb| qb.build_query_ast()) {
             Ok((expr, mut params)) => {
                 if !params.is_empty() {
                     carry_params.extend(params.drain(..));
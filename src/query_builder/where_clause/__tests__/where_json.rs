@@ -98,6 +98,7 @@ fn mysql_where_json_uses_functions() {
 
     // JSON_CONTAINS
     let qb1 = QB::new_empty()
+        .dialect(crate::renderer::Dialect::MySQL)
         .from("t")
         .select("*")
         .where_json_object("payload", r#"{"a":1}"#);
@@ -112,6 +113,7 @@ fn mysql_where_json_uses_functions() {
 
     // JSON_CONTAINS_PATH
     let qb2 = QB::new_empty()
+        .dialect(crate::renderer::Dialect::MySQL)
         .from("t")
         .select("*")
         .where_json_path("payload", "$.a");
@@ -178,6 +180,7 @@ fn sqlite_where_json_path_is_is_not_null_over_json_extract() {
     use sqlparser::ast::Expr as E;
 
     let qb = QB::new_empty()
+        .dialect(crate::renderer::Dialect::SQLite)
         .from("t")
         .select("*")
         .where_json_path("payload", "$.a");
@@ -204,6 +207,7 @@ fn sqlite_where_json_superset_subset_are_not_exists() {
     use sqlparser::ast::Expr as E;
 
     let qb1 = QB::new_empty()
+        .dialect(crate::renderer::Dialect::SQLite)
         .from("t")
         .select("*")
         .where_json_superset_of("payload", r#"{"a":1}"#);
@@ -214,6 +218,7 @@ fn sqlite_where_json_superset_subset_are_not_exists() {
     }
 
     let qb2 = QB::new_empty()
+        .dialect(crate::renderer::Dialect::SQLite)
         .from("t")
         .select("*")
         .where_json_subset_of(r#"{"a":1}"#, "payload");
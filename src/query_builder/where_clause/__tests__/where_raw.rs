@@ -1,5 +1,6 @@
 use super::extract_where;
 use crate::expression::helpers::{col, val};
+use crate::param::Param;
 use crate::query_builder::QueryBuilder;
 use sqlparser::ast::{BinaryOperator as BO, Expr as SqlExpr};
 
@@ -101,3 +102,43 @@ fn multiple_where_raw_calls_chain_with_and() {
         other => panic!("expected BinaryOp AND, got {:?}", other),
     }
 }
+
+#[test]
+fn where_raw_with_binds_params() {
+    let (sql, params) = QB::new_empty()
+        .dialect(crate::renderer::Dialect::Postgres)
+        .from("users")
+        .select("*")
+        .where_raw_with("?? = ?", ("users.name", val("bob")))
+        .or_where_raw_with("age BETWEEN ? AND ?", (val(18), val(30)))
+        .to_sql()
+        .expect("ok");
+    assert_eq!(
+        sql,
+        r#"SELECT * FROM "users" WHERE ("users"."name" = $1) OR (("age" >= $2) AND ("age" <= $3))"#
+    );
+    assert_eq!(params.len(), 3);
+}
+
+#[test]
+fn where_raw_with_count_mismatch_records_builder_error() {
+    let res = QB::new_empty()
+        .from("users")
+        .select("*")
+        .where_raw_with("a = ? AND b = ?", (val(1), val(2), val(3)))
+        .to_sql();
+    assert!(res.is_err());
+}
+
+#[test]
+fn where_raw_with_binds_str_as_value_not_sql() {
+    let (sql, params) = QB::new_empty()
+        .dialect(crate::renderer::Dialect::Postgres)
+        .from("users")
+        .select("id")
+        .where_raw_with("name = ?", ("1 OR 1=1",))
+        .to_sql()
+        .expect("ok");
+    assert_eq!(sql, r#"SELECT "id" FROM "users" WHERE "name" = $1"#);
+    assert!(matches!(params.as_slice(), [Param::Str(s)] if s == "1 OR 1=1"));
+}
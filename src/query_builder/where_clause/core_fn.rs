@@ -111,7 +111,7 @@ impl<'a, T> QueryBuilder<'a, T> {
                     }
                 },
                 QBArg::Closure(c) => {
-                    let built = c.call(QueryBuilder::new_empty());
+                    let built = c.call(QueryBuilder::new_empty().dialect(self.dialect));
                     match built.build_query_ast() {
                         Ok((q, p)) => {
                             out_params.extend(p);
@@ -169,13 +169,13 @@ impl<'a, T> QueryBuilder<'a, T> {
         arg: QBArg,
     ) -> Result<(SqlExpr, SmallVec<[Param; 8]>)> {
         match arg {
-            QBArg::Expr(e) => Ok((e.expr, e.params)), // Expression → как есть
+            QBArg::Expr(e) => e.checked().map(|e| (e.expr, e.params)), // Expression → как есть
             QBArg::Subquery(qb) => {
                 let (q, params) = qb.build_query_ast()?;
                 Ok((SqlExpr::Subquery(Box::new(q)), params.into()))
             }
             QBArg::Closure(c) => {
-                let built = c.call(QueryBuilder::new_empty());
+                let built = c.call(QueryBuilder::new_empty().dialect(self.dialect));
                 let (q, params) = built.build_query_ast()?;
                 Ok((SqlExpr::Subquery(Box::new(q)), params.into()))
             }
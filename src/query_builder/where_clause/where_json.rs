@@ -20,8 +20,6 @@ impl<'a, T> QueryBuilder<'a, T> {
             Dialect::MySQL => format!("JSON_CONTAINS({}, {})", col, quote_sql_str(json)),
             // SQLite: left ⊇ right  через проверку отсутствия «пробелов» в правом объекте
             Dialect::SQLite => sqlite_json_superset_sql(col, json),
-            // fallback: MySQL-стиль
-            _ => format!("JSON_CONTAINS({}, {})", col, quote_sql_str(json)),
         };
         match parse_where_expr(&sql) {
             Ok(expr) => self.attach_where_with_and(expr, smallvec![]),
@@ -51,12 +49,6 @@ impl<'a, T> QueryBuilder<'a, T> {
                 json_expr,
                 quote_sql_str(path)
             ),
-            // fallback → MySQL-стиль
-            _ => format!(
-                "JSON_CONTAINS_PATH({}, 'one', {})",
-                json_expr,
-                quote_sql_str(path)
-            ),
         };
         match parse_where_expr(&sql) {
             Ok(expr) => self.attach_where_with_and(expr, smallvec![]),
@@ -75,7 +67,6 @@ impl<'a, T> QueryBuilder<'a, T> {
             ),
             Dialect::MySQL => format!("JSON_CONTAINS({}, {})", left, quote_sql_str(right_json)),
             Dialect::SQLite => sqlite_json_superset_sql(left, right_json),
-            _ => format!("JSON_CONTAINS({}, {})", left, quote_sql_str(right_json)),
         };
         match parse_where_expr(&sql) {
             Ok(expr) => self.attach_where_with_and(expr, smallvec![]),
@@ -91,7 +82,6 @@ impl<'a, T> QueryBuilder<'a, T> {
             // MySQL: subset(a,b) эквивалент JSON_CONTAINS(b, a)
             Dialect::MySQL => format!("JSON_CONTAINS({}, {})", right, quote_sql_str(left_json)),
             Dialect::SQLite => sqlite_json_subset_sql(left_json, right),
-            _ => format!("JSON_CONTAINS({}, {})", right, quote_sql_str(left_json)),
         };
         match parse_where_expr(&sql) {
             Ok(expr) => self.attach_where_with_and(expr, smallvec![]),
@@ -1,7 +1,8 @@
 use smallvec::smallvec;
 
 use super::utils::parse_where_expr;
-use crate::query_builder::QueryBuilder;
+use crate::query_builder::raw::bind_positional;
+use crate::query_builder::{QueryBuilder, args::ArgList};
 
 impl<'a, T> QueryBuilder<'a, T> {
     /// WHERE <raw SQL>, парсится через sqlparser (GenericDialect).
@@ -20,4 +21,27 @@ impl<'a, T> QueryBuilder<'a, T> {
         }
         self
     }
+
+    /// WHERE <raw SQL> с биндингами: `?` — значение, `??` — идентификатор
+    pub fn where_raw_with<L>(mut self, raw: &str, bindings: L) -> Self
+    where
+        L: ArgList<'a>,
+    {
+        match bind_positional(raw, bindings.into_vec()) {
+            Ok((expr, params)) => self.attach_where_with_and(expr, params),
+            Err(e) => self.push_builder_error(format!("where_raw_with(): {}", e)),
+        }
+        self
+    }
+
+    pub fn or_where_raw_with<L>(mut self, raw: &str, bindings: L) -> Self
+    where
+        L: ArgList<'a>,
+    {
+        match bind_positional(raw, bindings.into_vec()) {
+            Ok((expr, params)) => self.attach_where_with_or(expr, params),
+            Err(e) => self.push_builder_error(format!("or_where_raw_with(): {}", e)),
+        }
+        self
+    }
 }
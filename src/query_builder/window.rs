@@ -0,0 +1,31 @@
+use smallvec::SmallVec;
+use sqlparser::ast::{Ident, NamedWindowDefinition, NamedWindowExpr};
+
+use crate::expression::WindowBuilder;
+use crate::param::Param;
+use crate::query_builder::QueryBuilder;
+
+#[derive(Debug, Clone)]
+pub(crate) struct WindowNode {
+    pub def: NamedWindowDefinition,
+    pub params: SmallVec<[Param; 8]>,
+}
+
+impl<'a, T> QueryBuilder<'a, T> {
+    /// Именованное окно: `WINDOW name AS (...)`. Используется через
+    /// `Expression::over_window(name)` или `WindowBuilder::base(name)`.
+    pub fn window<F>(mut self, name: &str, f: F) -> Self
+    where
+        F: FnOnce(WindowBuilder) -> WindowBuilder,
+    {
+        let (spec, params, error) = f(WindowBuilder::new()).into_spec();
+        if let Some(err) = error {
+            self.push_builder_error(format!("window(): {err}"));
+        }
+        self.windows.push(WindowNode {
+            def: NamedWindowDefinition(Ident::new(name), NamedWindowExpr::WindowSpec(spec)),
+            params,
+        });
+        self
+    }
+}
@@ -15,6 +15,7 @@ fn cfg(dialect: Dialect) -> SqlRenderCfg {
         emit_as_for_table_alias: true,
         emit_as_for_column_alias: true,
         fold_idents: None,
+        pretty: false,
     }
 }
 
@@ -36,6 +37,7 @@ fn base_select(items: Vec<R::SelectItem>) -> R::Select {
         group_by: vec![],
         group_by_modifiers: vec![],
         having: None,
+        windows: vec![],
         order_by: vec![],
         limit: None,
         offset: None,
@@ -49,6 +51,7 @@ fn wrap_query(sel: R::Select) -> R::Query {
         order_by: vec![],
         limit: None,
         offset: None,
+        lock: None,
     }
 }
 
@@ -133,6 +136,7 @@ fn strict_policy_blocks_by_name_in_set_ops() {
         order_by: vec![],
         limit: None,
         offset: None,
+        lock: None,
     };
 
     // Во всех трёх диалектах BY NAME не поддержан в строгом режиме
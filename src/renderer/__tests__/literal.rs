@@ -0,0 +1,64 @@
+use crate::param::Param;
+use crate::renderer::{Dialect, inline_params, render_literal};
+
+#[test]
+fn inline_params_replaces_numbered_placeholders_by_index() {
+    let sql = r#"SELECT * FROM "t" WHERE "a" = $2 AND "b" = $1 AND "c" = '$1'"#;
+    let params = [Param::I32(1), Param::Str("it's".into())];
+    assert_eq!(
+        inline_params(sql, &params, Dialect::Postgres),
+        r#"SELECT * FROM "t" WHERE "a" = 'it''s' AND "b" = 1 AND "c" = '$1'"#
+    );
+}
+
+#[test]
+fn inline_params_skips_question_marks_in_quotes() {
+    let sql = "SELECT `a?` FROM `t` WHERE `b` = ? AND `c` = 'x\\'?' AND `d` = ?";
+    let params = [Param::Bool(true), Param::NullText];
+    assert_eq!(
+        inline_params(sql, &params, Dialect::MySQL),
+        "SELECT `a?` FROM `t` WHERE `b` = 1 AND `c` = 'x\\'?' AND `d` = NULL"
+    );
+}
+
+#[test]
+fn literals_per_dialect() {
+    let bytes = Param::Bytes(vec![0xde, 0xad]);
+    assert_eq!(render_literal(&bytes, Dialect::Postgres), r"'\xdead'");
+    assert_eq!(render_literal(&bytes, Dialect::SQLite), "X'dead'");
+
+    let s = Param::Str(r"a\b'c".into());
+    assert_eq!(render_literal(&s, Dialect::MySQL), r"'a\\b''c'");
+    assert_eq!(render_literal(&s, Dialect::SQLite), r"'a\b''c'");
+
+    assert_eq!(
+        render_literal(&Param::F64(f64::NAN), Dialect::Postgres),
+        "'NaN'::float8"
+    );
+    assert_eq!(
+        render_literal(&Param::F64(f64::INFINITY), Dialect::SQLite),
+        "NULL"
+    );
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time_literals_are_typed_in_postgres() {
+    use time::macros::{date, datetime, time};
+
+    assert_eq!(
+        render_literal(&Param::Date(date!(2024 - 01 - 05)), Dialect::Postgres),
+        "DATE '2024-01-05'"
+    );
+    assert_eq!(
+        render_literal(&Param::Time(time!(1:02:03)), Dialect::MySQL),
+        "'01:02:03'"
+    );
+    assert_eq!(
+        render_literal(
+            &Param::DateTime(datetime!(2024-01-05 1:02:03.5 -03:30)),
+            Dialect::Postgres
+        ),
+        "TIMESTAMPTZ '2024-01-05 01:02:03.500000-03:30'"
+    );
+}
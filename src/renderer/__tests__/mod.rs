@@ -27,3 +27,9 @@ mod update;
 
 #[cfg(test)]
 mod delete;
+
+#[cfg(test)]
+mod literal;
+
+#[cfg(test)]
+mod pretty;
@@ -0,0 +1,122 @@
+use crate::expression::helpers::{col, val};
+use crate::query_builder::QueryBuilder;
+use crate::renderer::{self, Dialect, format_sql};
+
+type QB = QueryBuilder<'static, ()>;
+
+fn placeholders(sql: &str) -> Vec<&str> {
+    sql.split(|c: char| !(c == '$' || c == '?' || c.is_ascii_digit()))
+        .filter(|s| s.starts_with(['$', '?']))
+        .collect()
+}
+
+fn report_query() -> QB {
+    QB::new_empty()
+        .dialect(Dialect::Postgres)
+        .with(
+            "active",
+            QB::new_empty()
+                .from("users")
+                .select("id")
+                .r#where(col("active").eq(val(true))),
+        )
+        .from("orders")
+        .select(("orders.id", "users.name"))
+        .left_join("users", "users.id = orders.user_id")
+        .r#where(col("total").gt(val(100)))
+        .r#where(col("status").eq(val("paid")))
+        .group_by(("orders.id", "users.name"))
+        .limit(10)
+}
+
+#[test]
+fn pretty_select_indents_cte_list_joins_and_conditions() {
+    let (sql, params) = report_query().to_sql_pretty().unwrap();
+    assert_eq!(
+        sql,
+        r#"WITH "active" AS (
+  SELECT "id"
+  FROM "users"
+  WHERE "active" = $1
+)
+SELECT
+  "orders"."id",
+  "users"."name"
+FROM "orders"
+LEFT JOIN "users" ON "users"."id" = "orders"."user_id"
+WHERE ("total" > $2)
+  AND ("status" = $3)
+GROUP BY "orders"."id", "users"."name"
+LIMIT 10"#
+    );
+
+    let (compact, compact_params) = report_query().to_sql().unwrap();
+    assert_eq!(placeholders(&sql), placeholders(&compact));
+    assert_eq!(format!("{params:?}"), format!("{compact_params:?}"));
+}
+
+#[test]
+fn pretty_flag_in_cfg_matches_format_sql() {
+    let ast = report_query().build_query_ast().unwrap().0;
+    let rq = renderer::map_to_render_query(&ast);
+    let compact = renderer::render_sql_query(&rq, &renderer::cfg_postgres_knex());
+
+    let mut cfg = renderer::cfg_postgres_knex();
+    cfg.pretty = true;
+    let pretty = renderer::render_sql_query(&rq, &cfg);
+
+    assert!(pretty.contains('\n'));
+    assert_eq!(pretty, format_sql(&compact, Dialect::Postgres));
+}
+
+#[test]
+fn pretty_dml_breaks_values_rows_and_set_list() {
+    assert_eq!(
+        format_sql(
+            r#"INSERT INTO "t" ("a", "b") VALUES ($1, $2), ($3, $4) ON CONFLICT ("a") DO UPDATE SET "b" = excluded."b" RETURNING "id""#,
+            Dialect::Postgres,
+        ),
+        r#"INSERT INTO "t" ("a", "b")
+VALUES
+  ($1, $2),
+  ($3, $4)
+ON CONFLICT ("a") DO UPDATE
+SET "b" = excluded."b"
+RETURNING "id""#
+    );
+
+    assert_eq!(
+        format_sql(
+            r#"UPDATE "t" SET "a" = ?, "b" = ? WHERE "id" BETWEEN ? AND ? AND CASE WHEN "x" AND "y" THEN 1 END = 1 OR "z" IS DISTINCT FROM ?"#,
+            Dialect::SQLite,
+        ),
+        r#"UPDATE "t"
+SET
+  "a" = ?,
+  "b" = ?
+WHERE "id" BETWEEN ? AND ?
+  AND CASE WHEN "x" AND "y" THEN 1 END = 1
+  OR "z" IS DISTINCT FROM ?"#
+    );
+}
+
+#[test]
+fn pretty_nests_subqueries_and_keeps_quoted_text() {
+    assert_eq!(
+        format_sql(
+            r#"DELETE FROM `t` WHERE `note` = 'it\'s, AND (SELECT' AND `id` IN (SELECT `id` FROM `u` WHERE `a` = ? AND EXISTS (SELECT 1 FROM `v`))"#,
+            Dialect::MySQL,
+        ),
+        r#"DELETE FROM `t`
+WHERE `note` = 'it\'s, AND (SELECT'
+  AND `id` IN (
+    SELECT `id`
+    FROM `u`
+    WHERE `a` = ?
+      AND EXISTS (
+        SELECT 1
+        FROM `v`
+      )
+  )"#
+    );
+}
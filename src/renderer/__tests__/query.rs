@@ -15,6 +15,7 @@ fn cfg(d: Dialect) -> SqlRenderCfg {
         emit_as_for_table_alias: true,
         emit_as_for_column_alias: true,
         fold_idents: None,
+        pretty: false,
     }
 }
 
@@ -29,6 +30,7 @@ fn base_select(items: Vec<R::SelectItem>) -> R::Select {
         group_by: vec![],
         group_by_modifiers: vec![],
         having: None,
+        windows: vec![],
         order_by: vec![],
         limit: None,
         offset: None,
@@ -41,6 +43,7 @@ fn wrap_query(sel: R::Select) -> R::Query {
         order_by: vec![],
         limit: None,
         offset: None,
+        lock: None,
     }
 }
 
@@ -74,6 +77,7 @@ fn with_cte_simple_pg() {
         order_by: vec![],
         limit: None,
         offset: None,
+        lock: None,
     };
 
     let out = render_sql_query(&q, &cfg(Dialect::Postgres));
@@ -129,6 +133,7 @@ fn with_recursive_union_all_pg() {
         order_by: vec![],
         limit: None,
         offset: None,
+        lock: None,
     };
 
     let out = render_sql_query(&q, &cfg(Dialect::Postgres));
@@ -160,6 +165,7 @@ fn set_operations_parentheses_across_dialects() {
         order_by: vec![],
         limit: None,
         offset: None,
+        lock: None,
     };
 
     for d in [Dialect::Postgres, Dialect::MySQL, Dialect::SQLite] {
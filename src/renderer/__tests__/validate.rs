@@ -15,6 +15,7 @@ fn cfg_strict(dialect: Dialect) -> SqlRenderCfg {
         emit_as_for_table_alias: true,
         emit_as_for_column_alias: true,
         fold_idents: None,
+        pretty: false,
     }
 }
 
@@ -36,6 +37,7 @@ fn base_select(items: Vec<R::SelectItem>) -> R::Select {
         group_by: vec![],
         group_by_modifiers: vec![],
         having: None,
+        windows: vec![],
         order_by: vec![],
         limit: None,
         offset: None,
@@ -49,6 +51,7 @@ fn wrap_query(sel: R::Select) -> R::Query {
         order_by: vec![],
         limit: None,
         offset: None,
+        lock: None,
     }
 }
 
@@ -191,6 +194,7 @@ fn by_name_in_set_ops_is_forbidden_in_strict_everywhere() {
         order_by: vec![],
         limit: None,
         offset: None,
+        lock: None,
     };
 
     for d in [Dialect::Postgres, Dialect::MySQL, Dialect::SQLite] {
@@ -240,3 +244,72 @@ fn lenient_policy_allows_features() {
         assert!(validate_query_features(&q, &cfg_lenient(d)).is_none());
     }
 }
+
+#[test]
+fn groups_frame_is_forbidden_in_mysql_in_strict() {
+    let groups = R::WindowSpec {
+        frame: Some(R::WindowFrame {
+            units: R::FrameUnits::Groups,
+            start: R::FrameBound::CurrentRow,
+            end: Some(R::FrameBound::UnboundedFollowing),
+        }),
+        ..Default::default()
+    };
+    let sel = base_select(vec![R::SelectItem::Expr {
+        expr: R::Expr::WindowFunc {
+            name: "COUNT".into(),
+            args: vec![R::Expr::Star],
+            window: groups.clone(),
+        },
+        alias: None,
+    }]);
+    let q = wrap_query(sel);
+
+    assert!(validate_query_features(&q, &cfg_strict(Dialect::MySQL)).is_some());
+    assert!(validate_query_features(&q, &cfg_strict(Dialect::SQLite)).is_none());
+    assert!(validate_query_features(&q, &cfg_strict(Dialect::Postgres)).is_none());
+    assert!(validate_query_features(&q, &cfg_lenient(Dialect::MySQL)).is_none());
+
+    // то же в WINDOW name AS (...)
+    let mut sel = base_select(vec![]);
+    sel.windows = vec![R::NamedWindow {
+        name: "w".into(),
+        spec: groups,
+    }];
+    assert!(validate_query_features(&wrap_query(sel), &cfg_strict(Dialect::MySQL)).is_some());
+}
+
+#[test]
+fn row_locks_are_checked_per_dialect_in_strict() {
+    let locked = |strength| {
+        let mut q = wrap_query(base_select(vec![R::SelectItem::Star { opts: None }]));
+        q.lock = Some(R::RowLock {
+            strength,
+            of: vec![],
+            wait: R::LockWait::SkipLocked,
+        });
+        q
+    };
+
+    let upd = locked(R::LockStrength::Update);
+    assert!(validate_query_features(&upd, &cfg_strict(Dialect::Postgres)).is_none());
+    assert!(validate_query_features(&upd, &cfg_strict(Dialect::MySQL)).is_none());
+    assert!(validate_query_features(&upd, &cfg_strict(Dialect::SQLite)).is_some());
+    assert!(validate_query_features(&upd, &cfg_lenient(Dialect::SQLite)).is_none());
+
+    let no_key = locked(R::LockStrength::NoKeyUpdate);
+    assert!(validate_query_features(&no_key, &cfg_strict(Dialect::Postgres)).is_none());
+    assert!(validate_query_features(&no_key, &cfg_strict(Dialect::MySQL)).is_some());
+
+    // с UNION блокировка запрещена при любой политике (SQLite её опускает)
+    let mut set = locked(R::LockStrength::Share);
+    set.body = R::QueryBody::Set {
+        left: Box::new(R::QueryBody::Select(base_select(vec![]))),
+        op: R::SetOp::Union,
+        right: Box::new(R::QueryBody::Select(base_select(vec![]))),
+        by_name: false,
+    };
+    assert!(validate_query_features(&set, &cfg_strict(Dialect::Postgres)).is_some());
+    assert!(validate_query_features(&set, &cfg_lenient(Dialect::MySQL)).is_some());
+    assert!(validate_query_features(&set, &cfg_lenient(Dialect::SQLite)).is_none());
+}
@@ -23,6 +23,8 @@ pub struct Select {
     pub group_by: Vec<Expr>,
     pub group_by_modifiers: Vec<GroupByModifier>,
     pub having: Option<Expr>,
+    /// WINDOW name AS (...)
+    pub windows: Vec<NamedWindow>,
     pub order_by: Vec<OrderItem>,
     pub limit: Option<u64>,
     pub offset: Option<u64>,
@@ -60,7 +62,7 @@ pub enum TableRef {
         alias: Option<String>,
     },
     Subquery {
-        query: Box<Select>,
+        query: Box<Query>,
         alias: Option<String>,
     },
 }
@@ -228,12 +230,72 @@ pub struct Query {
     pub order_by: Vec<OrderItem>, // ORDER BY ... на уровне всего запроса
     pub limit: Option<u64>,
     pub offset: Option<u64>,
+    pub lock: Option<RowLock>, // FOR UPDATE/SHARE ... после LIMIT/OFFSET
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Сила блокировки строк
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LockStrength {
+    Update,
+    NoKeyUpdate, // только PG; в MySQL деградирует до FOR UPDATE
+    Share,
+}
+
+/// Поведение при занятых строках
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LockWait {
+    #[default]
+    Wait,
+    NoWait,
+    SkipLocked,
+}
+
+/// `FOR UPDATE|NO KEY UPDATE|SHARE [OF t1, t2] [NOWAIT|SKIP LOCKED]`
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct RowLock {
+    pub strength: LockStrength,
+    pub of: Vec<String>,
+    pub wait: LockWait,
+}
+
+/// Окно: `OVER name` (только `name`) или `OVER ([name] PARTITION BY ... ORDER BY ... frame)`
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct WindowSpec {
+    pub name: Option<String>,
     pub partition_by: Vec<Expr>,
     pub order_by: Vec<OrderItem>,
+    pub frame: Option<WindowFrame>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedWindow {
+    pub name: String,
+    pub spec: WindowSpec,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameUnits {
+    Rows,
+    Range,
+    /// PG/SQLite; в MySQL нет
+    Groups,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(Box<Expr>),
+    CurrentRow,
+    Following(Box<Expr>),
+    UnboundedFollowing,
+}
+
+/// `ROWS|RANGE|GROUPS BETWEEN start AND end` (без `end` — краткая форма)
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowFrame {
+    pub units: FrameUnits,
+    pub start: FrameBound,
+    pub end: Option<FrameBound>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -249,6 +311,7 @@ pub struct Insert {
     pub table: TableRef, // ожидаем Named { .. }, alias не обязателен (MySQL alias "new" проставим в рендере при необходимости)
     pub columns: Vec<String>, // пусто ⇒ вставка "по всем"
     pub rows: Vec<Vec<Expr>>, // VALUES(...) [, (...)]
+    pub select: Option<Box<Query>>, // INSERT ... SELECT (тогда rows пусты)
     pub ignore: bool, // MySQL: INSERT IGNORE; SQLite: INSERT OR IGNORE; PG: через ON CONFLICT DO NOTHING
     pub on_conflict: Option<OnConflict>,
     pub returning: Vec<SelectItem>, // PG/SQLite
@@ -301,3 +364,159 @@ pub struct Delete {
     pub r#where: Option<Expr>,      // WHERE ...
     pub returning: Vec<SelectItem>, // PG/SQLite; для MySQL игнорим при рендере
 }
+
+// ===================== DDL =====================
+
+/// Имя таблицы в DDL: [schema.]name
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableName {
+    pub schema: Option<String>,
+    pub name: String,
+}
+
+/// Логический тип колонки; конкретный SQL-тип выбирается рендером по диалекту.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColumnType {
+    /// Автоинкрементный INTEGER PRIMARY KEY
+    Increments,
+    /// Автоинкрементный BIGINT PRIMARY KEY
+    BigIncrements,
+    TinyInteger,
+    SmallInteger,
+    Integer,
+    BigInteger,
+    Float,
+    Double,
+    Decimal {
+        precision: u8,
+        scale: u8,
+    },
+    Boolean,
+    String(u32),
+    Text,
+    Date,
+    Time,
+    DateTime,
+    /// TIMESTAMP с часовым поясом (где есть)
+    Timestamp,
+    Binary,
+    Json,
+    /// JSONB (только PG; иначе JSON)
+    Jsonb,
+    Uuid,
+    /// Перечисление: ENUM в MySQL, TEXT + CHECK в PG/SQLite
+    Enum(Vec<String>),
+    /// Тип как есть (без преобразований)
+    Custom(String),
+}
+
+#[derive(Clone, Debug)]
+pub enum ColumnDefault {
+    /// Значение, печатается литералом
+    Value(crate::param::Param),
+    /// SQL как есть: CURRENT_TIMESTAMP, gen_random_uuid() и т.п.
+    Raw(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct ColumnDef {
+    pub name: String,
+    pub ty: ColumnType,
+    /// None — не указано (NULL по умолчанию СУБД)
+    pub nullable: Option<bool>,
+    pub default: Option<ColumnDefault>,
+    pub unsigned: bool,
+    pub unique: bool,
+    pub comment: Option<String>,
+    /// Внешний ключ уровня колонки
+    pub references: Option<ForeignKey>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReferentialAction {
+    Cascade,
+    SetNull,
+    SetDefault,
+    Restrict,
+    NoAction,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForeignKey {
+    pub name: Option<String>,
+    pub columns: Vec<String>,
+    pub ref_table: TableName,
+    pub ref_columns: Vec<String>,
+    pub on_delete: Option<ReferentialAction>,
+    pub on_update: Option<ReferentialAction>,
+}
+
+/// Условие в DDL (CHECK, WHERE частичного индекса): параметры печатаются литералами
+#[derive(Clone, Debug)]
+pub struct Predicate {
+    pub expr: Expr,
+    pub params: Vec<crate::param::Param>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CheckConstraint {
+    pub name: Option<String>,
+    pub predicate: Predicate,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateIndex {
+    pub name: String,
+    pub table: TableName,
+    pub columns: Vec<String>,
+    pub unique: bool,
+    /// WHERE частичного индекса (PG/SQLite)
+    pub predicate: Option<Predicate>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateTable {
+    pub table: TableName,
+    pub if_not_exists: bool,
+    pub columns: Vec<ColumnDef>,
+    /// PRIMARY KEY (...) уровня таблицы
+    pub primary_key: Vec<String>,
+    pub foreign_keys: Vec<ForeignKey>,
+    pub checks: Vec<CheckConstraint>,
+    /// Индексы создаются отдельными CREATE INDEX после таблицы
+    pub indexes: Vec<CreateIndex>,
+}
+
+#[derive(Clone, Debug)]
+pub enum AlterTableOp {
+    AddColumn(ColumnDef),
+    /// Переопределение существующей колонки (тип, NULL, DEFAULT)
+    AlterColumn(ColumnDef),
+    DropColumn(String),
+    RenameColumn {
+        from: String,
+        to: String,
+    },
+    AddForeignKey(ForeignKey),
+    DropForeignKey(String),
+    AddCheck(CheckConstraint),
+    DropCheck(String),
+    CreateIndex(CreateIndex),
+    DropIndex(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct AlterTable {
+    pub table: TableName,
+    pub ops: Vec<AlterTableOp>,
+}
+
+#[derive(Clone, Debug)]
+pub enum DdlStmt {
+    CreateTable(CreateTable),
+    AlterTable(AlterTable),
+    RenameTable { from: TableName, to: String },
+    DropTable { table: TableName, if_exists: bool },
+    CreateIndex(CreateIndex),
+    DropIndex { name: String, table: TableName },
+}
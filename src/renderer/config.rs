@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Dialect {
     Postgres,
     SQLite,
@@ -76,6 +76,9 @@ pub struct SqlRenderCfg {
 
     /// Принудительная нормализация идентификаторов (после квотирования логика не меняется)
     pub fold_idents: Option<FoldCase>,
+
+    /// Многострочный вывод (см. `format_sql`); плейсхолдеры те же, что в компактном
+    pub pretty: bool,
 }
 
 impl Default for SqlRenderCfg {
@@ -92,6 +95,7 @@ impl Default for SqlRenderCfg {
             emit_as_for_table_alias: true,
             emit_as_for_column_alias: true,
             fold_idents: None,
+            pretty: false,
         }
     }
 }
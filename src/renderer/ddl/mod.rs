@@ -0,0 +1,664 @@
+use crate::param::Param;
+use crate::renderer::ast as R;
+use crate::renderer::ident::quote_ident;
+use crate::renderer::literal::{inline_binds, quote_string, render_literal};
+use crate::renderer::select::render_expr;
+use crate::renderer::{
+    Dialect, Error, FeaturePolicy, PlaceholderStyle, Result, SqlRenderCfg, SqlWriter,
+};
+
+mod sqlite;
+
+pub use sqlite::render_sqlite_rebuild;
+
+/// Рендер DDL-инструкции. Одна инструкция может развернуться в несколько
+/// SQL-выражений (ALTER по одному действию, COMMENT ON в PG и т.п.).
+pub fn render_ddl(stmt: &R::DdlStmt, cfg: &SqlRenderCfg) -> Result<Vec<String>> {
+    let mut out = Vec::with_capacity(1);
+    match stmt {
+        R::DdlStmt::CreateTable(ct) => render_create_table(&mut out, ct, cfg)?,
+        R::DdlStmt::AlterTable(at) => render_alter_table(&mut out, at, cfg)?,
+        R::DdlStmt::RenameTable { from, to } => {
+            let sql = match cfg.dialect {
+                Dialect::MySQL => {
+                    let to = R::TableName {
+                        schema: from.schema.clone(),
+                        name: to.clone(),
+                    };
+                    format!(
+                        "RENAME TABLE {} TO {}",
+                        table_name(from, cfg),
+                        table_name(&to, cfg)
+                    )
+                }
+                Dialect::Postgres | Dialect::SQLite => format!(
+                    "ALTER TABLE {} RENAME TO {}",
+                    table_name(from, cfg),
+                    quote_ident(to, cfg)
+                ),
+            };
+            out.push(sql);
+        }
+        R::DdlStmt::DropTable { table, if_exists } => {
+            out.push(format!(
+                "DROP TABLE {}{}",
+                if *if_exists { "IF EXISTS " } else { "" },
+                table_name(table, cfg)
+            ));
+        }
+        R::DdlStmt::CreateIndex(ix) => out.push(render_create_index(ix, cfg)?),
+        R::DdlStmt::DropIndex { name, table } => out.push(render_drop_index(name, table, cfg)),
+    }
+    Ok(out)
+}
+
+/// Действия ALTER TABLE, которые SQLite выполняет только пересозданием таблицы
+/// (см. [`render_sqlite_rebuild`]).
+pub fn sqlite_requires_rebuild(op: &R::AlterTableOp) -> bool {
+    matches!(
+        op,
+        R::AlterTableOp::AlterColumn(_)
+            | R::AlterTableOp::AddForeignKey(_)
+            | R::AlterTableOp::DropForeignKey(_)
+            | R::AlterTableOp::AddCheck(_)
+            | R::AlterTableOp::DropCheck(_)
+    )
+}
+
+/// Проверка существования таблицы: `SELECT COUNT(*) ...` с параметрами.
+pub fn render_has_table(table: &R::TableName, cfg: &SqlRenderCfg) -> (String, Vec<Param>) {
+    let mut params = Vec::with_capacity(2);
+    let sql = match cfg.dialect {
+        Dialect::Postgres | Dialect::MySQL => {
+            let mut sql = String::from("SELECT COUNT(*) FROM information_schema.tables WHERE ");
+            push_schema_filter(&mut sql, &mut params, table, cfg);
+            sql.push_str(" AND table_name = ");
+            push_param(&mut sql, &mut params, Param::Str(table.name.clone()), cfg);
+            sql
+        }
+        Dialect::SQLite => {
+            let mut sql = String::from("SELECT COUNT(*) FROM ");
+            if let Some(s) = &table.schema {
+                sql.push_str(&quote_ident(s, cfg));
+                sql.push('.');
+            }
+            sql.push_str("sqlite_master WHERE type = 'table' AND name = ?");
+            params.push(Param::Str(table.name.clone()));
+            sql
+        }
+    };
+    (sql, params)
+}
+
+/// Проверка существования колонки: `SELECT COUNT(*) ...` с параметрами.
+pub fn render_has_column(
+    table: &R::TableName,
+    column: &str,
+    cfg: &SqlRenderCfg,
+) -> (String, Vec<Param>) {
+    let mut params = Vec::with_capacity(3);
+    let sql = match cfg.dialect {
+        Dialect::Postgres | Dialect::MySQL => {
+            let mut sql = String::from("SELECT COUNT(*) FROM information_schema.columns WHERE ");
+            push_schema_filter(&mut sql, &mut params, table, cfg);
+            sql.push_str(" AND table_name = ");
+            push_param(&mut sql, &mut params, Param::Str(table.name.clone()), cfg);
+            sql.push_str(" AND column_name = ");
+            push_param(&mut sql, &mut params, Param::Str(column.to_string()), cfg);
+            sql
+        }
+        Dialect::SQLite => {
+            params.push(Param::Str(table.name.clone()));
+            let mut sql = String::from("SELECT COUNT(*) FROM pragma_table_info(?");
+            if let Some(s) = &table.schema {
+                sql.push_str(", ?");
+                params.push(Param::Str(s.clone()));
+            }
+            sql.push_str(") WHERE name = ?");
+            params.push(Param::Str(column.to_string()));
+            sql
+        }
+    };
+    (sql, params)
+}
+
+/// table_schema = <схема> | текущая схема/БД соединения
+fn push_schema_filter(
+    sql: &mut String,
+    params: &mut Vec<Param>,
+    table: &R::TableName,
+    cfg: &SqlRenderCfg,
+) {
+    sql.push_str("table_schema = ");
+    match (&table.schema, cfg.dialect) {
+        (Some(s), _) => push_param(sql, params, Param::Str(s.clone()), cfg),
+        (None, Dialect::Postgres) => sql.push_str("current_schema()"),
+        (None, _) => sql.push_str("DATABASE()"),
+    }
+}
+
+fn push_param(sql: &mut String, params: &mut Vec<Param>, p: Param, cfg: &SqlRenderCfg) {
+    params.push(p);
+    match cfg.placeholders {
+        PlaceholderStyle::Numbered => {
+            sql.push('$');
+            sql.push_str(&params.len().to_string());
+        }
+        PlaceholderStyle::Question => sql.push('?'),
+    }
+}
+
+fn render_create_table(
+    out: &mut Vec<String>,
+    ct: &R::CreateTable,
+    cfg: &SqlRenderCfg,
+) -> Result<()> {
+    let mut extra = Vec::new();
+    let mut sql = String::with_capacity(128);
+    sql.push_str("CREATE TABLE ");
+    if ct.if_not_exists {
+        sql.push_str("IF NOT EXISTS ");
+    }
+    sql.push_str(&table_name(&ct.table, cfg));
+    sql.push_str(" (");
+
+    for (i, col) in ct.columns.iter().enumerate() {
+        if i > 0 {
+            sql.push_str(", ");
+        }
+        // FK колонок выносим на уровень таблицы: MySQL игнорирует inline REFERENCES
+        render_column(&mut sql, &mut extra, &ct.table, col, false, cfg)?;
+    }
+
+    if !ct.primary_key.is_empty() {
+        sql.push_str(", PRIMARY KEY ");
+        push_column_list(&mut sql, &ct.primary_key, cfg);
+    }
+
+    let column_fks = ct.columns.iter().filter_map(|c| c.references.as_ref());
+    for fk in column_fks.chain(ct.foreign_keys.iter()) {
+        sql.push_str(", ");
+        push_foreign_key(&mut sql, fk, cfg)?;
+    }
+
+    for check in &ct.checks {
+        sql.push_str(", ");
+        push_check(&mut sql, check, cfg);
+    }
+
+    sql.push(')');
+    out.push(sql);
+    out.append(&mut extra);
+    for ix in &ct.indexes {
+        out.push(render_create_index(ix, cfg)?);
+    }
+    Ok(())
+}
+
+fn render_alter_table(out: &mut Vec<String>, at: &R::AlterTable, cfg: &SqlRenderCfg) -> Result<()> {
+    let table = table_name(&at.table, cfg);
+    let mut extra = Vec::new();
+
+    // по одному действию на выражение: SQLite не умеет несколько, PG не смешивает RENAME с прочими
+    for op in &at.ops {
+        let mut sql = format!("ALTER TABLE {table} ");
+        match op {
+            R::AlterTableOp::AddColumn(col) => {
+                sql.push_str("ADD COLUMN ");
+                let inline_fk = !matches!(cfg.dialect, Dialect::MySQL);
+                render_column(&mut sql, &mut extra, &at.table, col, inline_fk, cfg)?;
+                if let (false, Some(fk)) = (inline_fk, &col.references) {
+                    extra.push(add_foreign_key_sql(&table, fk, cfg)?);
+                }
+            }
+            R::AlterTableOp::AlterColumn(col) => match cfg.dialect {
+                Dialect::Postgres => {
+                    push_pg_alter_column(&mut sql, col, cfg)?;
+                    if let Some(c) = &col.comment {
+                        extra.push(comment_on_column_sql(&at.table, &col.name, c, cfg));
+                    }
+                    if let Some(fk) = &col.references {
+                        extra.push(add_foreign_key_sql(&table, fk, cfg)?);
+                    }
+                }
+                Dialect::MySQL => {
+                    sql.push_str("MODIFY COLUMN ");
+                    render_column(&mut sql, &mut extra, &at.table, col, false, cfg)?;
+                    if let Some(fk) = &col.references {
+                        extra.push(add_foreign_key_sql(&table, fk, cfg)?);
+                    }
+                }
+                Dialect::SQLite => return Err(unsupported("ALTER COLUMN", cfg.dialect)),
+            },
+            R::AlterTableOp::DropColumn(name) => {
+                sql.push_str("DROP COLUMN ");
+                sql.push_str(&quote_ident(name, cfg));
+            }
+            R::AlterTableOp::RenameColumn { from, to } => {
+                sql.push_str("RENAME COLUMN ");
+                sql.push_str(&quote_ident(from, cfg));
+                sql.push_str(" TO ");
+                sql.push_str(&quote_ident(to, cfg));
+            }
+            R::AlterTableOp::AddForeignKey(fk) => {
+                if cfg.dialect == Dialect::SQLite {
+                    return Err(unsupported("ALTER TABLE ADD FOREIGN KEY", cfg.dialect));
+                }
+                sql = add_foreign_key_sql(&table, fk, cfg)?;
+            }
+            R::AlterTableOp::DropForeignKey(name) => {
+                sql.push_str(match cfg.dialect {
+                    Dialect::Postgres => "DROP CONSTRAINT ",
+                    Dialect::MySQL => "DROP FOREIGN KEY ",
+                    Dialect::SQLite => {
+                        return Err(unsupported("ALTER TABLE DROP FOREIGN KEY", cfg.dialect));
+                    }
+                });
+                sql.push_str(&quote_ident(name, cfg));
+            }
+            R::AlterTableOp::AddCheck(check) => {
+                if cfg.dialect == Dialect::SQLite {
+                    return Err(unsupported("ALTER TABLE ADD CHECK", cfg.dialect));
+                }
+                sql.push_str("ADD ");
+                push_check(&mut sql, check, cfg);
+            }
+            R::AlterTableOp::DropCheck(name) => {
+                sql.push_str(match cfg.dialect {
+                    Dialect::Postgres => "DROP CONSTRAINT ",
+                    Dialect::MySQL => "DROP CHECK ",
+                    Dialect::SQLite => {
+                        return Err(unsupported("ALTER TABLE DROP CHECK", cfg.dialect));
+                    }
+                });
+                sql.push_str(&quote_ident(name, cfg));
+            }
+            R::AlterTableOp::CreateIndex(ix) => sql = render_create_index(ix, cfg)?,
+            R::AlterTableOp::DropIndex(name) => sql = render_drop_index(name, &at.table, cfg),
+        }
+        out.push(sql);
+        out.append(&mut extra);
+    }
+    Ok(())
+}
+
+/// Определение колонки: "name" TYPE [UNSIGNED] [NOT NULL] [DEFAULT ...] [UNIQUE] ...
+fn render_column(
+    sql: &mut String,
+    extra: &mut Vec<String>,
+    table: &R::TableName,
+    col: &R::ColumnDef,
+    inline_fk: bool,
+    cfg: &SqlRenderCfg,
+) -> Result<()> {
+    sql.push_str(&quote_ident(&col.name, cfg));
+    sql.push(' ');
+
+    match col.ty {
+        R::ColumnType::Increments | R::ColumnType::BigIncrements => {
+            let big = col.ty == R::ColumnType::BigIncrements;
+            sql.push_str(match (cfg.dialect, big) {
+                (Dialect::Postgres, false) => "SERIAL PRIMARY KEY",
+                (Dialect::Postgres, true) => "BIGSERIAL PRIMARY KEY",
+                (Dialect::MySQL, false) => "INT UNSIGNED NOT NULL AUTO_INCREMENT PRIMARY KEY",
+                (Dialect::MySQL, true) => "BIGINT UNSIGNED NOT NULL AUTO_INCREMENT PRIMARY KEY",
+                // AUTOINCREMENT в SQLite допустим только у INTEGER PRIMARY KEY
+                (Dialect::SQLite, _) => "INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT",
+            });
+        }
+        _ => {
+            sql.push_str(&column_type_sql(&col.ty, cfg)?);
+
+            if col.unsigned {
+                if cfg.dialect == Dialect::MySQL {
+                    sql.push_str(" UNSIGNED");
+                } else {
+                    degrade("UNSIGNED", cfg)?;
+                }
+            }
+            match col.nullable {
+                Some(false) => sql.push_str(" NOT NULL"),
+                Some(true) => sql.push_str(" NULL"),
+                None => {}
+            }
+            if let Some(d) = &col.default {
+                sql.push_str(" DEFAULT ");
+                sql.push_str(&default_sql(d, cfg));
+            }
+            if col.unique {
+                sql.push_str(" UNIQUE");
+            }
+            if let (R::ColumnType::Enum(values), Dialect::Postgres | Dialect::SQLite) =
+                (&col.ty, cfg.dialect)
+            {
+                sql.push_str(" CHECK (");
+                sql.push_str(&quote_ident(&col.name, cfg));
+                sql.push_str(" IN (");
+                push_string_list(sql, values, cfg);
+                sql.push_str("))");
+            }
+        }
+    }
+
+    if let (true, Some(fk)) = (inline_fk, &col.references) {
+        if let Some(name) = &fk.name {
+            sql.push_str(" CONSTRAINT ");
+            sql.push_str(&quote_ident(name, cfg));
+        }
+        sql.push_str(" REFERENCES ");
+        push_references(sql, fk, cfg)?;
+    }
+
+    if let Some(c) = &col.comment {
+        match cfg.dialect {
+            Dialect::MySQL => {
+                sql.push_str(" COMMENT ");
+                sql.push_str(&quote_string(c, cfg.dialect));
+            }
+            Dialect::Postgres => extra.push(comment_on_column_sql(table, &col.name, c, cfg)),
+            Dialect::SQLite => degrade("column COMMENT", cfg)?,
+        }
+    }
+    Ok(())
+}
+
+/// PG: ALTER COLUMN c TYPE ..., ALTER COLUMN c SET/DROP NOT NULL, ALTER COLUMN c SET/DROP DEFAULT
+fn push_pg_alter_column(sql: &mut String, col: &R::ColumnDef, cfg: &SqlRenderCfg) -> Result<()> {
+    let name = quote_ident(&col.name, cfg);
+    if col.unsigned {
+        degrade("UNSIGNED", cfg)?;
+    }
+
+    sql.push_str(&format!(
+        "ALTER COLUMN {name} TYPE {}",
+        column_type_sql(&col.ty, cfg)?
+    ));
+    // колонка переопределяется целиком, как MODIFY в MySQL
+    if col.nullable == Some(false) {
+        sql.push_str(&format!(", ALTER COLUMN {name} SET NOT NULL"));
+    } else {
+        sql.push_str(&format!(", ALTER COLUMN {name} DROP NOT NULL"));
+    }
+    match &col.default {
+        Some(d) => sql.push_str(&format!(
+            ", ALTER COLUMN {name} SET DEFAULT {}",
+            default_sql(d, cfg)
+        )),
+        None => sql.push_str(&format!(", ALTER COLUMN {name} DROP DEFAULT")),
+    }
+    if col.unique {
+        sql.push_str(&format!(", ADD UNIQUE ({name})"));
+    }
+    Ok(())
+}
+
+fn column_type_sql(ty: &R::ColumnType, cfg: &SqlRenderCfg) -> Result<String> {
+    use R::ColumnType as T;
+
+    let d = cfg.dialect;
+    let s = match ty {
+        T::Increments | T::Integer => match d {
+            Dialect::MySQL => "INT",
+            Dialect::Postgres | Dialect::SQLite => "INTEGER",
+        },
+        T::BigIncrements | T::BigInteger => "BIGINT",
+        T::TinyInteger => match d {
+            Dialect::Postgres => "SMALLINT",
+            Dialect::MySQL | Dialect::SQLite => "TINYINT",
+        },
+        T::SmallInteger => "SMALLINT",
+        T::Float => match d {
+            Dialect::Postgres => "REAL",
+            Dialect::MySQL | Dialect::SQLite => "FLOAT",
+        },
+        T::Double => match d {
+            Dialect::Postgres => "DOUBLE PRECISION",
+            Dialect::MySQL | Dialect::SQLite => "DOUBLE",
+        },
+        T::Decimal { precision, scale } => return Ok(format!("DECIMAL({precision}, {scale})")),
+        T::Boolean => match d {
+            Dialect::MySQL => "TINYINT(1)",
+            Dialect::Postgres | Dialect::SQLite => "BOOLEAN",
+        },
+        T::String(len) => return Ok(format!("VARCHAR({len})")),
+        T::Text => "TEXT",
+        T::Date => "DATE",
+        T::Time => "TIME",
+        T::DateTime => match d {
+            Dialect::Postgres => "TIMESTAMP",
+            Dialect::MySQL | Dialect::SQLite => "DATETIME",
+        },
+        T::Timestamp => match d {
+            Dialect::Postgres => "TIMESTAMPTZ",
+            Dialect::MySQL => "TIMESTAMP",
+            Dialect::SQLite => "DATETIME",
+        },
+        T::Binary => match d {
+            Dialect::Postgres => "BYTEA",
+            Dialect::MySQL | Dialect::SQLite => "BLOB",
+        },
+        T::Json => match d {
+            Dialect::Postgres | Dialect::MySQL => "JSON",
+            Dialect::SQLite => "TEXT",
+        },
+        T::Jsonb => match d {
+            Dialect::Postgres => "JSONB",
+            Dialect::MySQL => {
+                degrade("JSONB", cfg)?;
+                "JSON"
+            }
+            Dialect::SQLite => {
+                degrade("JSONB", cfg)?;
+                "TEXT"
+            }
+        },
+        T::Uuid => match d {
+            Dialect::Postgres => "UUID",
+            Dialect::MySQL | Dialect::SQLite => "CHAR(36)",
+        },
+        T::Enum(values) => match d {
+            Dialect::MySQL => {
+                let mut s = String::from("ENUM(");
+                push_string_list(&mut s, values, cfg);
+                s.push(')');
+                return Ok(s);
+            }
+            // значения проверяются CHECK-ограничением в render_column
+            Dialect::Postgres | Dialect::SQLite => "TEXT",
+        },
+        T::Custom(raw) => return Ok(raw.clone()),
+    };
+    Ok(s.to_string())
+}
+
+fn default_sql(d: &R::ColumnDefault, cfg: &SqlRenderCfg) -> String {
+    match d {
+        R::ColumnDefault::Value(p) => render_literal(p, cfg.dialect),
+        R::ColumnDefault::Raw(raw) => raw.clone(),
+    }
+}
+
+fn add_foreign_key_sql(table: &str, fk: &R::ForeignKey, cfg: &SqlRenderCfg) -> Result<String> {
+    let mut sql = format!("ALTER TABLE {table} ADD ");
+    push_foreign_key(&mut sql, fk, cfg)?;
+    Ok(sql)
+}
+
+/// CREATE [UNIQUE] INDEX "name" ON "table" ("a", "b") [WHERE ...]
+fn render_create_index(ix: &R::CreateIndex, cfg: &SqlRenderCfg) -> Result<String> {
+    let mut sql = String::with_capacity(64);
+    sql.push_str(if ix.unique {
+        "CREATE UNIQUE INDEX "
+    } else {
+        "CREATE INDEX "
+    });
+    // в SQLite схема (attached БД) указывается у индекса, а не у таблицы
+    if cfg.dialect == Dialect::SQLite {
+        sql.push_str(&index_name(&ix.name, &ix.table, cfg));
+        sql.push_str(" ON ");
+        sql.push_str(&quote_ident(&ix.table.name, cfg));
+    } else {
+        sql.push_str(&quote_ident(&ix.name, cfg));
+        sql.push_str(" ON ");
+        sql.push_str(&table_name(&ix.table, cfg));
+    }
+    sql.push(' ');
+    push_column_list(&mut sql, &ix.columns, cfg);
+
+    if let Some(pred) = &ix.predicate {
+        match cfg.dialect {
+            Dialect::Postgres | Dialect::SQLite => {
+                sql.push_str(" WHERE ");
+                sql.push_str(&render_predicate(pred, cfg));
+            }
+            // без WHERE уникальный индекс стал бы строже задуманного — не деградируем
+            Dialect::MySQL if ix.unique => {
+                return Err(unsupported("partial UNIQUE INDEX", cfg.dialect));
+            }
+            Dialect::MySQL => degrade("partial INDEX", cfg)?,
+        }
+    }
+    Ok(sql)
+}
+
+fn render_drop_index(name: &str, table: &R::TableName, cfg: &SqlRenderCfg) -> String {
+    match cfg.dialect {
+        Dialect::MySQL => format!(
+            "DROP INDEX {} ON {}",
+            quote_ident(name, cfg),
+            table_name(table, cfg)
+        ),
+        Dialect::Postgres | Dialect::SQLite => {
+            format!("DROP INDEX {}", index_name(name, table, cfg))
+        }
+    }
+}
+
+/// Имя индекса в схеме таблицы (PG/SQLite)
+fn index_name(name: &str, table: &R::TableName, cfg: &SqlRenderCfg) -> String {
+    match &table.schema {
+        Some(s) => format!("{}.{}", quote_ident(s, cfg), quote_ident(name, cfg)),
+        None => quote_ident(name, cfg),
+    }
+}
+
+/// Условие с параметрами, подставленными литералами: DDL не принимает плейсхолдеры
+fn render_predicate(pred: &R::Predicate, cfg: &SqlRenderCfg) -> String {
+    let mut expr = pred.expr.clone();
+    inline_binds(&mut expr, &mut pred.params.iter(), cfg.dialect);
+    let mut w = SqlWriter::new(64, cfg.placeholders);
+    render_expr(&mut w, &expr, cfg);
+    w.buf
+}
+
+/// [CONSTRAINT "name"] CHECK (...)
+fn push_check(sql: &mut String, check: &R::CheckConstraint, cfg: &SqlRenderCfg) {
+    if let Some(name) = &check.name {
+        sql.push_str("CONSTRAINT ");
+        sql.push_str(&quote_ident(name, cfg));
+        sql.push(' ');
+    }
+    sql.push_str("CHECK (");
+    sql.push_str(&render_predicate(&check.predicate, cfg));
+    sql.push(')');
+}
+
+fn comment_on_column_sql(
+    table: &R::TableName,
+    column: &str,
+    comment: &str,
+    cfg: &SqlRenderCfg,
+) -> String {
+    format!(
+        "COMMENT ON COLUMN {}.{} IS {}",
+        table_name(table, cfg),
+        quote_ident(column, cfg),
+        quote_string(comment, cfg.dialect)
+    )
+}
+
+/// [CONSTRAINT "name"] FOREIGN KEY ("a") REFERENCES "t" ("id")
+fn push_foreign_key(sql: &mut String, fk: &R::ForeignKey, cfg: &SqlRenderCfg) -> Result<()> {
+    if let Some(name) = &fk.name {
+        sql.push_str("CONSTRAINT ");
+        sql.push_str(&quote_ident(name, cfg));
+        sql.push(' ');
+    }
+    sql.push_str("FOREIGN KEY ");
+    push_column_list(sql, &fk.columns, cfg);
+    sql.push_str(" REFERENCES ");
+    push_references(sql, fk, cfg)
+}
+
+/// "t" ("id") [ON DELETE ...] [ON UPDATE ...]
+fn push_references(sql: &mut String, fk: &R::ForeignKey, cfg: &SqlRenderCfg) -> Result<()> {
+    sql.push_str(&table_name(&fk.ref_table, cfg));
+    sql.push(' ');
+    push_column_list(sql, &fk.ref_columns, cfg);
+
+    for (clause, action) in [(" ON DELETE ", fk.on_delete), (" ON UPDATE ", fk.on_update)] {
+        let Some(action) = action else { continue };
+        // InnoDB отвергает SET DEFAULT при создании ключа
+        if action == R::ReferentialAction::SetDefault && cfg.dialect == Dialect::MySQL {
+            degrade("ON DELETE/UPDATE SET DEFAULT", cfg)?;
+            continue;
+        }
+        sql.push_str(clause);
+        sql.push_str(referential_action_sql(action));
+    }
+    Ok(())
+}
+
+fn referential_action_sql(action: R::ReferentialAction) -> &'static str {
+    match action {
+        R::ReferentialAction::Cascade => "CASCADE",
+        R::ReferentialAction::SetNull => "SET NULL",
+        R::ReferentialAction::SetDefault => "SET DEFAULT",
+        R::ReferentialAction::Restrict => "RESTRICT",
+        R::ReferentialAction::NoAction => "NO ACTION",
+    }
+}
+
+fn push_column_list(sql: &mut String, cols: &[String], cfg: &SqlRenderCfg) {
+    sql.push('(');
+    for (i, c) in cols.iter().enumerate() {
+        if i > 0 {
+            sql.push_str(", ");
+        }
+        sql.push_str(&quote_ident(c, cfg));
+    }
+    sql.push(')');
+}
+
+fn push_string_list(sql: &mut String, values: &[String], cfg: &SqlRenderCfg) {
+    for (i, v) in values.iter().enumerate() {
+        if i > 0 {
+            sql.push_str(", ");
+        }
+        sql.push_str(&quote_string(v, cfg.dialect));
+    }
+}
+
+fn table_name(t: &R::TableName, cfg: &SqlRenderCfg) -> String {
+    match &t.schema {
+        Some(s) => format!("{}.{}", quote_ident(s, cfg), quote_ident(&t.name, cfg)),
+        None => quote_ident(&t.name, cfg),
+    }
+}
+
+/// Конструкция без аналога в диалекте: в Strict — ошибка, в Lenient — опускается.
+#[inline]
+fn degrade(feature: &'static str, cfg: &SqlRenderCfg) -> Result<()> {
+    match cfg.policy {
+        FeaturePolicy::Strict => Err(unsupported(feature, cfg.dialect)),
+        FeaturePolicy::Lenient => Ok(()),
+    }
+}
+
+#[inline]
+fn unsupported(feature: &'static str, dialect: Dialect) -> Error {
+    Error::UnsupportedFeature {
+        feature: feature.into(),
+        dialect,
+    }
+}
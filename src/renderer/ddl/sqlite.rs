@@ -0,0 +1,155 @@
+use sqlparser::ast::{ColumnOption, Statement, TableConstraint};
+use sqlparser::dialect::SQLiteDialect;
+use sqlparser::parser::Parser;
+
+use super::{push_check, push_foreign_key, render_column, sqlite_requires_rebuild, table_name};
+use crate::renderer::ast as R;
+use crate::renderer::ident::quote_ident;
+use crate::renderer::{Error, Result, SqlRenderCfg};
+
+/// Префикс временной таблицы при пересоздании
+const TMP_PREFIX: &str = "_knux_tmp_";
+
+/// Пересоздание таблицы SQLite для действий, которых нет в его ALTER TABLE
+/// (изменение колонки, добавление/удаление FK и CHECK).
+///
+/// `table_sql` — текущий `CREATE TABLE` из `sqlite_master`, `dependents` — SQL
+/// индексов и триггеров таблицы: они удаляются вместе с ней и создаются заново.
+/// Выполнять нужно в транзакции при выключенных `foreign_keys`.
+pub fn render_sqlite_rebuild(
+    at: &R::AlterTable,
+    table_sql: &str,
+    dependents: &[String],
+    cfg: &SqlRenderCfg,
+) -> Result<Vec<String>> {
+    let mut stmts = Parser::parse_sql(&SQLiteDialect {}, table_sql)
+        .map_err(|e| rebuild_error(format!("cannot parse table definition: {e}")))?;
+    let Some(Statement::CreateTable(mut ct)) = stmts.pop() else {
+        return Err(rebuild_error(
+            "expected CREATE TABLE definition".to_string(),
+        ));
+    };
+
+    let copy_cols: Vec<String> = ct
+        .columns
+        .iter()
+        .map(|c| quote_ident(&c.name.value, cfg))
+        .collect();
+    // None — колонка без изменений, печатается как в исходном определении
+    let mut rendered: Vec<Option<String>> = vec![None; ct.columns.len()];
+    let mut added = Vec::new();
+
+    for op in &at.ops {
+        match op {
+            R::AlterTableOp::AlterColumn(col) => {
+                let Some(i) = ct
+                    .columns
+                    .iter()
+                    .position(|c| c.name.value.eq_ignore_ascii_case(&col.name))
+                else {
+                    return Err(rebuild_error(format!("column {} not found", col.name)));
+                };
+                let mut sql = String::new();
+                render_column(&mut sql, &mut Vec::new(), &at.table, col, true, cfg)?;
+                rendered[i] = Some(sql);
+            }
+            R::AlterTableOp::AddForeignKey(fk) => {
+                let mut sql = String::new();
+                push_foreign_key(&mut sql, fk, cfg)?;
+                added.push(sql);
+            }
+            R::AlterTableOp::AddCheck(check) => {
+                let mut sql = String::new();
+                push_check(&mut sql, check, cfg);
+                added.push(sql);
+            }
+            R::AlterTableOp::DropForeignKey(name) => drop_constraint(&mut ct, name, true)?,
+            R::AlterTableOp::DropCheck(name) => drop_constraint(&mut ct, name, false)?,
+            op => {
+                debug_assert!(!sqlite_requires_rebuild(op));
+                return Err(rebuild_error(format!(
+                    "{op:?} is rendered by render_ddl, not by table rebuild"
+                )));
+            }
+        }
+    }
+
+    let tmp = R::TableName {
+        schema: at.table.schema.clone(),
+        name: format!("{TMP_PREFIX}{}", at.table.name),
+    };
+    let tmp_sql = table_name(&tmp, cfg);
+    let old_sql = table_name(&at.table, cfg);
+
+    let mut create = format!("CREATE TABLE {tmp_sql} (");
+    let columns = ct
+        .columns
+        .iter()
+        .zip(rendered)
+        .map(|(c, r)| r.unwrap_or_else(|| c.to_string()));
+    let constraints = ct.constraints.iter().map(ToString::to_string);
+    let items: Vec<String> = columns.chain(constraints).chain(added).collect();
+    create.push_str(&items.join(", "));
+    create.push(')');
+    let options: Vec<&str> = [(ct.strict, "STRICT"), (ct.without_rowid, "WITHOUT ROWID")]
+        .into_iter()
+        .filter_map(|(on, opt)| on.then_some(opt))
+        .collect();
+    if !options.is_empty() {
+        create.push(' ');
+        create.push_str(&options.join(", "));
+    }
+
+    let cols = copy_cols.join(", ");
+    let mut out = vec![
+        create,
+        format!("INSERT INTO {tmp_sql} ({cols}) SELECT {cols} FROM {old_sql}"),
+        format!("DROP TABLE {old_sql}"),
+        // без legacy-режима RENAME перепроверяет представления, ссылающиеся на удалённую таблицу
+        "PRAGMA legacy_alter_table = ON".to_string(),
+        format!(
+            "ALTER TABLE {tmp_sql} RENAME TO {}",
+            quote_ident(&at.table.name, cfg)
+        ),
+        "PRAGMA legacy_alter_table = OFF".to_string(),
+    ];
+    out.extend(dependents.iter().cloned());
+    Ok(out)
+}
+
+/// Удаляет именованное ограничение уровня таблицы или колонки
+fn drop_constraint(ct: &mut sqlparser::ast::CreateTable, name: &str, foreign: bool) -> Result<()> {
+    let matches = |n: &Option<sqlparser::ast::Ident>| {
+        n.as_ref()
+            .is_some_and(|n| n.value.eq_ignore_ascii_case(name))
+    };
+
+    let before = ct.constraints.len();
+    ct.constraints.retain(|c| match c {
+        TableConstraint::ForeignKey { name, .. } if foreign => !matches(name),
+        TableConstraint::Check { name, .. } if !foreign => !matches(name),
+        _ => true,
+    });
+    if ct.constraints.len() != before {
+        return Ok(());
+    }
+
+    for col in &mut ct.columns {
+        let before = col.options.len();
+        col.options.retain(|o| match o.option {
+            ColumnOption::ForeignKey { .. } if foreign => !matches(&o.name),
+            ColumnOption::Check(_) if !foreign => !matches(&o.name),
+            _ => true,
+        });
+        if col.options.len() != before {
+            return Ok(());
+        }
+    }
+
+    let kind = if foreign { "foreign key" } else { "check" };
+    Err(rebuild_error(format!("{kind} constraint {name} not found")))
+}
+
+fn rebuild_error(msg: String) -> Error {
+    Error::TableRebuild(msg.into())
+}
@@ -1,7 +1,7 @@
 use crate::renderer::ast as R;
 use crate::renderer::ident::quote_ident;
-use crate::renderer::select::render_expr;
-use crate::renderer::{Dialect, SqlRenderCfg, SqlWriter, render_select};
+use crate::renderer::select::{render_expr, render_query_into};
+use crate::renderer::{Dialect, SqlRenderCfg, SqlWriter};
 
 #[inline]
 fn push_joined<T>(w: &mut SqlWriter, items: &[T], mut f: impl FnMut(&mut SqlWriter, &T)) {
@@ -30,8 +30,7 @@ fn render_table_ref(w: &mut SqlWriter, t: &R::TableRef, cfg: &SqlRenderCfg) {
         }
         R::TableRef::Subquery { query, alias } => {
             w.push("(");
-            let sub = render_select(query, cfg, 128); // оставляем как есть (без инвазивных правок select-рендера)
-            w.push(&sub);
+            render_query_into(w, query, cfg);
             w.push(")");
             push_table_alias(w, alias, cfg);
         }
@@ -84,7 +83,6 @@ pub fn render_delete(d: &R::Delete, cfg: &SqlRenderCfg, cap: usize) -> String {
     match cfg.dialect {
         Dialect::Postgres | Dialect::SQLite => render_returning(&mut w, &d.returning, cfg),
         Dialect::MySQL => { /* ignore */ }
-        _ => {}
     }
 
     w.finish()
@@ -11,4 +11,7 @@ pub enum Error {
         feature: Cow<'static, str>,
         dialect: Dialect,
     },
+
+    #[error("Table rebuild failed: {0}")]
+    TableRebuild(Cow<'static, str>),
 }
@@ -1,6 +1,6 @@
 use crate::renderer::ast as R;
 use crate::renderer::ident::quote_ident;
-use crate::renderer::select::render_expr;
+use crate::renderer::select::{render_expr, render_query_into};
 use crate::renderer::{Dialect, SqlRenderCfg, SqlWriter};
 
 /// Рендер `INSERT` с учётом диалектов.
@@ -25,7 +25,9 @@ pub fn render_insert(i: &R::Insert, cfg: &SqlRenderCfg, cap: usize) -> String {
     render_table_ref(&mut w, &i.table, cfg);
 
     // В MySQL для ON DUPLICATE KEY UPDATE понадобятся ссылки на "new.col"
+    // (у INSERT ... SELECT алиаса строки нет — там VALUES(col))
     let need_alias_new = matches!(cfg.dialect, Dialect::MySQL)
+        && i.select.is_none()
         && i.on_conflict.as_ref().map_or(false, |c| {
             matches!(c.action, Some(R::OnConflictAction::DoUpdate { .. }))
         });
@@ -36,8 +38,11 @@ pub fn render_insert(i: &R::Insert, cfg: &SqlRenderCfg, cap: usize) -> String {
 
     render_columns(&mut w, &i.columns, cfg);
 
-    // 3) VALUES
-    render_values(&mut w, &i.rows, cfg);
+    // 3) VALUES или SELECT
+    match &i.select {
+        Some(q) => render_insert_select(&mut w, q, i.on_conflict.is_some(), cfg),
+        None => render_values(&mut w, &i.rows, cfg),
+    }
 
     // 4) UPSERT/IGNORE
     match cfg.dialect {
@@ -116,20 +121,22 @@ pub fn render_insert(i: &R::Insert, cfg: &SqlRenderCfg, cap: usize) -> String {
                 }) = &spec.action
                 {
                     w.push(" ON DUPLICATE KEY UPDATE ");
-                    render_set_assignments_common(
-                        &mut w,
-                        set,
-                        where_predicate.as_ref(),
-                        cfg,
-                        "new.",
-                        " /* WHERE */ ",
-                    );
+                    if i.select.is_some() {
+                        render_set_assignments_mysql_values(&mut w, set, cfg);
+                    } else {
+                        render_set_assignments_common(
+                            &mut w,
+                            set,
+                            where_predicate.as_ref(),
+                            cfg,
+                            "new.",
+                            " /* WHERE */ ",
+                        );
+                    }
                 }
             }
             // RETURNING в MySQL не печатаем
         }
-
-        _ => {}
     }
 
     w.finish()
@@ -177,6 +184,40 @@ fn render_values(w: &mut SqlWriter, rows: &[Vec<R::Expr>], cfg: &SqlRenderCfg) {
     });
 }
 
+/// ` SELECT ...` в том же writer — нумерация `$N` продолжается.
+/// SQLite требует WHERE у SELECT перед ON CONFLICT (иначе `ON` читается как JOIN),
+/// поэтому при апсерте без условия добавляем `WHERE true`.
+fn render_insert_select(w: &mut SqlWriter, q: &R::Query, upsert: bool, cfg: &SqlRenderCfg) {
+    w.push(" ");
+    let needs_where = upsert
+        && matches!(cfg.dialect, Dialect::SQLite)
+        && matches!(&q.body, R::QueryBody::Select(s) if s.r#where.is_none());
+    if !needs_where {
+        render_query_into(w, q, cfg);
+        return;
+    }
+    let mut q = q.clone();
+    if let R::QueryBody::Select(s) = &mut q.body {
+        s.r#where = Some(R::Expr::Raw("true".into()));
+    }
+    render_query_into(w, &q, cfg);
+}
+
+/// MySQL `INSERT ... SELECT ... ON DUPLICATE KEY UPDATE col = VALUES(col)`
+fn render_set_assignments_mysql_values(w: &mut SqlWriter, set: &[R::Assign], cfg: &SqlRenderCfg) {
+    push_joined(w, set, |w, a| {
+        w.push(quote_ident(&a.col, cfg));
+        w.push(" = ");
+        if a.from_inserted {
+            w.push("VALUES(");
+            w.push(quote_ident(&a.col, cfg));
+            w.push(")");
+        } else {
+            render_expr(w, &a.value, cfg);
+        }
+    });
+}
+
 #[inline]
 fn render_returning(w: &mut SqlWriter, items: &[R::SelectItem], cfg: &SqlRenderCfg) {
     if items.is_empty() {
@@ -0,0 +1,268 @@
+use crate::{
+    param::Param,
+    renderer::{
+        Dialect,
+        ast::{Expr, FrameBound},
+    },
+};
+
+/// Значение параметра как SQL-литерал (для контекстов без плейсхолдеров: DDL и т.п.).
+pub fn render_literal(p: &Param, dialect: Dialect) -> String {
+    if p.is_null() {
+        return "NULL".to_string();
+    }
+
+    match p {
+        Param::I64(v) => v.to_string(),
+        Param::I32(v) => v.to_string(),
+        Param::I16(v) => v.to_string(),
+        Param::I8(v) => v.to_string(),
+        Param::F64(v) => float_literal(*v, dialect),
+        Param::F32(v) => float_literal(*v as f64, dialect),
+
+        Param::Bool(b) => match dialect {
+            Dialect::Postgres => if *b { "TRUE" } else { "FALSE" }.to_string(),
+            // MySQL/SQLite хранят boolean как 0/1
+            Dialect::MySQL | Dialect::SQLite => if *b { "1" } else { "0" }.to_string(),
+        },
+
+        Param::Str(s) => quote_string(s, dialect),
+        Param::Bytes(b) => quote_bytes(b, dialect),
+
+        #[cfg(feature = "time")]
+        Param::Date(v) => typed_string("DATE", &v.to_string(), dialect),
+        #[cfg(feature = "time")]
+        Param::Time(v) => typed_string("TIME", &time_hms(*v), dialect),
+        #[cfg(feature = "time")]
+        Param::DateTime(v) => {
+            let off = v.offset();
+            let (h, m, _) = off.as_hms();
+            let ts = format!(
+                "{} {}{}{:02}:{:02}",
+                v.date(),
+                time_hms(v.time()),
+                if off.is_negative() { '-' } else { '+' },
+                h.unsigned_abs(),
+                m.unsigned_abs()
+            );
+            typed_string("TIMESTAMPTZ", &ts, dialect)
+        }
+        #[cfg(feature = "time")]
+        Param::NaiveDateTime(v) => typed_string(
+            "TIMESTAMP",
+            &format!("{} {}", v.date(), time_hms(v.time())),
+            dialect,
+        ),
+
+        #[cfg(feature = "chrono")]
+        Param::ChronoNaiveDate(v) => typed_string("DATE", &v.to_string(), dialect),
+        #[cfg(feature = "chrono")]
+        Param::ChronoNaiveTime(v) => typed_string("TIME", &v.to_string(), dialect),
+        #[cfg(feature = "chrono")]
+        Param::ChronoNaiveDateTime(v) => typed_string("TIMESTAMP", &v.to_string(), dialect),
+        #[cfg(feature = "chrono")]
+        Param::ChronoDateTimeUtc(v) => typed_string("TIMESTAMPTZ", &v.to_rfc3339(), dialect),
+        #[cfg(feature = "chrono")]
+        Param::ChronoDateTimeFixed(v) => typed_string("TIMESTAMPTZ", &v.to_rfc3339(), dialect),
+
+        #[cfg(feature = "serde_json")]
+        Param::Json(v) => quote_string(&v.to_string(), dialect),
+        #[cfg(feature = "uuid")]
+        Param::Uuid(v) => quote_string(&v.to_string(), dialect),
+        #[cfg(feature = "rust_decimal")]
+        Param::Decimal(v) => v.to_string(),
+
+        // NULL-варианты обработаны выше
+        _ => "NULL".to_string(),
+    }
+}
+
+/// NaN/±∞ есть только в PG (как строки float8); в MySQL/SQLite их не записать — NULL
+fn float_literal(v: f64, dialect: Dialect) -> String {
+    if v.is_finite() {
+        return v.to_string();
+    }
+    match dialect {
+        Dialect::Postgres if v.is_nan() => "'NaN'::float8".to_string(),
+        Dialect::Postgres if v > 0.0 => "'Infinity'::float8".to_string(),
+        Dialect::Postgres => "'-Infinity'::float8".to_string(),
+        Dialect::MySQL | Dialect::SQLite => "NULL".to_string(),
+    }
+}
+
+/// Дата/время: в PG — типизированный литерал (`DATE '...'`), в MySQL/SQLite — строка
+#[cfg(any(feature = "time", feature = "chrono"))]
+fn typed_string(ty: &str, s: &str, dialect: Dialect) -> String {
+    match dialect {
+        Dialect::Postgres => format!("{ty} {}", quote_string(s, dialect)),
+        Dialect::MySQL | Dialect::SQLite => quote_string(s, dialect),
+    }
+}
+
+/// `HH:MM:SS[.ffffff]` — `Display` у `time::Time` не дополняет часы нулём
+#[cfg(feature = "time")]
+fn time_hms(t: time::Time) -> String {
+    let (h, m, s, us) = t.as_hms_micro();
+    if us == 0 {
+        format!("{h:02}:{m:02}:{s:02}")
+    } else {
+        format!("{h:02}:{m:02}:{s:02}.{us:06}")
+    }
+}
+
+/// Строковый литерал в одинарных кавычках; в MySQL дополнительно экранируем `\`.
+pub fn quote_string(s: &str, dialect: Dialect) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('\'');
+    for ch in s.chars() {
+        match ch {
+            '\'' => out.push_str("''"),
+            '\\' if dialect == Dialect::MySQL => out.push_str("\\\\"),
+            _ => out.push(ch),
+        }
+    }
+    out.push('\'');
+    out
+}
+
+fn quote_bytes(b: &[u8], dialect: Dialect) -> String {
+    use std::fmt::Write;
+
+    let mut hex = String::with_capacity(b.len() * 2);
+    for byte in b {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    match dialect {
+        Dialect::Postgres => format!("'\\x{hex}'"),
+        Dialect::MySQL | Dialect::SQLite => format!("X'{hex}'"),
+    }
+}
+
+/// Подставляет значения вместо плейсхолдеров готового SQL (`$n` в PG, `?` в
+/// MySQL/SQLite); строки и идентификаторы в кавычках не трогаются.
+/// Результат — для логов и отладки, выполнять его нельзя: экранирование здесь
+/// не заменяет bind-параметры.
+pub fn inline_params(sql: &str, params: &[Param], dialect: Dialect) -> String {
+    let bytes = sql.as_bytes();
+    let mut out = String::with_capacity(sql.len() + params.len() * 8);
+    let mut next = params.iter();
+    let mut copied = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            q @ (b'\'' | b'"' | b'`') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != q {
+                    // в MySQL внутри строки `\` экранирует следующий символ
+                    if q == b'\'' && dialect == Dialect::MySQL && bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'$' if dialect == Dialect::Postgres => {
+                let start = i + 1;
+                let mut end = start;
+                while end < bytes.len() && bytes[end].is_ascii_digit() {
+                    end += 1;
+                }
+                let param = sql[start..end]
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|n| params.get(n.wrapping_sub(1)));
+                if let Some(p) = param {
+                    out.push_str(&sql[copied..i]);
+                    out.push_str(&render_literal(p, dialect));
+                    copied = end;
+                }
+                i = end.max(i + 1);
+            }
+            b'?' if dialect != Dialect::Postgres => {
+                if let Some(p) = next.next() {
+                    out.push_str(&sql[copied..i]);
+                    out.push_str(&render_literal(p, dialect));
+                    copied = i + 1;
+                }
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+    out.push_str(&sql[copied..]);
+    out
+}
+
+/// Заменяет плейсхолдеры (`Expr::Bind`) литералами — в том же порядке, в каком
+/// их печатает `render_expr`.
+pub(crate) fn inline_binds<'p, I>(e: &mut Expr, params: &mut I, dialect: Dialect)
+where
+    I: Iterator<Item = &'p Param>,
+{
+    match e {
+        Expr::Bind => {
+            if let Some(p) = params.next() {
+                *e = Expr::Raw(render_literal(p, dialect));
+            }
+        }
+        Expr::Tuple(xs) | Expr::FuncCall { args: xs, .. } => {
+            for x in xs {
+                inline_binds(x, params, dialect);
+            }
+        }
+        Expr::Like { expr, pattern, .. } => {
+            inline_binds(expr, params, dialect);
+            inline_binds(pattern, params, dialect);
+        }
+        Expr::Binary { left, right, .. } => {
+            inline_binds(left, params, dialect);
+            inline_binds(right, params, dialect);
+        }
+        Expr::Unary { expr, .. }
+        | Expr::Paren(expr)
+        | Expr::Cast { expr, .. }
+        | Expr::Collate { expr, .. } => inline_binds(expr, params, dialect),
+        Expr::Case {
+            operand,
+            when_then,
+            else_expr,
+        } => {
+            if let Some(op) = operand {
+                inline_binds(op, params, dialect);
+            }
+            for (w, t) in when_then {
+                inline_binds(w, params, dialect);
+                inline_binds(t, params, dialect);
+            }
+            if let Some(el) = else_expr {
+                inline_binds(el, params, dialect);
+            }
+        }
+        Expr::WindowFunc { args, window, .. } => {
+            for a in args {
+                inline_binds(a, params, dialect);
+            }
+            for p in &mut window.partition_by {
+                inline_binds(p, params, dialect);
+            }
+            for oi in &mut window.order_by {
+                inline_binds(&mut oi.expr, params, dialect);
+            }
+            if let Some(f) = &mut window.frame {
+                for b in std::iter::once(&mut f.start).chain(f.end.as_mut()) {
+                    if let FrameBound::Preceding(e) | FrameBound::Following(e) = b {
+                        inline_binds(e, params, dialect);
+                    }
+                }
+            }
+        }
+        Expr::Raw(_)
+        | Expr::Ident { .. }
+        | Expr::String(_)
+        | Expr::Number(_)
+        | Expr::Bool(_)
+        | Expr::Null
+        | Expr::Star => {}
+    }
+}
@@ -1,3 +1,4 @@
+use super::select::map_to_render_query;
 use super::utils::{map_expr, map_select_item};
 use crate::renderer::{
     ast as R,
@@ -34,6 +35,14 @@ pub(crate) fn map_insert(i: &S::Insert) -> R::Insert {
         _ => Vec::new(),
     };
 
+    // INSERT ... SELECT
+    let select = match i.source.as_deref() {
+        Some(q) if !matches!(q.body.as_ref(), S::SetExpr::Values(_)) => {
+            Some(Box::new(map_to_render_query(q)))
+        }
+        _ => None,
+    };
+
     // RETURNING (с предвыделением)
     let returning = i.returning.as_ref().map_or_else(Vec::new, |v| {
         let mut out = Vec::with_capacity(v.len());
@@ -110,6 +119,7 @@ pub(crate) fn map_insert(i: &S::Insert) -> R::Insert {
         table,
         columns,
         rows,
+        select,
         ignore,
         on_conflict,
         returning,
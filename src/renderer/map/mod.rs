@@ -9,6 +9,7 @@ mod update;
 mod utils;
 
 pub use select::{map_query_body, map_to_render_ast, map_to_render_query};
+pub(crate) use utils::map_expr;
 
 // Универсальный роутер Statement -> renderer::ast::Stmt
 pub fn map_to_render_stmt(stmt: &S::Statement) -> R::Stmt {
@@ -1,4 +1,4 @@
-use super::utils::{map_expr, map_order_by, map_select_item};
+use super::utils::{map_expr, map_order_by, map_select_item, map_window_spec};
 use crate::renderer::{ast as R, map::utils::map_table_factor_any};
 use sqlparser::ast::{
     self as S, Cte as SCte, CteAsMaterialized as SCteMat, Distinct, Expr as SExpr, Function,
@@ -18,6 +18,7 @@ pub fn map_to_render_query(q: &SQuery) -> R::Query {
         .unwrap_or((None, None));
 
     let with = q.with.as_ref().map(map_with_clause);
+    let lock = map_locks(&q.locks);
 
     R::Query {
         with,
@@ -25,6 +26,7 @@ pub fn map_to_render_query(q: &SQuery) -> R::Query {
         order_by,
         limit,
         offset,
+        lock,
     }
 }
 
@@ -65,6 +67,7 @@ pub fn map_query_body(se: &SetExpr) -> R::QueryBody {
             group_by: vec![],
             group_by_modifiers: vec![],
             having: None,
+            windows: vec![],
             order_by: vec![],
             limit: None,
             offset: None,
@@ -111,6 +114,7 @@ pub fn map_to_render_ast(q: &SQuery) -> R::Select {
             group_by: vec![],
             group_by_modifiers: vec![],
             having: None,
+            windows: vec![],
             order_by: vec![],
             limit: None,
             offset: None,
@@ -185,6 +189,20 @@ fn map_select_to_render_ast(sel: &SSelect) -> R::Select {
         group_by: group_by_vec,
         group_by_modifiers: group_by_mods,
         having: sel.having.as_ref().map(map_expr),
+        windows: sel
+            .named_window
+            .iter()
+            .map(|S::NamedWindowDefinition(name, def)| R::NamedWindow {
+                name: name.value.clone(),
+                spec: match def {
+                    S::NamedWindowExpr::WindowSpec(spec) => map_window_spec(spec),
+                    S::NamedWindowExpr::NamedWindow(base) => R::WindowSpec {
+                        name: Some(base.value.clone()),
+                        ..Default::default()
+                    },
+                },
+            })
+            .collect(),
         order_by: Vec::new(),
         limit: None,
         offset: None,
@@ -348,6 +366,27 @@ fn map_join(j: &SJoin) -> R::Join {
     }
 }
 
+/// `FOR UPDATE OF a FOR UPDATE OF b` → одна блокировка `OF a, b`;
+/// сила и ожидание берутся из первой
+fn map_locks(locks: &[S::LockClause]) -> Option<R::RowLock> {
+    let first = locks.first()?;
+    let strength = match first.lock_type {
+        S::LockType::Update => R::LockStrength::Update,
+        S::LockType::Share => R::LockStrength::Share,
+    };
+    let wait = match first.nonblock {
+        None => R::LockWait::Wait,
+        Some(S::NonBlock::Nowait) => R::LockWait::NoWait,
+        Some(S::NonBlock::SkipLocked) => R::LockWait::SkipLocked,
+    };
+    let of = locks
+        .iter()
+        .filter_map(|l| l.of.as_ref())
+        .map(|o| super::utils::object_name_join(o, "."))
+        .collect();
+    Some(R::RowLock { strength, of, wait })
+}
+
 #[inline]
 fn read_limit_offset(lc: &LimitClause) -> (Option<u64>, Option<u64>) {
     match lc {
@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 
-use crate::renderer::{ast as R, map::map_to_render_ast};
+use crate::renderer::{ast as R, map::map_to_render_query};
 use sqlparser::ast::{
     self as S, Expr as SExpr, Function, FunctionArg, FunctionArgExpr, FunctionArguments,
     ObjectName, OrderBy, OrderByExpr, OrderByKind, UnaryOperator as SUnOp, Value, ValueWithSpan,
@@ -219,6 +219,7 @@ pub(crate) fn map_expr(e: &SExpr) -> R::Expr {
         }
 
         SExpr::Nested(inner) => E::Paren(Box::new(map_expr(inner))),
+        SExpr::Tuple(xs) => E::Tuple(xs.iter().map(map_expr).collect()),
 
         SExpr::Function(Function {
             name, args, over, ..
@@ -264,29 +265,14 @@ pub(crate) fn map_expr(e: &SExpr) -> R::Expr {
             args,
             over: Some(ow),
             ..
-        }) => match ow {
-            S::WindowType::WindowSpec(S::WindowSpec {
-                partition_by,
-                order_by,
-                ..
-            }) => {
-                let part = partition_by.iter().map(map_expr).collect::<Vec<_>>();
-                let ob = order_by.iter().map(map_order_by_expr).collect();
-                R::Expr::WindowFunc {
-                    name: name.to_string(),
-                    args: map_function_arguments(args),
-                    window: R::WindowSpec {
-                        partition_by: part,
-                        order_by: ob,
-                    },
-                }
-            }
-            S::WindowType::NamedWindow(_name) => R::Expr::WindowFunc {
-                name: name.to_string(),
-                args: map_function_arguments(args),
-                window: R::WindowSpec {
-                    partition_by: vec![],
-                    order_by: vec![],
+        }) => R::Expr::WindowFunc {
+            name: name.to_string(),
+            args: map_function_arguments(args),
+            window: match ow {
+                S::WindowType::WindowSpec(spec) => map_window_spec(spec),
+                S::WindowType::NamedWindow(name) => R::WindowSpec {
+                    name: Some(name.value.clone()),
+                    ..Default::default()
                 },
             },
         },
@@ -295,6 +281,34 @@ pub(crate) fn map_expr(e: &SExpr) -> R::Expr {
     }
 }
 
+pub(crate) fn map_window_spec(spec: &S::WindowSpec) -> R::WindowSpec {
+    R::WindowSpec {
+        name: spec.window_name.as_ref().map(|n| n.value.clone()),
+        partition_by: spec.partition_by.iter().map(map_expr).collect(),
+        order_by: spec.order_by.iter().map(map_order_by_expr).collect(),
+        frame: spec.window_frame.as_ref().map(|f| R::WindowFrame {
+            units: match f.units {
+                S::WindowFrameUnits::Rows => R::FrameUnits::Rows,
+                S::WindowFrameUnits::Range => R::FrameUnits::Range,
+                S::WindowFrameUnits::Groups => R::FrameUnits::Groups,
+            },
+            start: map_frame_bound(&f.start_bound),
+            end: f.end_bound.as_ref().map(map_frame_bound),
+        }),
+    }
+}
+
+// смещение None — UNBOUNDED
+fn map_frame_bound(b: &S::WindowFrameBound) -> R::FrameBound {
+    match b {
+        S::WindowFrameBound::CurrentRow => R::FrameBound::CurrentRow,
+        S::WindowFrameBound::Preceding(None) => R::FrameBound::UnboundedPreceding,
+        S::WindowFrameBound::Preceding(Some(e)) => R::FrameBound::Preceding(Box::new(map_expr(e))),
+        S::WindowFrameBound::Following(None) => R::FrameBound::UnboundedFollowing,
+        S::WindowFrameBound::Following(Some(e)) => R::FrameBound::Following(Box::new(map_expr(e))),
+    }
+}
+
 fn map_value_with_span(v: &ValueWithSpan) -> R::Expr {
     match &v.value {
         Value::SingleQuotedString(s) | Value::NationalStringLiteral(s) => {
@@ -317,7 +331,6 @@ fn map_function_arguments(args: &FunctionArguments) -> Vec<R::Expr> {
         FunctionArguments::Subquery(q) => vec![R::Expr::Ident {
             path: vec![q.to_string()],
         }],
-        _ => Vec::new(),
     }
 }
 
@@ -435,7 +448,7 @@ pub(crate) fn map_table_factor_any(tf: &S::TableFactor) -> R::TableRef {
         S::TableFactor::Derived {
             subquery, alias, ..
         } => {
-            let inner = map_to_render_ast(subquery);
+            let inner = map_to_render_query(subquery);
             R::TableRef::Subquery {
                 query: Box::new(inner),
                 alias: alias.as_ref().map(|a| a.name.value.clone()),
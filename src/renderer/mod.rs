@@ -1,11 +1,14 @@
 mod __tests__;
 pub mod ast;
 mod config;
+mod ddl;
 mod delete;
 mod error;
 mod ident;
 mod insert;
+mod literal;
 pub mod map;
+mod pretty;
 mod select;
 mod update;
 mod validate;
@@ -13,7 +16,15 @@ mod writer;
 
 pub use config::Dialect;
 pub use config::{FeaturePolicy, PlaceholderStyle, QuoteMode, SqlRenderCfg};
+pub use ddl::{
+    render_ddl, render_has_column, render_has_table, render_sqlite_rebuild, sqlite_requires_rebuild,
+};
+#[cfg(feature = "sqlite")]
+pub(crate) use ident::quote_ident;
+pub(crate) use ident::quote_ident_always;
+pub use literal::{inline_params, render_literal};
 pub use map::{map_to_render_query, map_to_render_stmt};
+pub use pretty::format_sql;
 pub use select::{render_select, render_sql_query};
 
 use crate::renderer::insert::render_insert;
@@ -27,7 +38,7 @@ pub use writer::SqlWriter;
 /// High-level API: рендер SELECT AST в строку SQL.
 pub fn render_sql_select(sel: &ast::Select, cfg: &SqlRenderCfg) -> String {
     // capacity эвристика; можно параметризовать
-    render_select(sel, cfg, 256)
+    finish(render_select(sel, cfg, 256), cfg)
 }
 
 pub fn try_render_sql_query(q: &ast::Query, cfg: &SqlRenderCfg) -> Result<String> {
@@ -38,12 +49,13 @@ pub fn try_render_sql_query(q: &ast::Query, cfg: &SqlRenderCfg) -> Result<String
 }
 
 pub fn render_sql_stmt(s: &Stmt, cfg: &SqlRenderCfg) -> String {
-    match s {
-        Stmt::Query(q) => render_sql_query(q, cfg),
+    let sql = match s {
+        Stmt::Query(q) => return render_sql_query(q, cfg),
         Stmt::Insert(i) => render_insert(i, cfg, 256),
         Stmt::Update(u) => update::render_update(u, cfg, 256),
         Stmt::Delete(d) => delete::render_delete(d, cfg, 256),
-    }
+    };
+    finish(sql, cfg)
 }
 
 pub fn try_render_sql_stmt(s: &Stmt, cfg: &SqlRenderCfg) -> Result<String> {
@@ -53,6 +65,15 @@ pub fn try_render_sql_stmt(s: &Stmt, cfg: &SqlRenderCfg) -> Result<String> {
     Ok(render_sql_stmt(s, cfg))
 }
 
+/// `cfg.pretty` применяется к готовой строке: форматтер трогает только пробелы
+pub(crate) fn finish(sql: String, cfg: &SqlRenderCfg) -> String {
+    if cfg.pretty {
+        format_sql(&sql, cfg.dialect)
+    } else {
+        sql
+    }
+}
+
 /// Удобные пресеты под диалекты
 pub fn cfg_postgres_knex() -> SqlRenderCfg {
     SqlRenderCfg {
@@ -65,6 +86,7 @@ pub fn cfg_postgres_knex() -> SqlRenderCfg {
         emit_as_for_table_alias: true,
         emit_as_for_column_alias: true,
         fold_idents: None,
+        pretty: false,
     }
 }
 pub fn cfg_mysql_knex() -> SqlRenderCfg {
@@ -78,6 +100,7 @@ pub fn cfg_mysql_knex() -> SqlRenderCfg {
         emit_as_for_table_alias: true,
         emit_as_for_column_alias: true,
         fold_idents: None,
+        pretty: false,
     }
 }
 pub fn cfg_sqlite_knex() -> SqlRenderCfg {
@@ -91,5 +114,6 @@ pub fn cfg_sqlite_knex() -> SqlRenderCfg {
         emit_as_for_table_alias: true,
         emit_as_for_column_alias: true,
         fold_idents: None,
+        pretty: false,
     }
 }
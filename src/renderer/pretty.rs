@@ -0,0 +1,371 @@
+//! Многострочный SQL для логов, `EXPLAIN` и golden-тестов. Форматтер меняет только
+//! пробелы между токенами: текст в кавычках и плейсхолдеры остаются как есть,
+//! поэтому нумерация `$n` совпадает с компактной формой.
+
+use super::config::Dialect;
+
+const INDENT: &str = "  ";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tok<'s> {
+    Word(&'s str),
+    Open,
+    Close,
+    Comma,
+    Semi,
+    /// Строка/идентификатор в кавычках, оператор, комментарий
+    Other(&'s str),
+    /// `-- ...` до конца строки: после него обязателен перевод строки
+    LineComment(&'s str),
+}
+
+impl<'s> Tok<'s> {
+    fn text(&self) -> &'s str {
+        match *self {
+            Tok::Word(s) | Tok::Other(s) | Tok::LineComment(s) => s,
+            Tok::Open => "(",
+            Tok::Close => ")",
+            Tok::Comma => ",",
+            Tok::Semi => ";",
+        }
+    }
+
+    fn is(&self, kw: &str) -> bool {
+        matches!(self, Tok::Word(w) if w.eq_ignore_ascii_case(kw))
+    }
+}
+
+/// Токен и был ли перед ним пробел в исходном тексте
+type Spanned<'s> = (Tok<'s>, bool);
+
+fn tokenize(sql: &str, dialect: Dialect) -> Vec<Spanned<'_>> {
+    let bytes = sql.as_bytes();
+    let mut out = Vec::new();
+    let mut space = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+        let tok = match bytes[i] {
+            b if b.is_ascii_whitespace() => {
+                space = true;
+                i += 1;
+                continue;
+            }
+            q @ (b'\'' | b'"' | b'`') => {
+                i += 1;
+                loop {
+                    if i >= bytes.len() {
+                        break;
+                    }
+                    if q != b'`' && dialect == Dialect::MySQL && bytes[i] == b'\\' {
+                        i += 2;
+                        continue;
+                    }
+                    if bytes[i] == q {
+                        // удвоенная кавычка — экранирование, строка продолжается
+                        if bytes.get(i + 1) == Some(&q) {
+                            i += 2;
+                            continue;
+                        }
+                        i += 1;
+                        break;
+                    }
+                    i += 1;
+                }
+                Tok::Other(&sql[start..i.min(bytes.len())])
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+                Tok::LineComment(sql[start..i].trim_end())
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = sql[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |p| i + 2 + p + 2);
+                Tok::Other(&sql[start..i])
+            }
+            b'(' => {
+                i += 1;
+                Tok::Open
+            }
+            b')' => {
+                i += 1;
+                Tok::Close
+            }
+            b',' => {
+                i += 1;
+                Tok::Comma
+            }
+            b';' => {
+                i += 1;
+                Tok::Semi
+            }
+            b if is_word_byte(b) => {
+                while i < bytes.len() && is_word_byte(bytes[i]) {
+                    i += 1;
+                }
+                Tok::Word(&sql[start..i])
+            }
+            _ => {
+                // оператор: всё подряд, что не слово, не скобка и не пробел
+                i += 1;
+                while i < bytes.len()
+                    && !is_word_byte(bytes[i])
+                    && !bytes[i].is_ascii_whitespace()
+                    && !matches!(bytes[i], b'(' | b')' | b',' | b';' | b'\'' | b'"' | b'`')
+                {
+                    i += 1;
+                }
+                // не разрезаем многобайтовый символ
+                while !sql.is_char_boundary(i) {
+                    i += 1;
+                }
+                Tok::Other(&sql[start..i])
+            }
+        };
+        out.push((tok, space));
+        space = false;
+    }
+    out
+}
+
+fn is_word_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || b == b'?' || b >= 0x80
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Clause {
+    Head,
+    With,
+    Select,
+    Values,
+    Set,
+    Filter,
+    Other,
+}
+
+/// Начало предложения на позиции `i`: вид и число слов ключевой фразы
+fn clause_at(toks: &[Spanned<'_>], i: usize) -> Option<(Clause, usize)> {
+    let t = |k: usize| toks.get(i + k).map(|(t, _)| *t);
+    let is = |k: usize, kw: &str| t(k).is_some_and(|t| t.is(kw));
+    let prev_is = |kw: &str| i > 0 && toks[i - 1].0.is(kw);
+
+    let Some(Tok::Word(w)) = t(0) else {
+        return None;
+    };
+    let found = match w.to_ascii_uppercase().as_str() {
+        "WITH" => (Clause::With, if is(1, "RECURSIVE") { 2 } else { 1 }),
+        "SELECT" => (Clause::Select, 1),
+        "VALUES" => (Clause::Values, 1),
+        "SET" => (Clause::Set, 1),
+        "WHERE" | "HAVING" => (Clause::Filter, 1),
+        "FROM" if !prev_is("DELETE") && !prev_is("DISTINCT") => (Clause::Other, 1),
+        "GROUP" | "ORDER" if is(1, "BY") => (Clause::Other, 2),
+        "LIMIT" | "OFFSET" | "FETCH" | "RETURNING" | "INTERSECT" | "EXCEPT" => (Clause::Other, 1),
+        "UNION" => (
+            Clause::Other,
+            if is(1, "ALL") || is(1, "DISTINCT") {
+                2
+            } else {
+                1
+            },
+        ),
+        "JOIN" => (Clause::Other, 1),
+        "INNER" | "CROSS" if is(1, "JOIN") => (Clause::Other, 2),
+        "LEFT" | "RIGHT" | "FULL" if is(1, "JOIN") => (Clause::Other, 2),
+        "LEFT" | "RIGHT" | "FULL" if is(1, "OUTER") && is(2, "JOIN") => (Clause::Other, 3),
+        "NATURAL" => {
+            let mut n = 1;
+            while t(n).is_some_and(|t| matches!(t, Tok::Word(_)) && !t.is("JOIN")) && n < 4 {
+                n += 1;
+            }
+            if !is(n, "JOIN") {
+                return None;
+            }
+            (Clause::Other, n + 1)
+        }
+        "ON" if is(1, "CONFLICT") => (Clause::Other, 2),
+        "ON" if is(1, "DUPLICATE") && is(2, "KEY") && is(3, "UPDATE") => (Clause::Set, 4),
+        "FOR" if ["UPDATE", "SHARE", "NO", "KEY"].iter().any(|kw| is(1, kw)) => (Clause::Other, 1),
+        _ => return None,
+    };
+    Some(found)
+}
+
+/// Есть ли в списке предложения (SELECT, VALUES, SET) больше одного элемента
+fn has_many_items(toks: &[Spanned<'_>], from: usize) -> bool {
+    let mut depth = 0usize;
+    for i in from..toks.len() {
+        match toks[i].0 {
+            Tok::Open => depth += 1,
+            Tok::Close if depth == 0 => return false,
+            Tok::Close => depth -= 1,
+            Tok::Comma if depth == 0 => return true,
+            Tok::Semi if depth == 0 => return false,
+            Tok::Word(_) if depth == 0 && clause_at(toks, i).is_some() => return false,
+            _ => {}
+        }
+    }
+    false
+}
+
+struct Frame {
+    /// Скобка с подзапросом (или весь запрос); внутри прочих скобок переносов нет,
+    /// кроме вложенных подзапросов
+    query: bool,
+    base: usize,
+    /// Отступ строки, на которой открыта скобка, — туда встаёт `)`
+    close: usize,
+    clause: Clause,
+    /// Перенос перед следующим токеном этого уровня
+    pending: Option<usize>,
+    in_between: bool,
+    case_depth: usize,
+}
+
+impl Frame {
+    fn query(base: usize, close: usize) -> Self {
+        Self {
+            query: true,
+            base,
+            close,
+            clause: Clause::Head,
+            pending: None,
+            in_between: false,
+            case_depth: 0,
+        }
+    }
+}
+
+/// Раскладывает SQL по строкам: предложения (`SELECT`, `FROM`, `JOIN`, `WHERE`, ...)
+/// с новой строки, элементы `SELECT`/`VALUES`/`SET` и условия `AND`/`OR` в `WHERE` —
+/// с отступом, CTE и подзапросы — блоком внутри скобок.
+pub fn format_sql(sql: &str, dialect: Dialect) -> String {
+    let toks = tokenize(sql, dialect);
+    let mut out = String::with_capacity(sql.len() + sql.len() / 4);
+    let mut stack = vec![Frame::query(0, 0)];
+    let mut line = 0usize;
+    let mut force_break = false;
+    // слова ключевой фразы предложения после первого — без переносов
+    let mut skip = 0usize;
+
+    for (i, &(tok, space)) in toks.iter().enumerate() {
+        let mut brk: Option<usize> = None;
+        let f = stack.last_mut().expect("frame");
+
+        if skip > 0 {
+            skip -= 1;
+        } else if f.query {
+            if let Some((clause, len)) = matches!(tok, Tok::Word(_))
+                .then(|| clause_at(&toks, i))
+                .flatten()
+            {
+                brk = Some(f.base);
+                skip = len - 1;
+                f.clause = clause;
+                f.in_between = false;
+                f.case_depth = 0;
+                f.pending = None;
+                if matches!(clause, Clause::Select | Clause::Values | Clause::Set)
+                    && has_many_items(&toks, i + len)
+                {
+                    f.pending = Some(f.base + 1);
+                }
+            } else {
+                match tok {
+                    Tok::Word(_) if tok.is("CASE") => f.case_depth += 1,
+                    Tok::Word(_) if tok.is("END") => f.case_depth = f.case_depth.saturating_sub(1),
+                    Tok::Word(_) if tok.is("BETWEEN") => f.in_between = true,
+                    Tok::Word(_)
+                        if f.clause == Clause::Filter
+                            && f.case_depth == 0
+                            && (tok.is("AND") || tok.is("OR")) =>
+                    {
+                        if tok.is("AND") && f.in_between {
+                            f.in_between = false;
+                        } else {
+                            brk = Some(f.base + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                // `SELECT DISTINCT [ON (...)]` остаются в строке с SELECT
+                let lead = f.clause == Clause::Select
+                    && (tok.is("DISTINCT")
+                        || tok.is("ALL")
+                        || (tok.is("ON") && i > 0 && toks[i - 1].0.is("DISTINCT"))
+                        || (tok == Tok::Open && i > 0 && toks[i - 1].0.is("ON")));
+                if brk.is_none() && !lead && !matches!(tok, Tok::Close | Tok::Comma) {
+                    brk = f.pending.take();
+                }
+            }
+        }
+
+        if force_break {
+            brk = brk.or(Some(line));
+            force_break = false;
+        }
+
+        match tok {
+            Tok::Open => {
+                let sub = toks
+                    .get(i + 1)
+                    .is_some_and(|(t, _)| t.is("SELECT") || t.is("WITH"));
+                let line_after = brk.unwrap_or(line);
+                stack.push(if sub {
+                    Frame::query(line_after + 1, line_after)
+                } else {
+                    Frame {
+                        query: false,
+                        ..Frame::query(0, 0)
+                    }
+                });
+            }
+            Tok::Close if stack.len() > 1 => {
+                let f = stack.pop().expect("frame");
+                if f.query {
+                    brk = Some(f.close);
+                }
+            }
+            Tok::Comma => {
+                let f = stack.last_mut().expect("frame");
+                if f.query {
+                    match f.clause {
+                        Clause::With => f.pending = Some(f.base),
+                        Clause::Select | Clause::Values | Clause::Set => {
+                            f.pending = Some(f.base + 1)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Tok::Semi => {
+                stack.truncate(1);
+                stack[0] = Frame::query(0, 0);
+                stack[0].pending = Some(0);
+            }
+            Tok::LineComment(_) => force_break = true,
+            _ => {}
+        }
+
+        match brk {
+            Some(n) if !out.is_empty() => {
+                out.push('\n');
+                for _ in 0..n {
+                    out.push_str(INDENT);
+                }
+                line = n;
+            }
+            _ => {
+                if space && !out.is_empty() {
+                    out.push(' ');
+                }
+            }
+        }
+        out.push_str(tok.text());
+    }
+    out
+}
@@ -28,6 +28,7 @@ fn base_select(items: Vec<R::SelectItem>) -> R::Select {
         group_by: vec![],
         group_by_modifiers: vec![],
         having: None,
+        windows: vec![],
         order_by: vec![],
         limit: None,
         offset: None,
@@ -239,7 +240,14 @@ fn join_and_subquery_in_from_render() {
             alias: None,
         }],
         from: Some(R::TableRef::Subquery {
-            query: Box::new(sub),
+            query: Box::new(R::Query {
+                with: None,
+                body: R::QueryBody::Select(sub),
+                order_by: vec![],
+                limit: None,
+                offset: None,
+                lock: None,
+            }),
             alias: Some("u".into()),
         }),
         joins: vec![R::Join {
@@ -260,6 +268,7 @@ fn join_and_subquery_in_from_render() {
         group_by: vec![],
         group_by_modifiers: vec![],
         having: None,
+        windows: vec![],
         order_by: vec![],
         limit: None,
         offset: None,
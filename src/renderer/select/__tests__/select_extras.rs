@@ -15,6 +15,7 @@ fn cfg(d: Dialect) -> SqlRenderCfg {
         emit_as_for_table_alias: true,
         emit_as_for_column_alias: true,
         fold_idents: None,
+        pretty: false,
     }
 }
 
@@ -29,6 +30,7 @@ fn base_select(items: Vec<R::SelectItem>) -> R::Select {
         group_by: vec![],
         group_by_modifiers: vec![],
         having: None,
+        windows: vec![],
         order_by: vec![],
         limit: None,
         offset: None,
@@ -41,6 +43,7 @@ fn wrap_query(sel: R::Select) -> R::Query {
         order_by: vec![],
         limit: None,
         offset: None,
+        lock: None,
     }
 }
 
@@ -260,6 +263,7 @@ fn window_function_partition_and_order() {
                     dir: R::OrderDirection::Asc,
                     nulls_last: false,
                 }],
+                ..Default::default()
             },
         },
         alias: None,
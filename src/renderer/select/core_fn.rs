@@ -10,7 +10,12 @@ use super::utils::*;
 
 pub fn render_sql_query(q: &R::Query, cfg: &SqlRenderCfg) -> String {
     let mut w = SqlWriter::new(256, cfg.placeholders);
+    render_query_into(&mut w, q, cfg);
+    crate::renderer::finish(w.finish(), cfg)
+}
 
+/// Рендер запроса в общий writer (например, источник `INSERT ... SELECT`)
+pub(crate) fn render_query_into(w: &mut SqlWriter, q: &R::Query, cfg: &SqlRenderCfg) {
     // WITH
     if let Some(with) = &q.with {
         w.push("WITH");
@@ -54,14 +59,14 @@ pub fn render_sql_query(q: &R::Query, cfg: &SqlRenderCfg) -> String {
                 }
             }
             w.push(" (");
-            render_query_body(&mut w, &cte.query, cfg);
+            render_query_body(w, &cte.query, cfg);
             w.push(")");
         }
         w.push(" ");
     }
 
     // тело (Select/Set)
-    render_query_body(&mut w, &q.body, cfg);
+    render_query_body(w, &q.body, cfg);
 
     // общий ORDER BY / LIMIT / OFFSET
     if !q.order_by.is_empty() {
@@ -76,11 +81,11 @@ pub fn render_sql_query(q: &R::Query, cfg: &SqlRenderCfg) -> String {
             {
                 // 1) (expr IS NULL) ASC
                 w.push("(");
-                render_expr(&mut w, &oi.expr, cfg);
+                render_expr(w, &oi.expr, cfg);
                 w.push(" IS NULL) ASC, ");
 
                 // 2) expr ASC|DESC
-                render_expr(&mut w, &oi.expr, cfg);
+                render_expr(w, &oi.expr, cfg);
                 match oi.dir {
                     OrderDirection::Asc => w.push(" ASC"),
                     OrderDirection::Desc => w.push(" DESC"),
@@ -89,7 +94,7 @@ pub fn render_sql_query(q: &R::Query, cfg: &SqlRenderCfg) -> String {
             }
 
             // обычный путь
-            render_expr(&mut w, &oi.expr, cfg);
+            render_expr(w, &oi.expr, cfg);
             match oi.dir {
                 OrderDirection::Asc => w.push(" ASC"),
                 OrderDirection::Desc => w.push(" DESC"),
@@ -120,12 +125,46 @@ pub fn render_sql_query(q: &R::Query, cfg: &SqlRenderCfg) -> String {
         }
     }
 
-    w.finish()
+    if let Some(lock) = &q.lock {
+        render_row_lock(w, lock, cfg);
+    }
+}
+
+/// `FOR ...` по диалекту. В SQLite блокировок строк нет — опускаем
+/// (в `FeaturePolicy::Strict` это отсекает валидация).
+fn render_row_lock(w: &mut SqlWriter, lock: &RowLock, cfg: &SqlRenderCfg) {
+    if matches!(cfg.dialect, Dialect::SQLite) {
+        return;
+    }
+    match (lock.strength, cfg.dialect) {
+        (LockStrength::Update, _) | (LockStrength::NoKeyUpdate, Dialect::MySQL) => {
+            w.push(" FOR UPDATE")
+        }
+        (LockStrength::NoKeyUpdate, _) => w.push(" FOR NO KEY UPDATE"),
+        (LockStrength::Share, _) => w.push(" FOR SHARE"),
+    }
+    if !lock.of.is_empty() {
+        w.push(" OF ");
+        for (i, t) in lock.of.iter().enumerate() {
+            w.push_sep(i, ", ");
+            push_quoted_path(w, t.split('.'), cfg);
+        }
+    }
+    match lock.wait {
+        LockWait::Wait => {}
+        LockWait::NoWait => w.push(" NOWAIT"),
+        LockWait::SkipLocked => w.push(" SKIP LOCKED"),
+    }
 }
 
 pub fn render_select(sel: &Select, cfg: &SqlRenderCfg, cap: usize) -> String {
     let mut w = SqlWriter::new(cap, cfg.placeholders);
+    render_select_into(&mut w, sel, cfg);
+    w.finish()
+}
 
+/// Рендер SELECT в общий writer — нумерация `$N` продолжается, а не начинается заново
+pub(crate) fn render_select_into(w: &mut SqlWriter, sel: &Select, cfg: &SqlRenderCfg) {
     w.push("SELECT ");
     if !sel.distinct_on.is_empty() {
         // DISTINCT ON поддерживается только в Postgres
@@ -134,7 +173,7 @@ pub fn render_select(sel: &Select, cfg: &SqlRenderCfg, cap: usize) -> String {
                 w.push("DISTINCT ON (");
                 for (i, e) in sel.distinct_on.iter().enumerate() {
                     w.push_sep(i, ", ");
-                    render_expr(&mut w, e, cfg);
+                    render_expr(w, e, cfg);
                 }
                 w.push(") ");
             }
@@ -152,23 +191,23 @@ pub fn render_select(sel: &Select, cfg: &SqlRenderCfg, cap: usize) -> String {
     } else {
         for (i, it) in sel.items.iter().enumerate() {
             w.push_sep(i, ", ");
-            render_select_item(&mut w, it, cfg);
+            render_select_item(w, it, cfg);
         }
     }
 
     if let Some(from) = &sel.from {
         w.push(" FROM ");
-        render_table_ref(&mut w, from, cfg);
+        render_table_ref(w, from, cfg);
     }
 
     for j in &sel.joins {
         w.push(" ");
-        render_join(&mut w, j, cfg);
+        render_join(w, j, cfg);
     }
 
     if let Some(pred) = &sel.r#where {
         w.push(" WHERE ");
-        render_expr(&mut w, pred, cfg);
+        render_expr(w, pred, cfg);
     }
 
     if !sel.group_by.is_empty() {
@@ -183,7 +222,7 @@ pub fn render_select(sel: &Select, cfg: &SqlRenderCfg, cap: usize) -> String {
                     }
                 }) {
                     w.push(" GROUP BY GROUPING SETS (");
-                    render_expr(&mut w, gs, cfg);
+                    render_expr(w, gs, cfg);
                     w.push(")");
                 } else if sel
                     .group_by_modifiers
@@ -193,7 +232,7 @@ pub fn render_select(sel: &Select, cfg: &SqlRenderCfg, cap: usize) -> String {
                     w.push(" GROUP BY ROLLUP (");
                     for (i, e) in sel.group_by.iter().enumerate() {
                         w.push_sep(i, ", ");
-                        render_expr(&mut w, e, cfg);
+                        render_expr(w, e, cfg);
                     }
                     w.push(")");
                 } else if sel
@@ -204,14 +243,14 @@ pub fn render_select(sel: &Select, cfg: &SqlRenderCfg, cap: usize) -> String {
                     w.push(" GROUP BY CUBE (");
                     for (i, e) in sel.group_by.iter().enumerate() {
                         w.push_sep(i, ", ");
-                        render_expr(&mut w, e, cfg);
+                        render_expr(w, e, cfg);
                     }
                     w.push(")");
                 } else {
                     w.push(" GROUP BY ");
                     for (i, e) in sel.group_by.iter().enumerate() {
                         w.push_sep(i, ", ");
-                        render_expr(&mut w, e, cfg);
+                        render_expr(w, e, cfg);
                     }
                 }
                 // Totals — не стандарт PG; игнорируем
@@ -221,7 +260,7 @@ pub fn render_select(sel: &Select, cfg: &SqlRenderCfg, cap: usize) -> String {
                 w.push(" GROUP BY ");
                 for (i, e) in sel.group_by.iter().enumerate() {
                     w.push_sep(i, ", ");
-                    render_expr(&mut w, e, cfg);
+                    render_expr(w, e, cfg);
                 }
                 if sel
                     .group_by_modifiers
@@ -237,7 +276,7 @@ pub fn render_select(sel: &Select, cfg: &SqlRenderCfg, cap: usize) -> String {
                 w.push(" GROUP BY ");
                 for (i, e) in sel.group_by.iter().enumerate() {
                     w.push_sep(i, ", ");
-                    render_expr(&mut w, e, cfg);
+                    render_expr(w, e, cfg);
                 }
             }
         }
@@ -245,7 +284,18 @@ pub fn render_select(sel: &Select, cfg: &SqlRenderCfg, cap: usize) -> String {
 
     if let Some(h) = &sel.having {
         w.push(" HAVING ");
-        render_expr(&mut w, h, cfg);
+        render_expr(w, h, cfg);
+    }
+
+    if !sel.windows.is_empty() {
+        w.push(" WINDOW ");
+        for (i, nw) in sel.windows.iter().enumerate() {
+            w.push_sep(i, ", ");
+            w.push(quote_ident(&nw.name, cfg));
+            w.push(" AS (");
+            render_window_spec(w, &nw.spec, cfg);
+            w.push(")");
+        }
     }
 
     if !sel.order_by.is_empty() {
@@ -258,16 +308,16 @@ pub fn render_select(sel: &Select, cfg: &SqlRenderCfg, cap: usize) -> String {
             if emulate_nulls && oi.nulls_last {
                 // (expr IS NULL) ASC, expr <ASC|DESC>
                 w.push("(");
-                render_expr(&mut w, &oi.expr, cfg);
+                render_expr(w, &oi.expr, cfg);
                 w.push(" IS NULL) ASC, ");
-                render_expr(&mut w, &oi.expr, cfg);
+                render_expr(w, &oi.expr, cfg);
                 match oi.dir {
                     OrderDirection::Asc => w.push(" ASC"),
                     OrderDirection::Desc => w.push(" DESC"),
                 }
             } else {
                 // обычный путь
-                render_expr(&mut w, &oi.expr, cfg);
+                render_expr(w, &oi.expr, cfg);
                 match oi.dir {
                     OrderDirection::Asc => w.push(" ASC"),
                     OrderDirection::Desc => w.push(" DESC"),
@@ -297,8 +347,6 @@ pub fn render_select(sel: &Select, cfg: &SqlRenderCfg, cap: usize) -> String {
             }
         }
     }
-
-    w.finish()
 }
 
 pub fn render_expr(w: &mut SqlWriter, e: &Expr, cfg: &SqlRenderCfg) {
@@ -463,29 +511,87 @@ pub fn render_expr(w: &mut SqlWriter, e: &Expr, cfg: &SqlRenderCfg) {
                 w.push_sep(i, ", ");
                 render_expr(w, a, cfg);
             }
-            w.push(") OVER (");
-            if !window.partition_by.is_empty() {
-                w.push("PARTITION BY ");
-                for (i, e) in window.partition_by.iter().enumerate() {
-                    w.push_sep(i, ", ");
-                    render_expr(w, e, cfg);
+            w.push(") OVER ");
+            match window {
+                // OVER w — ссылка на окно из WINDOW
+                WindowSpec {
+                    name: Some(n),
+                    partition_by,
+                    order_by,
+                    frame: None,
+                } if partition_by.is_empty() && order_by.is_empty() => {
+                    w.push(quote_ident(n, cfg));
                 }
-                if !window.order_by.is_empty() {
-                    w.push(" ");
+                _ => {
+                    w.push("(");
+                    render_window_spec(w, window, cfg);
+                    w.push(")");
                 }
             }
-            if !window.order_by.is_empty() {
-                w.push("ORDER BY ");
-                for (i, oi) in window.order_by.iter().enumerate() {
-                    w.push_sep(i, ", ");
-                    render_expr(w, &oi.expr, cfg);
-                    match oi.dir {
-                        OrderDirection::Asc => w.push(" ASC"),
-                        OrderDirection::Desc => w.push(" DESC"),
-                    }
-                }
+        }
+    }
+}
+
+/// Содержимое скобок окна: `[name] [PARTITION BY ...] [ORDER BY ...] [frame]`
+pub fn render_window_spec(w: &mut SqlWriter, spec: &WindowSpec, cfg: &SqlRenderCfg) {
+    let mut sep = "";
+    if let Some(n) = &spec.name {
+        w.push(quote_ident(n, cfg));
+        sep = " ";
+    }
+    if !spec.partition_by.is_empty() {
+        w.push(sep);
+        w.push("PARTITION BY ");
+        for (i, e) in spec.partition_by.iter().enumerate() {
+            w.push_sep(i, ", ");
+            render_expr(w, e, cfg);
+        }
+        sep = " ";
+    }
+    if !spec.order_by.is_empty() {
+        w.push(sep);
+        w.push("ORDER BY ");
+        for (i, oi) in spec.order_by.iter().enumerate() {
+            w.push_sep(i, ", ");
+            render_expr(w, &oi.expr, cfg);
+            match oi.dir {
+                OrderDirection::Asc => w.push(" ASC"),
+                OrderDirection::Desc => w.push(" DESC"),
             }
-            w.push(")");
         }
+        sep = " ";
+    }
+    if let Some(f) = &spec.frame {
+        w.push(sep);
+        w.push(match f.units {
+            FrameUnits::Rows => "ROWS ",
+            FrameUnits::Range => "RANGE ",
+            FrameUnits::Groups => "GROUPS ",
+        });
+        match &f.end {
+            Some(end) => {
+                w.push("BETWEEN ");
+                render_frame_bound(w, &f.start, cfg);
+                w.push(" AND ");
+                render_frame_bound(w, end, cfg);
+            }
+            None => render_frame_bound(w, &f.start, cfg),
+        }
+    }
+}
+
+fn render_frame_bound(w: &mut SqlWriter, b: &FrameBound, cfg: &SqlRenderCfg) {
+    match b {
+        FrameBound::UnboundedPreceding => w.push("UNBOUNDED PRECEDING"),
+        FrameBound::Preceding(e) => {
+            render_expr(w, e, cfg);
+            w.push(" PRECEDING");
+        }
+        FrameBound::CurrentRow => w.push("CURRENT ROW"),
+        FrameBound::Following(e) => {
+            render_expr(w, e, cfg);
+            w.push(" FOLLOWING");
+        }
+        FrameBound::UnboundedFollowing => w.push("UNBOUNDED FOLLOWING"),
     }
 }
@@ -2,4 +2,5 @@ mod __tests__;
 mod core_fn;
 mod utils;
 
+pub(crate) use core_fn::render_query_into;
 pub use core_fn::{render_expr, render_select, render_sql_query};
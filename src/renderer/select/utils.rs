@@ -10,8 +10,7 @@ use super::core_fn::*;
 pub(crate) fn render_query_body(w: &mut SqlWriter, body: &R::QueryBody, cfg: &SqlRenderCfg) {
     match body {
         R::QueryBody::Select(s) => {
-            let inner = render_select(s, cfg, 128);
-            w.push(inner);
+            render_select_into(w, s, cfg);
         }
         R::QueryBody::Set {
             left,
@@ -83,8 +82,7 @@ pub(crate) fn render_table_ref(w: &mut SqlWriter, t: &TableRef, cfg: &SqlRenderC
         }
         TableRef::Subquery { query, alias } => {
             w.push("(");
-            let inner = render_select(query, cfg, 128);
-            w.push(inner);
+            render_query_into(w, query, cfg);
             w.push(")");
             if let Some(a) = alias {
                 push_alias(w, a, cfg, cfg.emit_as_for_table_alias);
@@ -6,6 +6,18 @@ use crate::renderer::{
 };
 
 pub fn validate_query_features(q: &R::Query, cfg: &SqlRenderCfg) -> Option<Error> {
+    // блокировку строк с UNION/INTERSECT/EXCEPT не примет ни один сервер —
+    // ошибка при любой политике (SQLite клаузу просто опускает)
+    if q.lock.is_some()
+        && matches!(q.body, R::QueryBody::Set { .. })
+        && !matches!(cfg.dialect, Dialect::SQLite)
+    {
+        return Some(Error::UnsupportedFeature {
+            feature: "FOR UPDATE/FOR SHARE with UNION/INTERSECT/EXCEPT".into(),
+            dialect: cfg.dialect,
+        });
+    }
+
     if !matches!(cfg.policy, FeaturePolicy::Strict) {
         return None;
     }
@@ -74,6 +86,14 @@ pub fn validate_query_features(q: &R::Query, cfg: &SqlRenderCfg) -> Option<Error
         }
     }
 
+    // 6) рамка окна GROUPS — нет в MySQL
+    if matches!(cfg.dialect, Dialect::MySQL) && contains_groups_frame(&q.body) {
+        return Some(Error::UnsupportedFeature {
+            feature: "window frame GROUPS".into(),
+            dialect: cfg.dialect,
+        });
+    }
+
     if let Some(with) = &q.with {
         for cte in &with.ctes {
             if cte.materialized.is_some() && !matches!(cfg.dialect, Dialect::Postgres) {
@@ -85,6 +105,23 @@ pub fn validate_query_features(q: &R::Query, cfg: &SqlRenderCfg) -> Option<Error
         }
     }
 
+    // 7) блокировки строк: нет в SQLite, NO KEY UPDATE — только PG
+    if let Some(lock) = &q.lock {
+        let feature = match cfg.dialect {
+            Dialect::SQLite => Some("FOR UPDATE/FOR SHARE"),
+            Dialect::MySQL if lock.strength == R::LockStrength::NoKeyUpdate => {
+                Some("FOR NO KEY UPDATE")
+            }
+            _ => None,
+        };
+        if let Some(feature) = feature {
+            return Some(Error::UnsupportedFeature {
+                feature: feature.into(),
+                dialect: cfg.dialect,
+            });
+        }
+    }
+
     // 5) UNION ... BY NAME — не поддерживается в PG/MySQL/SQLite
     if contains_by_name(&q.body) {
         return Some(Error::UnsupportedFeature {
@@ -123,6 +160,46 @@ fn contains_ilike(w: &Option<R::Expr>) -> bool {
     w.as_ref().map(walk).unwrap_or(false)
 }
 
+fn contains_groups_frame(body: &R::QueryBody) -> bool {
+    fn is_groups(spec: &R::WindowSpec) -> bool {
+        matches!(&spec.frame, Some(f) if f.units == R::FrameUnits::Groups)
+    }
+    fn walk(e: &R::Expr) -> bool {
+        match e {
+            R::Expr::WindowFunc { args, window, .. } => is_groups(window) || args.iter().any(walk),
+            R::Expr::Unary { expr, .. }
+            | R::Expr::Paren(expr)
+            | R::Expr::Cast { expr, .. }
+            | R::Expr::Collate { expr, .. } => walk(expr),
+            R::Expr::Binary { left, right, .. } => walk(left) || walk(right),
+            R::Expr::Tuple(xs) | R::Expr::FuncCall { args: xs, .. } => xs.iter().any(walk),
+            R::Expr::Case {
+                operand,
+                when_then,
+                else_expr,
+            } => {
+                operand.as_deref().is_some_and(walk)
+                    || when_then.iter().any(|(a, b)| walk(a) || walk(b))
+                    || else_expr.as_deref().is_some_and(walk)
+            }
+            _ => false,
+        }
+    }
+    match body {
+        R::QueryBody::Select(s) => {
+            s.windows.iter().any(|nw| is_groups(&nw.spec))
+                || s.items.iter().any(|it| match it {
+                    R::SelectItem::Expr { expr, .. } => walk(expr),
+                    _ => false,
+                })
+                || s.order_by.iter().any(|oi| walk(&oi.expr))
+        }
+        R::QueryBody::Set { left, right, .. } => {
+            contains_groups_frame(left) || contains_groups_frame(right)
+        }
+    }
+}
+
 fn contains_by_name(body: &R::QueryBody) -> bool {
     match body {
         R::QueryBody::Select(_) => false,
@@ -0,0 +1,263 @@
+use crate::expression::helpers::{col, val};
+use crate::query_builder::Error;
+use crate::renderer::{self, Dialect, FeaturePolicy};
+use crate::schema::{ReferentialAction, SchemaBuilder};
+
+fn posts_table(d: Dialect) -> Vec<String> {
+    SchemaBuilder::new_empty()
+        .dialect(d)
+        .create_table("posts", |t| {
+            t.increments("id");
+            t.integer("user_id")
+                .not_null()
+                .references("users", "id")
+                .on_delete(ReferentialAction::Cascade);
+            t.integer("team_id");
+            t.integer("score").not_null();
+            t.foreign(["team_id"])
+                .references("teams", ["id"])
+                .name("posts_team_fk")
+                .on_delete(ReferentialAction::SetNull)
+                .on_update(ReferentialAction::Cascade);
+            t.check(col("score").gte(val(0))).name("posts_score_check");
+            t.index(["user_id", "team_id"]);
+            t.unique(["score"]).name("posts_score_uniq");
+        })
+        .to_sql()
+        .expect("to_sql")
+}
+
+#[test]
+fn create_table_with_constraints_postgres() {
+    assert_eq!(
+        posts_table(Dialect::Postgres),
+        vec![
+            r#"CREATE TABLE "posts" ("id" SERIAL PRIMARY KEY, "user_id" INTEGER NOT NULL, "team_id" INTEGER, "score" INTEGER NOT NULL, FOREIGN KEY ("user_id") REFERENCES "users" ("id") ON DELETE CASCADE, CONSTRAINT "posts_team_fk" FOREIGN KEY ("team_id") REFERENCES "teams" ("id") ON DELETE SET NULL ON UPDATE CASCADE, CONSTRAINT "posts_score_check" CHECK ("score" >= 0))"#,
+            r#"CREATE INDEX "posts_user_id_team_id_index" ON "posts" ("user_id", "team_id")"#,
+            r#"CREATE UNIQUE INDEX "posts_score_uniq" ON "posts" ("score")"#,
+        ]
+    );
+}
+
+#[test]
+fn create_table_with_constraints_mysql() {
+    assert_eq!(
+        posts_table(Dialect::MySQL),
+        vec![
+            "CREATE TABLE `posts` (`id` INT UNSIGNED NOT NULL AUTO_INCREMENT PRIMARY KEY, `user_id` INT NOT NULL, `team_id` INT, `score` INT NOT NULL, FOREIGN KEY (`user_id`) REFERENCES `users` (`id`) ON DELETE CASCADE, CONSTRAINT `posts_team_fk` FOREIGN KEY (`team_id`) REFERENCES `teams` (`id`) ON DELETE SET NULL ON UPDATE CASCADE, CONSTRAINT `posts_score_check` CHECK (`score` >= 0))",
+            "CREATE INDEX `posts_user_id_team_id_index` ON `posts` (`user_id`, `team_id`)",
+            "CREATE UNIQUE INDEX `posts_score_uniq` ON `posts` (`score`)",
+        ]
+    );
+}
+
+#[test]
+fn indexes_outside_table() {
+    let sql = |d| {
+        SchemaBuilder::new_empty()
+            .dialect(d)
+            .with_schema("app")
+            .create_index("users", ["email"])
+            .create_unique_index("users", ["tenant_id", "login"])
+            .drop_index("users", "users_email_index")
+            .to_sql()
+            .expect("to_sql")
+    };
+
+    assert_eq!(
+        sql(Dialect::Postgres),
+        vec![
+            r#"CREATE INDEX "users_email_index" ON "app"."users" ("email")"#,
+            r#"CREATE UNIQUE INDEX "users_tenant_id_login_unique" ON "app"."users" ("tenant_id", "login")"#,
+            r#"DROP INDEX "app"."users_email_index""#,
+        ]
+    );
+    assert_eq!(
+        sql(Dialect::MySQL),
+        vec![
+            "CREATE INDEX `users_email_index` ON `app`.`users` (`email`)",
+            "CREATE UNIQUE INDEX `users_tenant_id_login_unique` ON `app`.`users` (`tenant_id`, `login`)",
+            "DROP INDEX `users_email_index` ON `app`.`users`",
+        ]
+    );
+    // в SQLite схема (attached БД) ставится перед именем индекса
+    assert_eq!(
+        sql(Dialect::SQLite),
+        vec![
+            r#"CREATE INDEX "app"."users_email_index" ON "users" ("email")"#,
+            r#"CREATE UNIQUE INDEX "app"."users_tenant_id_login_unique" ON "users" ("tenant_id", "login")"#,
+            r#"DROP INDEX "app"."users_email_index""#,
+        ]
+    );
+}
+
+#[test]
+fn partial_index_inlines_params() {
+    let sql = |d, policy| {
+        SchemaBuilder::new_empty()
+            .dialect(d)
+            .policy(policy)
+            .create_index_with("users", |t| {
+                t.index(["email"])
+                    .name("active_email")
+                    .r#where(col("deleted_at").is_null().and(col("role").eq(val("it's"))));
+            })
+            .to_sql()
+    };
+
+    assert_eq!(
+        sql(Dialect::Postgres, FeaturePolicy::Strict).unwrap(),
+        vec![
+            r#"CREATE INDEX "active_email" ON "users" ("email") WHERE ("deleted_at" IS NULL) AND ("role" = 'it''s')"#
+        ]
+    );
+    assert_eq!(
+        sql(Dialect::SQLite, FeaturePolicy::Strict).unwrap(),
+        vec![
+            r#"CREATE INDEX "active_email" ON "users" ("email") WHERE ("deleted_at" IS NULL) AND ("role" = 'it''s')"#
+        ]
+    );
+    // MySQL: WHERE опускается в Lenient, в Strict — ошибка
+    assert_eq!(
+        sql(Dialect::MySQL, FeaturePolicy::Lenient).unwrap(),
+        vec!["CREATE INDEX `active_email` ON `users` (`email`)"]
+    );
+    assert!(matches!(
+        sql(Dialect::MySQL, FeaturePolicy::Strict),
+        Err(Error::SQLRenderError(
+            renderer::Error::UnsupportedFeature { .. }
+        ))
+    ));
+}
+
+#[test]
+fn partial_unique_index_mysql_is_unsupported() {
+    let res = SchemaBuilder::new_empty()
+        .dialect(Dialect::MySQL)
+        .alter_table("users", |t| {
+            t.unique(["email"]).r#where(col("deleted_at").is_null());
+        })
+        .to_sql();
+
+    assert!(matches!(
+        res,
+        Err(Error::SQLRenderError(
+            renderer::Error::UnsupportedFeature { .. }
+        ))
+    ));
+}
+
+#[test]
+fn alter_table_constraints() {
+    let sql = |d| {
+        SchemaBuilder::new_empty()
+            .dialect(d)
+            .alter_table("posts", |t| {
+                t.foreign(["author_id"])
+                    .references("users", ["id"])
+                    .name("posts_author_fk")
+                    .on_delete(ReferentialAction::Restrict);
+                t.drop_foreign("posts_team_fk");
+                t.check(col("score").lte(val(100))).name("posts_score_max");
+                t.drop_check("posts_score_check");
+                t.index(["author_id"]);
+                t.drop_index("posts_user_id_team_id_index");
+            })
+            .to_sql()
+    };
+
+    assert_eq!(
+        sql(Dialect::Postgres).unwrap(),
+        vec![
+            r#"ALTER TABLE "posts" ADD CONSTRAINT "posts_author_fk" FOREIGN KEY ("author_id") REFERENCES "users" ("id") ON DELETE RESTRICT"#,
+            r#"ALTER TABLE "posts" DROP CONSTRAINT "posts_team_fk""#,
+            r#"ALTER TABLE "posts" ADD CONSTRAINT "posts_score_max" CHECK ("score" <= 100)"#,
+            r#"ALTER TABLE "posts" DROP CONSTRAINT "posts_score_check""#,
+            r#"CREATE INDEX "posts_author_id_index" ON "posts" ("author_id")"#,
+            r#"DROP INDEX "posts_user_id_team_id_index""#,
+        ]
+    );
+    assert_eq!(
+        sql(Dialect::MySQL).unwrap(),
+        vec![
+            "ALTER TABLE `posts` ADD CONSTRAINT `posts_author_fk` FOREIGN KEY (`author_id`) REFERENCES `users` (`id`) ON DELETE RESTRICT",
+            "ALTER TABLE `posts` DROP FOREIGN KEY `posts_team_fk`",
+            "ALTER TABLE `posts` ADD CONSTRAINT `posts_score_max` CHECK (`score` <= 100)",
+            "ALTER TABLE `posts` DROP CHECK `posts_score_check`",
+            "CREATE INDEX `posts_author_id_index` ON `posts` (`author_id`)",
+            "DROP INDEX `posts_user_id_team_id_index` ON `posts`",
+        ]
+    );
+    // SQLite: только через пересоздание таблицы при выполнении
+    assert!(matches!(
+        sql(Dialect::SQLite),
+        Err(Error::SQLRenderError(
+            renderer::Error::UnsupportedFeature { .. }
+        ))
+    ));
+}
+
+#[test]
+fn set_default_action_on_mysql() {
+    let sql = |policy| {
+        SchemaBuilder::new_empty()
+            .dialect(Dialect::MySQL)
+            .policy(policy)
+            .create_table("t", |t| {
+                t.integer("u")
+                    .references("users", "id")
+                    .on_delete(ReferentialAction::SetDefault)
+                    .on_update(ReferentialAction::NoAction);
+            })
+            .to_sql()
+    };
+
+    assert_eq!(
+        sql(FeaturePolicy::Lenient).unwrap(),
+        vec![
+            "CREATE TABLE `t` (`u` INT, FOREIGN KEY (`u`) REFERENCES `users` (`id`) ON UPDATE NO ACTION)"
+        ]
+    );
+    assert!(matches!(
+        sql(FeaturePolicy::Strict),
+        Err(Error::SQLRenderError(
+            renderer::Error::UnsupportedFeature { .. }
+        ))
+    ));
+}
+
+#[test]
+fn sqlite_add_column_with_named_foreign_key() {
+    let sql = SchemaBuilder::new_empty()
+        .dialect(Dialect::SQLite)
+        .alter_table("posts", |t| {
+            t.integer("editor_id")
+                .references("users", "id")
+                .on_delete(ReferentialAction::SetNull);
+        })
+        .to_sql()
+        .unwrap();
+
+    assert_eq!(
+        sql,
+        vec![
+            r#"ALTER TABLE "posts" ADD COLUMN "editor_id" INTEGER REFERENCES "users" ("id") ON DELETE SET NULL"#
+        ]
+    );
+}
+
+#[test]
+fn constraint_builder_errors() {
+    let res = SchemaBuilder::new_empty()
+        .create_table("t", |t| {
+            t.integer("a").on_delete(ReferentialAction::Cascade);
+            t.index(Vec::<String>::new());
+            t.drop_index("x");
+        })
+        .to_sql();
+
+    match res {
+        Err(Error::BuilderErrors(list)) => assert_eq!(list.0.len(), 3, "{list}"),
+        other => panic!("expected builder errors, got {other:?}"),
+    }
+}
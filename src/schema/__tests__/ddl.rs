@@ -0,0 +1,257 @@
+use crate::param::Param;
+use crate::query_builder::Error;
+use crate::renderer::{self, Dialect, FeaturePolicy};
+use crate::schema::SchemaBuilder;
+
+fn users_table(d: Dialect) -> Vec<String> {
+    SchemaBuilder::new_empty()
+        .dialect(d)
+        .create_table("users", |t| {
+            t.increments("id");
+            t.string("name", 255).not_null();
+            t.integer("age").default(18);
+            t.boolean("active").not_null().default(true);
+            t.integer("team_id").references("teams", "id");
+        })
+        .to_sql()
+        .expect("to_sql")
+}
+
+#[test]
+fn create_table_postgres() {
+    assert_eq!(
+        users_table(Dialect::Postgres),
+        vec![
+            r#"CREATE TABLE "users" ("id" SERIAL PRIMARY KEY, "name" VARCHAR(255) NOT NULL, "age" INTEGER DEFAULT 18, "active" BOOLEAN NOT NULL DEFAULT TRUE, "team_id" INTEGER, FOREIGN KEY ("team_id") REFERENCES "teams" ("id"))"#
+        ]
+    );
+}
+
+#[test]
+fn create_table_mysql() {
+    assert_eq!(
+        users_table(Dialect::MySQL),
+        vec![
+            "CREATE TABLE `users` (`id` INT UNSIGNED NOT NULL AUTO_INCREMENT PRIMARY KEY, `name` VARCHAR(255) NOT NULL, `age` INT DEFAULT 18, `active` TINYINT(1) NOT NULL DEFAULT 1, `team_id` INT, FOREIGN KEY (`team_id`) REFERENCES `teams` (`id`))"
+        ]
+    );
+}
+
+#[test]
+fn create_table_sqlite() {
+    assert_eq!(
+        users_table(Dialect::SQLite),
+        vec![
+            r#"CREATE TABLE "users" ("id" INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT, "name" VARCHAR(255) NOT NULL, "age" INTEGER DEFAULT 18, "active" BOOLEAN NOT NULL DEFAULT 1, "team_id" INTEGER, FOREIGN KEY ("team_id") REFERENCES "teams" ("id"))"#
+        ]
+    );
+}
+
+#[test]
+fn column_types_per_dialect() {
+    let sql = |d| {
+        SchemaBuilder::new_empty()
+            .dialect(d)
+            .create_table("t", |t| {
+                t.big_integer("a");
+                t.decimal("b", 10, 2);
+                t.timestamp("c");
+                t.binary("d");
+                t.uuid("e");
+                t.json("f");
+                t.enu("g", ["x", "y"]);
+            })
+            .to_sql()
+            .unwrap()
+            .remove(0)
+    };
+
+    assert_eq!(
+        sql(Dialect::Postgres),
+        r#"CREATE TABLE "t" ("a" BIGINT, "b" DECIMAL(10, 2), "c" TIMESTAMPTZ, "d" BYTEA, "e" UUID, "f" JSON, "g" TEXT CHECK ("g" IN ('x', 'y')))"#
+    );
+    assert_eq!(
+        sql(Dialect::MySQL),
+        "CREATE TABLE `t` (`a` BIGINT, `b` DECIMAL(10, 2), `c` TIMESTAMP, `d` BLOB, `e` CHAR(36), `f` JSON, `g` ENUM('x', 'y'))"
+    );
+    assert_eq!(
+        sql(Dialect::SQLite),
+        r#"CREATE TABLE "t" ("a" BIGINT, "b" DECIMAL(10, 2), "c" DATETIME, "d" BLOB, "e" CHAR(36), "f" TEXT, "g" TEXT CHECK ("g" IN ('x', 'y')))"#
+    );
+}
+
+#[test]
+fn composite_primary_key_foreign_key_and_schema() {
+    let sql = SchemaBuilder::new_empty()
+        .dialect(Dialect::Postgres)
+        .with_schema("app")
+        .create_table_if_not_exists("memberships", |t| {
+            t.integer("user_id").not_null();
+            t.integer("team_id").not_null();
+            t.string("note", 20).default("it's");
+            t.primary(["user_id", "team_id"]);
+            t.foreign(["team_id"]).references("teams", ["id"]);
+        })
+        .to_sql()
+        .unwrap();
+
+    assert_eq!(
+        sql,
+        vec![
+            r#"CREATE TABLE IF NOT EXISTS "app"."memberships" ("user_id" INTEGER NOT NULL, "team_id" INTEGER NOT NULL, "note" VARCHAR(20) DEFAULT 'it''s', PRIMARY KEY ("user_id", "team_id"), FOREIGN KEY ("team_id") REFERENCES "app"."teams" ("id"))"#
+        ]
+    );
+}
+
+#[test]
+fn alter_rename_and_drop_per_dialect() {
+    let sql = |d| {
+        SchemaBuilder::new_empty()
+            .dialect(d)
+            .alter_table("users", |t| {
+                t.text("bio").nullable().comment("about");
+                t.string("name", 100).not_null().alter();
+                t.rename_column("age", "years");
+                t.drop_column("active");
+            })
+            .rename_table("users", "people")
+            .drop_table_if_exists("people")
+            .to_sql()
+    };
+
+    assert_eq!(
+        sql(Dialect::Postgres).unwrap(),
+        vec![
+            r#"ALTER TABLE "users" ADD COLUMN "bio" TEXT NULL"#,
+            r#"COMMENT ON COLUMN "users"."bio" IS 'about'"#,
+            r#"ALTER TABLE "users" ALTER COLUMN "name" TYPE VARCHAR(100), ALTER COLUMN "name" SET NOT NULL, ALTER COLUMN "name" DROP DEFAULT"#,
+            r#"ALTER TABLE "users" RENAME COLUMN "age" TO "years""#,
+            r#"ALTER TABLE "users" DROP COLUMN "active""#,
+            r#"ALTER TABLE "users" RENAME TO "people""#,
+            r#"DROP TABLE IF EXISTS "people""#,
+        ]
+    );
+    assert_eq!(
+        sql(Dialect::MySQL).unwrap(),
+        vec![
+            "ALTER TABLE `users` ADD COLUMN `bio` TEXT NULL COMMENT 'about'",
+            "ALTER TABLE `users` MODIFY COLUMN `name` VARCHAR(100) NOT NULL",
+            "ALTER TABLE `users` RENAME COLUMN `age` TO `years`",
+            "ALTER TABLE `users` DROP COLUMN `active`",
+            "RENAME TABLE `users` TO `people`",
+            "DROP TABLE IF EXISTS `people`",
+        ]
+    );
+    // SQLite не умеет ALTER COLUMN
+    assert!(matches!(
+        sql(Dialect::SQLite).unwrap_err(),
+        Error::SQLRenderError(renderer::Error::UnsupportedFeature {
+            dialect: Dialect::SQLite,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn add_column_with_foreign_key() {
+    let sql = |d| {
+        SchemaBuilder::new_empty()
+            .dialect(d)
+            .alter_table("posts", |t| {
+                t.integer("author_id").references("users", "id");
+            })
+            .to_sql()
+            .unwrap()
+    };
+
+    assert_eq!(
+        sql(Dialect::SQLite),
+        vec![r#"ALTER TABLE "posts" ADD COLUMN "author_id" INTEGER REFERENCES "users" ("id")"#]
+    );
+    // MySQL игнорирует inline REFERENCES — отдельный ADD FOREIGN KEY
+    assert_eq!(
+        sql(Dialect::MySQL),
+        vec![
+            "ALTER TABLE `posts` ADD COLUMN `author_id` INT",
+            "ALTER TABLE `posts` ADD FOREIGN KEY (`author_id`) REFERENCES `users` (`id`)",
+        ]
+    );
+}
+
+#[test]
+fn strict_policy_rejects_degraded_features() {
+    let build = |policy| {
+        SchemaBuilder::new_empty()
+            .dialect(Dialect::SQLite)
+            .policy(policy)
+            .create_table("t", |t| {
+                t.integer("n").unsigned();
+                t.jsonb("doc");
+            })
+            .to_sql()
+    };
+
+    assert_eq!(
+        build(FeaturePolicy::Lenient).unwrap(),
+        vec![r#"CREATE TABLE "t" ("n" INTEGER, "doc" TEXT)"#]
+    );
+    let err = build(FeaturePolicy::Strict).unwrap_err();
+    assert!(
+        matches!(
+            &err,
+            Error::SQLRenderError(renderer::Error::UnsupportedFeature { feature, .. }) if feature == "UNSIGNED"
+        ),
+        "got: {err:?}"
+    );
+}
+
+#[test]
+fn builder_errors_are_collected() {
+    let err = SchemaBuilder::new_empty()
+        .create_table("t", |t| {
+            t.integer("a");
+            t.drop_column("b");
+            t.foreign(["a"]);
+        })
+        .to_sql()
+        .unwrap_err();
+
+    match err {
+        Error::BuilderErrors(list) => assert_eq!(list.0.len(), 2, "{list}"),
+        other => panic!("unexpected: {other:?}"),
+    }
+}
+
+#[test]
+fn has_table_and_has_column_sql() {
+    let (sql, params) = SchemaBuilder::new_empty()
+        .dialect(Dialect::Postgres)
+        .has_column_sql("users", "email")
+        .unwrap();
+    assert_eq!(
+        sql,
+        "SELECT COUNT(*) FROM information_schema.columns WHERE table_schema = current_schema() AND table_name = $1 AND column_name = $2"
+    );
+    assert!(
+        matches!(params.as_slice(), [Param::Str(t), Param::Str(c)] if t == "users" && c == "email")
+    );
+
+    let (sql, params) = SchemaBuilder::new_empty()
+        .dialect(Dialect::MySQL)
+        .has_table_sql("app.users")
+        .unwrap();
+    assert_eq!(
+        sql,
+        "SELECT COUNT(*) FROM information_schema.tables WHERE table_schema = ? AND table_name = ?"
+    );
+    assert_eq!(params.len(), 2);
+
+    let (sql, _) = SchemaBuilder::new_empty()
+        .dialect(Dialect::SQLite)
+        .has_table_sql("users")
+        .unwrap();
+    assert_eq!(
+        sql,
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?"
+    );
+}
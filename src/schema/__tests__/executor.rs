@@ -0,0 +1,133 @@
+#![cfg(feature = "sqlite")]
+
+use sqlx::FromRow;
+
+use crate::executor::{ExecutorConfig, QueryExecutor};
+use crate::expression::helpers::{col, val};
+
+#[derive(Debug, FromRow, PartialEq)]
+struct User {
+    id: i64,
+    name: String,
+    age: i64,
+}
+
+async fn setup_db() -> QueryExecutor {
+    let cfg = ExecutorConfig::builder()
+        .database_url("sqlite::memory:")
+        .max_connections(1)
+        .after_connect_sql("PRAGMA foreign_keys = ON")
+        .build();
+    QueryExecutor::connect(cfg).await.unwrap()
+}
+
+async fn create_tables(exec: &QueryExecutor) {
+    exec.schema()
+        .create_table("teams", |t| {
+            t.increments("id");
+            t.string("title", 100).not_null();
+        })
+        .create_table("users", |t| {
+            t.increments("id");
+            t.string("name", 255).not_null();
+            t.integer("age").not_null().default(18);
+            t.integer("team_id").references("teams", "id");
+            t.timestamps();
+        })
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn create_table_and_introspect() {
+    let exec = setup_db().await;
+    assert!(!exec.schema().has_table("users").await.unwrap());
+
+    create_tables(&exec).await;
+
+    assert!(exec.schema().has_table("users").await.unwrap());
+    assert!(exec.schema().has_column("users", "team_id").await.unwrap());
+    assert!(!exec.schema().has_column("users", "email").await.unwrap());
+
+    exec.query::<()>()
+        .into("users")
+        .insert((col("name"), val("Alice")))
+        .exec()
+        .await
+        .unwrap();
+
+    let users: Vec<User> = exec
+        .query()
+        .from("users")
+        .select(("id", "name", "age"))
+        .await
+        .unwrap();
+    assert_eq!(
+        users,
+        vec![User {
+            id: 1,
+            name: "Alice".into(),
+            age: 18
+        }]
+    );
+}
+
+#[tokio::test]
+async fn foreign_key_is_enforced() {
+    let exec = setup_db().await;
+    create_tables(&exec).await;
+
+    let res = exec
+        .query::<()>()
+        .into("users")
+        .insert((col("name"), val("Bob"), col("team_id"), val(42)))
+        .exec()
+        .await;
+    assert!(res.is_err(), "FK violation expected");
+}
+
+#[tokio::test]
+async fn alter_rename_and_drop() {
+    let exec = setup_db().await;
+    create_tables(&exec).await;
+
+    exec.schema()
+        .alter_table("users", |t| {
+            t.string("email", 255).nullable();
+            t.rename_column("age", "years");
+            t.drop_column("updated_at");
+        })
+        .rename_table("users", "people")
+        .await
+        .unwrap();
+
+    let schema = || exec.schema();
+    assert!(!schema().has_table("users").await.unwrap());
+    assert!(schema().has_column("people", "email").await.unwrap());
+    assert!(schema().has_column("people", "years").await.unwrap());
+    assert!(!schema().has_column("people", "updated_at").await.unwrap());
+
+    exec.schema()
+        .drop_table_if_exists("people")
+        .drop_table_if_exists("people")
+        .await
+        .unwrap();
+    assert!(!exec.schema().has_table("people").await.unwrap());
+}
+
+#[tokio::test]
+async fn ddl_inside_transaction_rolls_back() {
+    let exec = setup_db().await;
+
+    let mut tx = exec.begin().await.unwrap();
+    tx.schema()
+        .create_table("tmp", |t| {
+            t.increments("id");
+        })
+        .await
+        .unwrap();
+    assert!(tx.schema().has_table("tmp").await.unwrap());
+    tx.rollback().await.unwrap();
+
+    assert!(!exec.schema().has_table("tmp").await.unwrap());
+}
@@ -0,0 +1,11 @@
+#[cfg(test)]
+mod ddl;
+
+#[cfg(test)]
+mod executor;
+
+#[cfg(test)]
+mod constraints;
+
+#[cfg(test)]
+mod rebuild;
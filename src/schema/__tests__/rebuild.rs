@@ -0,0 +1,266 @@
+#![cfg(feature = "sqlite")]
+
+use crate::executor::{Error as ExecError, ExecutorConfig, QueryExecutor};
+use crate::expression::helpers::{col, val};
+use crate::query_builder::Error;
+use crate::renderer::{self, FeaturePolicy};
+use crate::schema::ReferentialAction;
+
+async fn setup_db() -> QueryExecutor {
+    let cfg = ExecutorConfig::builder()
+        .database_url("sqlite::memory:")
+        .max_connections(1)
+        .after_connect_sql("PRAGMA foreign_keys = ON")
+        .build();
+    let exec = QueryExecutor::connect(cfg).await.unwrap();
+
+    exec.schema()
+        .create_table("teams", |t| {
+            t.increments("id");
+            t.string("title", 100).not_null();
+        })
+        .create_table("users", |t| {
+            t.increments("id");
+            t.string("name", 255).not_null();
+            t.integer("age").not_null().default(18);
+            t.integer("team_id");
+            t.index(["name"]);
+        })
+        .await
+        .unwrap();
+
+    exec.query::<()>()
+        .into("teams")
+        .insert((col("title"), val("core")))
+        .exec()
+        .await
+        .unwrap();
+    for name in ["Alice", "Bob"] {
+        exec.query::<()>()
+            .into("users")
+            .insert((col("name"), val(name), col("team_id"), val(1)))
+            .exec()
+            .await
+            .unwrap();
+    }
+    exec
+}
+
+async fn count(exec: &QueryExecutor, sql: &str) -> i64 {
+    exec.fetch_typed::<(i64,)>(sql, Vec::new()).await.unwrap()[0].0
+}
+
+async fn insert_user(exec: &QueryExecutor, age: i64, team_id: i64) -> Result<u64, ExecError> {
+    exec.query::<()>()
+        .into("users")
+        .insert((
+            col("name"),
+            val("Carol"),
+            col("age"),
+            val(age),
+            col("team_id"),
+            val(team_id),
+        ))
+        .exec()
+        .await
+}
+
+#[tokio::test]
+async fn add_foreign_key_and_check_keeps_data_and_indexes() {
+    let exec = setup_db().await;
+
+    exec.schema()
+        .alter_table("users", |t| {
+            t.foreign(["team_id"])
+                .references("teams", ["id"])
+                .name("users_team_fk")
+                .on_delete(ReferentialAction::Cascade);
+            t.check(col("age").gte(val(0))).name("users_age_check");
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(count(&exec, "SELECT COUNT(*) FROM users").await, 2);
+    assert_eq!(
+        count(
+            &exec,
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'index' AND name = 'users_name_index'"
+        )
+        .await,
+        1
+    );
+    assert_eq!(
+        count(
+            &exec,
+            "SELECT COUNT(*) FROM sqlite_master WHERE name LIKE '_knux_tmp_%'"
+        )
+        .await,
+        0
+    );
+    assert_eq!(count(&exec, "PRAGMA foreign_keys").await, 1);
+
+    assert!(insert_user(&exec, -1, 1).await.is_err(), "CHECK violation");
+    assert!(insert_user(&exec, 30, 42).await.is_err(), "FK violation");
+
+    exec.query::<()>()
+        .delete("teams")
+        .r#where(col("id").eq(val(1)))
+        .exec()
+        .await
+        .unwrap();
+    assert_eq!(count(&exec, "SELECT COUNT(*) FROM users").await, 0);
+}
+
+#[tokio::test]
+async fn drop_foreign_key_and_check() {
+    let exec = setup_db().await;
+    exec.schema()
+        .alter_table("users", |t| {
+            t.foreign(["team_id"])
+                .references("teams", ["id"])
+                .name("users_team_fk");
+            t.check(col("age").gte(val(0))).name("users_age_check");
+        })
+        .await
+        .unwrap();
+
+    exec.schema()
+        .alter_table("users", |t| {
+            t.drop_foreign("users_team_fk");
+            t.drop_check("users_age_check");
+        })
+        .await
+        .unwrap();
+
+    insert_user(&exec, -1, 42).await.unwrap();
+    assert_eq!(count(&exec, "SELECT COUNT(*) FROM users").await, 3);
+
+    let res = exec
+        .schema()
+        .alter_table("users", |t| t.drop_check("missing"))
+        .await;
+    assert!(matches!(
+        res,
+        Err(ExecError::QueryBuild(Error::SQLRenderError(
+            renderer::Error::TableRebuild(_)
+        )))
+    ));
+}
+
+#[tokio::test]
+async fn alter_column_of_referenced_table() {
+    let exec = setup_db().await;
+    exec.schema()
+        .alter_table("users", |t| {
+            t.foreign(["team_id"]).references("teams", ["id"]);
+        })
+        .await
+        .unwrap();
+
+    // на teams ссылаются users: через пул ключи выключаются на время пересоздания
+    exec.schema()
+        .alter_table("teams", |t| {
+            t.text("title").nullable().alter();
+            t.string("slug", 50).nullable();
+        })
+        .await
+        .unwrap();
+
+    let ty = exec
+        .fetch_typed::<(String,)>(
+            "SELECT type FROM pragma_table_info('teams') WHERE name = 'title'",
+            Vec::new(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(ty[0].0, "TEXT");
+    assert!(exec.schema().has_column("teams", "slug").await.unwrap());
+    assert_eq!(count(&exec, "SELECT COUNT(*) FROM teams").await, 1);
+    assert_eq!(count(&exec, "SELECT COUNT(*) FROM users").await, 2);
+    assert_eq!(
+        count(&exec, "SELECT COUNT(*) FROM pragma_foreign_key_check").await,
+        0
+    );
+}
+
+#[tokio::test]
+async fn violating_foreign_key_rolls_back() {
+    let exec = setup_db().await;
+    insert_user(&exec, 30, 1).await.unwrap();
+    exec.query::<()>()
+        .update("users")
+        .set((col("team_id"), val(42)))
+        .r#where(col("name").eq(val("Carol")))
+        .exec()
+        .await
+        .unwrap();
+
+    let res = exec
+        .schema()
+        .alter_table("users", |t| {
+            t.foreign(["team_id"]).references("teams", ["id"]);
+        })
+        .await;
+    assert!(matches!(res, Err(ExecError::ForeignKeyCheck(t)) if t == "users"));
+
+    assert_eq!(count(&exec, "SELECT COUNT(*) FROM users").await, 3);
+    assert_eq!(
+        count(
+            &exec,
+            "SELECT COUNT(*) FROM pragma_foreign_key_list('users')"
+        )
+        .await,
+        0
+    );
+    assert_eq!(count(&exec, "PRAGMA foreign_keys").await, 1);
+}
+
+#[tokio::test]
+async fn rebuild_inside_transaction() {
+    let exec = setup_db().await;
+    exec.schema()
+        .alter_table("users", |t| {
+            t.foreign(["team_id"]).references("teams", ["id"]);
+        })
+        .await
+        .unwrap();
+
+    let mut tx = exec.begin().await.unwrap();
+    // потомки teams потеряли бы строки при DROP TABLE
+    let res = tx
+        .schema()
+        .alter_table("teams", |t| {
+            t.check(col("title").ne(val("")));
+        })
+        .await;
+    assert!(matches!(res, Err(ExecError::Unsupported(_))));
+
+    tx.schema()
+        .alter_table("users", |t| {
+            t.check(col("age").lt(val(150))).name("users_age_max");
+        })
+        .await
+        .unwrap();
+    tx.rollback().await.unwrap();
+
+    insert_user(&exec, 200, 1).await.unwrap();
+}
+
+#[tokio::test]
+async fn strict_policy_refuses_rebuild() {
+    let exec = setup_db().await;
+    let res = exec
+        .schema()
+        .policy(FeaturePolicy::Strict)
+        .alter_table("users", |t| {
+            t.check(col("age").gte(val(0)));
+        })
+        .await;
+
+    assert!(matches!(
+        res,
+        Err(ExecError::QueryBuild(Error::SQLRenderError(
+            renderer::Error::UnsupportedFeature { .. }
+        )))
+    ));
+}
@@ -0,0 +1,357 @@
+//! Schema builder: DDL (`CREATE/ALTER/DROP TABLE`) в стиле knex.schema.
+
+mod __tests__;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod table;
+
+use std::{borrow::Cow, future::Future, pin::Pin};
+
+use smallvec::SmallVec;
+
+use crate::{
+    executor::{Error as ExecError, QueryExecutor, Result as ExecResult, transaction::TxExecutor},
+    param::Param,
+    query_builder::{DEFAULT_DIALECT, Error, ExecCtx, Result},
+    renderer::{
+        self, Dialect, FeaturePolicy, SqlRenderCfg,
+        ast::{AlterTableOp, DdlStmt, TableName},
+    },
+};
+
+pub use crate::renderer::ast::ReferentialAction;
+pub use table::{CheckBuilder, ColumnBuilder, ForeignBuilder, IndexBuilder, TableBuilder};
+
+/// Шаг выполнения: готовый SQL или пересоздание таблицы SQLite
+/// (его SQL зависит от текущего определения таблицы в БД).
+enum Step {
+    Sql(String),
+    #[cfg(feature = "sqlite")]
+    SqliteRebuild(renderer::ast::AlterTable),
+}
+
+/// Билдер DDL. Инструкции копятся и выполняются по порядку на `.await`
+/// (через пул или внутри транзакции).
+pub struct SchemaBuilder<'a> {
+    stmts: Vec<DdlStmt>,
+    builder_errors: SmallVec<[Cow<'static, str>; 2]>,
+    default_schema: Option<String>,
+    dialect: Dialect,
+    policy: FeaturePolicy,
+    exec_ctx: ExecCtx<'a>,
+}
+
+impl QueryExecutor {
+    /// DDL через пул: `exec.schema().create_table("users", |t| { ... }).await`
+    pub fn schema(&self) -> SchemaBuilder<'_> {
+        SchemaBuilder::new(ExecCtx::Pool(self.pool.clone()), self.schema.clone())
+    }
+}
+
+impl<'tx> TxExecutor<'tx> {
+    /// DDL внутри транзакции (там, где СУБД это поддерживает)
+    pub fn schema<'s>(&'s mut self) -> SchemaBuilder<'s> {
+        let schema = self.default_schema();
+        SchemaBuilder::new(self.exec_ctx(), schema)
+    }
+}
+
+impl<'a> SchemaBuilder<'a> {
+    pub(crate) fn new(exec_ctx: ExecCtx<'a>, schema: Option<String>) -> Self {
+        Self {
+            stmts: Vec::new(),
+            builder_errors: SmallVec::new(),
+            default_schema: schema,
+            dialect: exec_ctx.dialect().unwrap_or(DEFAULT_DIALECT),
+            policy: FeaturePolicy::Lenient,
+            exec_ctx,
+        }
+    }
+
+    /// Билдер без подключения — для `to_sql()`
+    pub fn new_empty() -> Self {
+        Self::new(ExecCtx::None, None)
+    }
+
+    #[inline]
+    pub fn dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Strict: конструкции без аналога в диалекте → `renderer::Error::UnsupportedFeature`
+    #[inline]
+    pub fn policy(mut self, policy: FeaturePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Схема для последующих таблиц (как knex.schema.withSchema)
+    #[inline]
+    pub fn with_schema<S: Into<String>>(mut self, schema: S) -> Self {
+        self.default_schema = Some(schema.into());
+        self
+    }
+
+    /// CREATE TABLE
+    pub fn create_table<F>(self, name: &str, f: F) -> Self
+    where
+        F: FnOnce(&mut TableBuilder),
+    {
+        self.push_create_table(name, false, f)
+    }
+
+    /// CREATE TABLE IF NOT EXISTS
+    pub fn create_table_if_not_exists<F>(self, name: &str, f: F) -> Self
+    where
+        F: FnOnce(&mut TableBuilder),
+    {
+        self.push_create_table(name, true, f)
+    }
+
+    /// ALTER TABLE: новые колонки добавляются, `.alter()` — изменяет существующую,
+    /// плюс `drop_column` / `rename_column` / `foreign`.
+    pub fn alter_table<F>(mut self, name: &str, f: F) -> Self
+    where
+        F: FnOnce(&mut TableBuilder),
+    {
+        let mut t = TableBuilder::new(self.table_name(name), self.default_schema.clone());
+        f(&mut t);
+        let at = t.into_alter_table(&mut self.builder_errors);
+        self.stmts.push(DdlStmt::AlterTable(at));
+        self
+    }
+
+    pub fn rename_table(mut self, from: &str, to: &str) -> Self {
+        let from = self.table_name(from);
+        self.stmts.push(DdlStmt::RenameTable {
+            from,
+            to: to.to_string(),
+        });
+        self
+    }
+
+    pub fn drop_table(self, name: &str) -> Self {
+        self.push_drop_table(name, false)
+    }
+
+    pub fn drop_table_if_exists(self, name: &str) -> Self {
+        self.push_drop_table(name, true)
+    }
+
+    /// CREATE INDEX вне описания таблицы (имя — `<table>_<cols>_index`)
+    pub fn create_index<I, S>(self, table: &str, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.create_index_with(table, |t| {
+            t.index(columns);
+        })
+    }
+
+    /// CREATE UNIQUE INDEX вне описания таблицы
+    pub fn create_unique_index<I, S>(self, table: &str, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.create_index_with(table, |t| {
+            t.unique(columns);
+        })
+    }
+
+    /// Индексы с настройкой: `.create_index_with("users", |t| { t.index(["email"]).name("ix"); })`
+    pub fn create_index_with<F>(mut self, table: &str, f: F) -> Self
+    where
+        F: FnOnce(&mut TableBuilder),
+    {
+        let mut t = TableBuilder::new(self.table_name(table), self.default_schema.clone());
+        f(&mut t);
+        for op in t.into_alter_table(&mut self.builder_errors).ops {
+            match op {
+                AlterTableOp::CreateIndex(ix) => {
+                    self.stmts.push(DdlStmt::CreateIndex(ix));
+                }
+                _ => self
+                    .builder_errors
+                    .push("create_index_with(): допустимы только index()/unique()".into()),
+            }
+        }
+        self
+    }
+
+    /// DROP INDEX; таблица нужна MySQL (`DROP INDEX n ON t`) и задаёт схему
+    pub fn drop_index(mut self, table: &str, name: &str) -> Self {
+        let table = self.table_name(table);
+        self.stmts.push(DdlStmt::DropIndex {
+            name: name.to_string(),
+            table,
+        });
+        self
+    }
+
+    /// SQL всех накопленных инструкций по порядку.
+    ///
+    /// ALTER COLUMN / ADD|DROP FOREIGN KEY / ADD|DROP CHECK в SQLite выполняются
+    /// пересозданием таблицы по её текущему определению, поэтому здесь дают
+    /// `UnsupportedFeature`; при `.await` с `FeaturePolicy::Lenient` — работают.
+    pub fn to_sql(&self) -> Result<Vec<String>> {
+        if !self.builder_errors.is_empty() {
+            return Err(Error::BuilderErrors(self.builder_errors.to_vec().into()));
+        }
+        let cfg = self.render_cfg();
+        let mut out = Vec::with_capacity(self.stmts.len());
+        for stmt in &self.stmts {
+            out.extend(renderer::render_ddl(stmt, &cfg)?);
+        }
+        Ok(out)
+    }
+
+    /// Есть ли таблица (в схеме по умолчанию/из имени)
+    pub async fn has_table(mut self, name: &str) -> ExecResult<bool> {
+        let (sql, params) = self.has_table_sql(name)?;
+        self.fetch_count(&sql, params).await.map(|n| n > 0)
+    }
+
+    /// Есть ли колонка в таблице
+    pub async fn has_column(mut self, table: &str, column: &str) -> ExecResult<bool> {
+        let (sql, params) = self.has_column_sql(table, column)?;
+        self.fetch_count(&sql, params).await.map(|n| n > 0)
+    }
+
+    /// SQL проверки `has_table`
+    pub fn has_table_sql(&self, name: &str) -> Result<(String, Vec<Param>)> {
+        self.ensure_no_pending("has_table()")?;
+        Ok(renderer::render_has_table(
+            &self.table_name(name),
+            &self.render_cfg(),
+        ))
+    }
+
+    /// SQL проверки `has_column`
+    pub fn has_column_sql(&self, table: &str, column: &str) -> Result<(String, Vec<Param>)> {
+        self.ensure_no_pending("has_column()")?;
+        Ok(renderer::render_has_column(
+            &self.table_name(table),
+            column,
+            &self.render_cfg(),
+        ))
+    }
+
+    // ===== helpers =====
+
+    fn push_create_table<F>(mut self, name: &str, if_not_exists: bool, f: F) -> Self
+    where
+        F: FnOnce(&mut TableBuilder),
+    {
+        let mut t = TableBuilder::new(self.table_name(name), self.default_schema.clone());
+        f(&mut t);
+        let ct = t.into_create_table(if_not_exists, &mut self.builder_errors);
+        self.stmts.push(DdlStmt::CreateTable(ct));
+        self
+    }
+
+    /// Рендер в шаги; ALTER-действия, требующие пересоздания таблицы SQLite,
+    /// группируются по порядку между обычными выражениями.
+    fn plan(&self) -> Result<Vec<Step>> {
+        #[cfg(feature = "sqlite")]
+        if self.dialect == Dialect::SQLite && self.policy == FeaturePolicy::Lenient {
+            return self.plan_sqlite();
+        }
+        Ok(self.to_sql()?.into_iter().map(Step::Sql).collect())
+    }
+
+    #[cfg(feature = "sqlite")]
+    fn plan_sqlite(&self) -> Result<Vec<Step>> {
+        if !self.builder_errors.is_empty() {
+            return Err(Error::BuilderErrors(self.builder_errors.to_vec().into()));
+        }
+        let cfg = self.render_cfg();
+        let mut steps = Vec::with_capacity(self.stmts.len());
+        for stmt in &self.stmts {
+            let DdlStmt::AlterTable(at) = stmt else {
+                steps.extend(renderer::render_ddl(stmt, &cfg)?.into_iter().map(Step::Sql));
+                continue;
+            };
+            for ops in at.ops.chunk_by(|a, b| {
+                renderer::sqlite_requires_rebuild(a) == renderer::sqlite_requires_rebuild(b)
+            }) {
+                let part = renderer::ast::AlterTable {
+                    table: at.table.clone(),
+                    ops: ops.to_vec(),
+                };
+                if renderer::sqlite_requires_rebuild(&ops[0]) {
+                    steps.push(Step::SqliteRebuild(part));
+                } else {
+                    let sqls = renderer::render_ddl(&DdlStmt::AlterTable(part), &cfg)?;
+                    steps.extend(sqls.into_iter().map(Step::Sql));
+                }
+            }
+        }
+        Ok(steps)
+    }
+
+    fn push_drop_table(mut self, name: &str, if_exists: bool) -> Self {
+        let table = self.table_name(name);
+        self.stmts.push(DdlStmt::DropTable { table, if_exists });
+        self
+    }
+
+    #[inline]
+    fn table_name(&self, name: &str) -> TableName {
+        table::table_name(name, self.default_schema.as_deref())
+    }
+
+    /// Проверки выполняются отдельно: накопленный DDL они бы молча отбросили
+    fn ensure_no_pending(&self, ctx: &'static str) -> Result<()> {
+        if self.stmts.is_empty() {
+            return Ok(());
+        }
+        Err(Error::BuilderErrors(
+            vec![format!(
+                "{ctx}: вызовите отдельно от DDL-инструкций (сначала .await)"
+            )]
+            .into(),
+        ))
+    }
+
+    async fn fetch_count(&mut self, sql: &str, params: Vec<Param>) -> ExecResult<i64> {
+        let rows = self.exec_ctx.fetch_all::<(i64,)>(sql, params).await?;
+        Ok(rows.first().map_or(0, |(n,)| *n))
+    }
+
+    fn render_cfg(&self) -> SqlRenderCfg {
+        let mut cfg = match self.dialect {
+            Dialect::Postgres => renderer::cfg_postgres_knex(),
+            Dialect::MySQL => renderer::cfg_mysql_knex(),
+            Dialect::SQLite => renderer::cfg_sqlite_knex(),
+        };
+        cfg.policy = self.policy;
+        cfg
+    }
+}
+
+impl<'a> std::future::IntoFuture for SchemaBuilder<'a> {
+    type Output = ExecResult<()>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'a>>;
+
+    fn into_future(mut self) -> Self::IntoFuture {
+        Box::pin(async move {
+            let steps = self.plan().map_err(ExecError::from)?;
+            for step in steps {
+                match step {
+                    Step::Sql(sql) => {
+                        self.exec_ctx.execute(&sql, Vec::new()).await?;
+                    }
+                    #[cfg(feature = "sqlite")]
+                    Step::SqliteRebuild(at) => {
+                        let cfg = self.render_cfg();
+                        sqlite::rebuild(&mut self.exec_ctx, &at, &cfg).await?;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,160 @@
+//! Пересоздание таблицы в SQLite для ALTER-действий, которых нет в его ALTER TABLE.
+
+use sqlx::Connection;
+
+use crate::{
+    executor::{DbPool, Error as ExecError, Result as ExecResult},
+    param::Param,
+    query_builder::{self, ExecCtx},
+    renderer::{self, SqlRenderCfg, ast::AlterTable},
+};
+
+/// Через пул: отдельное соединение, `foreign_keys` выключаются на время
+/// транзакции (внутри транзакции SQLite эту прагму игнорирует).
+pub(super) async fn rebuild(
+    exec_ctx: &mut ExecCtx<'_>,
+    at: &AlterTable,
+    cfg: &SqlRenderCfg,
+) -> ExecResult<()> {
+    match exec_ctx {
+        ExecCtx::None => Err(ExecError::MissingConnection),
+        ExecCtx::Pool(DbPool::Sqlite(pool)) => {
+            let mut conn = pool.acquire().await?;
+            let fk_on = pragma_foreign_keys(&mut ExecCtx::SqliteConn(&mut conn)).await?;
+            if fk_on {
+                ExecCtx::SqliteConn(&mut conn)
+                    .execute("PRAGMA foreign_keys = OFF", Vec::new())
+                    .await?;
+            }
+
+            let res = async {
+                let mut tx = conn.begin().await?;
+                let mut ctx = ExecCtx::SqliteConn(&mut tx);
+                rebuild_table(&mut ctx, at, cfg).await?;
+                if fk_on {
+                    foreign_key_check(&mut ctx, at).await?;
+                }
+                tx.commit().await?;
+                Ok(())
+            }
+            .await;
+
+            if fk_on {
+                ExecCtx::SqliteConn(&mut conn)
+                    .execute("PRAGMA foreign_keys = ON", Vec::new())
+                    .await?;
+            }
+            res
+        }
+        ExecCtx::SqliteConn(conn) => {
+            let mut ctx = ExecCtx::SqliteConn(conn);
+            // DROP TABLE при включённых ключах удалил бы (или каскадом изменил) строки потомков
+            if pragma_foreign_keys(&mut ctx).await? && is_referenced(&mut ctx, at, cfg).await? {
+                return Err(ExecError::Unsupported(
+                    format!(
+                        "SQLite: table {} is referenced by foreign keys and cannot be rebuilt inside a transaction with foreign_keys = ON",
+                        at.table.name
+                    )
+                    .into(),
+                ));
+            }
+            rebuild_table(&mut ctx, at, cfg).await
+        }
+        #[cfg(any(feature = "postgres", feature = "mysql"))]
+        _ => Err(ExecError::Unsupported(
+            "SQLite table rebuild requires a SQLite connection".into(),
+        )),
+    }
+}
+
+async fn rebuild_table(
+    ctx: &mut ExecCtx<'_>,
+    at: &AlterTable,
+    cfg: &SqlRenderCfg,
+) -> ExecResult<()> {
+    let master = master_table(at, cfg);
+    let table = Param::Str(at.table.name.clone());
+
+    let sql = format!("SELECT sql FROM {master} WHERE type = 'table' AND name = ?");
+    let Some((table_sql,)) = ctx
+        .fetch_all::<(String,)>(&sql, vec![table.clone()])
+        .await?
+        .pop()
+    else {
+        return Err(rebuild_error(format!("table {} not found", at.table.name)));
+    };
+
+    // индексы и триггеры удаляются вместе с таблицей; автоиндексы (sql IS NULL) создаст сама СУБД
+    let sql = format!(
+        "SELECT sql FROM {master} WHERE type IN ('index', 'trigger') AND tbl_name = ? AND sql IS NOT NULL"
+    );
+    let dependents: Vec<String> = ctx
+        .fetch_all::<(String,)>(&sql, vec![table])
+        .await?
+        .into_iter()
+        .map(|(s,)| s)
+        .collect();
+
+    let stmts = renderer::render_sqlite_rebuild(at, &table_sql, &dependents, cfg)
+        .map_err(query_builder::Error::from)?;
+    for sql in stmts {
+        ctx.execute(&sql, Vec::new()).await?;
+    }
+    Ok(())
+}
+
+async fn pragma_foreign_keys(ctx: &mut ExecCtx<'_>) -> ExecResult<bool> {
+    let rows = ctx
+        .fetch_all::<(i64,)>("PRAGMA foreign_keys", Vec::new())
+        .await?;
+    Ok(rows.first().is_some_and(|(on,)| *on == 1))
+}
+
+/// Ссылаются ли на таблицу внешние ключи других таблиц
+async fn is_referenced(
+    ctx: &mut ExecCtx<'_>,
+    at: &AlterTable,
+    cfg: &SqlRenderCfg,
+) -> ExecResult<bool> {
+    let schema = Param::Str(schema_name(at).to_string());
+    let table = Param::Str(at.table.name.clone());
+    let sql = format!(
+        "SELECT COUNT(*) FROM {} m JOIN pragma_foreign_key_list(m.name, ?) f \
+         WHERE m.type = 'table' AND m.name <> ? COLLATE NOCASE AND f.\"table\" = ? COLLATE NOCASE",
+        master_table(at, cfg)
+    );
+    let rows = ctx
+        .fetch_all::<(i64,)>(&sql, vec![schema, table.clone(), table])
+        .await?;
+    Ok(rows.first().is_some_and(|(n,)| *n > 0))
+}
+
+/// После пересоздания с выключенными ключами данные могли перестать им соответствовать
+async fn foreign_key_check(ctx: &mut ExecCtx<'_>, at: &AlterTable) -> ExecResult<()> {
+    let schema = Param::Str(schema_name(at).to_string());
+    let rows = ctx
+        .fetch_all::<(i64,)>(
+            "SELECT COUNT(*) FROM pragma_foreign_key_check(NULL, ?)",
+            vec![schema],
+        )
+        .await?;
+    match rows.first() {
+        Some((n,)) if *n > 0 => Err(ExecError::ForeignKeyCheck(at.table.name.clone())),
+        _ => Ok(()),
+    }
+}
+
+fn master_table(at: &AlterTable, cfg: &SqlRenderCfg) -> String {
+    match &at.table.schema {
+        Some(s) => format!("{}.sqlite_master", renderer::quote_ident(s, cfg)),
+        None => "sqlite_master".to_string(),
+    }
+}
+
+fn schema_name(at: &AlterTable) -> &str {
+    at.table.schema.as_deref().unwrap_or("main")
+}
+
+fn rebuild_error(msg: String) -> ExecError {
+    query_builder::Error::from(renderer::Error::TableRebuild(msg.into())).into()
+}
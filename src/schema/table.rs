@@ -0,0 +1,603 @@
+use std::borrow::Cow;
+
+use smallvec::SmallVec;
+
+use crate::{
+    expression::Expression,
+    param::Param,
+    renderer::{
+        ast::{
+            AlterTable, AlterTableOp, CheckConstraint, ColumnDef, ColumnDefault, ColumnType,
+            CreateIndex, CreateTable, ForeignKey, Predicate, ReferentialAction, TableName,
+        },
+        map::map_expr,
+    },
+};
+
+/// Элемент описания таблицы в порядке объявления
+#[derive(Debug)]
+enum TableItem {
+    Column { def: ColumnDef, alter: bool },
+    DropColumn(String),
+    RenameColumn { from: String, to: String },
+    Foreign(ForeignKey),
+    DropForeign(String),
+    Check(CheckConstraint),
+    DropCheck(String),
+    Index(CreateIndex),
+    DropIndex(String),
+}
+
+/// Описание колонок/ключей внутри `create_table(...)` / `alter_table(...)`.
+#[derive(Debug)]
+pub struct TableBuilder {
+    table: TableName,
+    default_schema: Option<String>,
+    items: Vec<TableItem>,
+    primary_key: Vec<String>,
+    errors: SmallVec<[Cow<'static, str>; 2]>,
+}
+
+/// Модификаторы колонки: `t.string("name", 255).not_null().unique()`
+pub struct ColumnBuilder<'t> {
+    def: &'t mut ColumnDef,
+    alter: &'t mut bool,
+    primary_key: &'t mut Vec<String>,
+    default_schema: Option<&'t str>,
+    errors: &'t mut SmallVec<[Cow<'static, str>; 2]>,
+}
+
+/// Внешний ключ уровня таблицы: `t.foreign(["team_id"]).references("teams", ["id"])`
+pub struct ForeignBuilder<'t> {
+    fk: &'t mut ForeignKey,
+    default_schema: Option<&'t str>,
+}
+
+/// Индекс: `t.index(["email"]).name("users_email_idx").r#where(col("deleted_at").is_null())`
+pub struct IndexBuilder<'t> {
+    index: &'t mut CreateIndex,
+}
+
+/// CHECK-ограничение: `t.check(col("age").gte(val(0))).name("age_non_negative")`
+pub struct CheckBuilder<'t> {
+    check: &'t mut CheckConstraint,
+}
+
+impl TableBuilder {
+    pub(crate) fn new(table: TableName, default_schema: Option<String>) -> Self {
+        Self {
+            table,
+            default_schema,
+            items: Vec::new(),
+            primary_key: Vec::new(),
+            errors: SmallVec::new(),
+        }
+    }
+
+    // ===== типы колонок =====
+
+    /// Автоинкрементный INTEGER PRIMARY KEY
+    pub fn increments(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::Increments)
+    }
+
+    /// Автоинкрементный BIGINT PRIMARY KEY
+    pub fn big_increments(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::BigIncrements)
+    }
+
+    pub fn tiny_integer(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::TinyInteger)
+    }
+
+    pub fn small_integer(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::SmallInteger)
+    }
+
+    pub fn integer(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::Integer)
+    }
+
+    pub fn big_integer(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::BigInteger)
+    }
+
+    pub fn float(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::Float)
+    }
+
+    pub fn double(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::Double)
+    }
+
+    pub fn decimal(&mut self, name: &str, precision: u8, scale: u8) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::Decimal { precision, scale })
+    }
+
+    pub fn boolean(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::Boolean)
+    }
+
+    /// VARCHAR(len)
+    pub fn string(&mut self, name: &str, len: u32) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::String(len))
+    }
+
+    pub fn text(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::Text)
+    }
+
+    pub fn date(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::Date)
+    }
+
+    pub fn time(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::Time)
+    }
+
+    /// Дата-время без часового пояса
+    pub fn datetime(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::DateTime)
+    }
+
+    /// Дата-время с часовым поясом (TIMESTAMPTZ в PG)
+    pub fn timestamp(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::Timestamp)
+    }
+
+    pub fn binary(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::Binary)
+    }
+
+    pub fn json(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::Json)
+    }
+
+    /// JSONB в PG; в остальных диалектах деградирует до JSON/TEXT (в Strict — ошибка)
+    pub fn jsonb(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::Jsonb)
+    }
+
+    pub fn uuid(&mut self, name: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::Uuid)
+    }
+
+    /// Перечисление: ENUM(...) в MySQL, TEXT + CHECK (... IN (...)) в PG/SQLite
+    pub fn enu<I, S>(&mut self, name: &str, values: I) -> ColumnBuilder<'_>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let values = values.into_iter().map(Into::into).collect();
+        self.column(name, ColumnType::Enum(values))
+    }
+
+    /// Тип как есть: `t.specific_type("tags", "text[]")`
+    pub fn specific_type(&mut self, name: &str, ty: &str) -> ColumnBuilder<'_> {
+        self.column(name, ColumnType::Custom(ty.to_string()))
+    }
+
+    /// `created_at` и `updated_at`: NOT NULL DEFAULT CURRENT_TIMESTAMP
+    pub fn timestamps(&mut self) {
+        for name in ["created_at", "updated_at"] {
+            self.timestamp(name)
+                .not_null()
+                .default_raw("CURRENT_TIMESTAMP");
+        }
+    }
+
+    // ===== ключи =====
+
+    /// PRIMARY KEY (...) уровня таблицы (составной ключ)
+    pub fn primary<I, S>(&mut self, columns: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.primary_key = columns.into_iter().map(Into::into).collect();
+    }
+
+    /// FOREIGN KEY (...) уровня таблицы
+    pub fn foreign<I, S>(&mut self, columns: I) -> ForeignBuilder<'_>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.items.push(TableItem::Foreign(ForeignKey {
+            name: None,
+            columns: columns.into_iter().map(Into::into).collect(),
+            ref_table: TableName {
+                schema: None,
+                name: String::new(),
+            },
+            ref_columns: Vec::new(),
+            on_delete: None,
+            on_update: None,
+        }));
+        let Some(TableItem::Foreign(fk)) = self.items.last_mut() else {
+            unreachable!("just pushed a foreign key");
+        };
+        ForeignBuilder {
+            fk,
+            default_schema: self.default_schema.as_deref(),
+        }
+    }
+
+    /// CREATE INDEX; имя по умолчанию — `<table>_<cols>_index`
+    pub fn index<I, S>(&mut self, columns: I) -> IndexBuilder<'_>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.push_index(columns, false)
+    }
+
+    /// CREATE UNIQUE INDEX; имя по умолчанию — `<table>_<cols>_unique`
+    pub fn unique<I, S>(&mut self, columns: I) -> IndexBuilder<'_>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.push_index(columns, true)
+    }
+
+    /// CHECK (...) уровня таблицы; параметры выражения печатаются литералами
+    pub fn check(&mut self, cond: Expression) -> CheckBuilder<'_> {
+        self.items.push(TableItem::Check(CheckConstraint {
+            name: None,
+            predicate: predicate(cond),
+        }));
+        let Some(TableItem::Check(check)) = self.items.last_mut() else {
+            unreachable!("just pushed a check");
+        };
+        CheckBuilder { check }
+    }
+
+    // ===== только для alter_table =====
+
+    pub fn drop_index(&mut self, name: &str) {
+        self.items.push(TableItem::DropIndex(name.to_string()));
+    }
+
+    /// Удалить внешний ключ по имени ограничения
+    pub fn drop_foreign(&mut self, name: &str) {
+        self.items.push(TableItem::DropForeign(name.to_string()));
+    }
+
+    pub fn drop_check(&mut self, name: &str) {
+        self.items.push(TableItem::DropCheck(name.to_string()));
+    }
+
+    pub fn drop_column(&mut self, name: &str) {
+        self.items.push(TableItem::DropColumn(name.to_string()));
+    }
+
+    pub fn rename_column(&mut self, from: &str, to: &str) {
+        self.items.push(TableItem::RenameColumn {
+            from: from.to_string(),
+            to: to.to_string(),
+        });
+    }
+
+    // ===== сборка =====
+
+    fn column(&mut self, name: &str, ty: ColumnType) -> ColumnBuilder<'_> {
+        self.items.push(TableItem::Column {
+            def: ColumnDef {
+                name: name.to_string(),
+                ty,
+                nullable: None,
+                default: None,
+                unsigned: false,
+                unique: false,
+                comment: None,
+                references: None,
+            },
+            alter: false,
+        });
+        let Some(TableItem::Column { def, alter }) = self.items.last_mut() else {
+            unreachable!("just pushed a column");
+        };
+        ColumnBuilder {
+            def,
+            alter,
+            primary_key: &mut self.primary_key,
+            default_schema: self.default_schema.as_deref(),
+            errors: &mut self.errors,
+        }
+    }
+
+    fn push_index<I, S>(&mut self, columns: I, unique: bool) -> IndexBuilder<'_>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let columns: Vec<String> = columns.into_iter().map(Into::into).collect();
+        let suffix = if unique { "unique" } else { "index" };
+        let name = format!("{}_{}_{suffix}", self.table.name, columns.join("_"));
+        self.items.push(TableItem::Index(CreateIndex {
+            name,
+            table: self.table.clone(),
+            columns,
+            unique,
+            predicate: None,
+        }));
+        let Some(TableItem::Index(index)) = self.items.last_mut() else {
+            unreachable!("just pushed an index");
+        };
+        IndexBuilder { index }
+    }
+
+    pub(crate) fn into_create_table(
+        self,
+        if_not_exists: bool,
+        errors: &mut SmallVec<[Cow<'static, str>; 2]>,
+    ) -> CreateTable {
+        errors.extend(self.errors);
+        let mut columns = Vec::with_capacity(self.items.len());
+        let mut foreign_keys = Vec::new();
+        let mut checks = Vec::new();
+        let mut indexes = Vec::new();
+
+        for item in self.items {
+            match item {
+                TableItem::Column { def, alter: false } => columns.push(def),
+                TableItem::Column { alter: true, .. }
+                | TableItem::DropColumn(_)
+                | TableItem::RenameColumn { .. }
+                | TableItem::DropForeign(_)
+                | TableItem::DropCheck(_)
+                | TableItem::DropIndex(_) => errors.push(
+                    "create_table(): alter()/drop_*()/rename_column() допустимы только в alter_table()"
+                        .into(),
+                ),
+                TableItem::Foreign(fk) => {
+                    check_foreign(&fk, errors);
+                    foreign_keys.push(fk);
+                }
+                TableItem::Check(check) => checks.push(check),
+                TableItem::Index(index) => {
+                    check_index(&index, errors);
+                    indexes.push(index);
+                }
+            }
+        }
+        if columns.is_empty() {
+            errors.push("create_table(): таблица без колонок".into());
+        }
+
+        CreateTable {
+            table: self.table,
+            if_not_exists,
+            columns,
+            primary_key: self.primary_key,
+            foreign_keys,
+            checks,
+            indexes,
+        }
+    }
+
+    pub(crate) fn into_alter_table(
+        self,
+        errors: &mut SmallVec<[Cow<'static, str>; 2]>,
+    ) -> AlterTable {
+        errors.extend(self.errors);
+        if !self.primary_key.is_empty() {
+            errors.push("alter_table(): изменение PRIMARY KEY не поддерживается".into());
+        }
+
+        let ops = self
+            .items
+            .into_iter()
+            .map(|item| match item {
+                TableItem::Column { def, alter: false } => AlterTableOp::AddColumn(def),
+                TableItem::Column { def, alter: true } => AlterTableOp::AlterColumn(def),
+                TableItem::DropColumn(name) => AlterTableOp::DropColumn(name),
+                TableItem::RenameColumn { from, to } => AlterTableOp::RenameColumn { from, to },
+                TableItem::Foreign(fk) => {
+                    check_foreign(&fk, errors);
+                    AlterTableOp::AddForeignKey(fk)
+                }
+                TableItem::DropForeign(name) => AlterTableOp::DropForeignKey(name),
+                TableItem::Check(check) => AlterTableOp::AddCheck(check),
+                TableItem::DropCheck(name) => AlterTableOp::DropCheck(name),
+                TableItem::Index(index) => {
+                    check_index(&index, errors);
+                    AlterTableOp::CreateIndex(index)
+                }
+                TableItem::DropIndex(name) => AlterTableOp::DropIndex(name),
+            })
+            .collect();
+
+        AlterTable {
+            table: self.table,
+            ops,
+        }
+    }
+}
+
+fn check_foreign(fk: &ForeignKey, errors: &mut SmallVec<[Cow<'static, str>; 2]>) {
+    if fk.ref_table.name.is_empty() {
+        errors.push("foreign(): не задана целевая таблица, вызовите references(...)".into());
+    } else if fk.columns.len() != fk.ref_columns.len() {
+        errors.push(
+            format!(
+                "foreign(): число колонок ({}) не совпадает с references ({})",
+                fk.columns.len(),
+                fk.ref_columns.len()
+            )
+            .into(),
+        );
+    }
+}
+
+fn check_index(index: &CreateIndex, errors: &mut SmallVec<[Cow<'static, str>; 2]>) {
+    if index.columns.is_empty() {
+        errors.push(format!("index(): индекс {} без колонок", index.name).into());
+    }
+}
+
+pub(crate) fn predicate(cond: Expression) -> Predicate {
+    Predicate {
+        expr: map_expr(&cond.expr),
+        params: cond.params.into_vec(),
+    }
+}
+
+/// `"schema.table"` → схема из имени, иначе схема по умолчанию
+pub(crate) fn table_name(name: &str, default_schema: Option<&str>) -> TableName {
+    match name.split_once('.') {
+        Some((schema, table)) => TableName {
+            schema: Some(schema.to_string()),
+            name: table.to_string(),
+        },
+        None => TableName {
+            schema: default_schema.map(str::to_string),
+            name: name.to_string(),
+        },
+    }
+}
+
+impl<'t> ColumnBuilder<'t> {
+    pub fn not_null(self) -> Self {
+        self.def.nullable = Some(false);
+        self
+    }
+
+    pub fn nullable(self) -> Self {
+        self.def.nullable = Some(true);
+        self
+    }
+
+    /// DEFAULT <литерал>
+    pub fn default<V: Into<Param>>(self, value: V) -> Self {
+        self.def.default = Some(ColumnDefault::Value(value.into()));
+        self
+    }
+
+    /// DEFAULT <SQL как есть>: `default_raw("CURRENT_TIMESTAMP")`
+    pub fn default_raw(self, sql: &str) -> Self {
+        self.def.default = Some(ColumnDefault::Raw(sql.to_string()));
+        self
+    }
+
+    /// UNSIGNED (только MySQL; в остальных диалектах опускается, в Strict — ошибка)
+    pub fn unsigned(self) -> Self {
+        self.def.unsigned = true;
+        self
+    }
+
+    pub fn unique(self) -> Self {
+        self.def.unique = true;
+        self
+    }
+
+    /// Колонка входит в PRIMARY KEY таблицы
+    pub fn primary(self) -> Self {
+        if !self.primary_key.contains(&self.def.name) {
+            self.primary_key.push(self.def.name.clone());
+        }
+        self
+    }
+
+    pub fn comment(self, text: &str) -> Self {
+        self.def.comment = Some(text.to_string());
+        self
+    }
+
+    /// FOREIGN KEY на `table(column)`
+    pub fn references(self, table: &str, column: &str) -> Self {
+        self.def.references = Some(ForeignKey {
+            name: None,
+            columns: vec![self.def.name.clone()],
+            ref_table: table_name(table, self.default_schema),
+            ref_columns: vec![column.to_string()],
+            on_delete: None,
+            on_update: None,
+        });
+        self
+    }
+
+    /// ON DELETE для ключа из `references(...)`
+    pub fn on_delete(self, action: ReferentialAction) -> Self {
+        match &mut self.def.references {
+            Some(fk) => fk.on_delete = Some(action),
+            None => self
+                .errors
+                .push(format!("{}: on_delete() без references(...)", self.def.name).into()),
+        }
+        self
+    }
+
+    /// ON UPDATE для ключа из `references(...)`
+    pub fn on_update(self, action: ReferentialAction) -> Self {
+        match &mut self.def.references {
+            Some(fk) => fk.on_update = Some(action),
+            None => self
+                .errors
+                .push(format!("{}: on_update() без references(...)", self.def.name).into()),
+        }
+        self
+    }
+
+    /// В alter_table(): изменить существующую колонку вместо добавления
+    pub fn alter(self) -> Self {
+        *self.alter = true;
+        self
+    }
+}
+
+impl<'t> ForeignBuilder<'t> {
+    /// Целевая таблица и колонки
+    pub fn references<I, S>(self, table: &str, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.fk.ref_table = table_name(table, self.default_schema);
+        self.fk.ref_columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Имя ограничения: нужно для `drop_foreign(...)`
+    pub fn name(self, name: &str) -> Self {
+        self.fk.name = Some(name.to_string());
+        self
+    }
+
+    pub fn on_delete(self, action: ReferentialAction) -> Self {
+        self.fk.on_delete = Some(action);
+        self
+    }
+
+    pub fn on_update(self, action: ReferentialAction) -> Self {
+        self.fk.on_update = Some(action);
+        self
+    }
+}
+
+impl<'t> IndexBuilder<'t> {
+    pub fn name(self, name: &str) -> Self {
+        self.index.name = name.to_string();
+        self
+    }
+
+    /// Частичный индекс: WHERE <cond> (PG/SQLite; в MySQL — деградация или ошибка)
+    pub fn r#where(self, cond: Expression) -> Self {
+        self.index.predicate = Some(predicate(cond));
+        self
+    }
+
+    #[inline]
+    pub fn where_(self, cond: Expression) -> Self {
+        self.r#where(cond)
+    }
+}
+
+impl<'t> CheckBuilder<'t> {
+    /// Имя ограничения: нужно для `drop_check(...)`
+    pub fn name(self, name: &str) -> Self {
+        self.check.name = Some(name.to_string());
+        self
+    }
+}
@@ -0,0 +1,4 @@
+#[cfg(test)]
+mod seeder;
+#[cfg(test)]
+mod truncate;
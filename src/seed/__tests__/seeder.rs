@@ -0,0 +1,172 @@
+#![cfg(feature = "sqlite")]
+
+use std::{fs, path::PathBuf};
+
+use crate::executor::{Error as ExecError, ExecutorConfig, QueryExecutor, transaction::TxExecutor};
+use crate::expression::helpers::{col, val};
+use crate::seed::{Error, Seed, SeedFuture, Seeder, SqlSeed};
+
+struct Users;
+
+impl Seed for Users {
+    fn name(&self) -> &str {
+        "01_users"
+    }
+    fn run<'a>(&'a self, tx: &'a mut TxExecutor<'_>) -> SeedFuture<'a> {
+        Box::pin(async move {
+            tx.query::<()>()
+                .into("users")
+                .columns((col("id"), col("name")))
+                .insert((val(1), val("alice"), val(2), val("bob")))
+                .on_conflict("id")
+                .merge((col("name"),))
+                .exec()
+                .await?;
+            Ok(())
+        })
+    }
+}
+
+struct Posts;
+
+impl Seed for Posts {
+    fn name(&self) -> &str {
+        "02_posts"
+    }
+    fn run<'a>(&'a self, tx: &'a mut TxExecutor<'_>) -> SeedFuture<'a> {
+        Box::pin(async move {
+            tx.query::<()>()
+                .into("posts")
+                .columns((col("id"), col("user_id"), col("title")))
+                .insert((val(1), val(1), val("hello"), val(2), val(2), val("world")))
+                .on_conflict("id")
+                .merge_all()
+                .exec()
+                .await?;
+            Ok(())
+        })
+    }
+}
+
+struct Broken;
+
+impl Seed for Broken {
+    fn name(&self) -> &str {
+        "03_broken"
+    }
+    fn run<'a>(&'a self, tx: &'a mut TxExecutor<'_>) -> SeedFuture<'a> {
+        Box::pin(async move {
+            tx.query::<()>()
+                .into("users")
+                .insert((col("id"), val(3), col("name"), val("carol")))
+                .exec()
+                .await?;
+            Err(ExecError::Unsupported("boom".into()))
+        })
+    }
+}
+
+async fn setup_db() -> QueryExecutor {
+    let cfg = ExecutorConfig::builder()
+        .database_url("sqlite::memory:")
+        .max_connections(1)
+        .after_connect_sql("PRAGMA foreign_keys = ON")
+        .build();
+    let exec = QueryExecutor::connect(cfg).await.unwrap();
+    exec.schema()
+        .create_table("users", |t| {
+            t.increments("id");
+            t.string("name", 255).not_null();
+        })
+        .create_table("posts", |t| {
+            t.increments("id");
+            t.integer("user_id").not_null().references("users", "id");
+            t.string("title", 255).not_null();
+        })
+        .await
+        .unwrap();
+    exec
+}
+
+async fn names(exec: &QueryExecutor) -> Vec<String> {
+    exec.fetch_typed::<(String,)>("SELECT name FROM users ORDER BY id", vec![])
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(n,)| n)
+        .collect()
+}
+
+#[tokio::test]
+async fn runs_seeds_in_name_order_and_reruns_idempotently() {
+    let exec = setup_db().await;
+    // posts ссылаются на users: порядок по имени, а не по регистрации
+    let seeder = Seeder::new(&exec).seed(Posts).seed(Users);
+
+    assert_eq!(seeder.run().await.unwrap(), vec!["01_users", "02_posts"]);
+    assert_eq!(names(&exec).await, vec!["alice", "bob"]);
+
+    exec.query::<()>()
+        .update("users")
+        .set((col("name"), val("changed")))
+        .exec()
+        .await
+        .unwrap();
+    seeder.run().await.unwrap();
+    assert_eq!(names(&exec).await, vec!["alice", "bob"]);
+
+    let posts = exec
+        .fetch_typed::<(i64,)>("SELECT COUNT(*) FROM posts", vec![])
+        .await
+        .unwrap();
+    assert_eq!(posts, vec![(2,)]);
+}
+
+#[tokio::test]
+async fn failed_seed_is_rolled_back() {
+    let exec = setup_db().await;
+    let res = Seeder::new(&exec).seed(Users).seed(Broken).run().await;
+
+    assert!(matches!(res, Err(Error::Failed { name, .. }) if name == "03_broken"));
+    assert_eq!(names(&exec).await, vec!["alice", "bob"]);
+}
+
+#[tokio::test]
+async fn duplicate_seed_names() {
+    let exec = setup_db().await;
+    let res = Seeder::new(&exec).seed(Users).seed(Users).run().await;
+    assert!(matches!(res, Err(Error::DuplicateSeed(n)) if n == "01_users"));
+}
+
+#[tokio::test]
+async fn sql_seed_files() {
+    let dir: PathBuf = std::env::temp_dir().join(format!("knux_seed_files_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("02_posts.sql"),
+        "INSERT INTO posts (user_id, title) VALUES (10, 'a;b');",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("01_users.sql"),
+        "-- пользователи\nINSERT INTO users (id, name) VALUES (10, 'dave');\n\
+         INSERT INTO users (id, name) VALUES (11, 'erin');\n",
+    )
+    .unwrap();
+    fs::write(dir.join("notes.txt"), "не сид").unwrap();
+
+    let loaded: Vec<_> = SqlSeed::load_dir(&dir)
+        .unwrap()
+        .iter()
+        .map(|s| s.name().to_string())
+        .collect();
+    assert_eq!(loaded, vec!["01_users", "02_posts"]);
+
+    let exec = setup_db().await;
+    let res = Seeder::new(&exec).directory(&dir).unwrap().run().await;
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(res.unwrap(), vec!["01_users", "02_posts"]);
+    assert_eq!(names(&exec).await, vec!["dave", "erin"]);
+}
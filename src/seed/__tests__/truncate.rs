@@ -0,0 +1,103 @@
+#![cfg(feature = "sqlite")]
+
+use crate::executor::{ExecutorConfig, QueryExecutor};
+use crate::seed::Seeder;
+
+async fn setup_db() -> QueryExecutor {
+    let cfg = ExecutorConfig::builder()
+        .database_url("sqlite::memory:")
+        .max_connections(1)
+        .after_connect_sql("PRAGMA foreign_keys = ON")
+        .build();
+    let exec = QueryExecutor::connect(cfg).await.unwrap();
+    exec.schema()
+        .create_table("users", |t| {
+            t.increments("id");
+            t.string("name", 255);
+            t.integer("manager_id").references("users", "id");
+        })
+        .create_table("posts", |t| {
+            t.increments("id");
+            t.integer("user_id").not_null().references("users", "id");
+        })
+        .create_table("comments", |t| {
+            t.increments("id");
+            t.integer("post_id").not_null().references("posts", "id");
+        })
+        .create_table("knux_migrations", |t| {
+            t.increments("id");
+            t.string("name", 255);
+        })
+        .await
+        .unwrap();
+
+    for sql in [
+        "INSERT INTO users (name) VALUES ('a')",
+        "INSERT INTO users (name, manager_id) VALUES ('b', 1)",
+        "INSERT INTO posts (user_id) VALUES (2)",
+        "INSERT INTO comments (post_id) VALUES (1)",
+        "INSERT INTO knux_migrations (name) VALUES ('0001_init')",
+    ] {
+        exec.fetch_typed::<(i64,)>(sql, vec![]).await.unwrap();
+    }
+    exec
+}
+
+async fn count(exec: &QueryExecutor, table: &str) -> i64 {
+    exec.fetch_typed::<(i64,)>(&format!("SELECT COUNT(*) FROM {table}"), vec![])
+        .await
+        .unwrap()[0]
+        .0
+}
+
+#[tokio::test]
+async fn truncates_children_first_and_keeps_listed() {
+    let exec = setup_db().await;
+
+    let truncated = Seeder::new(&exec)
+        .truncate_all_except(&["knux_migrations"])
+        .await
+        .unwrap();
+    assert_eq!(truncated, vec!["comments", "posts", "users"]);
+
+    for t in ["users", "posts", "comments"] {
+        assert_eq!(count(&exec, t).await, 0, "{t}");
+    }
+    assert_eq!(count(&exec, "knux_migrations").await, 1);
+
+    // автоинкремент сброшен
+    exec.fetch_typed::<(i64,)>("INSERT INTO users (name) VALUES ('c')", vec![])
+        .await
+        .unwrap();
+    let ids = exec
+        .fetch_typed::<(i64,)>("SELECT id FROM users", vec![])
+        .await
+        .unwrap();
+    assert_eq!(ids, vec![(1,)]);
+}
+
+#[tokio::test]
+async fn kept_table_referencing_truncated_one_fails() {
+    let exec = setup_db().await;
+
+    let res = Seeder::new(&exec)
+        .truncate_all_except(&["knux_migrations", "comments"])
+        .await;
+    assert!(res.is_err());
+    // откат: данные на месте
+    assert_eq!(count(&exec, "users").await, 2);
+    assert_eq!(count(&exec, "comments").await, 1);
+}
+
+#[tokio::test]
+async fn nothing_to_truncate() {
+    let exec = setup_db().await;
+    let all = ["users", "posts", "comments", "knux_migrations"];
+    assert!(
+        Seeder::new(&exec)
+            .truncate_all_except(&all)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
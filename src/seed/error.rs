@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+
+use crate::executor::Error as ExecError;
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Exec(#[from] ExecError),
+
+    #[error("Duplicate seed name: {0}")]
+    DuplicateSeed(String),
+
+    #[error("Failed to read {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Seed {name} failed: {source}")]
+    Failed {
+        name: String,
+        #[source]
+        source: ExecError,
+    },
+}
@@ -0,0 +1,113 @@
+//! Сиды в стиле knex `seed:run`: заполнение локальной/тестовой базы.
+//!
+//! Сиды — реализации [`Seed`] или SQL-файлы каталога ([`SqlSeed`]); выполняются
+//! по имени, каждый в своей транзакции. Для повторных запусков используйте
+//! upsert (`on_conflict(...).merge(...)`) — таблицы сидов не ведётся.
+
+mod __tests__;
+mod error;
+mod sql;
+mod truncate;
+
+use std::{future::Future, path::Path, pin::Pin};
+
+use crate::executor::{QueryExecutor, Result as ExecResult, transaction::TxExecutor};
+
+pub use error::{Error, Result};
+pub use sql::SqlSeed;
+
+/// Результат `run`
+pub type SeedFuture<'a> = Pin<Box<dyn Future<Output = ExecResult<()>> + Send + 'a>>;
+
+/// Сид. Порядок запуска — по имени: `01_users`, `02_posts`.
+///
+/// ```ignore
+/// struct Users;
+///
+/// impl Seed for Users {
+///     fn name(&self) -> &str {
+///         "01_users"
+///     }
+///     fn run<'a>(&'a self, tx: &'a mut TxExecutor<'_>) -> SeedFuture<'a> {
+///         Box::pin(async move {
+///             tx.query::<()>()
+///                 .into("users")
+///                 .columns((col("id"), col("name")))
+///                 .insert((val(1), val("alice"), val(2), val("bob")))
+///                 .on_conflict("id")
+///                 .merge((col("name"),))
+///                 .exec()
+///                 .await?;
+///             Ok(())
+///         })
+///     }
+/// }
+/// ```
+pub trait Seed: Send + Sync {
+    fn name(&self) -> &str;
+    fn run<'a>(&'a self, tx: &'a mut TxExecutor<'_>) -> SeedFuture<'a>;
+}
+
+/// `Seeder::new(&db).seed(Users).directory("seeds")?.run().await?`
+pub struct Seeder<'e> {
+    exec: &'e QueryExecutor,
+    seeds: Vec<Box<dyn Seed>>,
+}
+
+impl<'e> Seeder<'e> {
+    pub fn new(exec: &'e QueryExecutor) -> Self {
+        Self {
+            exec,
+            seeds: Vec::new(),
+        }
+    }
+
+    pub fn seed<S: Seed + 'static>(mut self, seed: S) -> Self {
+        self.seeds.push(Box::new(seed));
+        self
+    }
+
+    pub fn seed_boxed(mut self, seed: Box<dyn Seed>) -> Self {
+        self.seeds.push(seed);
+        self
+    }
+
+    /// Добавить SQL-сиды из каталога (см. [`SqlSeed::load_dir`])
+    pub fn directory<P: AsRef<Path>>(mut self, dir: P) -> Result<Self> {
+        for s in SqlSeed::load_dir(dir)? {
+            self.seeds.push(Box::new(s));
+        }
+        Ok(self)
+    }
+
+    /// Выполнить все сиды по имени. Первая ошибка останавливает запуск
+    /// (изменения упавшего сида откатываются). Возвращает имена выполненных.
+    pub async fn run(&self) -> Result<Vec<String>> {
+        let mut sorted: Vec<&dyn Seed> = self.seeds.iter().map(AsRef::as_ref).collect();
+        sorted.sort_by(|a, b| a.name().cmp(b.name()));
+        if let Some(w) = sorted.windows(2).find(|w| w[0].name() == w[1].name()) {
+            return Err(Error::DuplicateSeed(w[0].name().to_string()));
+        }
+
+        let mut conn = self.exec.acquire_conn().await?;
+        let mut names = Vec::with_capacity(sorted.len());
+        for s in sorted {
+            let failed = |source| Error::Failed {
+                name: s.name().to_string(),
+                source,
+            };
+            let mut tx = conn.begin(self.exec).await?;
+            s.run(&mut tx).await.map_err(failed)?;
+            tx.commit().await?;
+            names.push(s.name().to_string());
+        }
+        Ok(names)
+    }
+
+    /// Очистить все таблицы схемы, кроме `keep`, со сбросом автоинкремента.
+    /// Возвращает очищенные таблицы в порядке очистки (сначала ссылающиеся).
+    pub async fn truncate_all_except(&self, keep: &[&str]) -> Result<Vec<String>> {
+        let mut conn = self.exec.acquire_conn().await?;
+        truncate::truncate_all_except(&mut conn, self.exec, keep).await
+    }
+}
@@ -0,0 +1,60 @@
+//! Сиды из SQL-файлов каталога.
+
+use std::{fs, path::Path};
+
+use super::{Error, Result, Seed, SeedFuture};
+use crate::{executor::transaction::TxExecutor, migrate::execute_script};
+
+/// Сид из SQL-скрипта; имя — имя файла без `.sql` (`01_users`)
+#[derive(Clone, Debug)]
+pub struct SqlSeed {
+    name: String,
+    sql: String,
+}
+
+impl SqlSeed {
+    pub fn new<N: Into<String>, S: Into<String>>(name: N, sql: S) -> Self {
+        Self {
+            name: name.into(),
+            sql: sql.into(),
+        }
+    }
+
+    /// Все `*.sql` каталога по имени; прочие файлы пропускаются
+    pub fn load_dir<P: AsRef<Path>>(dir: P) -> Result<Vec<Self>> {
+        let dir = dir.as_ref();
+        let io = |path: &Path| {
+            let path = path.to_path_buf();
+            move |source| Error::Io { path, source }
+        };
+
+        let mut seeds = Vec::new();
+        for entry in fs::read_dir(dir).map_err(io(dir))? {
+            let path = entry.map_err(io(dir))?.path();
+            let Some(name) = path
+                .file_name()
+                .and_then(|f| f.to_str())
+                .and_then(|f| f.strip_suffix(".sql"))
+            else {
+                continue;
+            };
+            if !path.is_file() {
+                continue;
+            }
+            let sql = fs::read_to_string(&path).map_err(io(&path))?;
+            seeds.push(Self::new(name, sql));
+        }
+        seeds.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(seeds)
+    }
+}
+
+impl Seed for SqlSeed {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn run<'a>(&'a self, tx: &'a mut TxExecutor<'_>) -> SeedFuture<'a> {
+        Box::pin(execute_script(tx, &self.sql))
+    }
+}
@@ -0,0 +1,181 @@
+//! Очистка всех таблиц схемы с учётом внешних ключей.
+//!
+//! Postgres — один `TRUNCATE ... RESTART IDENTITY` на все таблицы; MySQL —
+//! `TRUNCATE` по одной с отключённым `FOREIGN_KEY_CHECKS`; SQLite (`TRUNCATE`
+//! нет) — `DELETE` в одной транзакции с отложенной проверкой ключей и сброс
+//! `sqlite_sequence`. Порядок везде: сначала ссылающиеся таблицы.
+
+use super::Result;
+use crate::{
+    executor::{DbConn, QueryExecutor},
+    param::Param,
+    renderer::{Dialect, quote_ident_always},
+};
+
+pub(super) async fn truncate_all_except(
+    conn: &mut DbConn,
+    exec: &QueryExecutor,
+    keep: &[&str],
+) -> Result<Vec<String>> {
+    let dialect = exec.dialect();
+    let schema = exec.schema.as_deref();
+    let mut tables = list_tables(conn, dialect, schema).await?;
+    tables.retain(|t| !keep.contains(&t.as_str()));
+    tables.sort();
+    if tables.is_empty() {
+        return Ok(tables);
+    }
+
+    let edges = foreign_keys(conn, dialect, schema).await?;
+    let tables = fk_order(tables, &edges);
+    let qualified: Vec<String> = tables.iter().map(|t| qualify(schema, t, dialect)).collect();
+
+    match dialect {
+        Dialect::Postgres => {
+            let sql = format!("TRUNCATE TABLE {} RESTART IDENTITY", qualified.join(", "));
+            conn.exec_ctx().execute(&sql, Vec::new()).await?;
+        }
+        Dialect::MySQL => {
+            // TRUNCATE запрещён для таблиц, на которые ссылаются, даже пустых
+            conn.exec_ctx()
+                .execute("SET FOREIGN_KEY_CHECKS = 0", Vec::new())
+                .await?;
+            let mut res = Ok(0);
+            for t in &qualified {
+                res = conn
+                    .exec_ctx()
+                    .execute(&format!("TRUNCATE TABLE {t}"), Vec::new())
+                    .await;
+                if res.is_err() {
+                    break;
+                }
+            }
+            let restored = conn
+                .exec_ctx()
+                .execute("SET FOREIGN_KEY_CHECKS = 1", Vec::new())
+                .await;
+            res?;
+            restored?;
+        }
+        Dialect::SQLite => {
+            let mut tx = conn.begin(exec).await?;
+            // циклы и самоссылки: проверка ключей при COMMIT
+            tx.execute("PRAGMA defer_foreign_keys = ON", Vec::new())
+                .await?;
+            for t in &qualified {
+                tx.execute(&format!("DELETE FROM {t}"), Vec::new()).await?;
+            }
+
+            let master = qualify(schema, "sqlite_master", dialect);
+            let has_sequence = tx
+                .fetch_typed::<(i64,)>(
+                    &format!("SELECT COUNT(*) FROM {master} WHERE name = 'sqlite_sequence'"),
+                    Vec::new(),
+                )
+                .await?;
+            if has_sequence.first().is_some_and(|(n,)| *n > 0) {
+                let sql = format!(
+                    "DELETE FROM {} WHERE name IN ({})",
+                    qualify(schema, "sqlite_sequence", dialect),
+                    vec!["?"; tables.len()].join(", ")
+                );
+                let params = tables.iter().map(Param::from).collect();
+                tx.execute(&sql, params).await?;
+            }
+            tx.commit().await?;
+        }
+    }
+    Ok(tables)
+}
+
+/// Базовые таблицы схемы (по умолчанию — текущей)
+async fn list_tables(
+    conn: &mut DbConn,
+    dialect: Dialect,
+    schema: Option<&str>,
+) -> Result<Vec<String>> {
+    let schema_param = schema.map_or(Param::NullText, Param::from);
+    let (sql, params) = match dialect {
+        Dialect::Postgres => (
+            "SELECT table_name::text FROM information_schema.tables \
+             WHERE table_schema = COALESCE($1, current_schema()) AND table_type = 'BASE TABLE'"
+                .to_string(),
+            vec![schema_param],
+        ),
+        Dialect::MySQL => (
+            "SELECT CAST(TABLE_NAME AS CHAR) FROM information_schema.TABLES \
+             WHERE TABLE_SCHEMA = COALESCE(?, DATABASE()) AND TABLE_TYPE = 'BASE TABLE'"
+                .to_string(),
+            vec![schema_param],
+        ),
+        Dialect::SQLite => (
+            format!(
+                "SELECT name FROM {} WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+                qualify(schema, "sqlite_master", dialect)
+            ),
+            Vec::new(),
+        ),
+    };
+    let rows = conn.exec_ctx().fetch_all::<(String,)>(&sql, params).await?;
+    Ok(rows.into_iter().map(|(t,)| t).collect())
+}
+
+/// Пары (ссылающаяся таблица, таблица, на которую ссылаются)
+async fn foreign_keys(
+    conn: &mut DbConn,
+    dialect: Dialect,
+    schema: Option<&str>,
+) -> Result<Vec<(String, String)>> {
+    let (sql, params) = match dialect {
+        // один TRUNCATE на все таблицы — порядок не важен
+        Dialect::Postgres => return Ok(Vec::new()),
+        Dialect::MySQL => (
+            "SELECT CAST(TABLE_NAME AS CHAR), CAST(REFERENCED_TABLE_NAME AS CHAR) \
+             FROM information_schema.REFERENTIAL_CONSTRAINTS \
+             WHERE CONSTRAINT_SCHEMA = COALESCE(?, DATABASE())"
+                .to_string(),
+            vec![schema.map_or(Param::NullText, Param::from)],
+        ),
+        Dialect::SQLite => (
+            format!(
+                "SELECT m.name, f.\"table\" FROM {} m \
+                 JOIN pragma_foreign_key_list(m.name, ?) f WHERE m.type = 'table'",
+                qualify(schema, "sqlite_master", dialect)
+            ),
+            vec![Param::from(schema.unwrap_or("main"))],
+        ),
+    };
+    Ok(conn
+        .exec_ctx()
+        .fetch_all::<(String, String)>(&sql, params)
+        .await?)
+}
+
+/// Сначала таблицы, на которые никто из оставшихся не ссылается;
+/// циклы — в порядке имён
+fn fk_order(mut tables: Vec<String>, edges: &[(String, String)]) -> Vec<String> {
+    let mut out = Vec::with_capacity(tables.len());
+    while !tables.is_empty() {
+        let free = tables.iter().position(|t| {
+            !edges
+                .iter()
+                .any(|(child, parent)| parent == t && child != t && tables.contains(child))
+        });
+        match free {
+            Some(i) => out.push(tables.remove(i)),
+            None => out.append(&mut tables),
+        }
+    }
+    out
+}
+
+fn qualify(schema: Option<&str>, name: &str, dialect: Dialect) -> String {
+    match schema {
+        Some(s) => format!(
+            "{}.{}",
+            quote_ident_always(s, dialect),
+            quote_ident_always(name, dialect)
+        ),
+        None => quote_ident_always(name, dialect),
+    }
+}
@@ -1,8 +1,9 @@
 #![allow(dead_code)]
 
-#[cfg(feature = "mysql")]
+// диалект по умолчанию — как DEFAULT_DIALECT (postgres > mysql > sqlite)
+#[cfg(all(feature = "mysql", not(feature = "postgres")))]
 pub const Q: char = '`';
-#[cfg(not(feature = "mysql"))]
+#[cfg(not(all(feature = "mysql", not(feature = "postgres"))))]
 pub const Q: char = '"';
 
 #[inline]
@@ -34,7 +35,7 @@ pub fn ph(n: usize) -> String {
         format!("${}", n)
     }
 
-    #[cfg(any(feature = "mysql", feature = "sqlite"))]
+    #[cfg(not(feature = "postgres"))]
     {
         "?".to_string()
     }